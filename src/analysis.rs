@@ -0,0 +1,282 @@
+// One-call analysis pipeline
+
+use crate::classification::crypto::{CryptoArtifact, find_crypto_artifacts};
+use crate::classification::{ScoreConfig, TagRule, classify_with_rules, score};
+use crate::container::debug_file::merge_external_debug;
+use crate::container::{create_parser_or_raw, detect_format};
+use crate::extraction::dedup::drop_covered;
+use crate::extraction::symbols::{demangle_strings, symbol_strings};
+use crate::extraction::top_n::TopN;
+use crate::extraction::xref::{XrefConfig, count_xrefs};
+use crate::extraction::{
+    ExtractionConfig, ExtractionProgress, extract_from_container_with_progress,
+};
+use crate::output::{SortKey, sort_strings};
+use crate::types::{ContainerInfo, FoundString, Result, StringyError};
+
+/// Options for [`analyze`].
+#[derive(Debug, Clone, Default)]
+pub struct AnalysisConfig {
+    /// What to extract: lengths, encodings, sections, deduplication
+    pub extraction: ExtractionConfig,
+    /// How classified strings are scored
+    pub scoring: ScoreConfig,
+    /// User-supplied tagging rules, applied after the built-in classifiers;
+    /// their bonuses are added to `scoring`
+    pub rules: Vec<TagRule>,
+    /// Contents of a separate debug file (GNU `.debug`, `.dwo`, or the binary
+    /// inside a `.dSYM` bundle) whose strings are added to the results; see
+    /// [`merge_external_debug`]
+    pub debug_file: Option<Vec<u8>>,
+    /// Virtual address unrecognized input is treated as loaded at, so its
+    /// strings report process addresses; see [`create_parser_or_raw`]
+    pub base_address: Option<u64>,
+    /// Count pointer-sized references to each string into
+    /// [`FoundString::xref_count`]; see [`count_xrefs`]
+    pub count_xrefs: bool,
+}
+
+/// Outcome of [`analyze`].
+#[derive(Debug, Clone)]
+pub struct AnalysisResult {
+    /// Parsed container metadata: format, sections, imports, exports
+    pub container: ContainerInfo,
+    /// Classified and scored strings, highest score first
+    pub strings: Vec<FoundString>,
+    /// Well-known cryptographic constants embedded anywhere in the input,
+    /// in file-offset order
+    pub crypto: Vec<CryptoArtifact>,
+}
+
+/// Runs the whole pipeline over `data`: format detection, container
+/// parsing, string extraction, classification and scoring, plus a scan for
+/// cryptographic constants.
+///
+/// Input in an unrecognized format is scanned as one raw section. Strings
+/// recovered from format metadata, including import and export names (see
+/// [`symbol_strings`]), are subject to the same length, encoding and region
+/// limits as scanned ones. A scanned string lying within the bytes of a
+/// string recovered from format metadata, such as a decoded resource string
+/// or the ELF interpreter path, is dropped in favor of the metadata one (see
+/// [`drop_covered`]). Strings of [`AnalysisConfig::debug_file`] are added
+/// subject to the length and encoding limits only, and a build identifier
+/// that does not match `data` is logged as a warning. With
+/// [`AnalysisConfig::count_xrefs`] set, every string with an address gets an
+/// `xref_count`. Strings are returned highest score first, ties in
+/// file-offset order; with
+/// [`ExtractionConfig::top_n`](crate::extraction::ExtractionConfig::top_n)
+/// set, only that many are kept, ranked by their final score.
+///
+/// # Errors
+///
+/// Returns `StringyError::ConfigError` if `config.extraction` is invalid or
+/// `config.base_address` is misaligned, and `StringyError::ParseError` if
+/// the container is recognized but malformed, or if the debug file is not a
+/// valid ELF or Mach-O file.
+///
+/// # Examples
+///
+/// ```rust
+/// use stringy::{AnalysisConfig, BinaryFormat, Tag, analyze};
+///
+/// let data = b"\x00\x01Connecting to https://update.example.com/check\x00\xff\xfeidle\x00";
+/// let result = analyze(data, &AnalysisConfig::default())?;
+///
+/// assert_eq!(result.container.format, BinaryFormat::Unknown);
+/// let top = &result.strings[0];
+/// assert_eq!(top.text, "Connecting to https://update.example.com/check");
+/// assert!(top.tags.contains(&Tag::Url));
+/// # Ok::<(), stringy::StringyError>(())
+/// ```
+pub fn analyze(data: &[u8], config: &AnalysisConfig) -> Result<AnalysisResult> {
+    analyze_with_progress(data, config, None)
+}
+
+/// Like [`analyze`], but reports the progress of string extraction, the
+/// longest step on large inputs, to `progress`; see
+/// [`extract_from_container_with_progress`].
+///
+/// # Errors
+///
+/// Same as [`analyze`].
+pub fn analyze_with_progress(
+    data: &[u8],
+    config: &AnalysisConfig,
+    progress: Option<&dyn Fn(ExtractionProgress)>,
+) -> Result<AnalysisResult> {
+    let extraction = &config.extraction;
+    extraction.validate()?;
+
+    let format = detect_format(data);
+    log::debug!("Detected format: {}", format);
+    let parser = create_parser_or_raw(format, config.base_address)?;
+    let container = parser.parse(data)?;
+
+    let scoring = config.scoring.clone().with_tag_rules(&config.rules);
+    let rescore = |string: &mut FoundString| {
+        classify_with_rules(string, &config.rules);
+        string.score = score(string, &scoring);
+    };
+
+    let mut strings =
+        extract_from_container_with_progress(data, &container, extraction, rescore, progress);
+    let selected = |s: &FoundString| {
+        s.text.chars().count() >= extraction.min_length
+            && extraction.includes_encoding(s.encoding)
+            && extraction.includes_location(s.section.as_deref(), s.offset)
+    };
+    // Parser metadata strings are located at their own bytes, which the
+    // section scan may also have found; symbol strings are located at the
+    // symbol's address instead
+    let located: Vec<FoundString> = parser
+        .extract_metadata_strings(data)?
+        .into_iter()
+        .filter(|s| selected(s))
+        .collect();
+    strings = drop_covered(strings, &located);
+    let mut metadata: Vec<FoundString> = located
+        .into_iter()
+        .chain(
+            symbol_strings(&container, extraction.demangle)
+                .into_iter()
+                .filter(|s| selected(s)),
+        )
+        .collect();
+    if let Some(debug_data) = &config.debug_file {
+        let mut debug = Vec::new();
+        merge_external_debug(data, debug_data, &mut debug)
+            .map_err(|err| StringyError::ParseError(format!("Debug file: {}", err)))?;
+        // Offsets point into the debug file, so no region limits apply
+        metadata.extend(debug.into_iter().filter(|s| {
+            s.text.chars().count() >= extraction.min_length
+                && extraction.includes_encoding(s.encoding)
+        }));
+    }
+    metadata.iter_mut().for_each(rescore);
+    strings.extend(metadata);
+    if extraction.demangle {
+        demangle_strings(&mut strings);
+    }
+    if config.count_xrefs {
+        let xrefs = XrefConfig::for_container(&container, data);
+        count_xrefs(data, &container.sections, &mut strings, &xrefs);
+    }
+    log::info!("Extracted {} strings", strings.len());
+
+    if let Some(n) = extraction.top_n {
+        let mut top = TopN::new(n);
+        top.extend(strings);
+        strings = top.into_sorted_vec();
+    }
+    sort_strings(&mut strings, SortKey::Score, true);
+    let crypto = find_crypto_artifacts(data, &container);
+
+    Ok(AnalysisResult {
+        container,
+        strings,
+        crypto,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{BinaryFormat, Tag};
+
+    #[test]
+    fn test_custom_rules_and_limits() {
+        let data = b"\0build-server-07.corp\0tiny\0just some words here\0";
+        let config = AnalysisConfig {
+            extraction: ExtractionConfig {
+                min_length: 5,
+                ..ExtractionConfig::default()
+            },
+            rules: vec![TagRule::new("internal-host", r"\.corp$", 40).unwrap()],
+            ..AnalysisConfig::default()
+        };
+
+        let result = analyze(data, &config).unwrap();
+        assert_eq!(result.container.format, BinaryFormat::Unknown);
+
+        let texts: Vec<&str> = result.strings.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["build-server-07.corp", "just some words here"]);
+        assert!(
+            result.strings[0]
+                .tags
+                .contains(&Tag::Custom("internal-host".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_crypto_constants_reported() {
+        use crate::classification::crypto::AES_SBOX;
+
+        let mut data = b"\0encrypting config\0".to_vec();
+        data.extend_from_slice(&AES_SBOX);
+        let result = analyze(&data, &AnalysisConfig::default()).unwrap();
+
+        assert_eq!(result.crypto.len(), 1);
+        assert_eq!(result.crypto[0].name, "AES S-box");
+        assert_eq!(result.crypto[0].offset, 19);
+        assert_eq!(result.crypto[0].section.as_deref(), Some("raw"));
+    }
+
+    /// Names stored for `GetProcAddress` are tagged although nothing imports
+    /// them.
+    #[test]
+    fn test_dynamic_api_names_tagged() {
+        let data = b"\0kernel32.dll\0LoadLibraryA\0GetTickCount\0loading plugins\0";
+        let result = analyze(data, &AnalysisConfig::default()).unwrap();
+        assert!(result.container.imports.is_empty());
+
+        let tagged = |text: &str| {
+            let string = result.strings.iter().find(|s| s.text == text).unwrap();
+            string.tags.contains(&Tag::Import)
+        };
+        assert!(tagged("kernel32.dll"));
+        assert!(tagged("LoadLibraryA"));
+        assert!(tagged("GetTickCount"));
+        assert!(!tagged("loading plugins"));
+    }
+
+    #[test]
+    fn test_count_xrefs() {
+        let mut data = b"\0\0\0\0referenced string\0\0\0unreferenced one\0\0\0\0".to_vec();
+        // A 32-bit pointer to the first string, loaded at 0x10000
+        data.extend_from_slice(&0x10004u32.to_le_bytes());
+        let config = AnalysisConfig {
+            base_address: Some(0x10000),
+            ..AnalysisConfig::default()
+        };
+
+        let result = analyze(&data, &config).unwrap();
+        assert!(result.strings.iter().all(|s| s.xref_count.is_none()));
+
+        let config = AnalysisConfig {
+            count_xrefs: true,
+            ..config
+        };
+        let result = analyze(&data, &config).unwrap();
+        let count = |text: &str| {
+            result
+                .strings
+                .iter()
+                .find(|s| s.text == text)
+                .and_then(|s| s.xref_count)
+        };
+        assert_eq!(count("referenced string"), Some(1));
+        assert_eq!(count("unreferenced one"), Some(0));
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let config = AnalysisConfig {
+            extraction: ExtractionConfig {
+                encodings: Vec::new(),
+                ..ExtractionConfig::default()
+            },
+            ..AnalysisConfig::default()
+        };
+        assert!(analyze(b"some data", &config).is_err());
+    }
+}
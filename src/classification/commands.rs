@@ -0,0 +1,236 @@
+use crate::types::{FoundString, Tag};
+
+/// Score boost applied to shell command lines.
+pub const COMMAND_BOOST: i32 = 25;
+
+/// Score boost applied to SQL statements.
+pub const SQL_BOOST: i32 = 15;
+
+/// Shell interpreters and launchers that introduce a command line.
+///
+/// Matched case-insensitively against whitespace-separated words; each entry
+/// lists the interpreter and the flag that makes it run the rest of the
+/// string as a command (empty when the launcher needs no flag).
+const SHELL_LAUNCHERS: &[(&str, &str)] = &[
+    ("/bin/sh", "-c"),
+    ("/bin/bash", "-c"),
+    ("/bin/zsh", "-c"),
+    ("/usr/bin/env", ""),
+    ("sh", "-c"),
+    ("bash", "-c"),
+    ("cmd", "/c"),
+    ("cmd.exe", "/c"),
+    ("cmd", "/k"),
+    ("cmd.exe", "/k"),
+    ("powershell", ""),
+    ("powershell.exe", ""),
+    ("pwsh", ""),
+];
+
+/// Commands commonly abused by malware; a string starting with one of these
+/// followed by arguments is treated as a command line.
+const SUSPICIOUS_COMMANDS: &[&str] = &[
+    "schtasks",
+    "reg",
+    "vssadmin",
+    "wmic",
+    "bcdedit",
+    "netsh",
+    "certutil",
+    "bitsadmin",
+    "rundll32",
+    "regsvr32",
+    "mshta",
+    "chmod",
+    "crontab",
+    "wget",
+    "curl",
+    "nc",
+];
+
+/// Returns `true` if `text` looks like a shell command line.
+///
+/// Recognizes an interpreter invoked with its command flag (`/bin/sh -c`,
+/// `cmd.exe /c`, `powershell ...`) anywhere in the string, or a string that
+/// starts with a commonly abused command followed by arguments
+/// (`vssadmin delete shadows /all`).
+pub fn is_shell_command(text: &str) -> bool {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|w| {
+            w.trim_matches(|c| c == '"' || c == '\'')
+                .to_ascii_lowercase()
+        })
+        .collect();
+
+    let launcher = words.iter().enumerate().any(|(i, word)| {
+        SHELL_LAUNCHERS.iter().any(|(name, flag)| {
+            word == name
+                && match words.get(i + 1) {
+                    Some(next) => flag.is_empty() || next == flag,
+                    None => false,
+                }
+        })
+    });
+    if launcher {
+        return true;
+    }
+
+    match words.first() {
+        Some(first) if words.len() >= 2 => {
+            let command = first.strip_suffix(".exe").unwrap_or(first);
+            SUSPICIOUS_COMMANDS.contains(&command)
+        }
+        _ => false,
+    }
+}
+
+/// Keyword sequences that make up a plausible SQL statement. Each keyword
+/// must appear as a whole word after the previous one.
+const SQL_PATTERNS: &[&[&str]] = &[
+    &["select", "from"],
+    &["insert", "into", "values"],
+    &["insert", "into", "select"],
+    &["update", "set"],
+    &["delete", "from"],
+    &["create", "table"],
+    &["create", "index", "on"],
+    &["drop", "table"],
+    &["alter", "table"],
+    &["pragma"],
+];
+
+/// Returns `true` if `text` looks like a SQL statement.
+///
+/// The statement must start with a SQL verb and contain the keywords that
+/// verb requires in order (`SELECT ... FROM`, `UPDATE ... SET`, ...). It
+/// must also look like code rather than prose: either the verb is written in
+/// upper case or the text contains SQL punctuation (`*`, `=`, `?`, `(`, `,`
+/// or `;`). This keeps "Select a file from the list" untagged. `PRAGMA`
+/// statements need an argument.
+pub fn is_sql(text: &str) -> bool {
+    let trimmed = text.trim_start();
+    let verb_upper = trimmed
+        .split_whitespace()
+        .next()
+        .is_some_and(|verb| verb.bytes().all(|b| b.is_ascii_uppercase()));
+    let has_punctuation = trimmed.contains(['*', '=', '?', '(', ',', ';']);
+    if !verb_upper && !has_punctuation {
+        return false;
+    }
+
+    let words: Vec<String> = text
+        .split(|c: char| c.is_whitespace() || c == '(' || c == ',')
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_ascii_lowercase())
+        .collect();
+    if words.len() < 2 {
+        return false;
+    }
+
+    SQL_PATTERNS.iter().any(|pattern| {
+        if words[0] != pattern[0] {
+            return false;
+        }
+        let mut rest = words[1..].iter();
+        pattern[1..]
+            .iter()
+            .all(|keyword| rest.any(|word| word == keyword))
+    })
+}
+
+/// Tags shell command lines with `Command` and SQL statements with `Sql`,
+/// boosting their scores.
+///
+/// Returns `true` if anything matched. Repeated calls do not apply the boosts
+/// twice.
+pub fn tag_commands(string: &mut FoundString) -> bool {
+    let mut matched = false;
+
+    if is_shell_command(&string.text) {
+        matched = true;
+        if !string.tags.contains(&Tag::Command) {
+            string.tags.push(Tag::Command);
+            string.score += COMMAND_BOOST;
+        }
+    }
+
+    if is_sql(&string.text) {
+        matched = true;
+        if !string.tags.contains(&Tag::Sql) {
+            string.tags.push(Tag::Sql);
+            string.score += SQL_BOOST;
+        }
+    }
+
+    matched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classification::tests::found;
+
+    #[test]
+    fn test_shell_commands() {
+        assert!(is_shell_command("/bin/sh -c \"rm -rf /tmp/x\""));
+        assert!(is_shell_command("cmd.exe /c del %TEMP%\\payload.exe"));
+        assert!(is_shell_command("CMD /C whoami"));
+        assert!(is_shell_command("powershell -enc SQBFAFgA"));
+        assert!(is_shell_command("vssadmin delete shadows /all /quiet"));
+        assert!(is_shell_command(
+            "schtasks /create /tn updater /tr evil.exe"
+        ));
+    }
+
+    #[test]
+    fn test_shell_command_near_misses() {
+        assert!(!is_shell_command("/bin/sh"));
+        assert!(!is_shell_command("Usage: sh [options]"));
+        assert!(!is_shell_command("curl"));
+        assert!(!is_shell_command("Failed to start cmd"));
+    }
+
+    #[test]
+    fn test_sql() {
+        assert!(is_sql("SELECT id, name FROM users WHERE id = ?"));
+        assert!(is_sql("insert into logins(user, pass) values (?, ?)"));
+        assert!(is_sql("UPDATE settings SET value = ? WHERE key = ?"));
+        assert!(is_sql("CREATE TABLE IF NOT EXISTS cookies (host TEXT)"));
+        assert!(is_sql("DELETE FROM history"));
+        assert!(is_sql("PRAGMA journal_mode=WAL"));
+    }
+
+    #[test]
+    fn test_sql_near_misses() {
+        assert!(!is_sql("Please select a file from the list"));
+        assert!(!is_sql("Select a file from the list"));
+        assert!(!is_sql("Delete from history"));
+        assert!(is_sql("select * from moz_cookies"));
+        assert!(!is_sql("select"));
+        assert!(!is_sql("SELECT"));
+        assert!(!is_sql("Update available"));
+        assert!(!is_sql("Delete this item?"));
+    }
+
+    #[test]
+    fn test_tag_commands() {
+        let mut command = found("cmd.exe /c net user admin P@ss /add");
+        assert!(tag_commands(&mut command));
+        assert_eq!(command.tags, vec![Tag::Command]);
+        assert_eq!(command.score, 10 + COMMAND_BOOST);
+
+        // Boost is not applied twice
+        assert!(tag_commands(&mut command));
+        assert_eq!(command.score, 10 + COMMAND_BOOST);
+
+        let mut sql = found("SELECT origin_url, password_value FROM logins");
+        assert!(tag_commands(&mut sql));
+        assert_eq!(sql.tags, vec![Tag::Sql]);
+        assert_eq!(sql.score, 10 + SQL_BOOST);
+
+        let mut benign = found("Hello, world");
+        assert!(!tag_commands(&mut benign));
+        assert!(benign.tags.is_empty());
+    }
+}
@@ -0,0 +1,237 @@
+use crate::types::ContainerInfo;
+use serde::Serialize;
+use std::sync::OnceLock;
+
+/// AES (Rijndael) forward S-box.
+pub const AES_SBOX: [u8; 256] = [
+    0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab, 0x76,
+    0xca, 0x82, 0xc9, 0x7d, 0xfa, 0x59, 0x47, 0xf0, 0xad, 0xd4, 0xa2, 0xaf, 0x9c, 0xa4, 0x72, 0xc0,
+    0xb7, 0xfd, 0x93, 0x26, 0x36, 0x3f, 0xf7, 0xcc, 0x34, 0xa5, 0xe5, 0xf1, 0x71, 0xd8, 0x31, 0x15,
+    0x04, 0xc7, 0x23, 0xc3, 0x18, 0x96, 0x05, 0x9a, 0x07, 0x12, 0x80, 0xe2, 0xeb, 0x27, 0xb2, 0x75,
+    0x09, 0x83, 0x2c, 0x1a, 0x1b, 0x6e, 0x5a, 0xa0, 0x52, 0x3b, 0xd6, 0xb3, 0x29, 0xe3, 0x2f, 0x84,
+    0x53, 0xd1, 0x00, 0xed, 0x20, 0xfc, 0xb1, 0x5b, 0x6a, 0xcb, 0xbe, 0x39, 0x4a, 0x4c, 0x58, 0xcf,
+    0xd0, 0xef, 0xaa, 0xfb, 0x43, 0x4d, 0x33, 0x85, 0x45, 0xf9, 0x02, 0x7f, 0x50, 0x3c, 0x9f, 0xa8,
+    0x51, 0xa3, 0x40, 0x8f, 0x92, 0x9d, 0x38, 0xf5, 0xbc, 0xb6, 0xda, 0x21, 0x10, 0xff, 0xf3, 0xd2,
+    0xcd, 0x0c, 0x13, 0xec, 0x5f, 0x97, 0x44, 0x17, 0xc4, 0xa7, 0x7e, 0x3d, 0x64, 0x5d, 0x19, 0x73,
+    0x60, 0x81, 0x4f, 0xdc, 0x22, 0x2a, 0x90, 0x88, 0x46, 0xee, 0xb8, 0x14, 0xde, 0x5e, 0x0b, 0xdb,
+    0xe0, 0x32, 0x3a, 0x0a, 0x49, 0x06, 0x24, 0x5c, 0xc2, 0xd3, 0xac, 0x62, 0x91, 0x95, 0xe4, 0x79,
+    0xe7, 0xc8, 0x37, 0x6d, 0x8d, 0xd5, 0x4e, 0xa9, 0x6c, 0x56, 0xf4, 0xea, 0x65, 0x7a, 0xae, 0x08,
+    0xba, 0x78, 0x25, 0x2e, 0x1c, 0xa6, 0xb4, 0xc6, 0xe8, 0xdd, 0x74, 0x1f, 0x4b, 0xbd, 0x8b, 0x8a,
+    0x70, 0x3e, 0xb5, 0x66, 0x48, 0x03, 0xf6, 0x0e, 0x61, 0x35, 0x57, 0xb9, 0x86, 0xc1, 0x1d, 0x9e,
+    0xe1, 0xf8, 0x98, 0x11, 0x69, 0xd9, 0x8e, 0x94, 0x9b, 0x1e, 0x87, 0xe9, 0xce, 0x55, 0x28, 0xdf,
+    0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb, 0x16,
+];
+
+/// SHA-256 initial hash values.
+pub const SHA256_IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// First 16 SHA-256 round constants.
+pub const SHA256_K: [u32; 16] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+];
+
+/// SHA-1 initial hash values. The first four are also MD5's.
+pub const SHA1_IV: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+/// First 16 entries of the MD5 sine table, `floor(abs(sin(i + 1)) * 2^32)`.
+pub const MD5_SINE_TABLE: [u32; 16] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+];
+
+/// First words of the Blowfish P-array, the hexadecimal digits of pi.
+pub const BLOWFISH_P: [u32; 8] = [
+    0x243f6a88, 0x85a308d3, 0x13198a2e, 0x03707344, 0xa4093822, 0x299f31d0, 0x082efa98, 0xec4e6c89,
+];
+
+/// Leading bytes, most significant first, shared by the RFC 2409 and RFC
+/// 3526 MODP Diffie-Hellman primes of every size.
+pub const MODP_PRIME_PREFIX: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xc9, 0x0f, 0xda, 0xa2, 0x21, 0x68, 0xc2, 0x34,
+    0xc4, 0xc6, 0x62, 0x8b, 0x80, 0xdc, 0x1c, 0xd1, 0x29, 0x02, 0x4e, 0x08, 0x8a, 0x67, 0xcc, 0x74,
+];
+
+/// Leading bytes, most significant first, shared by the RFC 7919 `ffdhe`
+/// Diffie-Hellman primes of every size.
+pub const FFDHE_PRIME_PREFIX: [u8; 24] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xad, 0xf8, 0x54, 0x58, 0xa2, 0xbb, 0x4a, 0x9a,
+    0xaf, 0xdc, 0x56, 0x20, 0x27, 0x3d, 0x3c, 0xf1,
+];
+
+/// The NIST P-256 field prime, most significant byte first.
+pub const P256_PRIME: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+];
+
+/// A well-known cryptographic constant found in a binary.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CryptoArtifact {
+    /// What the constant is, e.g. `AES S-box` or `SHA-256 IV`
+    pub name: &'static str,
+    /// File offset of the first byte
+    pub offset: u64,
+    /// Number of bytes matched
+    pub length: u32,
+    /// Name of the section holding the constant, if any does
+    pub section: Option<String>,
+    /// Virtual address of the first byte, if its section is mapped
+    pub rva: Option<u64>,
+}
+
+/// Byte patterns to search for: every table as stored on little- and
+/// big-endian targets, and every prime in both byte orders, as big-number
+/// libraries commonly keep them least significant limb first.
+fn patterns() -> &'static [(&'static str, Vec<u8>)] {
+    static PATTERNS: OnceLock<Vec<(&'static str, Vec<u8>)>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        let words: [(&'static str, &[u32]); 5] = [
+            ("SHA-256 IV", &SHA256_IV),
+            ("SHA-256 round constants", &SHA256_K),
+            ("SHA-1 IV", &SHA1_IV),
+            ("MD5 sine table", &MD5_SINE_TABLE),
+            ("Blowfish P-array", &BLOWFISH_P),
+        ];
+        let primes: [(&'static str, &[u8]); 3] = [
+            ("MODP Diffie-Hellman prime", &MODP_PRIME_PREFIX),
+            ("FFDHE Diffie-Hellman prime", &FFDHE_PRIME_PREFIX),
+            ("NIST P-256 prime", &P256_PRIME),
+        ];
+
+        let mut patterns = vec![("AES S-box", AES_SBOX.to_vec())];
+        for (name, table) in words {
+            patterns.push((name, table.iter().flat_map(|w| w.to_le_bytes()).collect()));
+            patterns.push((name, table.iter().flat_map(|w| w.to_be_bytes()).collect()));
+        }
+        for (name, prime) in primes {
+            patterns.push((name, prime.to_vec()));
+            patterns.push((name, prime.iter().rev().copied().collect()));
+        }
+        patterns
+    })
+}
+
+/// Scans raw bytes for well-known cryptographic constants.
+///
+/// Unlike the string classifiers this looks at every byte, not just at
+/// printable runs: the AES S-box, SHA-1 and SHA-256 initialization vectors,
+/// the SHA-256 round constants, the MD5 sine table, the Blowfish P-array,
+/// and the standard Diffie-Hellman and P-256 primes. Word tables match in
+/// either byte order. Offsets are relative to `data`; results are in offset
+/// order.
+///
+/// # Examples
+///
+/// ```rust
+/// use stringy::classification::crypto::{AES_SBOX, scan_crypto_constants};
+///
+/// let mut data = vec![0u8; 64];
+/// data.extend_from_slice(&AES_SBOX);
+/// let found = scan_crypto_constants(&data);
+/// assert_eq!(found[0].name, "AES S-box");
+/// assert_eq!(found[0].offset, 64);
+/// ```
+pub fn scan_crypto_constants(data: &[u8]) -> Vec<CryptoArtifact> {
+    let mut found = Vec::new();
+    for (name, pattern) in patterns() {
+        let Some((&first, _)) = pattern.split_first() else {
+            continue;
+        };
+        let mut start = 0;
+        while let Some(at) = data[start..].iter().position(|&b| b == first) {
+            let offset = start + at;
+            if data[offset..].starts_with(pattern) {
+                found.push(CryptoArtifact {
+                    name,
+                    offset: offset as u64,
+                    length: pattern.len() as u32,
+                    section: None,
+                    rva: None,
+                });
+            }
+            start = offset + 1;
+        }
+    }
+    found.sort_by_key(|artifact| (artifact.offset, artifact.name));
+    found
+}
+
+/// Scans a whole parsed file for cryptographic constants, as
+/// [`scan_crypto_constants`], and attributes each to the section holding
+/// it.
+pub fn find_crypto_artifacts(data: &[u8], container: &ContainerInfo) -> Vec<CryptoArtifact> {
+    let mut found = scan_crypto_constants(data);
+    for artifact in &mut found {
+        let Some(section) = container.sections.iter().find(|section| {
+            artifact.offset >= section.offset && artifact.offset - section.offset < section.size
+        }) else {
+            continue;
+        };
+        artifact.section = Some(section.name.clone());
+        artifact.rva = section
+            .rva
+            .map(|rva| rva + (artifact.offset - section.offset));
+    }
+    if !found.is_empty() {
+        log::info!("Found {} cryptographic constants", found.len());
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aes_sbox_detected() {
+        // Spot-check the table itself against FIPS-197
+        assert_eq!(AES_SBOX[..4], [0x63, 0x7c, 0x77, 0x7b]);
+        assert_eq!(AES_SBOX[0x53], 0xed);
+        assert_eq!(AES_SBOX[0xff], 0x16);
+
+        let mut data = b"\x00padding before the table\x00".to_vec();
+        let offset = data.len() as u64;
+        data.extend_from_slice(&AES_SBOX);
+        data.extend_from_slice(b"\x00and after");
+
+        let found = scan_crypto_constants(&data);
+        assert_eq!(
+            found,
+            vec![CryptoArtifact {
+                name: "AES S-box",
+                offset,
+                length: 256,
+                section: None,
+                rva: None,
+            }]
+        );
+        // A partial table is not reported
+        assert!(scan_crypto_constants(&AES_SBOX[..255]).is_empty());
+    }
+
+    #[test]
+    fn test_word_tables_in_both_byte_orders() {
+        let mut data = vec![0xccu8; 8];
+        data.extend(SHA256_IV.iter().flat_map(|w| w.to_be_bytes()));
+        data.extend(MD5_SINE_TABLE.iter().flat_map(|w| w.to_le_bytes()));
+
+        let found: Vec<(&str, u64)> = scan_crypto_constants(&data)
+            .iter()
+            .map(|artifact| (artifact.name, artifact.offset))
+            .collect();
+        assert_eq!(found, vec![("SHA-256 IV", 8), ("MD5 sine table", 40)]);
+    }
+
+    #[test]
+    fn test_prime_stored_least_significant_first() {
+        let mut limbs: Vec<u8> = vec![0x5a; 96];
+        limbs.extend(MODP_PRIME_PREFIX.iter().rev());
+        let found = scan_crypto_constants(&limbs);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name, "MODP Diffie-Hellman prime");
+        assert_eq!(found[0].offset, 96);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::types::{FoundString, Result, StringyError, Tag};
+use regex::Regex;
+
+/// A user-supplied tagging rule.
+///
+/// Strings matching `pattern` anywhere are tagged `Tag::Custom(name)` and
+/// get `score_bonus` added to their score. Rules let users flag
+/// domain-specific patterns, such as internal host names or license keys,
+/// without changes to the built-in classifiers.
+#[derive(Debug, Clone)]
+pub struct TagRule {
+    /// Rule name, used as the custom tag's name
+    pub name: String,
+    /// Pattern searched for in the string's text
+    pub pattern: Regex,
+    /// Score added to matching strings
+    pub score_bonus: i32,
+}
+
+impl TagRule {
+    /// Compiles a rule from a regular expression.
+    ///
+    /// Returns a [`StringyError::ConfigError`] if the name is empty or the
+    /// pattern is not a valid regular expression.
+    pub fn new(name: impl Into<String>, pattern: &str, score_bonus: i32) -> Result<Self> {
+        let name = name.into();
+        if name.is_empty() {
+            return Err(StringyError::ConfigError(
+                "tag rule name must not be empty".to_string(),
+            ));
+        }
+        let pattern = Regex::new(pattern).map_err(|e| {
+            StringyError::ConfigError(format!("invalid pattern for tag rule '{name}': {e}"))
+        })?;
+        Ok(Self {
+            name,
+            pattern,
+            score_bonus,
+        })
+    }
+
+    /// The tag this rule applies.
+    pub fn tag(&self) -> Tag {
+        Tag::Custom(self.name.clone())
+    }
+
+    /// Returns `true` if the rule matches `text`.
+    pub fn is_match(&self, text: &str) -> bool {
+        self.pattern.is_match(text)
+    }
+}
+
+/// Applies every matching rule to `string`, adding its tag and score bonus.
+///
+/// Returns `true` if any rule matched. Repeated calls do not apply a rule's
+/// bonus twice.
+pub fn apply_rules(string: &mut FoundString, rules: &[TagRule]) -> bool {
+    let mut matched = false;
+    for rule in rules {
+        if !rule.is_match(&string.text) {
+            continue;
+        }
+        matched = true;
+        let tag = rule.tag();
+        if !string.tags.contains(&tag) {
+            string.tags.push(tag);
+            string.score += rule.score_bonus;
+        }
+    }
+    matched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classification::tests::found;
+
+    fn rules() -> Vec<TagRule> {
+        vec![
+            TagRule::new("internal-host", r"\b[a-z0-9-]+\.corp\.example\b", 40).unwrap(),
+            TagRule::new("license-key", r"\b[A-Z0-9]{5}(?:-[A-Z0-9]{5}){4}\b", 25).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_custom_rules() {
+        let rules = rules();
+
+        let mut host = found("connecting to build01.corp.example:443");
+        assert!(apply_rules(&mut host, &rules));
+        assert_eq!(host.tags, vec![Tag::Custom("internal-host".to_string())]);
+        assert_eq!(host.score, 10 + 40);
+
+        let mut key = found("key=ABCDE-12345-FGHIJ-67890-KLMNO");
+        assert!(apply_rules(&mut key, &rules));
+        assert_eq!(key.tags, vec![Tag::Custom("license-key".to_string())]);
+        assert_eq!(key.score, 10 + 25);
+
+        // Bonus is not applied twice
+        assert!(apply_rules(&mut key, &rules));
+        assert_eq!(key.score, 10 + 25);
+
+        let mut other = found("nothing to see here");
+        assert!(!apply_rules(&mut other, &rules));
+        assert!(other.tags.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        assert!(matches!(
+            TagRule::new("broken", "(unclosed", 10),
+            Err(StringyError::ConfigError(_))
+        ));
+        assert!(TagRule::new("", "x", 10).is_err());
+    }
+
+    #[test]
+    fn test_custom_tag_serialization() {
+        let tag = rules()[0].tag();
+        let json = serde_json::to_string(&tag).unwrap();
+        assert_eq!(json, r#"{"custom":"internal-host"}"#);
+        assert_eq!(serde_json::from_str::<Tag>(&json).unwrap(), tag);
+    }
+}
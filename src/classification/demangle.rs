@@ -0,0 +1,319 @@
+use cpp_demangle::Symbol;
+use serde::Serialize;
+use std::fmt;
+
+/// Source language of a mangled symbol name, as detected by [`demangle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    /// Itanium C++ ABI (`_Z...`), as produced by GCC and Clang
+    Cpp,
+    /// Rust, legacy (`_ZN...17h<hash>E`) or v0 (`_R...`)
+    Rust,
+    /// Swift 4.2 and later (`$s...`)
+    Swift,
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Cpp => "C++",
+            Self::Rust => "Rust",
+            Self::Swift => "Swift",
+        })
+    }
+}
+
+/// Strips Mach-O's extra leading underscore and returns the name if it
+/// carries a Rust mangling prefix: `_R` (v0) or `_ZN` (legacy).
+fn rust_body(name: &str) -> Option<&str> {
+    let name = name
+        .strip_prefix('_')
+        .filter(|n| n.starts_with('_'))
+        .unwrap_or(name);
+    (name.starts_with("_R") || name.starts_with("_ZN")).then_some(name)
+}
+
+/// Returns `true` if a legacy (`_ZN...E`) name ends in the `17h<16 hex>E`
+/// hash segment rustc appends, which plain C++ names never have.
+fn has_legacy_rust_hash(name: &str) -> bool {
+    // LLVM may append suffixes such as `.llvm.1234` after the closing `E`
+    let name = name.split('.').next().unwrap_or(name);
+    let Some(path) = name.strip_suffix('E') else {
+        return false;
+    };
+    path.len() > 19
+        && path.is_char_boundary(path.len() - 19)
+        && path[path.len() - 19..]
+            .strip_prefix("17h")
+            .is_some_and(|hash| hash.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+/// Strips the extra leading underscore Mach-O prepends to C symbol names, so
+/// `__ZN3fooEv` is treated like `_ZN3fooEv`.
+fn itanium_body(name: &str) -> Option<&str> {
+    let name = if name.starts_with("__Z") {
+        &name[1..]
+    } else {
+        name
+    };
+    name.starts_with("_Z").then_some(name)
+}
+
+/// Returns `true` if `name` looks like an Itanium C++ ABI mangled name
+/// (`_Z...`, or `__Z...` on Mach-O).
+pub fn is_itanium_mangled(name: &str) -> bool {
+    itanium_body(name).is_some()
+}
+
+/// Demangles an Itanium C++ ABI symbol name, as produced by GCC and Clang.
+///
+/// Returns `None` when the name is not mangled or does not parse, so callers
+/// can keep the raw name as-is.
+///
+/// ```
+/// use stringy::classification::demangle::demangle_itanium;
+///
+/// assert_eq!(demangle_itanium("_ZN3foo3barEv").as_deref(), Some("foo::bar()"));
+/// assert_eq!(demangle_itanium("CreateFileW"), None);
+/// ```
+pub fn demangle_itanium(name: &str) -> Option<String> {
+    let mangled = itanium_body(name)?;
+    let symbol = Symbol::new(mangled).ok()?;
+    symbol.demangle().ok()
+}
+
+/// Demangles a Rust symbol name in either the legacy (`_ZN...17h<hash>E`) or
+/// v0 (`_R...`) scheme.
+///
+/// The trailing hash and crate disambiguators are left out of the result.
+/// Returns `None` for anything that is not a Rust symbol, including C++
+/// names, which share the legacy `_ZN` prefix but lack the hash segment.
+///
+/// ```
+/// use stringy::classification::demangle::demangle_rust;
+///
+/// assert_eq!(demangle_rust("_RNvCs1234_7mycrate3foo").as_deref(), Some("mycrate::foo"));
+/// assert_eq!(demangle_rust("_ZN3foo3barEv"), None);
+/// ```
+pub fn demangle_rust(name: &str) -> Option<String> {
+    let mangled = rust_body(name)?;
+    if mangled.starts_with("_ZN") && !has_legacy_rust_hash(mangled) {
+        return None;
+    }
+    let demangled = rustc_demangle::try_demangle(mangled).ok()?;
+    Some(format!("{:#}", demangled))
+}
+
+/// Strips Mach-O's extra leading underscore and the `$s` (or Swift 4.2's
+/// `$S`) prefix from a Swift symbol name.
+fn swift_body(name: &str) -> Option<&str> {
+    let name = name.strip_prefix('_').unwrap_or(name);
+    name.strip_prefix("$s").or_else(|| name.strip_prefix("$S"))
+}
+
+/// Demangles the entity path of a Swift symbol name, e.g.
+/// `$s4main3FooV3baryyF` to `main.Foo.bar`.
+///
+/// This is a simplified demangler: it reads the module and the chain of
+/// nominal types (struct, class, enum, protocol) down to the entity's name
+/// and leaves out the signature, accessor kind and argument labels. Names
+/// using word substitutions or Punycode identifiers, and names from the
+/// standard library's substitution table (`$sSS...`), return `None`.
+///
+/// ```
+/// use stringy::classification::demangle::demangle_swift;
+///
+/// assert_eq!(demangle_swift("$s4main3FooV3baryyF").as_deref(), Some("main.Foo.bar"));
+/// assert_eq!(demangle_swift("_ZN3foo3barEv"), None);
+/// ```
+pub fn demangle_swift(name: &str) -> Option<String> {
+    let mut rest = swift_body(name)?.as_bytes();
+    let mut path = Vec::new();
+    loop {
+        let digits = rest.iter().take_while(|b| b.is_ascii_digit()).count();
+        // A leading zero starts a word substitution or a Punycode identifier
+        if digits == 0 || rest[0] == b'0' {
+            break;
+        }
+        let len: usize = std::str::from_utf8(&rest[..digits]).ok()?.parse().ok()?;
+        let identifier = rest.get(digits..digits.checked_add(len)?)?;
+        path.push(std::str::from_utf8(identifier).ok()?);
+        rest = &rest[digits + len..];
+
+        // After the module, only a nominal type continues the path; anything
+        // else (argument labels, the signature) belongs to the entity
+        if path.len() > 1 {
+            match rest {
+                [b'V' | b'C' | b'O' | b'P', next, ..] if next.is_ascii_digit() => rest = &rest[1..],
+                _ => break,
+            }
+        }
+    }
+    (path.len() >= 2).then(|| path.join("."))
+}
+
+/// Detects how `name` was mangled and demangles it, returning the display
+/// name and the language.
+///
+/// Swift names are recognized by their `$s` prefix, Rust names by `_R` or a
+/// legacy `_ZN...E` name ending in a hash segment, and Itanium C++ names by
+/// `_Z`. Rust is tried before C++ because legacy Rust names are also valid
+/// Itanium names, and the C++ demangler would render their hash as a path
+/// component. Mach-O's extra leading underscore is accepted for all three.
+/// Returns `None` for plain C names and names that do not parse.
+///
+/// ```
+/// use stringy::classification::demangle::{Language, demangle};
+///
+/// assert_eq!(
+///     demangle("_ZN3foo3barEv"),
+///     Some(("foo::bar()".to_string(), Language::Cpp))
+/// );
+/// assert_eq!(demangle("printf"), None);
+/// ```
+pub fn demangle(name: &str) -> Option<(String, Language)> {
+    if swift_body(name).is_some() {
+        return demangle_swift(name).map(|demangled| (demangled, Language::Swift));
+    }
+    if let Some(demangled) = demangle_rust(name) {
+        return Some((demangled, Language::Rust));
+    }
+    demangle_itanium(name).map(|demangled| (demangled, Language::Cpp))
+}
+
+/// Demangles a Swift, Rust or Itanium C++ symbol name; [`demangle`] without
+/// the language.
+pub fn demangle_symbol(name: &str) -> Option<String> {
+    demangle(name).map(|(demangled, _)| demangled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_demangle_itanium() {
+        assert_eq!(demangle_itanium("_ZN3fooEv").as_deref(), Some("foo()"));
+        assert_eq!(
+            demangle_itanium("_ZNSt6vectorIiSaIiEE9push_backERKi").as_deref(),
+            Some("std::vector<int, std::allocator<int> >::push_back(int const&)")
+        );
+        assert_eq!(
+            demangle_itanium("_ZN7Crypter7decryptEPKhm").as_deref(),
+            Some("Crypter::decrypt(unsigned char const*, unsigned long)")
+        );
+        // Mach-O adds a leading underscore
+        assert_eq!(demangle_itanium("__ZN3fooEv").as_deref(), Some("foo()"));
+    }
+
+    #[test]
+    fn test_non_mangled_passthrough() {
+        for name in ["main", "getaddrinfo", "_start", "_Z", "_Zinvalid"] {
+            assert_eq!(demangle_itanium(name), None, "{}", name);
+        }
+        assert!(!is_itanium_mangled("printf"));
+        assert!(is_itanium_mangled("__ZN3fooEv"));
+    }
+
+    #[test]
+    fn test_demangle_rust() {
+        assert_eq!(
+            demangle_rust("_RNvCs1234_7mycrate3foo").as_deref(),
+            Some("mycrate::foo")
+        );
+        assert_eq!(
+            demangle_rust("_ZN4core3fmt9Formatter3pad17h0123456789abcdefE").as_deref(),
+            Some("core::fmt::Formatter::pad")
+        );
+        assert_eq!(
+            demangle_rust("_ZN3std2rt10lang_start17h5e0e9ab7a1e5f8c2E.llvm.4242").as_deref(),
+            Some("std::rt::lang_start")
+        );
+        // Mach-O adds a leading underscore
+        assert_eq!(
+            demangle_rust("__RNvCs1234_7mycrate3foo").as_deref(),
+            Some("mycrate::foo")
+        );
+    }
+
+    #[test]
+    fn test_demangle_rust_rejects_other_symbols() {
+        for name in [
+            "main",
+            "_ZN3foo3barEv",
+            "_ZN3foo3barE",
+            "_ZNSt6vectorIiSaIiEE9push_backERKi",
+            "_Rgarbage",
+            "Rust",
+        ] {
+            assert_eq!(demangle_rust(name), None, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_demangle_symbol_prefers_rust() {
+        assert_eq!(
+            demangle_symbol("_ZN4core3fmt9Formatter3pad17h0123456789abcdefE").as_deref(),
+            Some("core::fmt::Formatter::pad")
+        );
+        assert_eq!(demangle_symbol("_ZN3fooEv").as_deref(), Some("foo()"));
+        assert_eq!(demangle_symbol("printf"), None);
+    }
+
+    #[test]
+    fn test_demangle_swift() {
+        for (name, expected) in [
+            ("$s4main3FooV3baryyF", "main.Foo.bar"),
+            ("$s4main5helloyyF", "main.hello"),
+            ("$s4main7counterSivp", "main.counter"),
+            (
+                "$s7Payload6LoaderC5fetch3url10Foundation4DataVSS_tYaKF",
+                "Payload.Loader.fetch",
+            ),
+            ("$s4main6ShapesO6circleyACSdcACmF", "main.Shapes.circle"),
+            ("$S4main5helloyyF", "main.hello"),
+            // Mach-O adds a leading underscore
+            ("_$s4main3FooVMa", "main.Foo"),
+        ] {
+            assert_eq!(demangle_swift(name).as_deref(), Some(expected), "{}", name);
+        }
+        for name in [
+            "$sSS",
+            "$s4main",
+            "$s4main0A3FooV",
+            "$s99main",
+            "_ZN3fooEv",
+            "main",
+        ] {
+            assert_eq!(demangle_swift(name), None, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_demangle_dispatch() {
+        assert_eq!(
+            demangle("_ZN7Crypter7decryptEPKhm"),
+            Some((
+                "Crypter::decrypt(unsigned char const*, unsigned long)".to_string(),
+                Language::Cpp
+            ))
+        );
+        assert_eq!(
+            demangle("_ZN4core3fmt9Formatter3pad17h0123456789abcdefE"),
+            Some(("core::fmt::Formatter::pad".to_string(), Language::Rust))
+        );
+        assert_eq!(
+            demangle("_RNvCs1234_7mycrate3foo"),
+            Some(("mycrate::foo".to_string(), Language::Rust))
+        );
+        assert_eq!(
+            demangle("_$s4main3FooV3baryyF"),
+            Some(("main.Foo.bar".to_string(), Language::Swift))
+        );
+        for name in ["printf", "_start", "$sSS", "__libc_start_main"] {
+            assert_eq!(demangle(name), None, "{}", name);
+        }
+        assert_eq!(Language::Cpp.to_string(), "C++");
+    }
+}
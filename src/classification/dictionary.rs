@@ -0,0 +1,81 @@
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+/// Common English words, one per line, including the vocabulary of error
+/// and UI messages.
+const WORDS: &str = include_str!("words.txt");
+
+/// Inflection suffixes stripped when a word is not found as-is, so that
+/// `files`, `failed` and `loading` match `file`, `fail` and `load`.
+const SUFFIXES: &[&str] = &["ing", "ed", "es", "s", "ly", "d"];
+
+fn word_set() -> &'static HashSet<&'static str> {
+    static SET: OnceLock<HashSet<&'static str>> = OnceLock::new();
+    SET.get_or_init(|| {
+        WORDS
+            .lines()
+            .map(str::trim)
+            .filter(|w| !w.is_empty())
+            .collect()
+    })
+}
+
+/// Returns `true` if `word` is a common English word or a regular
+/// inflection of one. Matching is case-insensitive.
+pub fn is_word(word: &str) -> bool {
+    let lower = word.to_ascii_lowercase();
+    let words = word_set();
+    words.contains(lower.as_str())
+        || SUFFIXES.iter().any(|suffix| {
+            lower
+                .strip_suffix(suffix)
+                .is_some_and(|stem| stem.len() >= 2 && words.contains(stem))
+        })
+}
+
+/// Share of the words in `s` that are recognized English words, from `0.0`
+/// to `1.0`.
+///
+/// Words are runs of ASCII letters at least two long, so `x7f9q2k` has none
+/// and scores `0.0`, while `connection failed` scores `1.0`.
+pub fn looks_like_language(s: &str) -> f32 {
+    let mut total = 0u32;
+    let mut known = 0u32;
+    for word in s
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .filter(|word| word.len() >= 2)
+    {
+        total += 1;
+        if is_word(word) {
+            known += 1;
+        }
+    }
+
+    if total == 0 {
+        return 0.0;
+    }
+    known as f32 / total as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_word() {
+        for word in ["connection", "Failed", "files", "loading", "the"] {
+            assert!(is_word(word), "{word}");
+        }
+        for word in ["xq", "kqzv", "rodata", "s"] {
+            assert!(!is_word(word), "{word}");
+        }
+    }
+
+    #[test]
+    fn test_looks_like_language() {
+        assert_eq!(looks_like_language("connection failed"), 1.0);
+        assert_eq!(looks_like_language("x7f9q2k"), 0.0);
+        assert_eq!(looks_like_language(""), 0.0);
+        assert_eq!(looks_like_language("Unable to open config: zqxv"), 0.8);
+    }
+}
@@ -0,0 +1,212 @@
+use crate::types::{FoundString, Tag};
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD, URL_SAFE, URL_SAFE_NO_PAD};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Score boost applied to strings containing a base64 blob.
+pub const BASE64_BOOST: i32 = 10;
+
+/// Additional boost when the blob decodes to text or a known file format.
+pub const DECODED_BOOST: i32 = 20;
+
+/// Minimum length of a base64 blob, so that words like `Test` or
+/// `FileName` are not taken for encodings.
+pub const MIN_BASE64_LENGTH: usize = 16;
+
+/// Maximum number of decoded bytes kept in a preview.
+pub const PREVIEW_LENGTH: usize = 48;
+
+/// Minimum share of printable bytes in decoded content that counts as text.
+const MIN_PRINTABLE_RATIO: f32 = 0.9;
+
+/// File signatures recognized in decoded content.
+const MAGICS: &[(&[u8], &str)] = &[
+    (b"MZ", "PE executable"),
+    (b"\x7fELF", "ELF executable"),
+    (b"PK\x03\x04", "ZIP archive"),
+    (b"%PDF", "PDF document"),
+    (b"\x1f\x8b", "gzip data"),
+    (b"\xca\xfe\xba\xbe", "Mach-O universal binary"),
+    (b"\xcf\xfa\xed\xfe", "Mach-O executable"),
+];
+
+/// Candidate tokens: runs of either base64 alphabet with their trailing
+/// padding, so `key=SGVsbG8=` yields `key=` and `SGVsbG8=`.
+fn tokens(text: &str) -> impl Iterator<Item = &str> {
+    static TOKEN: OnceLock<Regex> = OnceLock::new();
+    TOKEN
+        .get_or_init(|| Regex::new(r"[A-Za-z0-9+/_-]+=*").expect("token pattern is valid"))
+        .find_iter(text)
+        .map(|token| token.as_str())
+        .filter(|token| token.len() >= MIN_BASE64_LENGTH)
+}
+
+/// Decodes `token` if it is a base64 blob.
+///
+/// The token must use one alphabet throughout, standard (`+/`) or URL-safe
+/// (`-_`), and be correctly padded; blobs using `-` or `_` may omit their
+/// padding. It must also mix upper-case letters, lower-case letters and
+/// digits or symbols, which identifiers and hex digests do not.
+pub fn decode_base64(token: &str) -> Option<Vec<u8>> {
+    if token.len() < MIN_BASE64_LENGTH {
+        return None;
+    }
+
+    let body = token.trim_end_matches('=');
+    let padding = token.len() - body.len();
+    let standard = body.contains(['+', '/']);
+    let url_safe = body.contains(['-', '_']);
+    if padding > 2 || (standard && url_safe) {
+        return None;
+    }
+
+    let has_upper = body.bytes().any(|b| b.is_ascii_uppercase());
+    let has_lower = body.bytes().any(|b| b.is_ascii_lowercase());
+    let has_other = padding > 0 || standard || url_safe || body.bytes().any(|b| b.is_ascii_digit());
+    if !(has_upper && has_lower && has_other) {
+        return None;
+    }
+
+    if token.len().is_multiple_of(4) {
+        let engine = if url_safe { &URL_SAFE } else { &STANDARD };
+        engine.decode(token).ok()
+    } else if padding == 0 && url_safe {
+        URL_SAFE_NO_PAD.decode(token).ok()
+    } else {
+        None
+    }
+}
+
+/// Finds the first base64 blob in `text`, returning it and its decoded
+/// bytes.
+pub fn find_base64(text: &str) -> Option<(&str, Vec<u8>)> {
+    tokens(text).find_map(|token| decode_base64(token).map(|decoded| (token, decoded)))
+}
+
+/// Names the file format of `bytes` if it starts with a known signature.
+pub fn known_magic(bytes: &[u8]) -> Option<&'static str> {
+    MAGICS
+        .iter()
+        .find(|(magic, _)| bytes.starts_with(magic))
+        .map(|&(_, name)| name)
+}
+
+/// Returns `true` if `bytes` is mostly printable ASCII text.
+fn is_printable(bytes: &[u8]) -> bool {
+    if bytes.is_empty() {
+        return false;
+    }
+    let printable = bytes
+        .iter()
+        .filter(|&&b| b.is_ascii_graphic() || matches!(b, b' ' | b'\t' | b'\r' | b'\n'))
+        .count();
+    printable as f32 >= bytes.len() as f32 * MIN_PRINTABLE_RATIO
+}
+
+/// Renders the first [`PREVIEW_LENGTH`] bytes of `bytes`, replacing
+/// non-printable bytes with `.` as a hex dump would.
+pub fn preview(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .take(PREVIEW_LENGTH)
+        .map(|&b| {
+            if b.is_ascii_graphic() || b == b' ' {
+                b as char
+            } else {
+                '.'
+            }
+        })
+        .collect()
+}
+
+/// Tags strings containing a base64 blob and boosts their score.
+///
+/// When the blob decodes to text or to a known file format, such as an
+/// embedded PE payload, the score gets [`DECODED_BOOST`] on top and a
+/// preview of the decoded bytes is stored in `decoded`. Returns `true` if a
+/// blob was found. Repeated calls do not apply the boosts twice.
+pub fn tag_base64(string: &mut FoundString) -> bool {
+    let Some((_, decoded)) = find_base64(&string.text) else {
+        return false;
+    };
+
+    if !string.tags.contains(&Tag::Base64) {
+        string.tags.push(Tag::Base64);
+        string.score += BASE64_BOOST;
+        if known_magic(&decoded).is_some() || is_printable(&decoded) {
+            string.score += DECODED_BOOST;
+            string.decoded = Some(preview(&decoded));
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classification::tests::found;
+
+    // The first 36 bytes of a DOS header
+    const PE_HEADER: &str = "TVqQAAMAAAAEAAAA//8AALgAAAAAAAAAQAAAAAAAAAAAAAAA";
+
+    #[test]
+    fn test_pe_payload() {
+        let decoded = decode_base64(PE_HEADER).unwrap();
+        assert_eq!(known_magic(&decoded), Some("PE executable"));
+
+        let mut string = found(&format!("payload={PE_HEADER}"));
+        assert!(tag_base64(&mut string));
+        assert_eq!(string.tags, vec![Tag::Base64]);
+        assert_eq!(string.score, 10 + BASE64_BOOST + DECODED_BOOST);
+        assert!(string.decoded.as_deref().unwrap().starts_with("MZ."));
+
+        // Boost is not applied twice
+        assert!(tag_base64(&mut string));
+        assert_eq!(string.score, 10 + BASE64_BOOST + DECODED_BOOST);
+    }
+
+    #[test]
+    fn test_text_payload() {
+        let mut string = found("SGVsbG8sIFN0cmluZ3kgd29ybGQh");
+        assert!(tag_base64(&mut string));
+        assert_eq!(string.decoded.as_deref(), Some("Hello, Stringy world!"));
+    }
+
+    #[test]
+    fn test_url_safe() {
+        let unpadded = "aHR0cHM6Ly9leGFtcGxlLmNvbS8_YT0xJmI9fn5-";
+        assert_eq!(
+            decode_base64(unpadded).unwrap(),
+            b"https://example.com/?a=1&b=~~~"
+        );
+        assert_eq!(decode_base64("-__-IGNvbmZpZz0x-w==").unwrap().len(), 13);
+    }
+
+    #[test]
+    fn test_opaque_blob_has_no_preview() {
+        let mut string = found("-__-IGNvbmZpZz0x-w==");
+        assert!(tag_base64(&mut string));
+        assert_eq!(string.score, 10 + BASE64_BOOST);
+        assert_eq!(string.decoded, None);
+    }
+
+    #[test]
+    fn test_non_base64() {
+        for text in [
+            "Test",
+            "SGVsbG8=",
+            // Identifiers and hex digests
+            "GetModuleHandleExW",
+            "CreateRemoteThreadEx",
+            "d41d8cd98f00b204e9800998ecf8427e",
+            // Mixed alphabets and bad padding
+            "SGVsbG8+d29y_bGQhIQ==",
+            "SGVsbG8sIFN0cmluZ3kgd29ybGQh===",
+            "SGVsbG8sIFN0cmluZ3kgd29ybGQ",
+            "/usr/lib/x86_64-linux-gnu",
+        ] {
+            assert_eq!(find_base64(text), None, "{text}");
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use crate::types::FoundString;
+
+/// Default entropy, in bits per character, above which an unstructured
+/// string is treated as garbage.
+///
+/// English text stays around 4 bits per character but is exempted by its
+/// word structure; printable runs of random bytes approach log2 of their
+/// length, so they cross this once they are longer than 16 characters.
+pub const DEFAULT_MAX_ENTROPY: f32 = 4.0;
+
+/// Default score penalty for garbage strings when they are down-scored
+/// rather than dropped.
+pub const DEFAULT_ENTROPY_PENALTY: i32 = 30;
+
+/// Minimum share of letters and spaces in text with word-like structure.
+const MIN_WORD_CHAR_RATIO: f32 = 0.8;
+
+/// Minimum length of hex and base64 runs exempt from the filter.
+const MIN_ENCODED_LENGTH: usize = 16;
+
+/// Shannon entropy of `s` in bits per character.
+///
+/// Returns `0.0` for the empty string and for strings of one repeated
+/// character.
+pub fn shannon_entropy(s: &str) -> f32 {
+    let mut counts: Vec<(char, u32)> = Vec::new();
+    let mut total = 0u32;
+    for c in s.chars() {
+        total += 1;
+        match counts.iter_mut().find(|(seen, _)| *seen == c) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((c, 1)),
+        }
+    }
+
+    let total = total as f32;
+    counts
+        .iter()
+        .map(|&(_, count)| {
+            let p = count as f32 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Shannon entropy of `bytes` in bits per byte.
+///
+/// Ranges from `0.0`, for empty input and runs of one repeated byte, to
+/// `8.0` for uniformly distributed bytes.
+pub fn byte_entropy(bytes: &[u8]) -> f32 {
+    let mut counts = [0u64; 256];
+    for &byte in bytes {
+        counts[usize::from(byte)] += 1;
+    }
+
+    let total = bytes.len() as f64;
+    let entropy: f64 = counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / total;
+            -p * p.log2()
+        })
+        .sum();
+    entropy as f32
+}
+
+/// Returns `true` if `s` is a hex or base64 encoding: high entropy by
+/// nature, but meaningful (hashes, keys, embedded blobs).
+fn is_encoded(s: &str) -> bool {
+    if s.len() < MIN_ENCODED_LENGTH {
+        return false;
+    }
+    if s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return true;
+    }
+    let body = s.trim_end_matches('=');
+    s.len() - body.len() <= 2
+        && s.len().is_multiple_of(4)
+        && body
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'-' | b'_'))
+}
+
+/// Returns `true` if `s` reads like words: mostly letters and spaces, with
+/// at least one vowel.
+fn has_word_structure(s: &str) -> bool {
+    let total = s.chars().count();
+    if total == 0 {
+        return false;
+    }
+    let word_chars = s.chars().filter(|c| c.is_alphabetic() || *c == ' ').count();
+    let has_vowel = s
+        .chars()
+        .any(|c| matches!(c.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u'));
+    has_vowel && word_chars as f32 >= total as f32 * MIN_WORD_CHAR_RATIO
+}
+
+/// Returns `true` if `text` looks like random bytes rather than a string.
+///
+/// That is the case when its entropy exceeds `max_entropy`, it does not read
+/// like words, and it is not a hex or base64 encoding.
+pub fn is_garbage(text: &str, max_entropy: f32) -> bool {
+    shannon_entropy(text) > max_entropy && !has_word_structure(text) && !is_encoded(text)
+}
+
+/// What [`EntropyFilter`] does with garbage strings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GarbageAction {
+    /// Remove the string from the results
+    Drop,
+    /// Subtract the given penalty from the string's score
+    Penalize(i32),
+}
+
+/// Filter for high-entropy garbage among extracted strings; see
+/// [`is_garbage`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EntropyFilter {
+    /// Entropy in bits per character above which unstructured strings are
+    /// garbage
+    pub max_entropy: f32,
+    /// What to do with garbage strings
+    pub action: GarbageAction,
+}
+
+impl Default for EntropyFilter {
+    fn default() -> Self {
+        Self {
+            max_entropy: DEFAULT_MAX_ENTROPY,
+            action: GarbageAction::Penalize(DEFAULT_ENTROPY_PENALTY),
+        }
+    }
+}
+
+impl EntropyFilter {
+    /// Drops or down-scores the garbage strings in `strings`.
+    pub fn apply(&self, mut strings: Vec<FoundString>) -> Vec<FoundString> {
+        match self.action {
+            GarbageAction::Drop => {
+                strings.retain(|string| !is_garbage(&string.text, self.max_entropy));
+            }
+            GarbageAction::Penalize(penalty) => {
+                for string in &mut strings {
+                    if is_garbage(&string.text, self.max_entropy) {
+                        string.score -= penalty;
+                    }
+                }
+            }
+        }
+        strings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classification::tests::found;
+
+    const RANDOM: &str = "x9#Qv!2Lr$8Zk@pW3&mT7^bN";
+    const SENTENCE: &str = "The quick brown fox jumps over the lazy dog";
+
+    #[test]
+    fn test_shannon_entropy() {
+        assert_eq!(shannon_entropy(""), 0.0);
+        assert_eq!(shannon_entropy("aaaa"), 0.0);
+        assert_eq!(shannon_entropy("abab"), 1.0);
+        assert_eq!(shannon_entropy("abcd"), 2.0);
+        assert!(shannon_entropy(RANDOM) > DEFAULT_MAX_ENTROPY);
+    }
+
+    #[test]
+    fn test_byte_entropy() {
+        assert_eq!(byte_entropy(&[]), 0.0);
+        assert_eq!(byte_entropy(&[0; 64]), 0.0);
+        assert_eq!(byte_entropy(b"\x00\xff\x00\xff"), 1.0);
+        let every_byte: Vec<u8> = (0..=255).collect();
+        assert_eq!(byte_entropy(&every_byte), 8.0);
+    }
+
+    #[test]
+    fn test_random_string_is_garbage() {
+        assert!(is_garbage(RANDOM, DEFAULT_MAX_ENTROPY));
+
+        let filter = EntropyFilter {
+            action: GarbageAction::Drop,
+            ..EntropyFilter::default()
+        };
+        let kept = filter.apply(vec![found(RANDOM), found(SENTENCE)]);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].text, SENTENCE);
+    }
+
+    #[test]
+    fn test_sentence_is_kept() {
+        // High entropy, but words
+        assert!(shannon_entropy(SENTENCE) > DEFAULT_MAX_ENTROPY);
+        assert!(!is_garbage(SENTENCE, DEFAULT_MAX_ENTROPY));
+    }
+
+    #[test]
+    fn test_encoded_strings_are_exempt() {
+        assert!(!is_garbage(
+            "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+            DEFAULT_MAX_ENTROPY
+        ));
+        assert!(!is_garbage(
+            "U3RyaW5neU1jU3RyaW5nRmFjZSByb2Nrcw==",
+            DEFAULT_MAX_ENTROPY
+        ));
+    }
+
+    #[test]
+    fn test_penalize_action() {
+        let strings = EntropyFilter::default().apply(vec![found(RANDOM), found(SENTENCE)]);
+        assert_eq!(strings[0].score, 10 - DEFAULT_ENTROPY_PENALTY);
+        assert_eq!(strings[1].score, 10);
+    }
+}
@@ -0,0 +1,158 @@
+use crate::types::{FoundString, Tag};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Score boost applied to format strings.
+///
+/// Small on purpose: format strings mostly mark logging and UI text, and
+/// the tag is more useful as a filter than as a ranking signal.
+pub const FORMAT_STRING_BOOST: i32 = 5;
+
+/// printf-style conversions, `{}`-style placeholders and their escapes.
+///
+/// The escapes (`%%`, `{{`, `}}`) are matched so that they are consumed
+/// before they could be read as the start of a specifier.
+fn specifier_regex() -> &'static Regex {
+    static SPECIFIER: OnceLock<Regex> = OnceLock::new();
+    SPECIFIER.get_or_init(|| {
+        Regex::new(
+            r"(?x)
+              %%
+            | \{\{
+            | \}\}
+            | (?P<printf>
+                %
+                (?:[1-9][0-9]*\$|\(\w+\))?                  # position or mapping key
+                [-+\#0']*                                   # flags
+                (?:[0-9]+|\*(?:[1-9][0-9]*\$)?)?            # width
+                (?:\.(?:[0-9]+|\*(?:[1-9][0-9]*\$)?))?      # precision
+                (?:hh|ll|I64|I32|[hlLqjztI])?               # length
+                [diouxXeEfFgGaAcCsSpn@]
+              )
+            | (?P<brace>
+                \{
+                (?:[0-9]+|[A-Za-z_][A-Za-z0-9_]*)?
+                (?::[^{}]*)?
+                \}
+              )
+            ",
+        )
+        .expect("format specifier pattern is valid")
+    })
+}
+
+/// Returns the format specifiers in `text`, in order.
+///
+/// Recognizes C conversions with positions (`%1$s`), flags, width,
+/// precision and length modifiers (`%-08.3lf`, `%ls`, `%zu`, `%I64d`), and
+/// `{}`, `{0}` and `{name:fmt}` placeholders. Escaped `%%`, `{{` and `}}`
+/// are not specifiers. To keep percentages and environment variables such as
+/// `%APPDATA%` out, a C conversion may not be followed by a letter, and the
+/// space flag is not supported (`50% off`). Placeholders preceded by `$` are
+/// shell or script variables, not format placeholders.
+pub fn format_specifiers(text: &str) -> Vec<&str> {
+    specifier_regex()
+        .captures_iter(text)
+        .filter_map(|captures| {
+            if let Some(printf) = captures.name("printf") {
+                let followed_by_letter = text[printf.end()..]
+                    .chars()
+                    .next()
+                    .is_some_and(|c| c.is_ascii_alphabetic());
+                return (!followed_by_letter).then_some(printf.as_str());
+            }
+            let brace = captures.name("brace")?;
+            let after_dollar = text[..brace.start()].ends_with('$');
+            (!after_dollar).then_some(brace.as_str())
+        })
+        .collect()
+}
+
+/// Returns `true` if `text` contains at least one format specifier.
+pub fn is_format_string(text: &str) -> bool {
+    !format_specifiers(text).is_empty()
+}
+
+/// Tags format strings and boosts their score.
+///
+/// Returns `true` if `string` is a format string. Repeated calls do not
+/// apply the boost twice.
+pub fn tag_format_string(string: &mut FoundString) -> bool {
+    if !is_format_string(&string.text) {
+        return false;
+    }
+    if !string.tags.contains(&Tag::FormatString) {
+        string.tags.push(Tag::FormatString);
+        string.score += FORMAT_STRING_BOOST;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classification::tests::found;
+
+    #[test]
+    fn test_printf_specifiers() {
+        for (text, specifiers) in [
+            ("Error: %s at line %d", vec!["%s", "%d"]),
+            ("%02x:%02X", vec!["%02x", "%02X"]),
+            ("path=%ls", vec!["%ls"]),
+            ("%2$s %1$s", vec!["%2$s", "%1$s"]),
+            ("%-10.3f|%+lld|%#010x", vec!["%-10.3f", "%+lld", "%#010x"]),
+            ("%*d %.*s %zu", vec!["%*d", "%.*s", "%zu"]),
+            ("size %I64u, %hhu", vec!["%I64u", "%hhu"]),
+            ("%(name)s is %(age)d", vec!["%(name)s", "%(age)d"]),
+            ("-[%@ %@]", vec!["%@", "%@"]),
+            ("Progress: 50%%, %d left", vec!["%d"]),
+        ] {
+            assert_eq!(format_specifiers(text), specifiers, "{text}");
+        }
+    }
+
+    #[test]
+    fn test_brace_placeholders() {
+        for (text, specifiers) in [
+            ("listening on {}", vec!["{}"]),
+            ("User {0} logged in from {1}", vec!["{0}", "{1}"]),
+            ("{name:>8} {value:.2}", vec!["{name:>8}", "{value:.2}"]),
+            ("{{literal}} {}", vec!["{}"]),
+        ] {
+            assert_eq!(format_specifiers(text), specifiers, "{text}");
+        }
+    }
+
+    #[test]
+    fn test_not_format_strings() {
+        for text in [
+            "100% complete",
+            "50% off",
+            "100%%",
+            "%APPDATA%\\update.exe",
+            "%SystemRoot%",
+            "${HOME}/.config",
+            "{{escaped}}",
+            "{6B29FC40-CA47-1067-B31D-00DD010662DA}",
+            "{\"key\": 1}",
+        ] {
+            assert!(!is_format_string(text), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_tag_format_string() {
+        let mut string = found("%s: %s (%d)");
+        assert!(tag_format_string(&mut string));
+        assert_eq!(string.tags, vec![Tag::FormatString]);
+        assert_eq!(string.score, 10 + FORMAT_STRING_BOOST);
+
+        // Boost is not applied twice
+        assert!(tag_format_string(&mut string));
+        assert_eq!(string.score, 10 + FORMAT_STRING_BOOST);
+
+        let mut literal = found("100%% sure");
+        assert!(!tag_format_string(&mut literal));
+        assert!(literal.tags.is_empty());
+    }
+}
@@ -0,0 +1,298 @@
+use crate::types::{FoundString, HashAlgorithm, Tag};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Score boost applied to strings containing a GUID.
+pub const GUID_BOOST: i32 = 10;
+
+/// Score boost applied to strings containing an email address.
+pub const EMAIL_BOOST: i32 = 15;
+
+/// Score boost applied to strings containing an MD5, SHA-1 or SHA-256 digest.
+pub const HASH_BOOST: i32 = 15;
+
+/// Canonical `8-4-4-4-12` GUIDs, with or without braces, in either case.
+fn guid_regex() -> &'static Regex {
+    static GUID: OnceLock<Regex> = OnceLock::new();
+    GUID.get_or_init(|| {
+        Regex::new(
+            r"(?xi)
+            (?:^|[^0-9a-z-])
+            (?P<guid>
+                \{[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}\}
+              | [0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}
+            )
+            (?:$|[^0-9a-z-])
+            ",
+        )
+        .expect("GUID pattern is valid")
+    })
+}
+
+/// Email addresses with a dot-separated local part and a domain ending in an
+/// alphabetic top-level domain.
+///
+/// The local part may not start or end with a dot or contain two in a row,
+/// and a dot after the top-level domain ends the address rather than being
+/// part of it.
+fn email_regex() -> &'static Regex {
+    static EMAIL: OnceLock<Regex> = OnceLock::new();
+    EMAIL.get_or_init(|| {
+        Regex::new(
+            r"(?x)
+            (?:^|[^\w.%+@-])
+            (?P<email>
+                [\w%+-]+(?:\.[\w%+-]+)*
+                @
+                (?:[A-Za-z0-9](?:[A-Za-z0-9-]{0,61}[A-Za-z0-9])?\.)+
+                [A-Za-z]{2,63}
+            )
+            (?:$|[^\w@-])
+            ",
+        )
+        .expect("email pattern is valid")
+    })
+}
+
+/// Unbroken runs of hex digits; the length is checked by [`find_hash`].
+///
+/// The run may not touch another letter, digit or dash, so longer hex,
+/// dash-separated groups and identifiers that merely contain a hex run are
+/// left alone.
+fn hex_run_regex() -> &'static Regex {
+    static HEX_RUN: OnceLock<Regex> = OnceLock::new();
+    HEX_RUN.get_or_init(|| {
+        Regex::new(
+            r"(?x)
+            (?:^|[^0-9A-Za-z_-])
+            (?P<hex>[0-9a-fA-F]{32,64})
+            (?:$|[^0-9A-Za-z_-])
+            ",
+        )
+        .expect("hex run pattern is valid")
+    })
+}
+
+/// Finds the first hex digest in `text` and the algorithm its length points
+/// to.
+///
+/// Only runs of exactly 32, 40 or 64 hex digits count, written in a single
+/// case and mixing letters with digits, as real digests practically always
+/// do; `00000000...` padding and runs of `f` are not hashes.
+pub fn find_hash(text: &str) -> Option<(&str, HashAlgorithm)> {
+    hex_run_regex()
+        .captures_iter(text)
+        .filter_map(|captures| captures.name("hex"))
+        .map(|hex| hex.as_str())
+        .find_map(|hex| {
+            let algorithm = HashAlgorithm::from_hex_len(hex.len())?;
+            let has_digit = hex.bytes().any(|b| b.is_ascii_digit());
+            let has_lower = hex.bytes().any(|b| b.is_ascii_lowercase());
+            let has_upper = hex.bytes().any(|b| b.is_ascii_uppercase());
+            (has_digit && has_lower != has_upper).then_some((hex, algorithm))
+        })
+}
+
+/// Finds the first GUID in `text`, including its braces if it has them.
+pub fn find_guid(text: &str) -> Option<&str> {
+    guid_regex()
+        .captures(text)
+        .and_then(|captures| captures.name("guid"))
+        .map(|guid| guid.as_str())
+}
+
+/// Finds the first email address in `text`.
+pub fn find_email(text: &str) -> Option<&str> {
+    email_regex()
+        .captures(text)
+        .and_then(|captures| captures.name("email"))
+        .map(|email| email.as_str())
+}
+
+/// Tags strings containing a GUID, an email address or a hex digest and
+/// boosts their score.
+///
+/// GUIDs identify COM classes and interfaces and often appear in registry
+/// keys; email addresses turn up in configuration and C2 code; hardcoded
+/// digests are used to verify payloads or recognize files. Returns
+/// `true` if anything matched. Repeated calls do not apply the boosts twice.
+pub fn tag_identifiers(string: &mut FoundString) -> bool {
+    let mut matched = false;
+
+    if find_guid(&string.text).is_some() {
+        matched = true;
+        if !string.tags.contains(&Tag::Guid) {
+            string.tags.push(Tag::Guid);
+            string.score += GUID_BOOST;
+        }
+    }
+
+    if find_email(&string.text).is_some() {
+        matched = true;
+        if !string.tags.contains(&Tag::Email) {
+            string.tags.push(Tag::Email);
+            string.score += EMAIL_BOOST;
+        }
+    }
+
+    if let Some((_, algorithm)) = find_hash(&string.text) {
+        matched = true;
+        let tag = Tag::Hash(algorithm);
+        if !string.tags.contains(&tag) {
+            string.tags.push(tag);
+            string.score += HASH_BOOST;
+        }
+    }
+
+    matched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classification::tests::found;
+
+    #[test]
+    fn test_guids() {
+        for (text, guid) in [
+            (
+                "{00021401-0000-0000-C000-000000000046}",
+                "{00021401-0000-0000-C000-000000000046}",
+            ),
+            (
+                r"CLSID\{0002DF01-0000-0000-C000-000000000046}\LocalServer32",
+                "{0002DF01-0000-0000-C000-000000000046}",
+            ),
+            (
+                "uuid=3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+                "3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+            ),
+            (
+                "Global\\6B29FC40-CA47-1067-B31D-00DD010662DA",
+                "6B29FC40-CA47-1067-B31D-00DD010662DA",
+            ),
+        ] {
+            assert_eq!(find_guid(text), Some(guid), "{text}");
+        }
+
+        for text in [
+            // Wrong group lengths
+            "0002140-0000-0000-C000-000000000046",
+            "00021401-0000-0000-C000-0000000000460",
+            // Not hex
+            "g0021401-0000-0000-C000-000000000046",
+            "00021401000000000C000000000000046",
+        ] {
+            assert_eq!(find_guid(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn test_emails() {
+        for (text, email) in [
+            ("admin@example.com", "admin@example.com"),
+            (
+                "mailto:first.last+tag@mail.example.co.uk",
+                "first.last+tag@mail.example.co.uk",
+            ),
+            ("Contact support@vendor.io.", "support@vendor.io"),
+            (
+                "<ops_team@corp-mail.example.org>",
+                "ops_team@corp-mail.example.org",
+            ),
+        ] {
+            assert_eq!(find_email(text), Some(email), "{text}");
+        }
+
+        for text in [
+            "user@@example.com",
+            "user@example",
+            "user@example.",
+            "user.@example.com",
+            ".user@example.com",
+            "us..er@example.com",
+            "user@-example.com",
+            "user@example.c0m",
+            "@example.com",
+            "printf(\"%s@%s\")",
+        ] {
+            assert_eq!(find_email(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn test_hashes() {
+        for (text, hash, algorithm) in [
+            (
+                "d41d8cd98f00b204e9800998ecf8427e",
+                "d41d8cd98f00b204e9800998ecf8427e",
+                HashAlgorithm::Md5,
+            ),
+            (
+                "sha1=DA39A3EE5E6B4B0D3255BFEF95601890AFD80709",
+                "DA39A3EE5E6B4B0D3255BFEF95601890AFD80709",
+                HashAlgorithm::Sha1,
+            ),
+            (
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855  payload.bin",
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+                HashAlgorithm::Sha256,
+            ),
+        ] {
+            assert_eq!(find_hash(text), Some((hash, algorithm)), "{text}");
+        }
+
+        for text in [
+            // GUIDs have their own tag
+            "{6B29FC40-CA47-1067-B31D-00DD010662DA}",
+            "6B29FC40CA471067B31D00DD010662DA-0001",
+            // Not a digest length
+            "d41d8cd98f00b204e9800998ecf8427",
+            "d41d8cd98f00b204e9800998ecf8427e0",
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b85500",
+            // Part of a longer identifier
+            "xd41d8cd98f00b204e9800998ecf8427e",
+            "d41d8cd98f00b204e9800998ecf8427e_tmp",
+            // Separators inside the run
+            "d41d8cd9:8f00b204:e9800998:ecf8427e",
+            // Padding and mixed case
+            "00000000000000000000000000000000",
+            "ffffffffffffffffffffffffffffffff",
+            "D41d8cd98f00b204e9800998ecf8427E",
+        ] {
+            assert_eq!(find_hash(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn test_hash_tag_serialization() {
+        let tag = Tag::Hash(HashAlgorithm::Sha256);
+        assert_eq!(serde_json::to_string(&tag).unwrap(), r#"{"hash":"sha256"}"#);
+        assert_eq!(tag.to_string(), "sha256");
+        let parsed: Tag = serde_json::from_str(r#"{"hash":"md5"}"#).unwrap();
+        assert_eq!(parsed, Tag::Hash(HashAlgorithm::Md5));
+    }
+
+    #[test]
+    fn test_tag_identifiers() {
+        let mut string = found("{6B29FC40-CA47-1067-B31D-00DD010662DA}");
+        assert!(tag_identifiers(&mut string));
+        assert_eq!(string.tags, vec![Tag::Guid]);
+        assert_eq!(string.score, 10 + GUID_BOOST);
+
+        // Boost is not applied twice
+        assert!(tag_identifiers(&mut string));
+        assert_eq!(string.score, 10 + GUID_BOOST);
+
+        let mut email = found("report to abuse@example.net");
+        assert!(tag_identifiers(&mut email));
+        assert_eq!(email.tags, vec![Tag::Email]);
+        assert_eq!(email.score, 10 + EMAIL_BOOST);
+
+        let mut hash = found("expected 5d41402abc4b2a76b9719d911017c592");
+        assert!(tag_identifiers(&mut hash));
+        assert!(tag_identifiers(&mut hash));
+        assert_eq!(hash.tags, vec![Tag::Hash(HashAlgorithm::Md5)]);
+        assert_eq!(hash.score, 10 + HASH_BOOST);
+    }
+}
@@ -1 +1,76 @@
 // String analysis and tagging
+
+pub mod commands;
+pub mod crypto;
+pub mod custom;
+pub mod demangle;
+#[cfg(feature = "dictionary")]
+pub mod dictionary;
+pub mod encoded;
+pub mod entropy;
+pub mod format_strings;
+pub mod identifiers;
+pub mod network;
+pub mod paths;
+pub mod ranking;
+pub mod secrets;
+pub mod symbols;
+pub mod versions;
+pub mod winapi;
+
+pub use custom::TagRule;
+pub use demangle::{Language, demangle};
+pub use ranking::{ScoreConfig, score};
+pub use symbols::{RiskLevel, import_risk};
+
+use crate::types::FoundString;
+
+/// Runs every content classifier over `string`, adding tags and score
+/// boosts.
+///
+/// Each classifier is idempotent, so classifying a string twice does not
+/// boost it twice. Returns `true` if any classifier matched.
+pub fn classify(string: &mut FoundString) -> bool {
+    let mut matched = network::tag_urls(string);
+    matched |= network::tag_ip_addresses(string);
+    matched |= network::tag_user_agent(string);
+    matched |= paths::tag_paths(string);
+    matched |= identifiers::tag_identifiers(string);
+    matched |= versions::tag_version(string);
+    matched |= encoded::tag_base64(string);
+    matched |= format_strings::tag_format_string(string);
+    matched |= secrets::tag_secrets(string);
+    matched |= commands::tag_commands(string);
+    matched |= symbols::tag_sensitive_api(string);
+    matched |= winapi::tag_api_reference(string);
+    matched
+}
+
+/// Runs every content classifier, then the user-supplied `rules`, over
+/// `string`.
+///
+/// Returns `true` if any classifier or rule matched.
+pub fn classify_with_rules(string: &mut FoundString, rules: &[TagRule]) -> bool {
+    let matched = classify(string);
+    custom::apply_rules(string, rules) || matched
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::types::{Encoding, FoundString, StringSource};
+
+    /// An ASCII string from `.rodata` with a base score of 10, as the
+    /// classifier tests use.
+    pub(crate) fn found(text: &str) -> FoundString {
+        FoundString::builder()
+            .text(text)
+            .encoding(Encoding::Ascii)
+            .offset(0)
+            .length(text.len() as u32)
+            .source(StringSource::SectionData)
+            .section(".rodata")
+            .score(10)
+            .build()
+            .unwrap()
+    }
+}
@@ -0,0 +1,480 @@
+use crate::types::{FoundString, Tag};
+use regex::Regex;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::sync::OnceLock;
+
+/// Score boost applied to strings containing a URL.
+pub const URL_BOOST: i32 = 30;
+
+/// Score boost applied to strings naming a domain, on top of any URL boost.
+pub const DOMAIN_BOOST: i32 = 10;
+
+/// Score boost applied to strings containing an IPv4 or IPv6 address.
+pub const IP_BOOST: i32 = 30;
+
+/// Score boost applied to strings containing an HTTP user agent.
+pub const USER_AGENT_BOOST: i32 = 25;
+
+/// Products that identify a string as a user agent on their own, compared
+/// case-insensitively with the name in a `product/version` token.
+const USER_AGENT_PRODUCTS: &[&str] = &[
+    "Mozilla",
+    "AppleWebKit",
+    "Chrome",
+    "Safari",
+    "Firefox",
+    "Gecko",
+    "Opera",
+    "Edg",
+    "curl",
+    "Wget",
+    "python-requests",
+    "python-urllib",
+    "aiohttp",
+    "Go-http-client",
+    "Java",
+    "okhttp",
+    "Apache-HttpClient",
+    "libwww-perl",
+    "WindowsPowerShell",
+    "Dalvik",
+    "axios",
+    "node-fetch",
+];
+
+/// URL schemes worth reporting.
+///
+/// Scheme-relative references (`//host/path`) are not matched: in binaries
+/// they are far more often comment markers or path fragments.
+fn url_regex() -> &'static Regex {
+    static URL: OnceLock<Regex> = OnceLock::new();
+    URL.get_or_init(|| {
+        Regex::new(
+            r#"(?xi)
+            \b(?:https?|ftp|wss?)://
+            (?:[a-z0-9._~%!$&'()*+,;=-]+@)?           # userinfo
+            (?P<host>
+                \[[0-9a-f:.]+\]                         # bracketed IPv6
+              | [a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?
+                (?:\.[a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?)*
+            )
+            (?::[0-9]{1,5})?                            # port
+            (?:[/?\#][^\s"'<>`{}|\\^]*)?                # path, query, fragment
+            "#,
+        )
+        .expect("URL pattern is valid")
+    })
+}
+
+/// Finds the first URL in `text`, returning it and its host.
+///
+/// The URL ends at the first whitespace, quote or angle bracket, so one
+/// embedded in a message (`failed to fetch https://x.test/a: timeout`) is
+/// found with trailing punctuation trimmed.
+pub fn find_url(text: &str) -> Option<(&str, &str)> {
+    let captures = url_regex().captures(text)?;
+    let url = captures.get(0)?.as_str();
+    let url = url.trim_end_matches(['.', ',', ';', ':', ')', ']', '!', '?']);
+    let host = captures.name("host")?.as_str();
+    Some((url, host))
+}
+
+/// Returns `true` if `host` is a DNS name with an alphabetic top-level
+/// domain, as opposed to an IP address or a single-label name.
+pub fn is_domain(host: &str) -> bool {
+    let Some((_, tld)) = host.rsplit_once('.') else {
+        return false;
+    };
+    tld.len() >= 2 && tld.bytes().all(|b| b.is_ascii_alphabetic())
+}
+
+/// Tags strings containing a URL with `Url`, and with `Domain` when the URL's
+/// host is a domain name, boosting their scores.
+///
+/// The whole string is tagged even when the URL is only part of it. Returns
+/// `true` if a URL was found. Repeated calls do not apply the boosts twice.
+pub fn tag_urls(string: &mut FoundString) -> bool {
+    let Some((_, host)) = find_url(&string.text) else {
+        return false;
+    };
+    let domain = is_domain(host);
+
+    if !string.tags.contains(&Tag::Url) {
+        string.tags.push(Tag::Url);
+        string.score += URL_BOOST;
+    }
+    if domain && !string.tags.contains(&Tag::Domain) {
+        string.tags.push(Tag::Domain);
+        string.score += DOMAIN_BOOST;
+    }
+    true
+}
+
+/// `product/version` tokens, e.g. `curl/7.68.0`, with the parenthesized
+/// comment that follows, if any.
+fn product_regex() -> &'static Regex {
+    static PRODUCT: OnceLock<Regex> = OnceLock::new();
+    PRODUCT.get_or_init(|| {
+        Regex::new(
+            r"(?x)
+            (?:^|[\s(;:])
+            (?P<name>[A-Za-z][A-Za-z0-9._-]*)
+            /
+            [0-9][A-Za-z0-9._+-]*
+            (?:\s+\((?P<comment>[^()]*)\))?
+            ",
+        )
+        .expect("product pattern is valid")
+    })
+}
+
+/// Platform names that mark a parenthesized comment as a user agent's.
+const PLATFORM_TOKENS: &[&str] = &[
+    "Windows",
+    "Win64",
+    "WOW64",
+    "Macintosh",
+    "Mac OS X",
+    "Linux",
+    "Android",
+    "iPhone",
+    "iPad",
+    "X11",
+    "CrOS",
+    "compatible",
+];
+
+/// Returns `true` if `text` contains an HTTP user agent.
+///
+/// A bare `product/version` token is not enough, since version strings look
+/// the same: the product must be a known client (`Mozilla`, `curl`,
+/// `python-requests`, ...) or be followed by a platform comment
+/// (`Updater/2.1 (Windows NT 10.0; Win64)`).
+pub fn is_user_agent(text: &str) -> bool {
+    product_regex().captures_iter(text).any(|captures| {
+        let known = captures.name("name").is_some_and(|name| {
+            USER_AGENT_PRODUCTS
+                .iter()
+                .any(|product| product.eq_ignore_ascii_case(name.as_str()))
+        });
+        let platform = captures.name("comment").is_some_and(|comment| {
+            PLATFORM_TOKENS
+                .iter()
+                .any(|token| comment.as_str().contains(token))
+        });
+        known || platform
+    })
+}
+
+/// Tags strings containing an HTTP user agent and boosts their score.
+///
+/// Returns `true` if a user agent was found. Repeated calls do not apply the
+/// boost twice.
+pub fn tag_user_agent(string: &mut FoundString) -> bool {
+    if !is_user_agent(&string.text) {
+        return false;
+    }
+    if !string.tags.contains(&Tag::UserAgent) {
+        string.tags.push(Tag::UserAgent);
+        string.score += USER_AGENT_BOOST;
+    }
+    true
+}
+
+/// An IP address found in a string, with the port that followed it, if any.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpMatch<A> {
+    /// The address
+    pub addr: A,
+    /// Port given as `addr:port` (IPv4) or `[addr]:port` (IPv6)
+    pub port: Option<u16>,
+}
+
+/// Splits text into candidate address tokens.
+///
+/// Tokens are maximal runs of alphanumerics, dots, colons and brackets, so an
+/// address only matches when it stands alone: `v1.2.3.4`, `1.2.3.4.5` and
+/// `com.example.1.2.3.4` are single tokens that are not addresses.
+fn address_tokens(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !(c.is_alphanumeric() || matches!(c, '.' | ':' | '[' | ']')))
+        .map(|token| token.trim_end_matches('.'))
+        .filter(|token| !token.is_empty())
+}
+
+fn parse_port(port: &str) -> Option<u16> {
+    if port.is_empty() || !port.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    port.parse().ok()
+}
+
+/// Parses `token` as an IPv4 address with an optional `:port`.
+///
+/// Octets must be in range and written without leading zeros, which the
+/// standard library parser already enforces.
+fn parse_ipv4(token: &str) -> Option<IpMatch<Ipv4Addr>> {
+    if let Ok(addr) = token.parse() {
+        return Some(IpMatch { addr, port: None });
+    }
+    let (addr, port) = token.rsplit_once(':')?;
+    Some(IpMatch {
+        addr: addr.parse().ok()?,
+        port: Some(parse_port(port)?),
+    })
+}
+
+/// Parses `token` as an IPv6 address, either bare or bracketed with an
+/// optional `:port`.
+///
+/// Bare addresses must contain a digit so that C++ scope operators such as
+/// `a::b` are not taken for compressed addresses.
+fn parse_ipv6(token: &str) -> Option<IpMatch<Ipv6Addr>> {
+    if let Some(rest) = token.strip_prefix('[') {
+        let (addr, rest) = rest.split_once(']')?;
+        let port = match rest {
+            "" => None,
+            _ => Some(parse_port(rest.strip_prefix(':')?)?),
+        };
+        return Some(IpMatch {
+            addr: addr.parse().ok()?,
+            port,
+        });
+    }
+    if !token.contains(':') || !token.bytes().any(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some(IpMatch {
+        addr: token.parse().ok()?,
+        port: None,
+    })
+}
+
+/// Finds the first IPv4 address in `text`, e.g. `10.0.0.1` or
+/// `10.0.0.1:8080`.
+pub fn find_ipv4(text: &str) -> Option<IpMatch<Ipv4Addr>> {
+    address_tokens(text).find_map(parse_ipv4)
+}
+
+/// Finds the first IPv6 address in `text`, e.g. `2001:db8::1` or
+/// `[::1]:8080`.
+pub fn find_ipv6(text: &str) -> Option<IpMatch<Ipv6Addr>> {
+    address_tokens(text).find_map(parse_ipv6)
+}
+
+/// Tags strings containing an IPv4 or IPv6 address and boosts their score.
+///
+/// Hard-coded addresses are common command-and-control indicators. Returns
+/// `true` if an address was found. Repeated calls do not apply the boost
+/// twice.
+pub fn tag_ip_addresses(string: &mut FoundString) -> bool {
+    let mut matched = false;
+
+    if find_ipv4(&string.text).is_some() {
+        matched = true;
+        if !string.tags.contains(&Tag::IPv4) {
+            string.tags.push(Tag::IPv4);
+            string.score += IP_BOOST;
+        }
+    }
+
+    if find_ipv6(&string.text).is_some() {
+        matched = true;
+        if !string.tags.contains(&Tag::IPv6) {
+            string.tags.push(Tag::IPv6);
+            string.score += IP_BOOST;
+        }
+    }
+
+    matched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classification::tests::found;
+
+    #[test]
+    fn test_valid_urls() {
+        for (text, url, host) in [
+            ("http://example.com", "http://example.com", "example.com"),
+            (
+                "https://api.example.org:8443/v1/status",
+                "https://api.example.org:8443/v1/status",
+                "api.example.org",
+            ),
+            (
+                "ftp://user@files.example.net/pub/",
+                "ftp://user@files.example.net/pub/",
+                "files.example.net",
+            ),
+            ("ws://[::1]:9000/socket", "ws://[::1]:9000/socket", "[::1]"),
+            (
+                "HTTPS://EXAMPLE.COM/",
+                "HTTPS://EXAMPLE.COM/",
+                "EXAMPLE.COM",
+            ),
+        ] {
+            assert_eq!(find_url(text), Some((url, host)), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_url_with_query() {
+        let text = "https://c2.example.com/gate.php?id=%s&v=2#top";
+        assert_eq!(find_url(text), Some((text, "c2.example.com")));
+    }
+
+    #[test]
+    fn test_embedded_url() {
+        let text = "Update failed (https://updates.example.com/latest.json). Retrying";
+        assert_eq!(
+            find_url(text),
+            Some((
+                "https://updates.example.com/latest.json",
+                "updates.example.com"
+            ))
+        );
+
+        let mut string = found(text);
+        assert!(tag_urls(&mut string));
+        assert_eq!(string.tags, vec![Tag::Url, Tag::Domain]);
+        assert_eq!(string.score, 10 + URL_BOOST + DOMAIN_BOOST);
+
+        // Idempotent
+        assert!(tag_urls(&mut string));
+        assert_eq!(string.score, 10 + URL_BOOST + DOMAIN_BOOST);
+    }
+
+    #[test]
+    fn test_non_urls() {
+        for text in [
+            "//example.com/path",
+            "// TODO: fix this",
+            "example.com/index.html",
+            "file:///etc/passwd",
+            "mailto:user@example.com",
+            "http://",
+            "xhttp://example.com",
+        ] {
+            assert_eq!(find_url(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn test_ip_host_is_not_a_domain() {
+        let mut string = found("http://192.168.1.10:8080/upload");
+        assert!(tag_urls(&mut string));
+        assert_eq!(string.tags, vec![Tag::Url]);
+
+        let mut string = found("http://localhost/");
+        assert!(tag_urls(&mut string));
+        assert_eq!(string.tags, vec![Tag::Url]);
+    }
+
+    #[test]
+    fn test_ip_addresses() {
+        // (text, expected IPv4, expected IPv6)
+        let cases: &[(&str, Option<&str>, Option<&str>)] = &[
+            ("192.168.1.1", Some("192.168.1.1"), None),
+            ("connect 10.0.0.1:4444 failed", Some("10.0.0.1"), None),
+            ("255.255.255.255", Some("255.255.255.255"), None),
+            ("http://203.0.113.7/gate", Some("203.0.113.7"), None),
+            ("2001:db8::1", None, Some("2001:db8::1")),
+            ("listening on [::1]:8080", None, Some("::1")),
+            (
+                "fe80::1ff:fe23:4567:890a",
+                None,
+                Some("fe80::1ff:fe23:4567:890a"),
+            ),
+            ("::ffff:192.0.2.128", None, Some("::ffff:192.0.2.128")),
+            // Out-of-range octet
+            ("999.1.1.1", None, None),
+            // Too many octets
+            ("version 1.2.3.4.5", None, None),
+            ("1.2.3", None, None),
+            ("01.2.3.4", None, None),
+            // Dotted identifiers
+            ("v1.2.3.4", None, None),
+            ("com.example.1.2.3.4", None, None),
+            ("10.0.0.1:99999", None, None),
+            // Scope operators, times and MAC addresses
+            ("std::string", None, None),
+            ("a::b", None, None),
+            ("12:30:45", None, None),
+            ("00:1a:2b:3c:4d:5e", None, None),
+        ];
+
+        for &(text, v4, v6) in cases {
+            assert_eq!(
+                find_ipv4(text).map(|m| m.addr.to_string()).as_deref(),
+                v4,
+                "{text}"
+            );
+            assert_eq!(
+                find_ipv6(text).map(|m| m.addr.to_string()).as_deref(),
+                v6,
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_ip_ports() {
+        assert_eq!(find_ipv4("10.0.0.1:4444").unwrap().port, Some(4444));
+        assert_eq!(find_ipv4("10.0.0.1").unwrap().port, None);
+        assert_eq!(find_ipv6("[2001:db8::2]:443").unwrap().port, Some(443));
+    }
+
+    #[test]
+    fn test_tag_ip_addresses() {
+        let mut string = found("beacon 198.51.100.23:8443 [2001:db8::7]:53");
+        assert!(tag_ip_addresses(&mut string));
+        assert_eq!(string.tags, vec![Tag::IPv4, Tag::IPv6]);
+        assert_eq!(string.score, 10 + 2 * IP_BOOST);
+
+        // Boost is not applied twice
+        assert!(tag_ip_addresses(&mut string));
+        assert_eq!(string.score, 10 + 2 * IP_BOOST);
+
+        let mut version = found("libfoo 1.2.3.4.5");
+        assert!(!tag_ip_addresses(&mut version));
+        assert!(version.tags.is_empty());
+    }
+
+    #[test]
+    fn test_user_agents() {
+        for text in [
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+            "curl/7.68.0",
+            "User-Agent: python-requests/2.31.0",
+            "Wget/1.21.3 (linux-gnu)",
+            "Go-http-client/1.1",
+            "Updater/2.1 (Windows NT 6.1; WOW64)",
+        ] {
+            assert!(is_user_agent(text), "{text}");
+        }
+
+        for text in [
+            "MyApp/1.2.3",
+            "version 1.2.3",
+            "Mozilla Public License 2.0",
+            "/usr/lib/curl/7",
+            "see section 3/4",
+            "libfoo/2.0 (built with gcc)",
+        ] {
+            assert!(!is_user_agent(text), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_tag_user_agent() {
+        let mut string = found("Mozilla/4.0 (compatible; MSIE 8.0; Windows NT 5.1)");
+        assert!(tag_user_agent(&mut string));
+        assert_eq!(string.tags, vec![Tag::UserAgent]);
+        assert_eq!(string.score, 10 + USER_AGENT_BOOST);
+
+        // Boost is not applied twice
+        assert!(tag_user_agent(&mut string));
+        assert_eq!(string.score, 10 + USER_AGENT_BOOST);
+    }
+}
@@ -0,0 +1,217 @@
+use crate::types::{FoundString, Tag};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Score boost applied to strings containing a file system path.
+pub const FILE_PATH_BOOST: i32 = 15;
+
+/// Score boost applied to strings containing a Windows registry path.
+pub const REGISTRY_PATH_BOOST: i32 = 25;
+
+/// Absolute Unix paths, drive-letter, UNC and environment-rooted Windows
+/// paths.
+///
+/// A path must start the string or follow whitespace or punctuation, so URL
+/// paths and sed expressions are not matched, and must have at least two
+/// components (counting a drive, share or variable root as one), so a lone
+/// `/` or `and/or` is not either.
+fn file_path_regex() -> &'static Regex {
+    static FILE_PATH: OnceLock<Regex> = OnceLock::new();
+    FILE_PATH.get_or_init(|| {
+        Regex::new(
+            r#"(?x)
+            (?:^|[\s"'=(\[,;<>])
+            (?P<path>
+                ~?/[\w.+@%~-]+(?:/[\w.+@%~-]+)+/?
+              | [A-Za-z]:[\\/][^\\/:*?"<>|\x00-\x1f]+(?:[\\/][^\\/:*?"<>|\x00-\x1f]*)*
+              | \\\\[\w.?$-]+\\[^\\/:*?"<>|\x00-\x1f]+(?:\\[^\\/:*?"<>|\x00-\x1f]*)*
+              | %[A-Za-z_][\w()]*%\\[^\\/:*?"<>|\x00-\x1f]+(?:\\[^\\/:*?"<>|\x00-\x1f]*)*
+            )
+            "#,
+        )
+        .expect("file path pattern is valid")
+    })
+}
+
+/// Registry keys rooted at a hive, by long or abbreviated name, or at a
+/// well-known hive-relative key.
+///
+/// Abbreviated hives must be followed by a backslash: `HKCU` alone is too
+/// short to be meaningful. Hive-relative `SOFTWARE\` and `SYSTEM\` keys are
+/// included because binaries usually pass the hive as a separate handle to
+/// `RegOpenKeyEx`.
+fn registry_path_regex() -> &'static Regex {
+    static REGISTRY_PATH: OnceLock<Regex> = OnceLock::new();
+    REGISTRY_PATH.get_or_init(|| {
+        Regex::new(
+            r"(?xi)
+            (?:^|[^\w\\])
+            (?P<path>
+                HKEY_(?:LOCAL_MACHINE|CURRENT_USER|CLASSES_ROOT|USERS|CURRENT_CONFIG|PERFORMANCE_DATA)
+                (?:\\[^\\\x00-\x1f]+)*\\?
+              | (?:HKLM|HKCU|HKCR|HKU|HKCC)\\[^\\\x00-\x1f]+(?:\\[^\\\x00-\x1f]+)*
+              | \\Registry\\(?:Machine|User)\\[^\\\x00-\x1f]+(?:\\[^\\\x00-\x1f]+)*
+              | (?:SOFTWARE|SYSTEM\\CurrentControlSet)\\[^\\\x00-\x1f]+(?:\\[^\\\x00-\x1f]+)+
+            )
+            (?:$|[^\w])
+            ",
+        )
+        .expect("registry path pattern is valid")
+    })
+}
+
+/// Finds the first file system path in `text`.
+///
+/// Windows paths may contain spaces, so a path embedded in a sentence may
+/// extend to the end of it.
+pub fn find_file_path(text: &str) -> Option<&str> {
+    file_path_regex()
+        .captures(text)
+        .and_then(|captures| captures.name("path"))
+        .map(|path| path.as_str())
+}
+
+/// Finds the first Windows registry path in `text`.
+pub fn find_registry_path(text: &str) -> Option<&str> {
+    registry_path_regex()
+        .captures(text)
+        .and_then(|captures| captures.name("path"))
+        .map(|path| path.as_str())
+}
+
+/// Tags strings containing a file system path or a registry path and boosts
+/// their score.
+///
+/// Returns `true` if anything matched. Repeated calls do not apply the boosts
+/// twice.
+pub fn tag_paths(string: &mut FoundString) -> bool {
+    let mut matched = false;
+
+    if find_file_path(&string.text).is_some() {
+        matched = true;
+        if !string.tags.contains(&Tag::FilePath) {
+            string.tags.push(Tag::FilePath);
+            string.score += FILE_PATH_BOOST;
+        }
+    }
+
+    if find_registry_path(&string.text).is_some() {
+        matched = true;
+        if !string.tags.contains(&Tag::RegistryPath) {
+            string.tags.push(Tag::RegistryPath);
+            string.score += REGISTRY_PATH_BOOST;
+        }
+    }
+
+    matched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classification::tests::found;
+
+    #[test]
+    fn test_file_paths() {
+        for (text, path) in [
+            ("/etc/passwd", "/etc/passwd"),
+            ("/usr/bin/python3.11", "/usr/bin/python3.11"),
+            ("~/.ssh/id_rsa", "~/.ssh/id_rsa"),
+            ("cannot open '/var/log/app.log'", "/var/log/app.log"),
+            (
+                r"C:\Windows\System32\cmd.exe",
+                r"C:\Windows\System32\cmd.exe",
+            ),
+            ("C:/Users/Public", "C:/Users/Public"),
+            (
+                r"\\fileserver\share\drop.exe",
+                r"\\fileserver\share\drop.exe",
+            ),
+            (r"\\.\PhysicalDrive0", r"\\.\PhysicalDrive0"),
+            (
+                r"%APPDATA%\Microsoft\update.exe",
+                r"%APPDATA%\Microsoft\update.exe",
+            ),
+        ] {
+            assert_eq!(find_file_path(text), Some(path), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_non_file_paths() {
+        for text in [
+            "https://example.com/path/to/file",
+            "ftp://mirror.example.org/pub/",
+            "/tmp",
+            "/",
+            "and/or",
+            "1/2/2024",
+            "s/foo/bar/g",
+            "// comment",
+            r"C:\",
+            r"HKLM\Software\Vendor",
+        ] {
+            assert_eq!(find_file_path(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn test_registry_paths() {
+        for (text, path) in [
+            (
+                r"HKEY_LOCAL_MACHINE\SOFTWARE\Microsoft\Windows\CurrentVersion\Run",
+                r"HKEY_LOCAL_MACHINE\SOFTWARE\Microsoft\Windows\CurrentVersion\Run",
+            ),
+            ("HKEY_CURRENT_USER", "HKEY_CURRENT_USER"),
+            (
+                r"HKLM\SYSTEM\CurrentControlSet",
+                r"HKLM\SYSTEM\CurrentControlSet",
+            ),
+            (r"hkcu\Software\Classes", r"hkcu\Software\Classes"),
+            (
+                r"reg add HKCU\Software\Run /v updater",
+                r"HKCU\Software\Run /v updater",
+            ),
+            (
+                r"\Registry\Machine\SOFTWARE\Policies",
+                r"\Registry\Machine\SOFTWARE\Policies",
+            ),
+            (
+                r"Software\Microsoft\Windows\CurrentVersion\Run",
+                r"Software\Microsoft\Windows\CurrentVersion\Run",
+            ),
+        ] {
+            assert_eq!(find_registry_path(text), Some(path), "{text}");
+        }
+
+        for text in [
+            "HKCU",
+            r"HKLMX\foo",
+            r"MyHKLM\foo",
+            "HKEY_BOGUS",
+            r"C:\Software\x",
+        ] {
+            assert_eq!(find_registry_path(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn test_tag_paths() {
+        let mut string = found(r"C:\Program Files\Vendor\agent.exe");
+        assert!(tag_paths(&mut string));
+        assert_eq!(string.tags, vec![Tag::FilePath]);
+        assert_eq!(string.score, 10 + FILE_PATH_BOOST);
+
+        // Boost is not applied twice
+        assert!(tag_paths(&mut string));
+        assert_eq!(string.score, 10 + FILE_PATH_BOOST);
+
+        let mut key = found(r"HKLM\Software\Microsoft\Windows\CurrentVersion\Run");
+        assert!(tag_paths(&mut key));
+        assert_eq!(key.tags, vec![Tag::RegistryPath]);
+
+        let mut url = found("https://example.com/downloads/setup.exe");
+        assert!(!tag_paths(&mut url));
+        assert!(url.tags.is_empty());
+    }
+}
@@ -0,0 +1,520 @@
+use crate::classification::custom::TagRule;
+use crate::classification::entropy::{DEFAULT_ENTROPY_PENALTY, DEFAULT_MAX_ENTROPY, is_garbage};
+use crate::classification::{
+    commands, encoded, format_strings, identifiers, network, paths, secrets, symbols, versions,
+    winapi,
+};
+use crate::container::overlay::OVERLAY_SECTION;
+use crate::types::{FoundString, HashAlgorithm, SectionType, StringSource, Tag};
+
+/// Tunable parameters for string scoring.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoringConfig {
+    /// Penalty subtracted from short strings found in executable code.
+    ///
+    /// Instruction bytes regularly form short printable runs, so this is
+    /// applied on top of the already-low code section weight. Set to `0` to
+    /// disable.
+    pub code_section_penalty: i32,
+    /// Strings from code sections at least this many characters long are
+    /// exempt from [`ScoringConfig::code_section_penalty`].
+    ///
+    /// Long runs inside code are usually genuine embedded strings rather than
+    /// coincidental opcodes.
+    pub code_section_exempt_length: usize,
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            code_section_penalty: 20,
+            code_section_exempt_length: 16,
+        }
+    }
+}
+
+/// Base weight of a section type, reflecting how likely it is to contain
+/// meaningful strings.
+pub fn section_weight(section_type: SectionType) -> i32 {
+    match section_type {
+        SectionType::StringData => 40,
+        SectionType::Resources => 35,
+        SectionType::ReadOnlyData => 25,
+        SectionType::Debug => 15,
+        SectionType::WritableData => 10,
+        SectionType::Code => 5,
+        SectionType::Other => 0,
+    }
+}
+
+/// Weight of the synthetic [`OVERLAY_SECTION`]: data appended by installers
+/// and droppers often holds configuration, but just as often compressed
+/// payload.
+pub const OVERLAY_WEIGHT: i32 = 20;
+
+/// Weight of a section by name and type: [`OVERLAY_WEIGHT`] for the overlay,
+/// [`section_weight`] otherwise.
+pub fn named_section_weight(name: Option<&str>, section_type: SectionType) -> i32 {
+    if section_type == SectionType::Other && name == Some(OVERLAY_SECTION) {
+        OVERLAY_WEIGHT
+    } else {
+        section_weight(section_type)
+    }
+}
+
+/// Penalty for a string found in a code section.
+///
+/// Returns `0` for other sections and for code strings at least
+/// `code_section_exempt_length` characters long.
+pub fn code_section_penalty(
+    string: &FoundString,
+    section_type: SectionType,
+    config: &ScoringConfig,
+) -> i32 {
+    if section_type != SectionType::Code {
+        return 0;
+    }
+    if string.text.chars().count() >= config.code_section_exempt_length {
+        return 0;
+    }
+    config.code_section_penalty
+}
+
+/// Section-derived part of a string's score: the [`named_section_weight`]
+/// minus the code-section penalty.
+///
+/// Strings whose originating section is unknown contribute `0`.
+pub fn section_score(
+    string: &FoundString,
+    section_type: Option<SectionType>,
+    config: &ScoringConfig,
+) -> i32 {
+    match section_type {
+        Some(ty) => {
+            named_section_weight(string.section.as_deref(), ty)
+                - code_section_penalty(string, ty, config)
+        }
+        None => 0,
+    }
+}
+
+/// Bonus for Objective-C class names and selectors, which describe what the
+/// program does.
+pub const OBJC_BOOST: i32 = 30;
+
+/// Bonus for embedded manifests, such as a PE application manifest that
+/// requests `requireAdministrator` or macOS entitlements.
+pub const MANIFEST_BOOST: i32 = 40;
+
+/// Bonus for resource strings such as `STRINGTABLE` entries, `VERSIONINFO`
+/// values and code signature fields.
+pub const RESOURCE_BOOST: i32 = 10;
+
+/// Bonus for the `Export` tag on strings other than export names, such as
+/// the names of Objective-C classes the binary implements itself.
+///
+/// Export names score their [`symbols::symbol_score`] instead.
+pub const EXPORT_BOOST: i32 = 15;
+
+/// Bonus for strings from a Mach-O code signature: entitlements, the signing
+/// identifier and the Team ID identify the developer and what the program
+/// may do.
+pub const CODE_SIGNATURE_BOOST: i32 = 25;
+
+/// Weights for the composite score computed by [`score`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreConfig {
+    /// Section weighting and code-section penalty
+    pub section: ScoringConfig,
+    /// Bonus for each tag; tags not listed contribute nothing
+    ///
+    /// Defaults to the boosts the classifiers apply, so network indicators
+    /// and credentials rank high and format strings low.
+    pub tag_bonuses: Vec<(Tag, i32)>,
+    /// Bonus for strings with source `StringSource::CodeSignature`, on top of
+    /// their tag bonuses
+    pub code_signature_bonus: i32,
+    /// Number of characters per point of length bonus
+    pub length_step: usize,
+    /// Upper bound on the length bonus
+    pub max_length_bonus: i32,
+    /// Entropy in bits per character above which an unstructured string is
+    /// garbage; see [`is_garbage`]
+    pub max_entropy: f32,
+    /// Penalty subtracted from garbage strings
+    pub entropy_penalty: i32,
+    /// Bonus for strings made entirely of English words, scaled by the share
+    /// of recognized words
+    ///
+    /// Only applied with the `dictionary` feature; see
+    /// `classification::dictionary::looks_like_language`.
+    pub language_bonus: i32,
+}
+
+impl Default for ScoreConfig {
+    fn default() -> Self {
+        Self {
+            section: ScoringConfig::default(),
+            tag_bonuses: vec![
+                (Tag::Url, network::URL_BOOST),
+                (Tag::Domain, network::DOMAIN_BOOST),
+                (Tag::IPv4, network::IP_BOOST),
+                (Tag::IPv6, network::IP_BOOST),
+                (Tag::UserAgent, network::USER_AGENT_BOOST),
+                (Tag::FilePath, paths::FILE_PATH_BOOST),
+                (Tag::RegistryPath, paths::REGISTRY_PATH_BOOST),
+                (Tag::Guid, identifiers::GUID_BOOST),
+                (Tag::Email, identifiers::EMAIL_BOOST),
+                (Tag::Hash(HashAlgorithm::Md5), identifiers::HASH_BOOST),
+                (Tag::Hash(HashAlgorithm::Sha1), identifiers::HASH_BOOST),
+                (Tag::Hash(HashAlgorithm::Sha256), identifiers::HASH_BOOST),
+                (Tag::Version, versions::VERSION_BOOST),
+                (Tag::Base64, encoded::BASE64_BOOST),
+                (Tag::FormatString, format_strings::FORMAT_STRING_BOOST),
+                (Tag::Jwt, secrets::JWT_BOOST),
+                (Tag::ApiKey, secrets::API_KEY_BOOST),
+                (Tag::Command, commands::COMMAND_BOOST),
+                (Tag::Sql, commands::SQL_BOOST),
+                (Tag::Import, symbols::SENSITIVE_API_BOOST),
+                (Tag::Export, EXPORT_BOOST),
+                (Tag::ObjC, OBJC_BOOST),
+                (Tag::Manifest, MANIFEST_BOOST),
+                (Tag::Resource, RESOURCE_BOOST),
+            ],
+            code_signature_bonus: CODE_SIGNATURE_BOOST,
+            length_step: 8,
+            max_length_bonus: 10,
+            max_entropy: DEFAULT_MAX_ENTROPY,
+            entropy_penalty: DEFAULT_ENTROPY_PENALTY,
+            language_bonus: 15,
+        }
+    }
+}
+
+impl ScoreConfig {
+    /// Adds the score bonus of each custom rule as the bonus of its tag, so
+    /// that [`score`] keeps rule matches ranked.
+    pub fn with_tag_rules(mut self, rules: &[TagRule]) -> Self {
+        for rule in rules {
+            let tag = rule.tag();
+            self.tag_bonuses.retain(|(t, _)| *t != tag);
+            self.tag_bonuses.push((tag, rule.score_bonus));
+        }
+        self
+    }
+
+    /// Bonus for a single tag.
+    pub fn tag_bonus(&self, tag: &Tag) -> i32 {
+        self.tag_bonuses
+            .iter()
+            .find(|(t, _)| t == tag)
+            .map_or(0, |&(_, bonus)| bonus)
+    }
+
+    /// Bonus for the length of `text`: one point per `length_step`
+    /// characters, up to `max_length_bonus`.
+    pub fn length_bonus(&self, text: &str) -> i32 {
+        let steps = text.chars().count() / self.length_step.max(1);
+        i32::try_from(steps)
+            .unwrap_or(i32::MAX)
+            .min(self.max_length_bonus)
+    }
+}
+
+/// Computes the final relevance score of `string`.
+///
+/// ```text
+/// score = section_score + sum(tag bonuses) + source_bonus + length_bonus
+///         - entropy_penalty
+/// ```
+///
+/// - `section_score` is the [`section_score`] of `string.section_type`
+///   (0 when unknown)
+/// - each distinct tag adds its [`ScoreConfig::tag_bonus`], except that
+///   import and export names score their [`symbols::symbol_score`] instead
+///   of the `Import`/`Export` bonus, so only sensitive APIs rank high;
+///   likewise, other strings tagged `Import` that name a DLL or an everyday
+///   API get [`winapi::API_REFERENCE_BOOST`] instead of the `Import` bonus
+/// - code signature strings add [`ScoreConfig::code_signature_bonus`]
+/// - `length_bonus` is [`ScoreConfig::length_bonus`]
+/// - `entropy_penalty` applies only when the text [`is_garbage`]
+///
+/// With the `dictionary` feature, `language_bonus` scaled by the share of
+/// recognized English words is added as well.
+///
+/// The score is computed from scratch: the boosts classifiers added to
+/// `string.score` are ignored, so the result does not depend on how often
+/// a string was classified. Scores set by container parsers are ignored as
+/// well; parsers convey relevance through tags, `section_type` and
+/// `source` instead.
+pub fn score(string: &FoundString, config: &ScoreConfig) -> i32 {
+    let mut total = section_score(string, string.section_type, &config.section);
+
+    let is_symbol = matches!(
+        string.source,
+        StringSource::ImportName | StringSource::ExportName
+    );
+    for (i, tag) in string.tags.iter().enumerate() {
+        if is_symbol && matches!(tag, Tag::Import | Tag::Export) {
+            continue;
+        }
+        if string.tags[..i].contains(tag) {
+            continue;
+        }
+        if *tag == Tag::Import && !symbols::is_sensitive_api(&string.text) {
+            total += winapi::API_REFERENCE_BOOST;
+        } else {
+            total += config.tag_bonus(tag);
+        }
+    }
+    if is_symbol {
+        total += symbols::symbol_score(&string.text);
+    }
+    if string.source == StringSource::CodeSignature {
+        total += config.code_signature_bonus;
+    }
+
+    total += config.length_bonus(&string.text);
+
+    if is_garbage(&string.text, config.max_entropy) {
+        total -= config.entropy_penalty;
+    }
+
+    #[cfg(feature = "dictionary")]
+    {
+        let ratio = crate::classification::dictionary::looks_like_language(&string.text);
+        total += (ratio * config.language_bonus as f32).round() as i32;
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Encoding, StringSource};
+
+    fn found(text: &str, section: &str) -> FoundString {
+        FoundString::builder()
+            .text(text)
+            .encoding(Encoding::Ascii)
+            .offset(0)
+            .length(text.len() as u32)
+            .source(StringSource::SectionData)
+            .section(section)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_short_code_string_scores_below_rodata() {
+        let config = ScoringConfig::default();
+        let code = found("AWAVI", ".text");
+        let rodata = found("Failed to open configuration file", ".rodata");
+
+        let code_score = section_score(&code, Some(SectionType::Code), &config);
+        let rodata_score = section_score(&rodata, Some(SectionType::StringData), &config);
+
+        assert_eq!(code_score, 5 - 20);
+        assert!(code_score < rodata_score);
+    }
+
+    #[test]
+    fn test_long_code_string_is_exempt() {
+        let config = ScoringConfig::default();
+        let long = found("embedded usage message in code", ".text");
+        assert_eq!(code_section_penalty(&long, SectionType::Code, &config), 0);
+        assert_eq!(
+            section_score(&long, Some(SectionType::Code), &config),
+            section_weight(SectionType::Code)
+        );
+    }
+
+    #[test]
+    fn test_overlay_weight() {
+        let config = ScoringConfig::default();
+        let overlay = found("setup payload v2", OVERLAY_SECTION);
+        let comment = found("GCC: (GNU) 13.2.0", ".comment");
+
+        assert_eq!(
+            section_score(&overlay, Some(SectionType::Other), &config),
+            OVERLAY_WEIGHT
+        );
+        assert_eq!(
+            section_score(&comment, Some(SectionType::Other), &config),
+            0
+        );
+    }
+
+    #[test]
+    fn test_penalty_is_configurable() {
+        let short = found("H\\$8", ".text");
+
+        let disabled = ScoringConfig {
+            code_section_penalty: 0,
+            ..ScoringConfig::default()
+        };
+        assert_eq!(
+            code_section_penalty(&short, SectionType::Code, &disabled),
+            0
+        );
+
+        let strict = ScoringConfig {
+            code_section_penalty: 50,
+            code_section_exempt_length: 64,
+        };
+        assert_eq!(code_section_penalty(&short, SectionType::Code, &strict), 50);
+
+        // Non-code sections are never penalized
+        assert_eq!(
+            code_section_penalty(&short, SectionType::WritableData, &strict),
+            0
+        );
+        assert_eq!(section_score(&short, None, &strict), 0);
+    }
+
+    /// Default weights without the optional language bonus, so expected
+    /// scores do not depend on enabled features.
+    fn exact() -> ScoreConfig {
+        ScoreConfig {
+            language_bonus: 0,
+            ..ScoreConfig::default()
+        }
+    }
+
+    fn typed(text: &str, section_type: SectionType, tags: Vec<Tag>) -> FoundString {
+        FoundString {
+            section_type: Some(section_type),
+            tags,
+            ..found(text, "")
+        }
+    }
+
+    #[test]
+    fn test_tagged_rodata_url_beats_random_code_run() {
+        let config = exact();
+        let url = typed(
+            "https://example.com/gate.php",
+            SectionType::StringData,
+            vec![Tag::Url, Tag::Domain],
+        );
+        let noise = typed("x9#Qv!2Lr$8Zk@pW3&mT7^b", SectionType::Code, Vec::new());
+
+        assert!(score(&url, &config) > score(&noise, &config));
+        assert_eq!(
+            score(&url, &config),
+            40 + network::URL_BOOST + network::DOMAIN_BOOST + 3
+        );
+        // Long enough to escape the code penalty, but garbage
+        assert_eq!(score(&noise, &config), 5 + 2 - DEFAULT_ENTROPY_PENALTY);
+    }
+
+    #[test]
+    fn test_score_ordering_invariants() {
+        let config = exact();
+        let text = "configuration file not found";
+
+        // Better sections rank higher
+        let rodata = typed(text, SectionType::StringData, Vec::new());
+        let data = typed(text, SectionType::WritableData, Vec::new());
+        assert!(score(&rodata, &config) > score(&data, &config));
+
+        // Tags rank higher, network indicators above format strings
+        let ip = typed("10.0.0.1:4444", SectionType::StringData, vec![Tag::IPv4]);
+        let fmt = typed("%s:%d", SectionType::StringData, vec![Tag::FormatString]);
+        let plain = typed("hello", SectionType::StringData, Vec::new());
+        assert!(score(&ip, &config) > score(&fmt, &config));
+        assert!(score(&fmt, &config) > score(&plain, &config));
+
+        // Longer strings rank higher, up to the cap
+        let short = typed("open", SectionType::StringData, Vec::new());
+        assert!(score(&rodata, &config) > score(&short, &config));
+        let huge = typed(&"word ".repeat(100), SectionType::StringData, Vec::new());
+        assert_eq!(score(&huge, &config), 40 + config.max_length_bonus);
+    }
+
+    #[test]
+    fn test_score_ignores_existing_score_and_repeated_tags() {
+        let config = exact();
+        let mut string = typed("%s", SectionType::StringData, vec![Tag::FormatString]);
+        let expected = score(&string, &config);
+
+        string.score = 1000;
+        string.tags.push(Tag::FormatString);
+        assert_eq!(score(&string, &config), expected);
+    }
+
+    #[test]
+    fn test_score_config_is_configurable() {
+        let string = typed("%s", SectionType::StringData, vec![Tag::FormatString]);
+        let config = ScoreConfig {
+            tag_bonuses: vec![(Tag::FormatString, 100)],
+            max_length_bonus: 0,
+            ..exact()
+        };
+        assert_eq!(score(&string, &config), 40 + 100);
+        assert_eq!(config.tag_bonus(&Tag::Url), 0);
+
+        // Unknown sections contribute nothing
+        let unplaced = found("plain", "");
+        assert_eq!(score(&unplaced, &config), 0);
+    }
+
+    #[test]
+    fn test_parser_tags_have_bonuses() {
+        let config = ScoreConfig::default();
+        for tag in [Tag::ObjC, Tag::Manifest, Tag::Resource, Tag::Export] {
+            assert!(config.tag_bonus(&tag) > 0, "{tag:?}");
+        }
+    }
+
+    #[test]
+    fn test_everyday_api_references_rank_below_sensitive_ones() {
+        let config = exact();
+        let sensitive = typed("VirtualProtect", SectionType::StringData, vec![Tag::Import]);
+        let everyday = typed("GetTickCount", SectionType::StringData, vec![Tag::Import]);
+        assert_eq!(
+            score(&sensitive, &config),
+            40 + symbols::SENSITIVE_API_BOOST + 1
+        );
+        assert_eq!(
+            score(&everyday, &config),
+            40 + winapi::API_REFERENCE_BOOST + 1
+        );
+    }
+
+    #[test]
+    fn test_code_signature_bonus() {
+        let config = exact();
+        let plain = typed("ABCDE12345", SectionType::Resources, vec![Tag::Resource]);
+        let signed = FoundString {
+            source: StringSource::CodeSignature,
+            ..plain.clone()
+        };
+        assert_eq!(
+            score(&signed, &config) - score(&plain, &config),
+            CODE_SIGNATURE_BOOST
+        );
+    }
+
+    #[cfg(feature = "dictionary")]
+    #[test]
+    fn test_language_outranks_noise() {
+        let config = ScoreConfig {
+            max_length_bonus: 0,
+            ..ScoreConfig::default()
+        };
+        let words = typed("connection failed", SectionType::StringData, Vec::new());
+        let noise = typed("x7f9q2k", SectionType::StringData, Vec::new());
+
+        assert_eq!(score(&words, &config), 40 + config.language_bonus);
+        assert_eq!(score(&noise, &config), 40);
+    }
+
+    #[test]
+    fn test_custom_rule_bonus() {
+        let rule = TagRule::new("license-key", "^LIC-", 25).unwrap();
+        let config = exact().with_tag_rules(std::slice::from_ref(&rule));
+        let string = typed("LIC-1234", SectionType::StringData, vec![rule.tag()]);
+        assert_eq!(score(&string, &config), 40 + 25 + 1);
+    }
+}
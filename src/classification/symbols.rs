@@ -0,0 +1,421 @@
+use crate::types::{FoundString, StringSource, Tag};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Score boost applied to data strings naming a sensitive Win32 API.
+pub const SENSITIVE_API_BOOST: i32 = 30;
+
+/// Behavior associated with a sensitive API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiCategory {
+    /// Writing to or executing code in another process
+    Injection,
+    /// Allocating or re-protecting executable memory
+    Memory,
+    /// Launching processes or shell commands
+    Execution,
+    /// Resolving APIs at runtime
+    DynamicResolution,
+    /// Detecting debuggers and analysis environments
+    AntiAnalysis,
+    /// Surviving reboots or tampering with services
+    Persistence,
+    /// Keylogging, screen capture, and credential theft
+    Surveillance,
+    /// Raw network communication and downloads
+    Network,
+    /// Privilege and token manipulation
+    Privilege,
+}
+
+impl ApiCategory {
+    /// Score of an import or export of an API in this category: code
+    /// injection and execution outrank the dynamic resolution nearly every
+    /// program does.
+    pub fn symbol_score(self) -> i32 {
+        match self {
+            ApiCategory::Injection => 40,
+            ApiCategory::Execution => 35,
+            ApiCategory::Memory | ApiCategory::Surveillance => 30,
+            ApiCategory::Network | ApiCategory::Persistence | ApiCategory::Privilege => 25,
+            ApiCategory::AntiAnalysis => 20,
+            ApiCategory::DynamicResolution => 15,
+        }
+    }
+
+    /// Triage verdict for an import of an API in this category: injection,
+    /// executable memory and process launching are high risk, the rest
+    /// medium.
+    pub fn risk(self) -> RiskLevel {
+        match self {
+            ApiCategory::Injection | ApiCategory::Memory | ApiCategory::Execution => {
+                RiskLevel::High
+            }
+            ApiCategory::DynamicResolution
+            | ApiCategory::AntiAnalysis
+            | ApiCategory::Persistence
+            | ApiCategory::Surveillance
+            | ApiCategory::Network
+            | ApiCategory::Privilege => RiskLevel::Medium,
+        }
+    }
+}
+
+/// How much an imported API matters for triage, lowest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RiskLevel {
+    /// Not a sensitive API
+    Low,
+    /// Sensitive, but common in benign programs (`GetProcAddress`, `socket`)
+    Medium,
+    /// Typical of code injection and payload execution
+    /// (`WriteProcessMemory`, `mprotect`, `execve`)
+    High,
+}
+
+impl RiskLevel {
+    /// Returns `true` for [`RiskLevel::Low`].
+    pub fn is_low(&self) -> bool {
+        *self == RiskLevel::Low
+    }
+}
+
+/// A sensitive API entry.
+#[derive(Debug, Clone, Copy)]
+pub struct SensitiveApi {
+    /// Base API name without the `A`/`W` character-set suffix
+    pub name: &'static str,
+    /// Behavior the API is typically associated with
+    pub category: ApiCategory,
+}
+
+const fn api(name: &'static str, category: ApiCategory) -> SensitiveApi {
+    SensitiveApi { name, category }
+}
+
+/// Curated list of Win32 APIs that are high-value when seen as plain strings.
+///
+/// Malware frequently resolves these dynamically via `GetProcAddress` so they
+/// never appear in the import table. Names are listed without the `A`/`W`
+/// suffix; both variants match.
+pub const SENSITIVE_WIN32_APIS: &[SensitiveApi] = &[
+    // Process injection
+    api("VirtualAllocEx", ApiCategory::Injection),
+    api("WriteProcessMemory", ApiCategory::Injection),
+    api("ReadProcessMemory", ApiCategory::Injection),
+    api("CreateRemoteThread", ApiCategory::Injection),
+    api("CreateRemoteThreadEx", ApiCategory::Injection),
+    api("NtCreateThreadEx", ApiCategory::Injection),
+    api("RtlCreateUserThread", ApiCategory::Injection),
+    api("QueueUserAPC", ApiCategory::Injection),
+    api("NtQueueApcThread", ApiCategory::Injection),
+    api("SetThreadContext", ApiCategory::Injection),
+    api("NtUnmapViewOfSection", ApiCategory::Injection),
+    api("ZwUnmapViewOfSection", ApiCategory::Injection),
+    api("NtMapViewOfSection", ApiCategory::Injection),
+    api("NtWriteVirtualMemory", ApiCategory::Injection),
+    api("OpenProcess", ApiCategory::Injection),
+    api("SetWindowsHookEx", ApiCategory::Injection),
+    // Executable memory
+    api("VirtualAlloc", ApiCategory::Memory),
+    api("VirtualProtect", ApiCategory::Memory),
+    api("VirtualProtectEx", ApiCategory::Memory),
+    api("NtAllocateVirtualMemory", ApiCategory::Memory),
+    api("NtProtectVirtualMemory", ApiCategory::Memory),
+    // Execution
+    api("WinExec", ApiCategory::Execution),
+    api("ShellExecute", ApiCategory::Execution),
+    api("ShellExecuteEx", ApiCategory::Execution),
+    api("CreateProcess", ApiCategory::Execution),
+    api("CreateProcessAsUser", ApiCategory::Execution),
+    api("CreateProcessWithToken", ApiCategory::Execution),
+    // Dynamic resolution
+    api("GetProcAddress", ApiCategory::DynamicResolution),
+    api("LoadLibrary", ApiCategory::DynamicResolution),
+    api("LoadLibraryEx", ApiCategory::DynamicResolution),
+    api("LdrLoadDll", ApiCategory::DynamicResolution),
+    api("LdrGetProcedureAddress", ApiCategory::DynamicResolution),
+    // Anti-analysis
+    api("IsDebuggerPresent", ApiCategory::AntiAnalysis),
+    api("CheckRemoteDebuggerPresent", ApiCategory::AntiAnalysis),
+    api("NtQueryInformationProcess", ApiCategory::AntiAnalysis),
+    api("NtSetInformationThread", ApiCategory::AntiAnalysis),
+    api("OutputDebugString", ApiCategory::AntiAnalysis),
+    // Persistence
+    api("RegSetValueEx", ApiCategory::Persistence),
+    api("RegCreateKeyEx", ApiCategory::Persistence),
+    api("CreateService", ApiCategory::Persistence),
+    api("StartService", ApiCategory::Persistence),
+    api("ChangeServiceConfig", ApiCategory::Persistence),
+    // Surveillance
+    api("GetAsyncKeyState", ApiCategory::Surveillance),
+    api("GetKeyState", ApiCategory::Surveillance),
+    api("SetClipboardData", ApiCategory::Surveillance),
+    api("GetClipboardData", ApiCategory::Surveillance),
+    api("BitBlt", ApiCategory::Surveillance),
+    api("CredEnumerate", ApiCategory::Surveillance),
+    api("CryptUnprotectData", ApiCategory::Surveillance),
+    api("MiniDumpWriteDump", ApiCategory::Surveillance),
+    // Network
+    api("URLDownloadToFile", ApiCategory::Network),
+    api("InternetOpen", ApiCategory::Network),
+    api("InternetOpenUrl", ApiCategory::Network),
+    api("InternetReadFile", ApiCategory::Network),
+    api("HttpSendRequest", ApiCategory::Network),
+    api("WinHttpOpen", ApiCategory::Network),
+    api("WinHttpSendRequest", ApiCategory::Network),
+    api("WSAStartup", ApiCategory::Network),
+    // Privilege
+    api("AdjustTokenPrivileges", ApiCategory::Privilege),
+    api("OpenProcessToken", ApiCategory::Privilege),
+    api("LookupPrivilegeValue", ApiCategory::Privilege),
+    api("ImpersonateLoggedOnUser", ApiCategory::Privilege),
+    api("DuplicateTokenEx", ApiCategory::Privilege),
+];
+
+/// POSIX and libc functions worth flagging when a binary imports them.
+///
+/// Several (`system`, `connect`, `socket`) are ordinary words, so unlike
+/// [`SENSITIVE_WIN32_APIS`] these only apply to symbol table entries, never to
+/// data strings.
+pub const SENSITIVE_POSIX_APIS: &[SensitiveApi] = &[
+    api("ptrace", ApiCategory::Injection),
+    api("process_vm_writev", ApiCategory::Injection),
+    api("process_vm_readv", ApiCategory::Injection),
+    api("mprotect", ApiCategory::Memory),
+    api("memfd_create", ApiCategory::Memory),
+    api("execve", ApiCategory::Execution),
+    api("execv", ApiCategory::Execution),
+    api("execvp", ApiCategory::Execution),
+    api("execl", ApiCategory::Execution),
+    api("execlp", ApiCategory::Execution),
+    api("system", ApiCategory::Execution),
+    api("popen", ApiCategory::Execution),
+    api("dlopen", ApiCategory::DynamicResolution),
+    api("dlsym", ApiCategory::DynamicResolution),
+    api("socket", ApiCategory::Network),
+    api("connect", ApiCategory::Network),
+    api("bind", ApiCategory::Network),
+    api("listen", ApiCategory::Network),
+    api("setuid", ApiCategory::Privilege),
+    api("setgid", ApiCategory::Privilege),
+];
+
+/// Lowercased lookup table built from [`SENSITIVE_WIN32_APIS`].
+fn sensitive_api_index() -> &'static HashMap<String, &'static SensitiveApi> {
+    static INDEX: OnceLock<HashMap<String, &'static SensitiveApi>> = OnceLock::new();
+    INDEX.get_or_init(|| {
+        SENSITIVE_WIN32_APIS
+            .iter()
+            .map(|entry| (entry.name.to_ascii_lowercase(), entry))
+            .collect()
+    })
+}
+
+/// Looks up a string as a sensitive Win32 API name.
+///
+/// Matching is case-insensitive, ignores surrounding whitespace, and accepts
+/// the `A`/`W` character-set suffixes (`CreateProcessW` matches
+/// `CreateProcess`). Only whole strings match; an API name embedded in a
+/// longer sentence is not reported.
+pub fn lookup_sensitive_api(text: &str) -> Option<&'static SensitiveApi> {
+    let name = text.trim();
+    if name.is_empty() || !name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_') {
+        return None;
+    }
+
+    let index = sensitive_api_index();
+    let lower = name.to_ascii_lowercase();
+    if let Some(entry) = index.get(&lower) {
+        return Some(entry);
+    }
+
+    lower
+        .strip_suffix('a')
+        .or_else(|| lower.strip_suffix('w'))
+        .and_then(|base| index.get(base).copied())
+}
+
+/// Looks up an imported or exported symbol name as a sensitive Win32 or
+/// POSIX API.
+///
+/// Win32 names match as in [`lookup_sensitive_api`]. POSIX names must match
+/// exactly, after dropping the leading underscore Mach-O adds to C symbols
+/// and any `@VERSION` suffix.
+pub fn lookup_sensitive_symbol(name: &str) -> Option<&'static SensitiveApi> {
+    if let Some(entry) = lookup_sensitive_api(name) {
+        return Some(entry);
+    }
+    let name = name.split('@').next().unwrap_or(name);
+    let name = name.strip_prefix('_').unwrap_or(name);
+    SENSITIVE_POSIX_APIS.iter().find(|entry| entry.name == name)
+}
+
+/// Score of an import or export named `name`: its category's
+/// [`ApiCategory::symbol_score`] if it is a sensitive API, `0` otherwise.
+pub fn symbol_score(name: &str) -> i32 {
+    lookup_sensitive_symbol(name).map_or(0, |entry| entry.category.symbol_score())
+}
+
+/// Rates an import of `name` from `library` for triage.
+///
+/// Imports from a DLL are only looked up as Win32 APIs, so a DLL export that
+/// happens to be called `system` is not mistaken for libc's; other imports
+/// are looked up as by [`lookup_sensitive_symbol`]. APIs not in the curated
+/// lists are [`RiskLevel::Low`].
+///
+/// # Examples
+///
+/// ```rust
+/// use stringy::classification::{RiskLevel, import_risk};
+///
+/// assert_eq!(import_risk("CreateRemoteThread", Some("KERNEL32.dll")), RiskLevel::High);
+/// assert_eq!(import_risk("GetProcAddress", Some("KERNEL32.dll")), RiskLevel::Medium);
+/// assert_eq!(import_risk("printf", None), RiskLevel::Low);
+/// ```
+pub fn import_risk(name: &str, library: Option<&str>) -> RiskLevel {
+    let is_dll = library.is_some_and(|library| {
+        library.len() > 4
+            && library.is_char_boundary(library.len() - 4)
+            && library[library.len() - 4..].eq_ignore_ascii_case(".dll")
+    });
+    let entry = if is_dll {
+        lookup_sensitive_api(name)
+    } else {
+        lookup_sensitive_symbol(name)
+    };
+    entry.map_or(RiskLevel::Low, |entry| entry.category.risk())
+}
+
+/// Returns `true` if `text` names a sensitive Win32 API.
+pub fn is_sensitive_api(text: &str) -> bool {
+    lookup_sensitive_api(text).is_some()
+}
+
+/// Tags a string naming a sensitive Win32 API and boosts its score.
+///
+/// This is content matching, independent of the import table: it catches
+/// APIs resolved dynamically at runtime. Returns `true` if the string matched.
+/// Repeated calls do not apply the boost twice. Import and export names are
+/// left alone; they are scored by [`symbol_score`].
+pub fn tag_sensitive_api(string: &mut FoundString) -> bool {
+    if matches!(
+        string.source,
+        StringSource::ImportName | StringSource::ExportName
+    ) {
+        return false;
+    }
+    if lookup_sensitive_api(&string.text).is_none() {
+        return false;
+    }
+
+    if !string.tags.contains(&Tag::Import) {
+        string.tags.push(Tag::Import);
+        string.score += SENSITIVE_API_BOOST;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classification::tests::found;
+
+    #[test]
+    fn test_sensitive_api_lookup() {
+        assert!(is_sensitive_api("VirtualAllocEx"));
+        assert!(is_sensitive_api("writeprocessmemory"));
+        assert!(is_sensitive_api("CREATEREMOTETHREAD"));
+        assert!(is_sensitive_api("  GetProcAddress "));
+
+        let entry = lookup_sensitive_api("VirtualAllocEx").unwrap();
+        assert_eq!(entry.category, ApiCategory::Injection);
+    }
+
+    #[test]
+    fn test_charset_suffixes() {
+        assert!(is_sensitive_api("CreateProcessW"));
+        assert!(is_sensitive_api("LoadLibraryA"));
+        assert!(is_sensitive_api("ShellExecuteExW"));
+        assert_eq!(
+            lookup_sensitive_api("RegSetValueExA").unwrap().name,
+            "RegSetValueEx"
+        );
+    }
+
+    #[test]
+    fn test_non_matches() {
+        assert!(!is_sensitive_api(""));
+        assert!(!is_sensitive_api("printf"));
+        assert!(!is_sensitive_api("VirtualAllocExX"));
+        assert!(!is_sensitive_api("call VirtualAllocEx failed"));
+        assert!(!is_sensitive_api("kernel32.dll"));
+    }
+
+    #[test]
+    fn test_tag_sensitive_api() {
+        let mut string = found("WriteProcessMemory");
+        assert!(tag_sensitive_api(&mut string));
+        assert_eq!(string.tags, vec![Tag::Import]);
+        assert_eq!(string.score, 10 + SENSITIVE_API_BOOST);
+
+        // Boost is not applied twice
+        assert!(tag_sensitive_api(&mut string));
+        assert_eq!(string.score, 10 + SENSITIVE_API_BOOST);
+
+        let mut benign = found("Hello, world");
+        assert!(!tag_sensitive_api(&mut benign));
+        assert!(benign.tags.is_empty());
+        assert_eq!(benign.score, 10);
+    }
+
+    #[test]
+    fn test_import_risk() {
+        for (name, library) in [
+            ("VirtualAllocEx", Some("KERNEL32.dll")),
+            ("WriteProcessMemory", Some("kernel32.dll")),
+            ("CreateRemoteThread", Some("KERNEL32.DLL")),
+            ("NtUnmapViewOfSection", Some("ntdll.dll")),
+            ("ptrace", Some("libc.so.6")),
+            ("_mprotect", None),
+            ("execve@GLIBC_2.2.5", Some("libc.so.6")),
+        ] {
+            assert_eq!(import_risk(name, library), RiskLevel::High, "{name}");
+        }
+        assert_eq!(
+            import_risk("LoadLibraryW", Some("KERNEL32.dll")),
+            RiskLevel::Medium
+        );
+        assert_eq!(import_risk("dlsym", Some("libc.so.6")), RiskLevel::Medium);
+
+        for (name, library) in [
+            ("printf", Some("libc.so.6")),
+            ("CreateFileW", Some("KERNEL32.dll")),
+            // POSIX names only count outside DLLs
+            ("system", Some("legacy.dll")),
+            ("ORDINAL 165", Some("SHELL32.dll")),
+        ] {
+            assert_eq!(import_risk(name, library), RiskLevel::Low, "{name}");
+        }
+        assert!(RiskLevel::High > RiskLevel::Medium && RiskLevel::Medium > RiskLevel::Low);
+    }
+
+    #[test]
+    fn test_symbol_score() {
+        assert_eq!(symbol_score("VirtualAllocEx"), 40);
+        assert_eq!(symbol_score("WinExec"), 35);
+        assert_eq!(symbol_score("socket"), 25);
+        // Mach-O underscore and ELF version suffix
+        assert_eq!(symbol_score("_ptrace"), 40);
+        assert_eq!(symbol_score("mprotect@GLIBC_2.2.5"), 30);
+        assert!(symbol_score("GetProcAddress") < symbol_score("VirtualAlloc"));
+
+        assert_eq!(symbol_score("printf"), 0);
+        assert_eq!(symbol_score("GetTickCount"), 0);
+        // POSIX names are exact, unlike Win32 ones
+        assert_eq!(symbol_score("Socket"), 0);
+        assert!(!is_sensitive_api("socket"));
+    }
+}
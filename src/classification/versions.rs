@@ -0,0 +1,142 @@
+use crate::classification::network::find_ipv4;
+use crate::types::{FoundString, Tag};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Score boost applied to strings containing a software version.
+///
+/// Modest: versions help match embedded libraries against vulnerability
+/// data, but on their own say little about what a binary does.
+pub const VERSION_BOOST: i32 = 12;
+
+/// Dotted version numbers: three or four numeric components (two with a `v`
+/// prefix), then an optional semver pre-release and build metadata.
+///
+/// The number may not touch another letter, digit or dot, so OIDs, longer
+/// dotted runs and `libfoo.so.1.2.3` suffixes are left alone.
+fn version_regex() -> &'static Regex {
+    static VERSION: OnceLock<Regex> = OnceLock::new();
+    VERSION.get_or_init(|| {
+        Regex::new(
+            r"(?x)
+            (?:^|[^0-9A-Za-z._-])
+            (?P<version>
+                (?P<prefix>[vV])?
+                (?P<core>[0-9]{1,5}\.[0-9]{1,5}(?:\.[0-9]{1,6}){0,2})
+                (?:-[0-9A-Za-z]+(?:\.[0-9A-Za-z]+)*)?       # pre-release
+                (?:\+[0-9A-Za-z]+(?:\.[0-9A-Za-z]+)*)?      # build metadata
+            )
+            (?:$|[^0-9A-Za-z.+-]|\.$|\.\s)
+            ",
+        )
+        .expect("version pattern is valid")
+    })
+}
+
+/// Words that announce a version number, e.g. `FileVersion 1.0.0.1`.
+fn context_regex() -> &'static Regex {
+    static CONTEXT: OnceLock<Regex> = OnceLock::new();
+    CONTEXT.get_or_init(|| {
+        Regex::new(r"(?i)(?:version|ver|build|release|rev)[\s:=/]*$")
+            .expect("version context pattern is valid")
+    })
+}
+
+/// Finds the first software version in `text`, e.g. `1.2.3`,
+/// `v4.0.1-rc2` or `10.0.19041.1`.
+///
+/// Bare two-part numbers such as `3.14` are not versions; they need a `v`
+/// prefix. Four-part numbers that also read as an IPv4 address (every part
+/// at most 255) only count when a `v` prefix or a word such as `version`
+/// or `build` introduces them, so `192.168.1.10` stays an address while
+/// `FileVersion 6.1.7601.17514` and `v1.0.0.1` are versions.
+pub fn find_version(text: &str) -> Option<&str> {
+    version_regex().captures_iter(text).find_map(|captures| {
+        let version = captures.name("version")?;
+        let core = captures.name("core")?.as_str();
+        let prefixed = captures.name("prefix").is_some();
+        let parts = core.split('.').count();
+        let introduced = prefixed || context_regex().is_match(&text[..version.start()]);
+
+        if parts == 2 && !prefixed {
+            return None;
+        }
+        if parts == 4 && !introduced && find_ipv4(core).is_some() {
+            return None;
+        }
+        Some(version.as_str())
+    })
+}
+
+/// Tags strings containing a software version and boosts their score.
+///
+/// Version strings identify the libraries and OS builds a binary embeds or
+/// targets. Returns `true` if a version was found. Repeated calls do not
+/// apply the boost twice.
+pub fn tag_version(string: &mut FoundString) -> bool {
+    if find_version(&string.text).is_none() {
+        return false;
+    }
+    if !string.tags.contains(&Tag::Version) {
+        string.tags.push(Tag::Version);
+        string.score += VERSION_BOOST;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classification::network::tag_ip_addresses;
+    use crate::classification::tests::found;
+
+    #[test]
+    fn test_versions() {
+        for (text, version) in [
+            ("1.2.3", "1.2.3"),
+            ("OpenSSL 3.0.13 30 Jan 2024", "3.0.13"),
+            ("client v4.0.1-rc2 starting", "v4.0.1-rc2"),
+            ("1.0.0-alpha.1+build.5", "1.0.0-alpha.1+build.5"),
+            ("v2.1", "v2.1"),
+            ("Windows 10.0.19041.1", "10.0.19041.1"),
+            ("FileVersion 6.1.7601.17514", "6.1.7601.17514"),
+            ("build 1.0.0.1", "1.0.0.1"),
+            ("v1.0.0.1", "v1.0.0.1"),
+            ("zlib 1.2.13.", "1.2.13"),
+        ] {
+            assert_eq!(find_version(text), Some(version), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_non_versions() {
+        for text in [
+            // IPv4 addresses stay addresses
+            "192.168.1.10",
+            "connect to 10.0.0.1:443",
+            // Plain decimals and longer dotted runs
+            "pi is 3.14",
+            "1.3.6.1.4.1.311",
+            "libssl.so.1.1.1",
+            "1.2.3.4.5",
+            "no digits here",
+        ] {
+            assert_eq!(find_version(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn test_tag_version() {
+        let mut string = found("Qt 5.15.2");
+        assert!(tag_version(&mut string));
+        assert!(tag_version(&mut string));
+        assert_eq!(string.tags, vec![Tag::Version]);
+        assert_eq!(string.score, 10 + VERSION_BOOST);
+
+        // An address is tagged IPv4, not Version
+        let mut address = found("192.168.1.10");
+        assert!(tag_ip_addresses(&mut address));
+        assert!(!tag_version(&mut address));
+        assert_eq!(address.tags, vec![Tag::IPv4]);
+    }
+}
@@ -0,0 +1,310 @@
+use crate::classification::symbols::SENSITIVE_WIN32_APIS;
+use crate::types::{FoundString, StringSource, Tag};
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+/// System DLLs whose names, stored as data, point at APIs loaded at runtime
+/// with `LoadLibrary`. Listed lowercase, without the `.dll` extension.
+pub const COMMON_DLLS: &[&str] = &[
+    "advapi32",
+    "amsi",
+    "bcrypt",
+    "cfgmgr32",
+    "comctl32",
+    "comdlg32",
+    "crypt32",
+    "dbghelp",
+    "dnsapi",
+    "gdi32",
+    "iphlpapi",
+    "kernel32",
+    "kernelbase",
+    "mpr",
+    "mscoree",
+    "msvcrt",
+    "ncrypt",
+    "netapi32",
+    "ntdll",
+    "ole32",
+    "oleaut32",
+    "psapi",
+    "rpcrt4",
+    "samlib",
+    "secur32",
+    "setupapi",
+    "shell32",
+    "shlwapi",
+    "ucrtbase",
+    "urlmon",
+    "user32",
+    "userenv",
+    "vcruntime140",
+    "version",
+    "winhttp",
+    "wininet",
+    "winmm",
+    "wintrust",
+    "ws2_32",
+    "wsock32",
+    "wtsapi32",
+];
+
+/// Everyday Win32 APIs that loaders resolve by name alongside the
+/// [sensitive ones](crate::classification::symbols::SENSITIVE_WIN32_APIS).
+/// Names are listed without the `A`/`W` suffix; both variants match.
+pub const COMMON_WIN32_APIS: &[&str] = &[
+    // Modules and resources
+    "GetModuleHandle",
+    "GetModuleHandleEx",
+    "GetModuleFileName",
+    "FreeLibrary",
+    "FindResource",
+    "LoadResource",
+    "LockResource",
+    "SizeofResource",
+    // Files
+    "CreateFile",
+    "ReadFile",
+    "WriteFile",
+    "DeleteFile",
+    "CopyFile",
+    "MoveFile",
+    "MoveFileEx",
+    "FindFirstFile",
+    "FindNextFile",
+    "GetTempPath",
+    "GetSystemDirectory",
+    "GetWindowsDirectory",
+    // Processes and threads
+    "CreateThread",
+    "ResumeThread",
+    "SuspendThread",
+    "GetThreadContext",
+    "ExitProcess",
+    "TerminateProcess",
+    "CreateToolhelp32Snapshot",
+    "Process32First",
+    "Process32Next",
+    "Module32First",
+    "Module32Next",
+    "Thread32First",
+    "Thread32Next",
+    "NtQuerySystemInformation",
+    // Memory
+    "VirtualFree",
+    "VirtualQuery",
+    "HeapAlloc",
+    "HeapCreate",
+    "RtlMoveMemory",
+    "RtlDecompressBuffer",
+    // Synchronization and timing
+    "CloseHandle",
+    "CreateMutex",
+    "OpenMutex",
+    "Sleep",
+    "GetTickCount",
+    "GetTickCount64",
+    // System information
+    "GetComputerName",
+    "GetUserName",
+    "GetSystemInfo",
+    "GetVersionEx",
+    "GetNativeSystemInfo",
+    // Registry
+    "RegOpenKeyEx",
+    "RegQueryValueEx",
+    "RegCloseKey",
+    "RegDeleteKey",
+    "RegDeleteValue",
+    // Cryptography
+    "CryptAcquireContext",
+    "CryptCreateHash",
+    "CryptHashData",
+    "CryptEncrypt",
+    "CryptDecrypt",
+    "BCryptDecrypt",
+    "BCryptEncrypt",
+    // Network
+    "InternetConnect",
+    "HttpOpenRequest",
+    "InternetCloseHandle",
+    "WinHttpConnect",
+    "WinHttpOpenRequest",
+    "WSASocket",
+    "gethostbyname",
+    "getaddrinfo",
+    // Windows
+    "MessageBox",
+    "FindWindow",
+    "GetForegroundWindow",
+    "GetWindowText",
+];
+
+/// Score boost for a data string naming a system DLL or an everyday Win32
+/// API.
+///
+/// Lower than [`SENSITIVE_API_BOOST`](super::symbols::SENSITIVE_API_BOOST):
+/// benign programs load `kernel32.dll` and call `Sleep` too.
+pub const API_REFERENCE_BOOST: i32 = 10;
+
+/// Lookup table of [`COMMON_WIN32_APIS`] and the sensitive Win32 API names.
+fn known_api_index() -> &'static HashSet<&'static str> {
+    static INDEX: OnceLock<HashSet<&'static str>> = OnceLock::new();
+    INDEX.get_or_init(|| {
+        COMMON_WIN32_APIS
+            .iter()
+            .copied()
+            .chain(SENSITIVE_WIN32_APIS.iter().map(|entry| entry.name))
+            .collect()
+    })
+}
+
+/// Returns `true` if `text` names a system DLL, e.g. `kernel32.dll` or
+/// `WS2_32.DLL`.
+///
+/// Matching is case-insensitive and ignores surrounding whitespace. Besides
+/// [`COMMON_DLLS`], API set names (`api-ms-win-*.dll`, `ext-ms-win-*.dll`)
+/// match. The `.dll` extension is required, so words such as `version` are
+/// not mistaken for DLLs.
+pub fn is_dll_name(text: &str) -> bool {
+    let lower = text.trim().to_ascii_lowercase();
+    let Some(stem) = lower.strip_suffix(".dll") else {
+        return false;
+    };
+    if stem.starts_with("api-ms-win-") || stem.starts_with("ext-ms-win-") {
+        return stem
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_');
+    }
+    COMMON_DLLS.contains(&stem)
+}
+
+/// Returns `true` if `text` names a known Win32 API, sensitive or not.
+///
+/// Matching ignores surrounding whitespace, accepts the `A`/`W` suffixes and
+/// only considers whole strings. Unlike DLL names it is case-sensitive:
+/// `GetProcAddress` resolves exact names, and lowercase words such as
+/// `sleep` are ordinary text.
+pub fn is_known_api(text: &str) -> bool {
+    let name = text.trim();
+    let index = known_api_index();
+    index.contains(name)
+        || name
+            .strip_suffix(['A', 'W'])
+            .is_some_and(|base| index.contains(base))
+}
+
+/// Tags a data string naming a system DLL or a known Win32 API as an
+/// `Import` and boosts its score by [`API_REFERENCE_BOOST`].
+///
+/// Malware hides its imports by storing names such as `kernel32.dll` and
+/// `VirtualProtect` as data and resolving them with `LoadLibrary` and
+/// `GetProcAddress`; this finds those references the import table misses.
+/// Returns `true` if the string matched. Repeated calls, or a string already
+/// tagged by [`tag_sensitive_api`](super::symbols::tag_sensitive_api), do not
+/// apply the boost again. Import and export names are left alone.
+pub fn tag_api_reference(string: &mut FoundString) -> bool {
+    if matches!(
+        string.source,
+        StringSource::ImportName | StringSource::ExportName
+    ) {
+        return false;
+    }
+    if !is_dll_name(&string.text) && !is_known_api(&string.text) {
+        return false;
+    }
+
+    if !string.tags.contains(&Tag::Import) {
+        string.tags.push(Tag::Import);
+        string.score += API_REFERENCE_BOOST;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classification::symbols::{SENSITIVE_API_BOOST, tag_sensitive_api};
+    use crate::classification::tests::found;
+
+    #[test]
+    fn test_dll_names() {
+        for text in [
+            "kernel32.dll",
+            "KERNEL32.DLL",
+            "ws2_32.dll",
+            " ntdll.dll ",
+            "api-ms-win-core-synch-l1-2-0.dll",
+        ] {
+            assert!(is_dll_name(text), "{text}");
+        }
+        for text in [
+            "kernel32",
+            "version",
+            "mylib.dll",
+            "api-ms-win-core/x.dll",
+            ".dll",
+        ] {
+            assert!(!is_dll_name(text), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_known_apis() {
+        for text in [
+            "LoadLibraryA",
+            "VirtualProtect",
+            "GetModuleHandleW",
+            " CreateFileA ",
+            "Sleep",
+            "gethostbyname",
+        ] {
+            assert!(is_known_api(text), "{text}");
+        }
+        for text in [
+            "printf",
+            "Sleepy",
+            "sleep",
+            "createfilea",
+            "VIRTUALPROTECT",
+            "CreateFileX",
+            "call CreateFileW",
+            "",
+        ] {
+            assert!(!is_known_api(text), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_tag_api_reference() {
+        let mut string = found("GetTickCount");
+        assert!(tag_api_reference(&mut string));
+        assert!(tag_api_reference(&mut string));
+        assert_eq!(string.tags, vec![Tag::Import]);
+        assert_eq!(string.score, 10 + API_REFERENCE_BOOST);
+
+        let mut dll = found("kernel32.dll");
+        assert!(tag_api_reference(&mut dll));
+        assert_eq!(dll.tags, vec![Tag::Import]);
+
+        // Not boosted twice alongside the sensitive API classifier
+        let mut sensitive = found("VirtualProtect");
+        assert!(tag_sensitive_api(&mut sensitive));
+        assert!(tag_api_reference(&mut sensitive));
+        assert_eq!(sensitive.score, 10 + SENSITIVE_API_BOOST);
+
+        let mut import = found("CreateFileW");
+        import.source = StringSource::ImportName;
+        import.tags = vec![Tag::Import];
+        assert!(!tag_api_reference(&mut import));
+        assert_eq!(import.score, 10);
+
+        let mut plain = found("Hello, world");
+        assert!(!tag_api_reference(&mut plain));
+        assert!(plain.tags.is_empty());
+
+        let mut word = found("sleep");
+        assert!(!tag_api_reference(&mut word));
+        assert!(word.tags.is_empty());
+    }
+}
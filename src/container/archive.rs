@@ -0,0 +1,246 @@
+use crate::container::{ContainerParser, create_parser, detect_format};
+use crate::types::{BinaryFormat, ContainerInfo, FoundString, Result, StringyError};
+use goblin::archive::{Archive, MAGIC};
+
+/// Parser for `ar` archives: static libraries (`.a`, `.lib`) and `.deb`
+/// packages.
+///
+/// Each member is parsed with the parser for its own format, and the
+/// results are merged into one `ContainerInfo`. Section names are prefixed
+/// with the member name (`crypto.o:.rodata`) and offsets are relative to the
+/// start of the archive. Members in an unknown format, and members that fail
+/// to parse, are skipped.
+///
+/// # Examples
+///
+/// ```rust
+/// use stringy::container::{ContainerParser, archive::ArchiveParser};
+///
+/// assert!(ArchiveParser::detect(b"!<arch>\n"));
+/// assert!(!ArchiveParser::detect(b"\x7fELF"));
+/// ```
+pub struct ArchiveParser;
+
+impl Default for ArchiveParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArchiveParser {
+    /// Creates a new archive parser instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns the name and bytes of each member in archive order, with the
+    /// offset of the bytes within `data`.
+    ///
+    /// The symbol index (`/`, `__.SYMDEF`) and the long-name table (`//`)
+    /// are not members.
+    fn members(data: &[u8]) -> Result<Vec<(String, u64, &[u8])>> {
+        let archive = Archive::parse(data)?;
+
+        let mut members = Vec::new();
+        for index in 0..archive.len() {
+            let Some(member) = archive.get_at(index) else {
+                continue;
+            };
+            let name = member.extended_name();
+            if matches!(name, "" | "/" | "//" | "/SYM64") || name.starts_with("__.SYMDEF") {
+                continue;
+            }
+            let start = member.offset as usize;
+            let Some(bytes) = start
+                .checked_add(member.size())
+                .and_then(|end| data.get(start..end))
+            else {
+                log::warn!("Archive member {} extends past the end of the file", name);
+                continue;
+            };
+            members.push((name.to_string(), member.offset, bytes));
+        }
+        Ok(members)
+    }
+
+    /// Parses a member with the parser for its format, or returns `None` if
+    /// the format is unknown or parsing fails.
+    fn parse_member(name: &str, bytes: &[u8]) -> Option<(Box<dyn ContainerParser>, ContainerInfo)> {
+        let format = detect_format(bytes);
+        if format == BinaryFormat::Unknown {
+            log::debug!("Skipping archive member {} in an unknown format", name);
+            return None;
+        }
+        let parser = create_parser(format).ok()?;
+        match parser.parse(bytes) {
+            Ok(info) => Some((parser, info)),
+            Err(err) => {
+                log::warn!("Skipping archive member {}: {}", name, err);
+                None
+            }
+        }
+    }
+
+    /// Prefixes a section name with the member it belongs to.
+    fn member_section(member: &str, section: &str) -> String {
+        format!("{}:{}", member, section)
+    }
+}
+
+impl ContainerParser for ArchiveParser {
+    /// Detects the `!<arch>\n` magic.
+    fn detect(data: &[u8]) -> bool {
+        data.starts_with(MAGIC)
+    }
+
+    /// Parses every member and merges their sections, imports and exports.
+    ///
+    /// The address width and architecture are those of the first member
+    /// that parses.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StringyError::ParseError` if the archive headers are
+    /// malformed.
+    fn parse(&self, data: &[u8]) -> Result<ContainerInfo> {
+        if !Self::detect(data) {
+            return Err(StringyError::ParseError("Not an ar archive".to_string()));
+        }
+
+        let mut merged = ContainerInfo {
+            format: BinaryFormat::Archive,
+            is_64bit: false,
+            architecture: None,
+            sections: Vec::new(),
+            imports: Vec::new(),
+            exports: Vec::new(),
+            elf: None,
+            rich_header: None,
+            tls_callbacks: Vec::new(),
+        };
+        let mut first = true;
+
+        for (name, offset, bytes) in Self::members(data)? {
+            let Some((_, info)) = Self::parse_member(&name, bytes) else {
+                continue;
+            };
+            if first {
+                merged.is_64bit = info.is_64bit;
+                merged.architecture = info.architecture;
+                first = false;
+            }
+            merged
+                .sections
+                .extend(info.sections.into_iter().map(|mut section| {
+                    section.name = Self::member_section(&name, &section.name);
+                    section.offset += offset;
+                    section
+                }));
+            merged.imports.extend(info.imports);
+            merged.exports.extend(info.exports);
+        }
+
+        Ok(merged)
+    }
+
+    /// Returns the metadata strings of every member, attributed to the member
+    /// like its sections.
+    fn extract_metadata_strings(&self, data: &[u8]) -> Result<Vec<FoundString>> {
+        let mut strings = Vec::new();
+        for (name, offset, bytes) in Self::members(data)? {
+            let Some((parser, _)) = Self::parse_member(&name, bytes) else {
+                continue;
+            };
+            let member_strings = match parser.extract_metadata_strings(bytes) {
+                Ok(member_strings) => member_strings,
+                Err(err) => {
+                    log::warn!("Skipping metadata of archive member {}: {}", name, err);
+                    continue;
+                }
+            };
+            strings.extend(member_strings.into_iter().map(|mut string| {
+                string.offset += offset;
+                string.section = Some(match string.section {
+                    Some(section) => Self::member_section(&name, &section),
+                    None => name.clone(),
+                });
+                string
+            }));
+        }
+        Ok(strings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a GNU-style archive from `(name, contents)` members.
+    fn build_archive(members: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut out = MAGIC.to_vec();
+        for (name, contents) in members {
+            let header = format!(
+                "{:<16}{:<12}{:<6}{:<6}{:<8}{:<10}`\n",
+                name,
+                0,
+                0,
+                0,
+                644,
+                contents.len()
+            );
+            out.extend_from_slice(header.as_bytes());
+            out.extend_from_slice(contents);
+            if out.len() % 2 == 1 {
+                out.push(b'\n');
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_archive_detection() {
+        assert!(ArchiveParser::detect(&build_archive(&[])));
+        assert!(!ArchiveParser::detect(b"!<arch"));
+        let data = build_archive(&[("a.txt/", b"hello")]);
+        assert_eq!(detect_format(&data), BinaryFormat::Archive);
+        assert_eq!(
+            crate::container::detect_format_fast(&data),
+            BinaryFormat::Archive
+        );
+    }
+
+    #[test]
+    fn test_unknown_members_are_skipped() {
+        let data = build_archive(&[("README/", b"not an object"), ("notes.txt/", b"x")]);
+        let info = ArchiveParser::new().parse(&data).unwrap();
+        assert_eq!(info.format, BinaryFormat::Archive);
+        assert!(info.sections.is_empty());
+        assert!(
+            ArchiveParser::new()
+                .extract_metadata_strings(&data)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_members_skip_index_and_name_table() {
+        let data = build_archive(&[
+            ("/", &[0, 0, 0, 0]),
+            ("//", b"a_very_long_member_name.o/\n"),
+            ("short.o/", b"abc"),
+        ]);
+        let members = ArchiveParser::members(&data).unwrap();
+        let names: Vec<&str> = members.iter().map(|(name, ..)| name.as_str()).collect();
+        assert_eq!(names, vec!["short.o"]);
+
+        let (_, offset, bytes) = &members[0];
+        assert_eq!(*bytes, b"abc");
+        assert_eq!(&data[*offset as usize..][..3], b"abc");
+    }
+
+    #[test]
+    fn test_not_an_archive() {
+        assert!(ArchiveParser::new().parse(b"\x7fELF").is_err());
+    }
+}
@@ -0,0 +1,510 @@
+//! Parsing of Mach-O code signatures.
+//!
+//! `LC_CODE_SIGNATURE` points at a big-endian SuperBlob in `__LINKEDIT` that
+//! indexes the blobs making up the signature. Three of them carry strings
+//! worth reporting: the CodeDirectory holds the signing identifier (usually
+//! the bundle ID) and the developer Team ID, the entitlements blob is an XML
+//! property list of the privileges the binary claims, and the requirements
+//! blob holds compiled designated-requirement expressions whose operands name
+//! identifiers and certificate fields. The CMS signature and DER entitlements
+//! are not decoded.
+
+/// `CSMAGIC_EMBEDDED_SIGNATURE`: the SuperBlob indexing all other blobs
+const EMBEDDED_SIGNATURE_MAGIC: u32 = 0xfade_0cc0;
+/// `CSMAGIC_CODEDIRECTORY`
+const CODE_DIRECTORY_MAGIC: u32 = 0xfade_0c02;
+/// `CSMAGIC_REQUIREMENTS`: a SuperBlob of requirements
+const REQUIREMENTS_MAGIC: u32 = 0xfade_0c01;
+/// `CSMAGIC_REQUIREMENT`: a single requirement
+const REQUIREMENT_MAGIC: u32 = 0xfade_0c00;
+/// `CSMAGIC_EMBEDDED_ENTITLEMENTS`: an XML property list
+const ENTITLEMENTS_MAGIC: u32 = 0xfade_7171;
+
+/// `CSSLOT_CODEDIRECTORY`; alternate CodeDirectories repeat its contents
+const CODE_DIRECTORY_SLOT: u32 = 0;
+/// First CodeDirectory version with a `teamOffset` field
+const TEAM_ID_VERSION: u32 = 0x20200;
+/// Requirement kind whose body is an expression
+const EXPRESSION_KIND: u32 = 1;
+
+/// Expression opcode flags: `opFlagDefault` and `opGenericSkip`
+const OP_FLAGS: u32 = 0xc000_0000;
+/// `opGenericSkip`: the opcode is followed by a data operand to skip
+const OP_GENERIC_SKIP: u32 = 0x4000_0000;
+
+/// What a string in a code signature is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureField {
+    /// Signing identifier from the CodeDirectory
+    Identifier,
+    /// Developer Team ID from the CodeDirectory
+    TeamId,
+    /// The whole entitlements property list
+    Entitlements,
+    /// Key of an entitlement
+    EntitlementKey,
+    /// String value of an entitlement
+    EntitlementValue,
+    /// String operand of a requirement expression
+    Requirement,
+}
+
+/// A string found in a code signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureString {
+    /// Offset of the string's bytes from the start of the SuperBlob
+    pub offset: usize,
+    /// Length of the string's bytes, which for entitlements may differ from
+    /// `text` once XML entities are decoded
+    pub length: usize,
+    pub text: String,
+    pub field: SignatureField,
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_be_bytes(bytes.try_into().ok()?))
+}
+
+/// Returns the blob at `offset` in `data` if it starts with `magic`, bounded
+/// by its own length field.
+fn blob(data: &[u8], offset: usize, magic: u32) -> Option<&[u8]> {
+    if read_u32(data, offset)? != magic {
+        return None;
+    }
+    let length = read_u32(data, offset + 4)? as usize;
+    data.get(offset..offset.checked_add(length)?)
+}
+
+/// Iterates the `(type, offset)` index of the SuperBlob `data`, skipping the
+/// magic and length.
+fn blob_index(data: &[u8]) -> impl Iterator<Item = (u32, usize)> + '_ {
+    let count = read_u32(data, 8).unwrap_or(0) as usize;
+    (0..count).map_while(move |i| {
+        let entry = 12 + i * 8;
+        Some((read_u32(data, entry)?, read_u32(data, entry + 4)? as usize))
+    })
+}
+
+/// Reads the NUL-terminated string at `offset`, if it is non-empty UTF-8.
+fn c_string(data: &[u8], offset: usize) -> Option<&str> {
+    let bytes = data.get(offset..)?;
+    let len = bytes.iter().position(|&b| b == 0)?;
+    std::str::from_utf8(&bytes[..len])
+        .ok()
+        .filter(|text| !text.is_empty())
+}
+
+/// Extracts the strings of the code signature SuperBlob `data`.
+///
+/// Strings come in blob index order, which puts the CodeDirectory's signing
+/// identifier and Team ID first. The entitlements property list is followed
+/// by its keys and string values. Data that is not an embedded signature
+/// yields nothing; malformed blobs are skipped.
+pub fn signature_strings(data: &[u8]) -> Vec<SignatureString> {
+    let mut strings = Vec::new();
+    let Some(superblob) = blob(data, 0, EMBEDDED_SIGNATURE_MAGIC) else {
+        return strings;
+    };
+
+    for (slot, offset) in blob_index(superblob) {
+        if slot == CODE_DIRECTORY_SLOT {
+            if let Some(directory) = blob(superblob, offset, CODE_DIRECTORY_MAGIC) {
+                code_directory_strings(directory, offset, &mut strings);
+            }
+        } else if let Some(entitlements) = blob(superblob, offset, ENTITLEMENTS_MAGIC) {
+            if let Some(plist) = entitlements.get(8..) {
+                entitlement_strings(plist, offset + 8, &mut strings);
+            }
+        } else if let Some(requirements) = blob(superblob, offset, REQUIREMENTS_MAGIC) {
+            for (_, requirement_offset) in blob_index(requirements) {
+                let Some(requirement) = blob(requirements, requirement_offset, REQUIREMENT_MAGIC)
+                else {
+                    continue;
+                };
+                if read_u32(requirement, 8) == Some(EXPRESSION_KIND) {
+                    let mut expression = Expression {
+                        data: requirement,
+                        pos: 12,
+                        base: offset + requirement_offset,
+                    };
+                    expression.strings(&mut strings);
+                }
+            }
+        }
+    }
+
+    strings
+}
+
+/// Adds the identifier and Team ID of the CodeDirectory `directory`, found
+/// at `base` in the SuperBlob.
+fn code_directory_strings(directory: &[u8], base: usize, out: &mut Vec<SignatureString>) {
+    let version = read_u32(directory, 8).unwrap_or(0);
+    let mut fields = vec![(read_u32(directory, 20), SignatureField::Identifier)];
+    if version >= TEAM_ID_VERSION {
+        fields.push((read_u32(directory, 48), SignatureField::TeamId));
+    }
+
+    for (offset, field) in fields {
+        let Some(offset) = offset.filter(|&offset| offset != 0).map(|o| o as usize) else {
+            continue;
+        };
+        if let Some(text) = c_string(directory, offset) {
+            out.push(SignatureString {
+                offset: base + offset,
+                length: text.len(),
+                text: text.to_string(),
+                field,
+            });
+        }
+    }
+}
+
+/// Adds the entitlements property list `plist`, found at `base` in the
+/// SuperBlob, and the text of its `<key>` and `<string>` elements.
+fn entitlement_strings(plist: &[u8], base: usize, out: &mut Vec<SignatureString>) {
+    let Ok(text) = std::str::from_utf8(plist) else {
+        return;
+    };
+    let text = text.trim_end_matches('\0');
+    if text.trim().is_empty() {
+        return;
+    }
+    out.push(SignatureString {
+        offset: base,
+        length: text.len(),
+        text: text.to_string(),
+        field: SignatureField::Entitlements,
+    });
+
+    let mut rest = 0;
+    while let Some(open) = text[rest..].find('<').map(|i| rest + i) {
+        let (field, tag) = if text[open..].starts_with("<key>") {
+            (SignatureField::EntitlementKey, "key")
+        } else if text[open..].starts_with("<string>") {
+            (SignatureField::EntitlementValue, "string")
+        } else {
+            rest = open + 1;
+            continue;
+        };
+        let start = open + tag.len() + 2;
+        let Some(end) = text[start..].find("</").map(|i| start + i) else {
+            break;
+        };
+        let raw = &text[start..end];
+        if !raw.trim().is_empty() {
+            out.push(SignatureString {
+                offset: base + start,
+                length: raw.len(),
+                text: unescape_xml(raw),
+                field,
+            });
+        }
+        rest = end;
+    }
+}
+
+/// Decodes the predefined XML entities in `text`.
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Reader over a compiled requirement expression.
+///
+/// Expressions are stored in prefix order, so reading operators and their
+/// operands front to back visits every operand without building a tree.
+struct Expression<'a> {
+    /// The requirement blob
+    data: &'a [u8],
+    pos: usize,
+    /// Offset of the requirement blob in the SuperBlob
+    base: usize,
+}
+
+impl<'a> Expression<'a> {
+    fn u32(&mut self) -> Option<u32> {
+        let value = read_u32(self.data, self.pos)?;
+        self.pos += 4;
+        Some(value)
+    }
+
+    /// Reads a length-prefixed data operand, padded to four bytes, and
+    /// returns its offset and bytes.
+    fn data(&mut self) -> Option<(usize, &'a [u8])> {
+        let length = self.u32()? as usize;
+        let start = self.pos;
+        let bytes = self.data.get(start..start.checked_add(length)?)?;
+        self.pos = start + length.next_multiple_of(4);
+        Some((start, bytes))
+    }
+
+    /// Reads a data operand, keeping it if it is printable text; hashes and
+    /// OIDs are binary and are dropped.
+    fn text(&mut self, out: &mut Vec<SignatureString>) -> Option<()> {
+        let (start, bytes) = self.data()?;
+        let printable =
+            !bytes.is_empty() && bytes.iter().all(|&b| b.is_ascii_graphic() || b == b' ');
+        if let Some(text) = std::str::from_utf8(bytes).ok().filter(|_| printable) {
+            out.push(SignatureString {
+                offset: self.base + start,
+                length: bytes.len(),
+                text: text.to_string(),
+                field: SignatureField::Requirement,
+            });
+        }
+        Some(())
+    }
+
+    /// Reads a match operation and its argument, if it takes one.
+    fn match_suffix(&mut self, out: &mut Vec<SignatureString>) -> Option<()> {
+        match self.u32()? {
+            // matchExists, matchAbsent
+            0 | 14 => {}
+            // date comparisons take a 64-bit timestamp
+            9..=13 => self.pos += 8,
+            _ => self.text(out)?,
+        }
+        Some(())
+    }
+
+    /// Collects the text operands until the expression ends or an unknown
+    /// opcode is met.
+    fn strings(&mut self, out: &mut Vec<SignatureString>) {
+        while self.pos < self.data.len() {
+            if self.op(out).is_none() {
+                break;
+            }
+        }
+    }
+
+    fn op(&mut self, out: &mut Vec<SignatureString>) -> Option<()> {
+        let op = self.u32()?;
+        if op & OP_GENERIC_SKIP != 0 {
+            self.data()?;
+            return Some(());
+        }
+
+        match op & !OP_FLAGS {
+            // false, true, anchor apple, and, or, not, anchor trusted,
+            // anchor apple generic, notarized, legacy
+            0 | 1 | 3 | 6 | 7 | 9 | 13 | 15 | 21 | 23 => {}
+            // identifier, named anchor, named code
+            2 | 18 | 19 => self.text(out)?,
+            // anchor hash
+            4 => {
+                self.u32()?;
+                self.data()?;
+            }
+            // info[key] = value (legacy)
+            5 => {
+                self.text(out)?;
+                self.text(out)?;
+            }
+            // cdhash
+            8 => {
+                self.data()?;
+            }
+            // info[key], entitlement[key]
+            10 | 16 => {
+                self.text(out)?;
+                self.match_suffix(out)?;
+            }
+            // certificate field, certificate policy, certificate field date
+            11 | 17 | 22 => {
+                self.u32()?;
+                self.text(out)?;
+                self.match_suffix(out)?;
+            }
+            // trusted certificate
+            12 => {
+                self.u32()?;
+            }
+            // certificate extension, named by OID
+            14 => {
+                self.u32()?;
+                self.data()?;
+                self.match_suffix(out)?;
+            }
+            // platform
+            20 => {
+                self.u32()?;
+            }
+            _ => return None,
+        }
+        Some(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    fn be(values: &[u32]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_be_bytes()).collect()
+    }
+
+    /// Length-prefixed requirement data operand.
+    fn operand(bytes: &[u8]) -> Vec<u8> {
+        let mut out = be(&[bytes.len() as u32]);
+        out.extend_from_slice(bytes);
+        out.resize(4 + bytes.len().next_multiple_of(4), 0);
+        out
+    }
+
+    /// Wraps `blobs` in a SuperBlob with magic `magic`, one index entry each.
+    fn superblob(magic: u32, blobs: &[(u32, Vec<u8>)]) -> Vec<u8> {
+        let header = 12 + 8 * blobs.len();
+        let mut index = Vec::new();
+        let mut body = Vec::new();
+        for (slot, blob) in blobs {
+            index.extend(be(&[*slot, (header + body.len()) as u32]));
+            body.extend_from_slice(blob);
+        }
+        let mut out = be(&[magic, (header + body.len()) as u32, blobs.len() as u32]);
+        out.extend(index);
+        out.extend(body);
+        out
+    }
+
+    /// Prefixes `payload` with a blob header.
+    fn with_header(magic: u32, payload: &[u8]) -> Vec<u8> {
+        let mut out = be(&[magic, 8 + payload.len() as u32]);
+        out.extend_from_slice(payload);
+        out
+    }
+
+    /// Builds an embedded signature with a CodeDirectory naming `identifier`
+    /// and `team_id`, the entitlements `plist` and one designated
+    /// requirement `identifier "<identifier>" and anchor apple generic and
+    /// certificate leaf[subject.OU] = "<team_id>"`.
+    pub(crate) fn signature(identifier: &str, team_id: &str, plist: &str) -> Vec<u8> {
+        // version, flags, hashOffset, identOffset, nSpecialSlots, nCodeSlots,
+        // codeLimit, hash size/type/platform/page size, spare2,
+        // scatterOffset, teamOffset
+        let ident_offset = 52;
+        let team_offset = ident_offset + identifier.len() as u32 + 1;
+        let mut directory = be(&[
+            TEAM_ID_VERSION,
+            0,
+            0,
+            ident_offset,
+            0,
+            0,
+            0,
+            0x2002_000c,
+            0,
+            0,
+            team_offset,
+        ]);
+        directory.extend_from_slice(identifier.as_bytes());
+        directory.push(0);
+        directory.extend_from_slice(team_id.as_bytes());
+        directory.push(0);
+
+        let mut expression = be(&[EXPRESSION_KIND, 6]); // and
+        expression.extend(be(&[2])); // identifier
+        expression.extend(operand(identifier.as_bytes()));
+        expression.extend(be(&[6, 15])); // and, anchor apple generic
+        expression.extend(be(&[11, 0])); // certificate leaf
+        expression.extend(operand(b"subject.OU"));
+        expression.extend(be(&[1])); // matchEqual
+        expression.extend(operand(team_id.as_bytes()));
+        let requirements = superblob(
+            REQUIREMENTS_MAGIC,
+            &[(3, with_header(REQUIREMENT_MAGIC, &expression))],
+        );
+
+        superblob(
+            EMBEDDED_SIGNATURE_MAGIC,
+            &[
+                (
+                    CODE_DIRECTORY_SLOT,
+                    with_header(CODE_DIRECTORY_MAGIC, &directory),
+                ),
+                (2, requirements),
+                (5, with_header(ENTITLEMENTS_MAGIC, plist.as_bytes())),
+            ],
+        )
+    }
+
+    pub(crate) const PLIST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+	<key>com.apple.security.app-sandbox</key>
+	<true/>
+	<key>com.apple.security.application-groups</key>
+	<array>
+		<string>ABCDE12345.group.com.example.app</string>
+	</array>
+	<key>com.example.note</key>
+	<string>Tom &amp; Jerry</string>
+</dict>
+</plist>
+"#;
+
+    #[test]
+    fn test_signature_strings() {
+        let data = signature("com.example.app", "ABCDE12345", PLIST);
+        let strings = signature_strings(&data);
+        let fields: Vec<(SignatureField, &str)> =
+            strings.iter().map(|s| (s.field, s.text.as_str())).collect();
+        assert_eq!(
+            fields,
+            vec![
+                (SignatureField::Identifier, "com.example.app"),
+                (SignatureField::TeamId, "ABCDE12345"),
+                (SignatureField::Requirement, "com.example.app"),
+                (SignatureField::Requirement, "subject.OU"),
+                (SignatureField::Requirement, "ABCDE12345"),
+                (SignatureField::Entitlements, PLIST),
+                (
+                    SignatureField::EntitlementKey,
+                    "com.apple.security.app-sandbox"
+                ),
+                (
+                    SignatureField::EntitlementKey,
+                    "com.apple.security.application-groups"
+                ),
+                (
+                    SignatureField::EntitlementValue,
+                    "ABCDE12345.group.com.example.app"
+                ),
+                (SignatureField::EntitlementKey, "com.example.note"),
+                (SignatureField::EntitlementValue, "Tom & Jerry"),
+            ]
+        );
+
+        // Offsets point at the raw bytes
+        for string in &strings {
+            let raw = &data[string.offset..string.offset + string.length];
+            if string.field != SignatureField::EntitlementValue || !string.text.contains('&') {
+                assert_eq!(raw, string.text.as_bytes());
+            }
+        }
+        let jerry = strings.last().unwrap();
+        assert_eq!(
+            &data[jerry.offset..jerry.offset + jerry.length],
+            b"Tom &amp; Jerry"
+        );
+    }
+
+    #[test]
+    fn test_malformed_signatures() {
+        assert!(signature_strings(&[]).is_empty());
+        assert!(signature_strings(b"not a signature").is_empty());
+
+        // Truncating the SuperBlob drops the blobs past the end
+        let data = signature("com.example.app", "ABCDE12345", PLIST);
+        for len in 0..data.len() {
+            let _ = signature_strings(&data[..len]);
+        }
+        let mut truncated = data.clone();
+        truncated[4..8].copy_from_slice(&100u32.to_be_bytes());
+        assert!(signature_strings(&truncated).is_empty());
+    }
+}
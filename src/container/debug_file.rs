@@ -0,0 +1,547 @@
+//! Support for separate debug information files.
+//!
+//! Stripped release binaries often ship with their symbols and DWARF data in
+//! a companion file: a GNU `.debug` file (from `objcopy --only-keep-debug`), a
+//! split-DWARF `.dwo` object, or the Mach-O binary inside a `.dSYM` bundle.
+//! This module reads such a file and turns its symbol names and DWARF string
+//! tables into [`FoundString`]s that can be merged into the primary binary's
+//! results.
+//!
+//! Files are matched by build identifier (ELF `NT_GNU_BUILD_ID` note, Mach-O
+//! `LC_UUID`) where both sides have one. A mismatch is reported with a
+//! warning, since merging the wrong debug file silently produces misleading
+//! results. `.dwo` files carry no build-id and can only be reported as
+//! unverified.
+
+use crate::types::{Encoding, FoundString, Result, SectionType, StringSource, StringyError};
+use goblin::Object;
+use goblin::elf::Elf;
+use goblin::elf::note::NT_GNU_BUILD_ID;
+use goblin::elf::section_header::{SHF_COMPRESSED, SHT_NOBITS, SHT_SYMTAB};
+use goblin::elf::sym::{STT_FUNC, STT_OBJECT};
+use goblin::mach::load_command::CommandVariant;
+use goblin::mach::{Mach, MachO};
+
+/// ELF sections holding NUL-terminated DWARF strings.
+const ELF_DEBUG_STRING_SECTIONS: &[&str] = &[
+    ".debug_str",
+    ".debug_line_str",
+    ".debug_str.dwo",
+    ".debug_line_str.dwo",
+];
+
+/// Mach-O `__DWARF` sections holding NUL-terminated strings.
+const MACHO_DEBUG_STRING_SECTIONS: &[&str] = &["__debug_str", "__debug_line_str"];
+
+/// Section name given to strings merged by [`merge_external_debug`].
+///
+/// Their offsets point into the debug file rather than the primary binary,
+/// so they must not be attributed to one of its sections.
+pub const DEBUG_FILE_SECTION: &str = "<debug-file>";
+
+/// Strings and identity of an external debug file.
+#[derive(Debug, Clone, Default)]
+pub struct ExternalDebugInfo {
+    /// Build identifier as lowercase hex, if the file has one
+    pub build_id: Option<String>,
+    /// Symbol names and DWARF strings, all with `StringSource::DebugInfo`.
+    ///
+    /// Offsets refer to the debug file, not the primary binary, and `rva` is
+    /// always `None`.
+    pub strings: Vec<FoundString>,
+}
+
+/// Result of matching a debug file against the primary binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildIdCheck {
+    /// Both files carry the same build identifier
+    Match,
+    /// The build identifiers differ
+    Mismatch {
+        /// Build identifier of the primary binary
+        primary: String,
+        /// Build identifier of the debug file
+        debug: String,
+    },
+    /// At least one file has no build identifier, so the pairing is unverified
+    Unverified,
+}
+
+/// Reads the build identifier of an ELF or Mach-O binary as lowercase hex.
+///
+/// For fat Mach-O binaries the first architecture is used.
+pub fn build_id(data: &[u8]) -> Option<String> {
+    match Object::parse(data).ok()? {
+        Object::Elf(elf) => elf_build_id(&elf, data),
+        Object::Mach(Mach::Binary(macho)) => macho_uuid(&macho),
+        Object::Mach(Mach::Fat(fat)) => fat
+            .into_iter()
+            .flatten()
+            .find_map(|arch| match arch {
+                goblin::mach::SingleArch::MachO(macho) => Some(macho),
+                goblin::mach::SingleArch::Archive(_) => None,
+            })
+            .and_then(|macho| macho_uuid(&macho)),
+        _ => None,
+    }
+}
+
+/// Reads symbol names and DWARF strings from an external debug file.
+///
+/// For fat Mach-O files (multi-architecture dSYMs) the slice whose `LC_UUID`
+/// equals `primary_build_id` is used, falling back to the first slice.
+///
+/// # Errors
+///
+/// Returns `StringyError::UnsupportedFormat` if the file is neither ELF nor
+/// Mach-O, and `StringyError::ParseError` if it cannot be parsed.
+pub fn read_external_debug(
+    data: &[u8],
+    primary_build_id: Option<&str>,
+) -> Result<ExternalDebugInfo> {
+    match Object::parse(data)? {
+        Object::Elf(elf) => Ok(ExternalDebugInfo {
+            build_id: elf_build_id(&elf, data),
+            strings: elf_debug_strings(&elf, data),
+        }),
+        Object::Mach(Mach::Binary(macho)) => Ok(macho_debug_info(&macho, 0)),
+        Object::Mach(Mach::Fat(fat)) => {
+            let mut fallback = None;
+            for arch in fat.iter_arches() {
+                let arch = arch?;
+                let start = arch.offset as usize;
+                let end = start.saturating_add(arch.size as usize);
+                let Some(slice) = data.get(start..end) else {
+                    continue;
+                };
+                let Ok(macho) = MachO::parse(slice, 0) else {
+                    continue;
+                };
+                let info = macho_debug_info(&macho, arch.offset as u64);
+                if primary_build_id.is_some() && info.build_id.as_deref() == primary_build_id {
+                    return Ok(info);
+                }
+                fallback.get_or_insert(info);
+            }
+            fallback.ok_or_else(|| {
+                StringyError::ParseError("No usable architecture in fat debug file".to_string())
+            })
+        }
+        _ => Err(StringyError::UnsupportedFormat),
+    }
+}
+
+/// Compares the build identifiers of the primary binary and a debug file.
+pub fn check_build_id(primary_build_id: Option<&str>, debug: &ExternalDebugInfo) -> BuildIdCheck {
+    match (primary_build_id, debug.build_id.as_deref()) {
+        (Some(primary), Some(debug)) if primary == debug => BuildIdCheck::Match,
+        (Some(primary), Some(debug)) => BuildIdCheck::Mismatch {
+            primary: primary.to_string(),
+            debug: debug.to_string(),
+        },
+        _ => BuildIdCheck::Unverified,
+    }
+}
+
+/// Reads an external debug file and appends its strings to `strings`.
+///
+/// The strings are merged even when the build identifiers disagree, since
+/// the user asked for this file explicitly, but a warning is logged so the
+/// mismatch is never silent. Merged strings have their `section` set to
+/// [`DEBUG_FILE_SECTION`].
+///
+/// # Errors
+///
+/// See [`read_external_debug`].
+pub fn merge_external_debug(
+    primary: &[u8],
+    debug_data: &[u8],
+    strings: &mut Vec<FoundString>,
+) -> Result<BuildIdCheck> {
+    let primary_id = build_id(primary);
+    let debug = read_external_debug(debug_data, primary_id.as_deref())?;
+    let check = check_build_id(primary_id.as_deref(), &debug);
+
+    match &check {
+        BuildIdCheck::Match => log::debug!("Debug file build-id matches primary binary"),
+        BuildIdCheck::Mismatch { primary, debug } => log::warn!(
+            "Debug file build-id {} does not match binary build-id {}; merged strings may be unrelated",
+            debug,
+            primary
+        ),
+        BuildIdCheck::Unverified => {
+            log::info!("Debug file could not be verified by build-id; merging anyway")
+        }
+    }
+
+    strings.extend(debug.strings.into_iter().map(|string| FoundString {
+        section: Some(DEBUG_FILE_SECTION.to_string()),
+        ..string
+    }));
+    Ok(check)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn elf_build_id(elf: &Elf, data: &[u8]) -> Option<String> {
+    let notes = elf
+        .iter_note_sections(data, None)
+        .into_iter()
+        .flatten()
+        .chain(elf.iter_note_headers(data).into_iter().flatten());
+    notes
+        .flatten()
+        .find(|note| note.n_type == NT_GNU_BUILD_ID && note.name == "GNU")
+        .map(|note| hex(note.desc))
+}
+
+fn macho_uuid(macho: &MachO) -> Option<String> {
+    macho.load_commands.iter().find_map(|lc| match &lc.command {
+        CommandVariant::Uuid(cmd) => Some(hex(&cmd.uuid)),
+        _ => None,
+    })
+}
+
+/// Builds a debug-info string for a name found at `offset` in the debug file.
+fn debug_string(text: &str, offset: u64, section: Option<String>) -> FoundString {
+    FoundString {
+        section_type: section.as_ref().map(|_| SectionType::Debug),
+        section,
+        ..FoundString::new(
+            text,
+            Encoding::Utf8,
+            offset,
+            text.len() as u32,
+            StringSource::DebugInfo,
+        )
+    }
+}
+
+/// Splits a string table into its non-empty, valid UTF-8 entries.
+fn push_string_table(
+    strings: &mut Vec<FoundString>,
+    table: &[u8],
+    file_offset: u64,
+    section: &str,
+) {
+    let mut pos = 0usize;
+    for entry in table.split(|&b| b == 0) {
+        let start = pos;
+        pos += entry.len() + 1;
+        if let Ok(text) = std::str::from_utf8(entry) {
+            if !text.is_empty() {
+                strings.push(debug_string(
+                    text,
+                    file_offset + start as u64,
+                    Some(section.to_string()),
+                ));
+            }
+        }
+    }
+}
+
+fn elf_debug_strings(elf: &Elf, data: &[u8]) -> Vec<FoundString> {
+    let mut strings = Vec::new();
+
+    // Symbol names live in the string table linked from .symtab
+    let strtab_offset = elf
+        .section_headers
+        .iter()
+        .find(|h| h.sh_type == SHT_SYMTAB)
+        .and_then(|symtab| elf.section_headers.get(symtab.sh_link as usize))
+        .map_or(0, |strtab| strtab.sh_offset);
+
+    for sym in elf.syms.iter() {
+        let st_type = sym.st_type();
+        if sym.st_shndx == 0 || (st_type != STT_FUNC && st_type != STT_OBJECT) {
+            continue;
+        }
+        if let Some(name) = elf.strtab.get_at(sym.st_name) {
+            if !name.is_empty() {
+                strings.push(debug_string(
+                    name,
+                    strtab_offset + sym.st_name as u64,
+                    Some(".strtab".to_string()),
+                ));
+            }
+        }
+    }
+
+    strings.extend(elf_dwarf_strings(elf, data, None));
+    strings
+}
+
+/// Reads the DWARF string tables (`.debug_str`, `.debug_line_str` and their
+/// `.dwo` variants) of an ELF file.
+///
+/// Sections larger than `max_section_size` bytes are skipped, as are
+/// compressed sections and `NOBITS` placeholders left by `strip`.
+pub(crate) fn elf_dwarf_strings(
+    elf: &Elf,
+    data: &[u8],
+    max_section_size: Option<u64>,
+) -> Vec<FoundString> {
+    let mut strings = Vec::new();
+
+    for header in &elf.section_headers {
+        let name = elf.shdr_strtab.get_at(header.sh_name).unwrap_or("");
+        if !ELF_DEBUG_STRING_SECTIONS.contains(&name) || header.sh_type == SHT_NOBITS {
+            continue;
+        }
+        if header.sh_flags & u64::from(SHF_COMPRESSED) != 0 {
+            log::debug!("Skipping compressed debug section {}", name);
+            continue;
+        }
+        if let Some(max) = max_section_size {
+            if header.sh_size > max {
+                log::info!(
+                    "Skipping debug section {} ({} bytes, limit {})",
+                    name,
+                    header.sh_size,
+                    max
+                );
+                continue;
+            }
+        }
+        let start = header.sh_offset as usize;
+        let end = start.saturating_add(header.sh_size as usize);
+        if let Some(table) = data.get(start..end) {
+            push_string_table(&mut strings, table, header.sh_offset, name);
+        }
+    }
+
+    strings
+}
+
+fn macho_debug_info(macho: &MachO, base_offset: u64) -> ExternalDebugInfo {
+    let mut strings = Vec::new();
+
+    let stroff = macho
+        .load_commands
+        .iter()
+        .find_map(|lc| match &lc.command {
+            CommandVariant::Symtab(cmd) => Some(u64::from(cmd.stroff)),
+            _ => None,
+        })
+        .unwrap_or(0);
+
+    for (name, nlist) in macho.symbols().flatten() {
+        if nlist.is_stab() || nlist.n_sect == 0 || name.is_empty() {
+            continue;
+        }
+        strings.push(debug_string(
+            name,
+            base_offset + stroff + nlist.n_strx as u64,
+            Some("LC_SYMTAB".to_string()),
+        ));
+    }
+
+    for segment in &macho.segments {
+        if segment.name().unwrap_or("") != "__DWARF" {
+            continue;
+        }
+        for (section, table) in segment.sections().into_iter().flatten() {
+            let section_name = section.name().unwrap_or("");
+            if MACHO_DEBUG_STRING_SECTIONS.contains(&section_name) {
+                push_string_table(
+                    &mut strings,
+                    table,
+                    base_offset + section.offset as u64,
+                    &format!("__DWARF,{}", section_name),
+                );
+            }
+        }
+    }
+
+    ExternalDebugInfo {
+        build_id: macho_uuid(macho),
+        strings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use goblin::elf::section_header::{SHT_NOTE, SHT_PROGBITS, SHT_STRTAB};
+
+    /// Builds a minimal little-endian ELF64 relocatable file containing the
+    /// given `(name, sh_type, contents)` sections plus `.shstrtab`.
+    fn build_elf(sections: &[(&str, u32, &[u8])]) -> Vec<u8> {
+        let mut shstrtab = vec![0u8];
+        let mut name_offsets = Vec::new();
+        for (name, _, _) in sections.iter().chain([(".shstrtab", 0, &[][..])].iter()) {
+            name_offsets.push(shstrtab.len() as u32);
+            shstrtab.extend_from_slice(name.as_bytes());
+            shstrtab.push(0);
+        }
+
+        let mut body = Vec::new();
+        let mut placed = Vec::new();
+        for (_, _, contents) in sections {
+            placed.push((64 + body.len()) as u64);
+            body.extend_from_slice(contents);
+        }
+        let shstrtab_offset = (64 + body.len()) as u64;
+        body.extend_from_slice(&shstrtab);
+        while body.len() % 8 != 0 {
+            body.push(0);
+        }
+        let shoff = (64 + body.len()) as u64;
+        let shnum = sections.len() as u16 + 2;
+
+        let mut out = Vec::new();
+        out.extend_from_slice(&[0x7f, b'E', b'L', b'F', 2, 1, 1, 0]);
+        out.extend_from_slice(&[0u8; 8]);
+        out.extend_from_slice(&1u16.to_le_bytes()); // ET_REL
+        out.extend_from_slice(&62u16.to_le_bytes()); // EM_X86_64
+        out.extend_from_slice(&1u32.to_le_bytes());
+        out.extend_from_slice(&0u64.to_le_bytes()); // e_entry
+        out.extend_from_slice(&0u64.to_le_bytes()); // e_phoff
+        out.extend_from_slice(&shoff.to_le_bytes());
+        out.extend_from_slice(&0u32.to_le_bytes()); // e_flags
+        out.extend_from_slice(&64u16.to_le_bytes()); // e_ehsize
+        out.extend_from_slice(&56u16.to_le_bytes()); // e_phentsize
+        out.extend_from_slice(&0u16.to_le_bytes()); // e_phnum
+        out.extend_from_slice(&64u16.to_le_bytes()); // e_shentsize
+        out.extend_from_slice(&shnum.to_le_bytes());
+        out.extend_from_slice(&(shnum - 1).to_le_bytes()); // e_shstrndx
+        out.extend_from_slice(&body);
+
+        let mut section_header = |name: u32, sh_type: u32, offset: u64, size: u64| {
+            out.extend_from_slice(&name.to_le_bytes());
+            out.extend_from_slice(&sh_type.to_le_bytes());
+            out.extend_from_slice(&0u64.to_le_bytes()); // sh_flags
+            out.extend_from_slice(&0u64.to_le_bytes()); // sh_addr
+            out.extend_from_slice(&offset.to_le_bytes());
+            out.extend_from_slice(&size.to_le_bytes());
+            out.extend_from_slice(&0u32.to_le_bytes()); // sh_link
+            out.extend_from_slice(&0u32.to_le_bytes()); // sh_info
+            out.extend_from_slice(&1u64.to_le_bytes()); // sh_addralign
+            out.extend_from_slice(&0u64.to_le_bytes()); // sh_entsize
+        };
+        section_header(0, 0, 0, 0);
+        for (i, (_, sh_type, contents)) in sections.iter().enumerate() {
+            section_header(name_offsets[i], *sh_type, placed[i], contents.len() as u64);
+        }
+        section_header(
+            name_offsets[sections.len()],
+            SHT_STRTAB,
+            shstrtab_offset,
+            shstrtab.len() as u64,
+        );
+        out
+    }
+
+    fn build_id_note(id: &[u8]) -> Vec<u8> {
+        let mut note = Vec::new();
+        note.extend_from_slice(&4u32.to_le_bytes());
+        note.extend_from_slice(&(id.len() as u32).to_le_bytes());
+        note.extend_from_slice(&NT_GNU_BUILD_ID.to_le_bytes());
+        note.extend_from_slice(b"GNU\0");
+        note.extend_from_slice(id);
+        note
+    }
+
+    #[test]
+    fn test_elf_build_id_and_debug_strings() {
+        let note = build_id_note(&[0xde, 0xad, 0xbe, 0xef]);
+        let debug = build_elf(&[
+            (".note.gnu.build-id", SHT_NOTE, &note),
+            (".debug_str", SHT_PROGBITS, b"main.c\0decrypt_config\0"),
+        ]);
+
+        assert_eq!(build_id(&debug).as_deref(), Some("deadbeef"));
+
+        let info = read_external_debug(&debug, None).unwrap();
+        let texts: Vec<&str> = info.strings.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["main.c", "decrypt_config"]);
+        assert!(
+            info.strings
+                .iter()
+                .all(|s| s.source == StringSource::DebugInfo)
+        );
+        let second = &info.strings[1];
+        assert_eq!(&debug[second.offset as usize..][..14], b"decrypt_config");
+    }
+
+    #[test]
+    fn test_merge_reports_build_id_mismatch() {
+        let primary = build_elf(&[(".note.gnu.build-id", SHT_NOTE, &build_id_note(&[1, 2]))]);
+        let matching = build_elf(&[
+            (".note.gnu.build-id", SHT_NOTE, &build_id_note(&[1, 2])),
+            (".debug_str", SHT_PROGBITS, b"src/net.c\0"),
+        ]);
+        let other = build_elf(&[
+            (".note.gnu.build-id", SHT_NOTE, &build_id_note(&[3, 4])),
+            (".debug_str", SHT_PROGBITS, b"src/other.c\0"),
+        ]);
+
+        let mut strings = Vec::new();
+        assert_eq!(
+            merge_external_debug(&primary, &matching, &mut strings).unwrap(),
+            BuildIdCheck::Match
+        );
+        assert_eq!(strings.len(), 1);
+
+        // Mismatched data is still merged, but the mismatch is reported
+        assert_eq!(
+            merge_external_debug(&primary, &other, &mut strings).unwrap(),
+            BuildIdCheck::Mismatch {
+                primary: "0102".to_string(),
+                debug: "0304".to_string(),
+            }
+        );
+        assert_eq!(strings.len(), 2);
+    }
+
+    #[test]
+    fn test_analyze_merges_debug_file() {
+        let primary = build_elf(&[(".note.gnu.build-id", SHT_NOTE, &build_id_note(&[1, 2]))]);
+        let debug = build_elf(&[
+            (".note.gnu.build-id", SHT_NOTE, &build_id_note(&[1, 2])),
+            (".debug_str", SHT_PROGBITS, b"src/implant/beacon.c\0ab\0"),
+        ]);
+        let config = crate::AnalysisConfig {
+            debug_file: Some(debug),
+            ..crate::AnalysisConfig::default()
+        };
+
+        let result = crate::analyze(&primary, &config).unwrap();
+        let merged: Vec<&FoundString> = result
+            .strings
+            .iter()
+            .filter(|s| s.source == StringSource::DebugInfo)
+            .collect();
+        // "ab" is below the minimum length
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].text, "src/implant/beacon.c");
+        assert_eq!(merged[0].section.as_deref(), Some(DEBUG_FILE_SECTION));
+
+        let config = crate::AnalysisConfig {
+            debug_file: Some(b"not a debug file".to_vec()),
+            ..crate::AnalysisConfig::default()
+        };
+        assert!(crate::analyze(&primary, &config).is_err());
+    }
+
+    #[test]
+    fn test_dwo_without_build_id_is_unverified() {
+        let primary = build_elf(&[(".note.gnu.build-id", SHT_NOTE, &build_id_note(&[1, 2]))]);
+        let dwo = build_elf(&[(".debug_str.dwo", SHT_PROGBITS, b"parse_args\0")]);
+
+        let mut strings = Vec::new();
+        assert_eq!(
+            merge_external_debug(&primary, &dwo, &mut strings).unwrap(),
+            BuildIdCheck::Unverified
+        );
+        assert_eq!(strings[0].text, "parse_args");
+        assert_eq!(strings[0].section.as_deref(), Some(DEBUG_FILE_SECTION));
+    }
+
+    #[test]
+    fn test_unsupported_debug_file() {
+        assert!(read_external_debug(b"not a debug file", None).is_err());
+    }
+}
@@ -1,10 +1,152 @@
+use crate::classification::demangle::demangle_symbol;
 use crate::container::ContainerParser;
+use crate::container::debug_file::elf_dwarf_strings;
+use crate::container::overlay::overlay_section;
 use crate::types::{
-    BinaryFormat, ContainerInfo, ExportInfo, ImportInfo, Result, SectionInfo, SectionType,
-    StringyError,
+    BinaryFormat, ContainerInfo, ElfMetadata, Encoding, ExportInfo, FoundString, ImportInfo,
+    Result, SectionInfo, SectionType, StringSource, StringyError, Tag,
 };
 use goblin::Object;
+use goblin::elf::symver::VER_FLG_BASE;
 use goblin::elf::{Elf, SectionHeader};
+use std::collections::{HashMap, HashSet};
+
+/// `NT_GNU_ABI_TAG` note type: minimum kernel ABI (`.note.ABI-tag`)
+const NT_GNU_ABI_TAG: u32 = 1;
+/// `NT_GNU_BUILD_ID` note type: unique build identifier
+const NT_GNU_BUILD_ID: u32 = 3;
+/// `NT_GNU_GOLD_VERSION` note type: gold linker version string
+const NT_GNU_GOLD_VERSION: u32 = 4;
+/// `NT_FDO_PACKAGING_METADATA` note type: JSON package metadata (`.note.package`)
+const NT_FDO_PACKAGING_METADATA: u32 = 0xcafe_1a7e;
+
+/// A single entry of an ELF note section
+#[derive(Debug, PartialEq)]
+struct ElfNote<'a> {
+    /// Owner name without its NUL terminator
+    name: &'a [u8],
+    n_type: u32,
+    /// Offset of the descriptor from the start of the section
+    desc_offset: usize,
+    desc: &'a [u8],
+}
+
+/// Parse the entries of a note section
+///
+/// Each entry is a 12-byte header (`namesz`, `descsz`, `type`) followed by the
+/// name and descriptor, each padded to `align` bytes (4, or 8 for 8-byte
+/// aligned sections such as `.note.gnu.property`). Parsing stops at the first
+/// truncated entry.
+fn parse_notes(bytes: &[u8], align: usize, little_endian: bool) -> Vec<ElfNote<'_>> {
+    let read_u32 = |pos: usize| -> Option<usize> {
+        let word: [u8; 4] = bytes.get(pos..pos.checked_add(4)?)?.try_into().ok()?;
+        let value = if little_endian {
+            u32::from_le_bytes(word)
+        } else {
+            u32::from_be_bytes(word)
+        };
+        Some(value as usize)
+    };
+
+    let mut notes = Vec::new();
+    let mut pos = 0;
+    while pos + 12 <= bytes.len() {
+        let (Some(namesz), Some(descsz), Some(n_type)) =
+            (read_u32(pos), read_u32(pos + 4), read_u32(pos + 8))
+        else {
+            break;
+        };
+
+        let name_start = pos + 12;
+        let Some(name_end) = name_start.checked_add(namesz) else {
+            break;
+        };
+        let desc_start = name_end.next_multiple_of(align);
+        let Some(desc_end) = desc_start.checked_add(descsz) else {
+            break;
+        };
+        let (Some(name), Some(desc)) = (
+            bytes.get(name_start..name_end),
+            bytes.get(desc_start..desc_end),
+        ) else {
+            break;
+        };
+
+        let name_len = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+        notes.push(ElfNote {
+            name: &name[..name_len],
+            n_type: n_type as u32,
+            desc_offset: desc_start,
+            desc,
+        });
+        pos = desc_end.next_multiple_of(align);
+    }
+
+    notes
+}
+
+/// Read `count` 32-bit words from a note descriptor
+fn note_words(desc: &[u8], count: usize, little_endian: bool) -> Option<Vec<u32>> {
+    let words: Vec<u32> = desc
+        .chunks_exact(4)
+        .take(count)
+        .map(|chunk| {
+            let word = [chunk[0], chunk[1], chunk[2], chunk[3]];
+            if little_endian {
+                u32::from_le_bytes(word)
+            } else {
+                u32::from_be_bytes(word)
+            }
+        })
+        .collect();
+    (words.len() == count).then_some(words)
+}
+
+/// Decode a descriptor that holds NUL-terminated printable text
+fn note_text(desc: &[u8]) -> Option<&str> {
+    let len = desc.iter().position(|&b| b == 0).unwrap_or(desc.len());
+    let text = std::str::from_utf8(&desc[..len]).ok()?;
+    let printable = text
+        .chars()
+        .all(|c| !c.is_control() || c == '\t' || c == '\n');
+    (text.len() >= 4 && printable).then_some(text)
+}
+
+/// Render a note as text, with the tag it deserves
+///
+/// Build IDs become lowercase hex, ABI tags a readable `<os> <major>.<minor>.<patch>`
+/// version, and other notes are reported only when their descriptor is text.
+fn describe_note(note: &ElfNote, little_endian: bool) -> Option<(String, Option<Tag>)> {
+    match (note.name, note.n_type) {
+        (b"GNU", NT_GNU_BUILD_ID) => Some((hex(note.desc), None)),
+        (b"GNU", NT_GNU_ABI_TAG) => {
+            let words = note_words(note.desc, 4, little_endian)?;
+            let os = match words[0] {
+                0 => "Linux",
+                1 => "Hurd",
+                2 => "Solaris",
+                3 => "FreeBSD",
+                _ => "Unknown OS",
+            };
+            let version = format!("{} {}.{}.{}", os, words[1], words[2], words[3]);
+            Some((version, Some(Tag::Version)))
+        }
+        (b"GNU", NT_GNU_GOLD_VERSION) => {
+            note_text(note.desc).map(|text| (text.to_string(), Some(Tag::Version)))
+        }
+        (b"FDO", NT_FDO_PACKAGING_METADATA) => {
+            note_text(note.desc).map(|text| (text.to_string(), Some(Tag::Manifest)))
+        }
+        _ => note_text(note.desc).map(|text| (text.to_string(), None)),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Default size limit for a single DWARF string section, in bytes
+pub const DEFAULT_MAX_DEBUG_SECTION_SIZE: u64 = 64 * 1024 * 1024;
 
 /// Parser for ELF (Executable and Linkable Format) binaries
 pub struct ElfParser;
@@ -20,6 +162,57 @@ pub fn new() -> Self {
         Self
     }
 
+    /// Extract identifiers from the DWARF string sections
+    ///
+    /// `.debug_str` and `.debug_line_str` hold source file names, type names
+    /// and variable names, which survive even when the symbol table is
+    /// stripped. Strings are returned with `StringSource::DebugInfo`. Sections
+    /// larger than `max_section_size` bytes (see
+    /// [`DEFAULT_MAX_DEBUG_SECTION_SIZE`]) are skipped; binaries without debug
+    /// sections yield no strings.
+    pub fn extract_debug_strings(
+        &self,
+        data: &[u8],
+        max_section_size: Option<u64>,
+    ) -> Result<Vec<FoundString>> {
+        let elf = match Object::parse(data)? {
+            Object::Elf(elf) => elf,
+            _ => return Err(StringyError::ParseError("Not an ELF file".to_string())),
+        };
+
+        Ok(elf_dwarf_strings(&elf, data, max_section_size))
+    }
+
+    /// Returns the file offset just past the last byte described by the ELF
+    /// header, the program and section header tables, a segment, or a
+    /// section with file data.
+    fn image_end(elf: &Elf) -> u64 {
+        let header = &elf.header;
+        let table_end = |offset: u64, count: u16, entry_size: u16| {
+            offset.saturating_add(u64::from(count) * u64::from(entry_size))
+        };
+
+        let segments = elf
+            .program_headers
+            .iter()
+            .map(|ph| ph.p_offset.saturating_add(ph.p_filesz));
+        let sections = elf
+            .section_headers
+            .iter()
+            .filter(|sh| sh.sh_type != goblin::elf::section_header::SHT_NOBITS)
+            .map(|sh| sh.sh_offset.saturating_add(sh.sh_size));
+
+        segments
+            .chain(sections)
+            .chain([
+                u64::from(header.e_ehsize),
+                table_end(header.e_phoff, header.e_phnum, header.e_phentsize),
+                table_end(header.e_shoff, header.e_shnum, header.e_shentsize),
+            ])
+            .max()
+            .unwrap_or(0)
+    }
+
     /// Classify ELF section based on its name and flags
     fn classify_section(section: &SectionHeader, name: &str) -> SectionType {
         // Check section flags first
@@ -50,44 +243,306 @@ fn classify_section(section: &SectionHeader, name: &str) -> SectionType {
         }
     }
 
+    /// Map GNU symbol version indices to the `DT_NEEDED` library that defines them
+    ///
+    /// Each `.gnu.version_r` entry names a needed library and lists the version
+    /// indices (`vna_other`) it provides; `.gnu.version` then assigns one of those
+    /// indices to every dynamic symbol.
+    fn library_versions<'a>(elf: &Elf<'a>) -> HashMap<u16, &'a str> {
+        let mut versions = HashMap::new();
+
+        if let Some(verneed) = &elf.verneed {
+            for need in verneed.iter() {
+                let Some(library) = elf.dynstrtab.get_at(need.vn_file) else {
+                    continue;
+                };
+                for aux in need.iter() {
+                    versions.insert(aux.vna_other, library);
+                }
+            }
+        }
+
+        versions
+    }
+
+    /// Map GNU symbol version indices to version names
+    ///
+    /// Defined versions come from `.gnu.version_d`, skipping the base entry
+    /// that names the file itself; required versions from `.gnu.version_r`.
+    fn version_names<'a>(elf: &Elf<'a>) -> HashMap<u16, &'a str> {
+        let mut names = HashMap::new();
+
+        if let Some(verdef) = &elf.verdef {
+            for def in verdef.iter() {
+                if def.vd_flags & VER_FLG_BASE != 0 {
+                    continue;
+                }
+                if let Some(name) = def
+                    .iter()
+                    .next()
+                    .and_then(|aux| elf.dynstrtab.get_at(aux.vda_name))
+                {
+                    names.insert(def.vd_ndx, name);
+                }
+            }
+        }
+        if let Some(verneed) = &elf.verneed {
+            for need in verneed.iter() {
+                for aux in need.iter() {
+                    if let Some(name) = elf.dynstrtab.get_at(aux.vna_name) {
+                        names.insert(aux.vna_other, name);
+                    }
+                }
+            }
+        }
+
+        names
+    }
+
+    /// Version name of the dynamic symbol at `index` and whether it is
+    /// hidden, i.e. not the default version of the symbol
+    fn symbol_version(
+        elf: &Elf,
+        names: &HashMap<u16, &str>,
+        index: usize,
+    ) -> Option<(String, bool)> {
+        let versym = elf.versym.as_ref()?.get_at(index)?;
+        names
+            .get(&versym.version())
+            .map(|name| (name.to_string(), versym.is_hidden()))
+    }
+
+    /// Attribute the dynamic symbol at `index` to a needed library
+    ///
+    /// Only versioned symbols can be attributed reliably; unversioned symbols
+    /// return `None` rather than guessing among the `DT_NEEDED` entries.
+    fn library_for_symbol<'a>(
+        elf: &Elf<'a>,
+        versions: &HashMap<u16, &'a str>,
+        index: usize,
+    ) -> Option<String> {
+        let versym = elf.versym.as_ref()?.get_at(index)?;
+        versions
+            .get(&versym.version())
+            .map(|library| library.to_string())
+    }
+
     /// Extract basic import information from ELF dynamic section
+    ///
+    /// Undefined global functions are imports, as are undefined symbols referenced
+    /// by PLT relocations (some toolchains leave those untyped).
     fn extract_imports(&self, elf: &Elf) -> Vec<ImportInfo> {
         let mut imports = Vec::new();
+        let versions = Self::library_versions(elf);
+        let version_names = Self::version_names(elf);
+        let plt_symbols: HashSet<usize> = elf.pltrelocs.iter().map(|rel| rel.r_sym).collect();
 
         // Extract from dynamic symbol table
-        for sym in &elf.dynsyms {
-            if sym.st_bind() == goblin::elf::sym::STB_GLOBAL
-                && sym.st_type() == goblin::elf::sym::STT_FUNC
-                && sym.st_shndx == (goblin::elf::section_header::SHN_UNDEF as usize)
-            {
-                if let Some(name) = elf.dynstrtab.get_at(sym.st_name) {
-                    imports.push(ImportInfo {
-                        name: name.to_string(),
-                        library: None, // ELF doesn't directly specify library names in symbols
-                        address: Some(sym.st_value),
-                    });
+        for (index, sym) in elf.dynsyms.iter().enumerate() {
+            if sym.st_shndx != (goblin::elf::section_header::SHN_UNDEF as usize) {
+                continue;
+            }
+            let is_function = sym.st_bind() == goblin::elf::sym::STB_GLOBAL
+                && sym.st_type() == goblin::elf::sym::STT_FUNC;
+            if !is_function && !plt_symbols.contains(&index) {
+                continue;
+            }
+
+            if let Some(name) = elf.dynstrtab.get_at(sym.st_name) {
+                if name.is_empty() {
+                    continue;
                 }
+                let version =
+                    Self::symbol_version(elf, &version_names, index).map(|(version, _)| version);
+                // Keep the version on the demangled name too, as c++filt does
+                let demangled = demangle_symbol(name).map(|demangled| match &version {
+                    Some(version) => format!("{}@{}", demangled, version),
+                    None => demangled,
+                });
+                imports.push(ImportInfo {
+                    name: name.to_string(),
+                    library: Self::library_for_symbol(elf, &versions, index),
+                    address: Some(sym.st_value),
+                    demangled,
+                    delayed: false,
+                    version,
+                });
             }
         }
 
         imports
     }
 
+    /// Read the program interpreter and library search paths
+    fn extract_metadata(elf: &Elf) -> ElfMetadata {
+        let split_paths = |entries: &[&str]| -> Vec<String> {
+            entries
+                .iter()
+                .flat_map(|entry| entry.split(':'))
+                .filter(|path| !path.is_empty())
+                .map(str::to_string)
+                .collect()
+        };
+
+        ElfMetadata {
+            interpreter: elf.interpreter.map(str::to_string),
+            rpaths: split_paths(&elf.rpaths),
+            runpaths: split_paths(&elf.runpaths),
+            build_id: None,
+        }
+    }
+
+    /// Decode the entries of every `SHT_NOTE` section
+    ///
+    /// Returns the GNU build ID along with a string for each note that has a
+    /// textual rendering (see [`describe_note`]).
+    fn note_strings(elf: &Elf, data: &[u8]) -> (Option<String>, Vec<FoundString>) {
+        let mut build_id = None;
+        let mut strings = Vec::new();
+
+        for section in &elf.section_headers {
+            if section.sh_type != goblin::elf::section_header::SHT_NOTE {
+                continue;
+            }
+            let start = section.sh_offset as usize;
+            let Some(bytes) = start
+                .checked_add(section.sh_size as usize)
+                .and_then(|end| data.get(start..end))
+            else {
+                continue;
+            };
+            let name = elf
+                .shdr_strtab
+                .get_at(section.sh_name)
+                .unwrap_or_default()
+                .to_string();
+            let align = if section.sh_addralign == 8 { 8 } else { 4 };
+
+            for note in parse_notes(bytes, align, elf.little_endian) {
+                let Some((text, tag)) = describe_note(&note, elf.little_endian) else {
+                    continue;
+                };
+                if note.name == b"GNU" && note.n_type == NT_GNU_BUILD_ID {
+                    build_id.get_or_insert_with(|| text.clone());
+                }
+                strings.push(FoundString {
+                    rva: (section.sh_addr != 0).then(|| section.sh_addr + note.desc_offset as u64),
+                    section: Some(name.clone()),
+                    tags: tag.into_iter().collect(),
+                    ..FoundString::new(
+                        text,
+                        Encoding::Ascii,
+                        section.sh_offset + note.desc_offset as u64,
+                        note.desc.len() as u32,
+                        StringSource::SectionData,
+                    )
+                });
+            }
+        }
+
+        (build_id, strings)
+    }
+
+    /// Translate a file offset to a virtual address through the `PT_LOAD` segments
+    fn offset_to_vaddr(elf: &Elf, offset: u64) -> Option<u64> {
+        elf.program_headers
+            .iter()
+            .filter(|ph| ph.p_type == goblin::elf::program_header::PT_LOAD)
+            .find(|ph| offset >= ph.p_offset && offset - ph.p_offset < ph.p_filesz)
+            .map(|ph| ph.p_vaddr + (offset - ph.p_offset))
+    }
+
+    /// Surface the interpreter and each `DT_RPATH`/`DT_RUNPATH` search path as
+    /// `FilePath` strings located at their file offsets
+    fn loader_strings(elf: &Elf, data: &[u8]) -> Vec<FoundString> {
+        let mut strings = Vec::new();
+        let mut push = |text: &str, offset: u64, rva: Option<u64>, section: &str| {
+            strings.push(FoundString {
+                rva,
+                section: Some(section.to_string()),
+                tags: vec![Tag::FilePath],
+                ..FoundString::new(
+                    text,
+                    Encoding::Ascii,
+                    offset,
+                    text.len() as u32,
+                    StringSource::LoadCommand,
+                )
+            });
+        };
+
+        for ph in &elf.program_headers {
+            if ph.p_type != goblin::elf::program_header::PT_INTERP {
+                continue;
+            }
+            let start = ph.p_offset as usize;
+            let end = start.saturating_add(ph.p_filesz as usize).min(data.len());
+            let Some(bytes) = data.get(start..end) else {
+                continue;
+            };
+            let bytes = bytes.split(|&b| b == 0).next().unwrap_or_default();
+            if let Ok(text) = std::str::from_utf8(bytes) {
+                if !text.is_empty() {
+                    push(text, ph.p_offset, Some(ph.p_vaddr), ".interp");
+                }
+            }
+        }
+
+        let Some(dynamic) = &elf.dynamic else {
+            return strings;
+        };
+        let strtab = dynamic.info.strtab as u64;
+        for dyn_entry in &dynamic.dyns {
+            if dyn_entry.d_tag != goblin::elf::dynamic::DT_RPATH
+                && dyn_entry.d_tag != goblin::elf::dynamic::DT_RUNPATH
+            {
+                continue;
+            }
+            let Some(entry) = elf.dynstrtab.get_at(dyn_entry.d_val as usize) else {
+                continue;
+            };
+
+            // Each entry is a colon-separated list; report every path at its own offset
+            let mut position = 0;
+            for path in entry.split(':') {
+                if !path.is_empty() {
+                    let offset = strtab + dyn_entry.d_val + position as u64;
+                    push(path, offset, Self::offset_to_vaddr(elf, offset), ".dynstr");
+                }
+                position += path.len() + 1;
+            }
+        }
+
+        strings
+    }
+
     /// Extract basic export information from ELF symbol table
     fn extract_exports(&self, elf: &Elf) -> Vec<ExportInfo> {
         let mut exports = Vec::new();
+        let version_names = Self::version_names(elf);
 
         // Extract from dynamic symbol table
-        for sym in &elf.dynsyms {
+        for (index, sym) in elf.dynsyms.iter().enumerate() {
             if sym.st_bind() == goblin::elf::sym::STB_GLOBAL
                 && sym.st_shndx != (goblin::elf::section_header::SHN_UNDEF as usize)
                 && sym.st_value != 0
             {
                 if let Some(name) = elf.dynstrtab.get_at(sym.st_name) {
+                    let version = Self::symbol_version(elf, &version_names, index);
+                    let demangled = demangle_symbol(name).map(|demangled| match &version {
+                        Some((version, true)) => format!("{}@{}", demangled, version),
+                        Some((version, false)) => format!("{}@@{}", demangled, version),
+                        None => demangled,
+                    });
+                    let (version, version_hidden) = version.unzip();
                     exports.push(ExportInfo {
                         name: name.to_string(),
                         address: sym.st_value,
                         ordinal: None, // ELF doesn't use ordinals
+                        demangled,
+                        forwarded_to: None,
+                        version,
+                        version_hidden: version_hidden.unwrap_or(false),
                     });
                 }
             }
@@ -95,6 +550,51 @@ fn extract_exports(&self, elf: &Elf) -> Vec<ExportInfo> {
 
         exports
     }
+    /// Extract the imports and exports of a relocatable object (`.o`)
+    ///
+    /// Objects have no dynamic symbol table, so both come from `.symtab`:
+    /// undefined globals are imports and defined globals are exports. Symbol
+    /// values are offsets into their section, so an export at offset 0 is kept.
+    fn extract_object_symbols(&self, elf: &Elf) -> (Vec<ImportInfo>, Vec<ExportInfo>) {
+        let mut imports = Vec::new();
+        let mut exports = Vec::new();
+
+        for sym in &elf.syms {
+            let bind = sym.st_bind();
+            if bind != goblin::elf::sym::STB_GLOBAL && bind != goblin::elf::sym::STB_WEAK {
+                continue;
+            }
+            let Some(name) = elf.strtab.get_at(sym.st_name) else {
+                continue;
+            };
+            if name.is_empty() {
+                continue;
+            }
+
+            if sym.st_shndx == (goblin::elf::section_header::SHN_UNDEF as usize) {
+                imports.push(ImportInfo {
+                    name: name.to_string(),
+                    library: None,
+                    address: None,
+                    demangled: demangle_symbol(name),
+                    delayed: false,
+                    version: None,
+                });
+            } else {
+                exports.push(ExportInfo {
+                    name: name.to_string(),
+                    address: sym.st_value,
+                    ordinal: None,
+                    demangled: demangle_symbol(name),
+                    forwarded_to: None,
+                    version: None,
+                    version_hidden: false,
+                });
+            }
+        }
+
+        (imports, exports)
+    }
 }
 
 impl ContainerParser for ElfParser {
@@ -129,27 +629,65 @@ fn parse(&self, data: &[u8]) -> Result<ContainerInfo> {
             sections.push(SectionInfo {
                 name,
                 offset: section.sh_offset,
-                size: section.sh_size,
-                rva: Some(section.sh_addr), // ELF uses virtual addresses
+                // SHT_NOBITS sections (.bss, .tbss) occupy no bytes in the file
+                size: if section.sh_type == goblin::elf::section_header::SHT_NOBITS {
+                    0
+                } else {
+                    section.sh_size
+                },
+                // ELF uses virtual addresses; sections that are not loaded
+                // (.comment, .debug_*, .symtab) have none
+                rva: (section.sh_flags & u64::from(goblin::elf::section_header::SHF_ALLOC) != 0)
+                    .then_some(section.sh_addr),
                 section_type,
                 is_executable: section.sh_flags
                     & (goblin::elf::section_header::SHF_EXECINSTR as u64)
                     != 0,
                 is_writable: section.sh_flags & (goblin::elf::section_header::SHF_WRITE as u64)
                     != 0,
+                entropy: None,
+                truncated: false,
             });
         }
+        sections.extend(overlay_section(Self::image_end(&elf), data.len() as u64));
+        for section in &mut sections {
+            section.clamp_to(data.len() as u64);
+            section.measure_entropy(data);
+        }
 
-        let imports = self.extract_imports(&elf);
-        let exports = self.extract_exports(&elf);
+        let (imports, exports) = if elf.header.e_type == goblin::elf::header::ET_REL {
+            self.extract_object_symbols(&elf)
+        } else {
+            (self.extract_imports(&elf), self.extract_exports(&elf))
+        };
+        let mut metadata = Self::extract_metadata(&elf);
+        metadata.build_id = Self::note_strings(&elf, data).0;
 
         Ok(ContainerInfo {
             format: BinaryFormat::Elf,
+            is_64bit: elf.is_64,
+            architecture: None,
             sections,
             imports,
             exports,
+            elf: Some(metadata),
+            rich_header: None,
+            tls_callbacks: Vec::new(),
         })
     }
+
+    /// Returns the program interpreter, library search paths and decoded note
+    /// entries; see [`ElfMetadata`].
+    fn extract_metadata_strings(&self, data: &[u8]) -> Result<Vec<FoundString>> {
+        let elf = match Object::parse(data)? {
+            Object::Elf(elf) => elf,
+            _ => return Err(StringyError::ParseError("Not an ELF file".to_string())),
+        };
+
+        let mut strings = Self::loader_strings(&elf, data);
+        strings.extend(Self::note_strings(&elf, data).1);
+        Ok(strings)
+    }
 }
 
 #[cfg(test)]
@@ -219,6 +757,272 @@ fn test_section_classification() {
         );
     }
 
+    /// Builds a minimal ELF64 executable with `PT_INTERP`, `PT_DYNAMIC` and one
+    /// `PT_LOAD` mapping the whole file at `0x400000`.
+    fn build_dynamic_elf(interp: &str, rpath: &str, runpath: &str) -> Vec<u8> {
+        use goblin::elf::dynamic::{DT_NULL, DT_RPATH, DT_RUNPATH, DT_STRSZ, DT_STRTAB};
+        use goblin::elf::program_header::{PT_DYNAMIC, PT_INTERP, PT_LOAD};
+
+        const BASE: u64 = 0x400000;
+        let interp_offset = 64 + 3 * 56;
+        let mut body = Vec::new();
+        body.extend_from_slice(interp.as_bytes());
+        body.push(0);
+
+        let dynstr_offset = interp_offset + body.len();
+        let mut dynstr = vec![0u8];
+        let rpath_index = dynstr.len() as u64;
+        dynstr.extend_from_slice(rpath.as_bytes());
+        dynstr.push(0);
+        let runpath_index = dynstr.len() as u64;
+        dynstr.extend_from_slice(runpath.as_bytes());
+        dynstr.push(0);
+        body.extend_from_slice(&dynstr);
+        while (interp_offset + body.len()) % 8 != 0 {
+            body.push(0);
+        }
+
+        let dynamic_offset = interp_offset + body.len();
+        let entries = [
+            (DT_STRTAB, BASE + dynstr_offset as u64),
+            (DT_STRSZ, dynstr.len() as u64),
+            (DT_RPATH, rpath_index),
+            (DT_RUNPATH, runpath_index),
+            (DT_NULL, 0),
+        ];
+        for (tag, value) in entries {
+            body.extend_from_slice(&tag.to_le_bytes());
+            body.extend_from_slice(&value.to_le_bytes());
+        }
+        let file_size = (interp_offset + body.len()) as u64;
+
+        let mut out = Vec::new();
+        out.extend_from_slice(&[0x7f, b'E', b'L', b'F', 2, 1, 1, 0]);
+        out.extend_from_slice(&[0u8; 8]);
+        out.extend_from_slice(&2u16.to_le_bytes()); // ET_EXEC
+        out.extend_from_slice(&62u16.to_le_bytes()); // EM_X86_64
+        out.extend_from_slice(&1u32.to_le_bytes());
+        out.extend_from_slice(&BASE.to_le_bytes()); // e_entry
+        out.extend_from_slice(&64u64.to_le_bytes()); // e_phoff
+        out.extend_from_slice(&0u64.to_le_bytes()); // e_shoff
+        out.extend_from_slice(&0u32.to_le_bytes()); // e_flags
+        out.extend_from_slice(&64u16.to_le_bytes()); // e_ehsize
+        out.extend_from_slice(&56u16.to_le_bytes()); // e_phentsize
+        out.extend_from_slice(&3u16.to_le_bytes()); // e_phnum
+        out.extend_from_slice(&64u16.to_le_bytes()); // e_shentsize
+        out.extend_from_slice(&0u16.to_le_bytes()); // e_shnum
+        out.extend_from_slice(&0u16.to_le_bytes()); // e_shstrndx
+
+        let mut program_header = |p_type: u32, offset: u64, size: u64| {
+            out.extend_from_slice(&p_type.to_le_bytes());
+            out.extend_from_slice(&4u32.to_le_bytes()); // PF_R
+            out.extend_from_slice(&offset.to_le_bytes());
+            out.extend_from_slice(&(BASE + offset).to_le_bytes()); // p_vaddr
+            out.extend_from_slice(&(BASE + offset).to_le_bytes()); // p_paddr
+            out.extend_from_slice(&size.to_le_bytes()); // p_filesz
+            out.extend_from_slice(&size.to_le_bytes()); // p_memsz
+            out.extend_from_slice(&8u64.to_le_bytes()); // p_align
+        };
+        program_header(PT_LOAD, 0, file_size);
+        program_header(PT_INTERP, interp_offset as u64, interp.len() as u64 + 1);
+        program_header(
+            PT_DYNAMIC,
+            dynamic_offset as u64,
+            file_size - dynamic_offset as u64,
+        );
+
+        out.extend_from_slice(&body);
+        out
+    }
+
+    #[test]
+    fn test_loader_metadata() {
+        let data = build_dynamic_elf(
+            "/lib64/ld-linux-x86-64.so.2",
+            "/opt/bundle/lib",
+            "$ORIGIN/../lib:/tmp/.hidden",
+        );
+        let parser = ElfParser::new();
+
+        let info = parser.parse(&data).unwrap();
+        let metadata = info.elf.unwrap();
+        assert_eq!(
+            metadata.interpreter.as_deref(),
+            Some("/lib64/ld-linux-x86-64.so.2")
+        );
+        assert_eq!(metadata.rpaths, vec!["/opt/bundle/lib"]);
+        assert_eq!(metadata.runpaths, vec!["$ORIGIN/../lib", "/tmp/.hidden"]);
+
+        let strings = parser.extract_metadata_strings(&data).unwrap();
+        let texts: Vec<&str> = strings.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "/lib64/ld-linux-x86-64.so.2",
+                "/opt/bundle/lib",
+                "$ORIGIN/../lib",
+                "/tmp/.hidden"
+            ]
+        );
+        for string in &strings {
+            assert_eq!(string.tags, vec![Tag::FilePath]);
+            assert_eq!(string.source, StringSource::LoadCommand);
+            let start = string.offset as usize;
+            assert_eq!(
+                &data[start..start + string.text.len()],
+                string.text.as_bytes()
+            );
+            assert_eq!(string.rva, Some(0x400000 + string.offset));
+        }
+    }
+
+    fn note(name: &[u8], n_type: u32, desc: &[u8], align: usize) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&(name.len() as u32 + 1).to_le_bytes());
+        out.extend_from_slice(&(desc.len() as u32).to_le_bytes());
+        out.extend_from_slice(&n_type.to_le_bytes());
+        out.extend_from_slice(name);
+        out.push(0);
+        out.resize(out.len().next_multiple_of(align), 0);
+        out.extend_from_slice(desc);
+        out.resize(out.len().next_multiple_of(align), 0);
+        out
+    }
+
+    #[test]
+    fn test_parse_notes_alignment() {
+        // "Go" (namesz 3) and a 5-byte descriptor both need padding
+        let mut section = note(b"Go", 4, b"abcde", 4);
+        section.extend(note(b"GNU", NT_GNU_BUILD_ID, &[0xde, 0xad, 0xbe, 0xef], 4));
+
+        let notes = parse_notes(&section, 4, true);
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[0].name, b"Go");
+        assert_eq!(notes[0].desc_offset, 16);
+        assert_eq!(notes[0].desc, b"abcde");
+        assert_eq!(notes[1].name, b"GNU");
+        assert_eq!(notes[1].desc, &[0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(&section[notes[1].desc_offset..][..4], notes[1].desc);
+
+        // 8-byte aligned sections pad names and descriptors to 8
+        let mut wide = note(b"GNU", 5, &[1, 2, 3, 4], 8);
+        wide.extend(note(b"GNU", NT_GNU_BUILD_ID, &[0xaa; 20], 8));
+        let notes = parse_notes(&wide, 8, true);
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[1].desc, &[0xaa; 20]);
+
+        // Truncated entries are dropped
+        assert!(parse_notes(&section[..20], 4, true).is_empty());
+    }
+
+    #[test]
+    fn test_describe_notes() {
+        let build_id = ElfNote {
+            name: b"GNU",
+            n_type: NT_GNU_BUILD_ID,
+            desc_offset: 16,
+            desc: &[0x01, 0xab, 0xff],
+        };
+        assert_eq!(
+            describe_note(&build_id, true),
+            Some(("01abff".to_string(), None))
+        );
+
+        let abi_words: Vec<u8> = [0u32, 3, 2, 0]
+            .iter()
+            .flat_map(|w| w.to_le_bytes())
+            .collect();
+        let abi_tag = ElfNote {
+            name: b"GNU",
+            n_type: NT_GNU_ABI_TAG,
+            desc_offset: 16,
+            desc: &abi_words,
+        };
+        assert_eq!(
+            describe_note(&abi_tag, true),
+            Some(("Linux 3.2.0".to_string(), Some(Tag::Version)))
+        );
+
+        let package = ElfNote {
+            name: b"FDO",
+            n_type: NT_FDO_PACKAGING_METADATA,
+            desc_offset: 16,
+            desc: b"{\"type\":\"rpm\",\"name\":\"stringy\"}\0",
+        };
+        assert_eq!(
+            describe_note(&package, true),
+            Some((
+                "{\"type\":\"rpm\",\"name\":\"stringy\"}".to_string(),
+                Some(Tag::Manifest)
+            ))
+        );
+
+        // Binary descriptors of unknown notes are skipped
+        let property = ElfNote {
+            name: b"GNU",
+            n_type: 5,
+            desc_offset: 16,
+            desc: &[2, 0, 0, 0xc0, 4, 0, 0, 0],
+        };
+        assert_eq!(describe_note(&property, true), None);
+    }
+
+    /// Builds an ELF header with no sections or segments
+    fn build_elf_header(is_64: bool) -> Vec<u8> {
+        let mut out = vec![0x7f, b'E', b'L', b'F', if is_64 { 2 } else { 1 }, 1, 1, 0];
+        out.extend_from_slice(&[0u8; 8]);
+        out.extend_from_slice(&2u16.to_le_bytes()); // ET_EXEC
+        out.extend_from_slice(&(if is_64 { 62u16 } else { 40 }).to_le_bytes()); // x86-64 / ARM
+        out.extend_from_slice(&1u32.to_le_bytes());
+        let word = |out: &mut Vec<u8>, value: u64| {
+            if is_64 {
+                out.extend_from_slice(&value.to_le_bytes());
+            } else {
+                out.extend_from_slice(&(value as u32).to_le_bytes());
+            }
+        };
+        word(&mut out, 0x8000); // e_entry
+        word(&mut out, 0); // e_phoff
+        word(&mut out, 0); // e_shoff
+        out.extend_from_slice(&0u32.to_le_bytes()); // e_flags
+        let ehsize: u16 = if is_64 { 64 } else { 52 };
+        out.extend_from_slice(&ehsize.to_le_bytes());
+        out.extend_from_slice(&(if is_64 { 56u16 } else { 32 }).to_le_bytes()); // e_phentsize
+        out.extend_from_slice(&0u16.to_le_bytes()); // e_phnum
+        out.extend_from_slice(&(if is_64 { 64u16 } else { 40 }).to_le_bytes()); // e_shentsize
+        out.extend_from_slice(&0u16.to_le_bytes()); // e_shnum
+        out.extend_from_slice(&0u16.to_le_bytes()); // e_shstrndx
+        out
+    }
+
+    #[test]
+    fn test_address_width() {
+        let parser = ElfParser::new();
+
+        let elf32 = build_elf_header(false);
+        assert_eq!(elf32.len(), 52);
+        assert!(!parser.parse(&elf32).unwrap().is_64bit);
+
+        let elf64 = build_elf_header(true);
+        assert_eq!(elf64.len(), 64);
+        assert!(parser.parse(&elf64).unwrap().is_64bit);
+    }
+
+    #[test]
+    fn test_detect_format_detailed() {
+        use crate::container::{detect_format_detailed, detect_format_fast};
+
+        let elf = build_elf_header(true);
+        assert_eq!(detect_format_detailed(&elf), vec![(BinaryFormat::Elf, 1.0)]);
+        assert_eq!(detect_format_fast(&elf), BinaryFormat::Elf);
+
+        // Cut off inside the header: still recognizable, but not parseable
+        let truncated = &elf[..24];
+        let candidates = detect_format_detailed(truncated);
+        assert_eq!(candidates[0].0, BinaryFormat::Unknown);
+        assert_eq!(candidates[1], (BinaryFormat::Elf, 0.45));
+    }
+
     #[test]
     fn test_elf_parser_creation() {
         let _parser = ElfParser::new();
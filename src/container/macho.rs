@@ -1,10 +1,16 @@
+use crate::classification::demangle::demangle_symbol;
 use crate::container::ContainerParser;
+use crate::container::codesign::{SignatureField, signature_strings};
+use crate::container::overlay::overlay_section;
 use crate::types::{
-    BinaryFormat, ContainerInfo, ExportInfo, ImportInfo, Result, SectionInfo, SectionType,
-    StringyError,
+    BinaryFormat, ContainerInfo, Encoding, ExportInfo, FoundString, ImportInfo, Result,
+    SectionInfo, SectionType, StringSource, StringyError, Tag,
 };
 use goblin::Object;
+use goblin::mach::exports::ExportInfo as TrieExportInfo;
+use goblin::mach::load_command::CommandVariant;
 use goblin::mach::{Mach, MachO};
+use std::collections::HashSet;
 
 /// Parser for Mach-O (Mach Object) binaries.
 ///
@@ -53,6 +59,9 @@ fn classify_section(segment_name: &str, section_name: &str) -> SectionType {
                 StringData
             }
 
+            // Objective-C class names and selectors
+            (segment, section) if Self::is_objc_name_section(segment, section) => StringData,
+
             // Read-only data sections
             ("__DATA_CONST", _) => ReadOnlyData,
 
@@ -71,10 +80,419 @@ fn classify_section(segment_name: &str, section_name: &str) -> SectionType {
         }
     }
 
+    /// Returns `true` for sections holding NUL-terminated Objective-C class
+    /// names or method selectors.
+    ///
+    /// Covers the modern runtime (`__TEXT,__objc_classname`,
+    /// `__TEXT,__objc_methname`) and the legacy runtime (`__OBJC,__class_names`,
+    /// `__OBJC,__meth_var_names`).
+    fn is_objc_name_section(segment_name: &str, section_name: &str) -> bool {
+        matches!(
+            (segment_name, section_name),
+            ("__TEXT", "__objc_classname")
+                | ("__TEXT", "__objc_methname")
+                | ("__OBJC", "__class_names")
+                | ("__OBJC", "__meth_var_names")
+        )
+    }
+
+    /// Extracts Objective-C class names and method selectors.
+    ///
+    /// Each NUL-terminated name in the Objective-C name sections becomes a
+    /// `FoundString` tagged `ObjC`. Names of classes listed in
+    /// `__objc_classlist`, i.e. implemented by this binary rather than merely
+    /// referenced, are tagged `Export` as well, so they rank higher.
+    /// Binaries without Objective-C sections yield nothing. For fat binaries
+    /// the first architecture is used, matching [`ContainerParser::parse`];
+    /// offsets are relative to the start of `data`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StringyError::ParseError` if the data is not a valid Mach-O.
+    pub fn extract_objc_strings(&self, data: &[u8]) -> Result<Vec<FoundString>> {
+        let (macho, base_offset) = self.first_macho(data)?;
+        self.objc_strings_from_macho(&macho, base_offset)
+    }
+
+    /// Extracts the strings carried by load commands.
+    ///
+    /// Dylib install names (`LC_LOAD_DYLIB` and its weak/re-export/lazy
+    /// variants, `LC_ID_DYLIB`), `LC_RPATH` search paths and the dynamic
+    /// linker path become `FoundString`s tagged `FilePath`. The minimum OS
+    /// version (`LC_BUILD_VERSION`, `LC_VERSION_MIN_*`) is rendered as e.g.
+    /// `macOS 10.15` and tagged `Version`. All use `StringSource::LoadCommand`.
+    ///
+    /// For fat binaries the first architecture is used; offsets are relative
+    /// to the start of `data`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StringyError::ParseError` if the data is not a valid Mach-O.
+    pub fn extract_load_command_strings(&self, data: &[u8]) -> Result<Vec<FoundString>> {
+        let (macho, base_offset) = self.first_macho(data)?;
+        let slice = &data[base_offset as usize..];
+        let mut strings = Vec::new();
+
+        for lc in &macho.load_commands {
+            let (text, field_offset, tag) = match &lc.command {
+                CommandVariant::LoadDylib(cmd)
+                | CommandVariant::LoadWeakDylib(cmd)
+                | CommandVariant::ReexportDylib(cmd)
+                | CommandVariant::LazyLoadDylib(cmd)
+                | CommandVariant::LoadUpwardDylib(cmd)
+                | CommandVariant::IdDylib(cmd) => {
+                    let offset = lc.offset + cmd.dylib.name as usize;
+                    let end = lc.offset + cmd.cmdsize as usize;
+                    let Some(text) = Self::load_command_str(slice, offset, end) else {
+                        continue;
+                    };
+                    (text.to_string(), offset, Tag::FilePath)
+                }
+                CommandVariant::Rpath(cmd) => {
+                    let offset = lc.offset + cmd.path as usize;
+                    let end = lc.offset + cmd.cmdsize as usize;
+                    let Some(text) = Self::load_command_str(slice, offset, end) else {
+                        continue;
+                    };
+                    (text.to_string(), offset, Tag::FilePath)
+                }
+                CommandVariant::LoadDylinker(cmd) => {
+                    let offset = lc.offset + cmd.name as usize;
+                    let end = lc.offset + cmd.cmdsize as usize;
+                    let Some(text) = Self::load_command_str(slice, offset, end) else {
+                        continue;
+                    };
+                    (text.to_string(), offset, Tag::FilePath)
+                }
+                CommandVariant::BuildVersion(cmd) => {
+                    let Some(platform) = Self::platform_name(cmd.platform) else {
+                        continue;
+                    };
+                    // minos follows cmd, cmdsize and platform
+                    let text = format!("{} {}", platform, Self::format_version(cmd.minos));
+                    (text, lc.offset + 12, Tag::Version)
+                }
+                CommandVariant::VersionMinMacosx(cmd)
+                | CommandVariant::VersionMinIphoneos(cmd)
+                | CommandVariant::VersionMinTvos(cmd)
+                | CommandVariant::VersionMinWatchos(cmd) => {
+                    let platform = match lc.command {
+                        CommandVariant::VersionMinMacosx(_) => "macOS",
+                        CommandVariant::VersionMinIphoneos(_) => "iOS",
+                        CommandVariant::VersionMinTvos(_) => "tvOS",
+                        _ => "watchOS",
+                    };
+                    let text = format!("{} {}", platform, Self::format_version(cmd.version));
+                    (text, lc.offset + 8, Tag::Version)
+                }
+                _ => continue,
+            };
+
+            let length = match tag {
+                Tag::Version => 4,
+                _ => text.len() as u32,
+            };
+            strings.push(FoundString {
+                rva: Self::file_offset_to_vmaddr(&macho, field_offset as u64),
+                tags: vec![tag],
+                ..FoundString::new(
+                    text,
+                    Encoding::Utf8,
+                    base_offset + field_offset as u64,
+                    length,
+                    StringSource::LoadCommand,
+                )
+            });
+        }
+
+        Ok(strings)
+    }
+
+    /// Extracts the strings of the code signature `LC_CODE_SIGNATURE` points
+    /// at.
+    ///
+    /// The entitlements property list becomes a `FoundString` tagged
+    /// `Manifest`; its keys and string values, the CodeDirectory's signing
+    /// identifier and Team ID, and the text operands of the requirements
+    /// become strings tagged `Resource`. All use `StringSource::CodeSignature`
+    /// and `SectionType::Resources`, since the signature is metadata embedded
+    /// in the file like PE resources. Unsigned binaries yield nothing.
+    ///
+    /// For fat binaries the first architecture is used; offsets are relative
+    /// to the start of `data`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StringyError::ParseError` if the data is not a valid Mach-O.
+    pub fn extract_code_signature_strings(&self, data: &[u8]) -> Result<Vec<FoundString>> {
+        let (macho, base_offset) = self.first_macho(data)?;
+        let slice = &data[base_offset as usize..];
+
+        let Some((dataoff, datasize)) =
+            macho.load_commands.iter().find_map(|lc| match &lc.command {
+                CommandVariant::CodeSignature(cmd) => {
+                    Some((cmd.dataoff as usize, cmd.datasize as usize))
+                }
+                _ => None,
+            })
+        else {
+            return Ok(Vec::new());
+        };
+        let Some(signature) = dataoff
+            .checked_add(datasize)
+            .and_then(|end| slice.get(dataoff..end))
+        else {
+            log::debug!("Code signature lies outside the file");
+            return Ok(Vec::new());
+        };
+
+        let strings = signature_strings(signature)
+            .into_iter()
+            .map(|string| {
+                let tag = match string.field {
+                    SignatureField::Entitlements => Tag::Manifest,
+                    SignatureField::Identifier
+                    | SignatureField::TeamId
+                    | SignatureField::EntitlementKey
+                    | SignatureField::EntitlementValue
+                    | SignatureField::Requirement => Tag::Resource,
+                };
+                let offset = (dataoff + string.offset) as u64;
+                FoundString {
+                    rva: Self::file_offset_to_vmaddr(&macho, offset),
+                    section_type: Some(SectionType::Resources),
+                    tags: vec![tag],
+                    ..FoundString::new(
+                        string.text,
+                        Encoding::Utf8,
+                        base_offset + offset,
+                        string.length as u32,
+                        StringSource::CodeSignature,
+                    )
+                }
+            })
+            .collect();
+
+        Ok(strings)
+    }
+
+    /// Reads the NUL-terminated `lc_str` at `offset`, bounded by the end of
+    /// its load command.
+    fn load_command_str(data: &[u8], offset: usize, end: usize) -> Option<&str> {
+        let bytes = data.get(offset..end.min(data.len()))?;
+        let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        std::str::from_utf8(&bytes[..len])
+            .ok()
+            .filter(|text| !text.is_empty())
+    }
+
+    /// Names an `LC_BUILD_VERSION` platform.
+    fn platform_name(platform: u32) -> Option<&'static str> {
+        match platform {
+            1 => Some("macOS"),
+            2 => Some("iOS"),
+            3 => Some("tvOS"),
+            4 => Some("watchOS"),
+            5 => Some("bridgeOS"),
+            6 => Some("Mac Catalyst"),
+            7 => Some("iOS Simulator"),
+            8 => Some("tvOS Simulator"),
+            9 => Some("watchOS Simulator"),
+            10 => Some("DriverKit"),
+            11 => Some("visionOS"),
+            12 => Some("visionOS Simulator"),
+            _ => None,
+        }
+    }
+
+    /// Formats a version encoded in nibbles as `xxxx.yy.zz`, omitting a zero
+    /// patch level.
+    fn format_version(version: u32) -> String {
+        let (major, minor, patch) = (version >> 16, (version >> 8) & 0xff, version & 0xff);
+        if patch == 0 {
+            format!("{}.{}", major, minor)
+        } else {
+            format!("{}.{}.{}", major, minor, patch)
+        }
+    }
+
+    /// Maps an offset within the Mach-O to its virtual address through the
+    /// segment that maps it (load commands live in `__TEXT`).
+    fn file_offset_to_vmaddr(macho: &MachO, offset: u64) -> Option<u64> {
+        macho
+            .segments
+            .iter()
+            .find(|seg| offset >= seg.fileoff && offset - seg.fileoff < seg.filesize)
+            .map(|seg| seg.vmaddr + (offset - seg.fileoff))
+    }
+
+    /// Returns the virtual address the image is loaded at: that of the
+    /// segment mapping the start of the file (usually `__TEXT`).
+    fn image_base(macho: &MachO) -> u64 {
+        macho
+            .segments
+            .iter()
+            .find(|seg| seg.fileoff == 0 && seg.filesize != 0)
+            .map_or(0, |seg| seg.vmaddr)
+    }
+
+    /// Reads `len` bytes of file-backed segment data at virtual address `addr`.
+    fn read_vm<'a>(macho: &MachO<'a>, addr: u64, len: usize) -> Option<&'a [u8]> {
+        let segment = macho
+            .segments
+            .iter()
+            .find(|seg| addr >= seg.vmaddr && addr - seg.vmaddr < seg.data.len() as u64)?;
+        let data: &'a [u8] = segment.data;
+        let start = usize::try_from(addr - segment.vmaddr).ok()?;
+        data.get(start..start.checked_add(len)?)
+    }
+
+    /// Reads a pointer stored at virtual address `addr` and returns the
+    /// address it points to.
+    ///
+    /// Binaries linked with chained fixups store rebase targets in the low
+    /// bits of the pointer, either as an address or as an offset from the
+    /// image base; those encodings are tried when the raw value is not mapped.
+    fn read_pointer(macho: &MachO, addr: u64) -> Option<u64> {
+        let width = if macho.is_64 { 8 } else { 4 };
+        let bytes = Self::read_vm(macho, addr, width)?;
+        let raw = match (macho.is_64, macho.little_endian) {
+            (true, true) => u64::from_le_bytes(bytes.try_into().ok()?),
+            (true, false) => u64::from_be_bytes(bytes.try_into().ok()?),
+            (false, true) => u64::from(u32::from_le_bytes(bytes.try_into().ok()?)),
+            (false, false) => u64::from(u32::from_be_bytes(bytes.try_into().ok()?)),
+        };
+
+        let image_base = Self::image_base(macho);
+        let target = raw & 0xf_ffff_ffff;
+        [
+            raw,
+            target,
+            image_base + target,
+            image_base + (raw & 0xffff_ffff),
+        ]
+        .into_iter()
+        .find(|&candidate| Self::read_vm(macho, candidate, 1).is_some())
+    }
+
+    /// Returns the addresses of the names of the classes this binary
+    /// implements, found through `__objc_classlist`.
+    ///
+    /// `__objc_classname` also holds the names of classes that are only
+    /// referenced (superclasses from system frameworks, for example); the
+    /// class list tells them apart. Each entry points to a `class_t` whose
+    /// `data` field points to a `class_ro_t` holding the name pointer.
+    fn objc_implemented_class_names(macho: &MachO) -> HashSet<u64> {
+        let width: u64 = if macho.is_64 { 8 } else { 4 };
+        // class_t: isa, superclass, cache, vtable, data
+        let data_field = 4 * width;
+        // class_ro_t: flags, instanceStart, instanceSize, [reserved,] ivarLayout, name
+        let name_field = if macho.is_64 { 24 } else { 16 };
+
+        let mut names = HashSet::new();
+        for segment in &macho.segments {
+            let Ok(sections) = segment.sections() else {
+                continue;
+            };
+            for (section, _) in sections {
+                if section.name().ok() != Some("__objc_classlist") {
+                    continue;
+                }
+                for entry in (0..section.size / width).map(|i| section.addr + i * width) {
+                    let name = Self::read_pointer(macho, entry)
+                        .and_then(|class| Self::read_pointer(macho, class + data_field))
+                        // The low bits of `data` are runtime flags
+                        .and_then(|ro| Self::read_pointer(macho, (ro & !7) + name_field));
+                    if let Some(name) = name {
+                        names.insert(name);
+                    }
+                }
+            }
+        }
+        names
+    }
+
+    /// Parses the Mach-O, or the first architecture of a fat binary, and
+    /// returns it with the offset of its data within `data`.
+    fn first_macho<'a>(&self, data: &'a [u8]) -> Result<(MachO<'a>, u64)> {
+        match self.parse_mach_object(data)? {
+            Mach::Binary(macho) => Ok((macho, 0)),
+            Mach::Fat(fat) => {
+                let arch = fat.iter_arches().next().ok_or_else(|| {
+                    StringyError::ParseError("No architectures found in fat binary".to_string())
+                })??;
+                let arch_data = self.extract_architecture_data(&arch, data)?;
+                match Object::parse(arch_data)? {
+                    Object::Mach(Mach::Binary(macho)) => Ok((macho, arch.offset as u64)),
+                    _ => Err(StringyError::ParseError(
+                        "Invalid architecture data in fat binary".to_string(),
+                    )),
+                }
+            }
+        }
+    }
+
+    /// Collects Objective-C names from a single Mach-O whose file data starts
+    /// at `base_offset` within the input.
+    fn objc_strings_from_macho(&self, macho: &MachO, base_offset: u64) -> Result<Vec<FoundString>> {
+        let mut strings = Vec::new();
+        let implemented_classes = Self::objc_implemented_class_names(macho);
+
+        for segment in &macho.segments {
+            let segment_name = segment.name().unwrap_or("unknown");
+
+            for (section, section_data) in segment.sections()? {
+                let section_name = section.name().unwrap_or("unknown");
+                if !Self::is_objc_name_section(segment_name, section_name) {
+                    continue;
+                }
+
+                let full_name = Self::format_section_name(segment_name, section_name);
+                let section_type = Self::classify_section(segment_name, section_name);
+                let mut pos = 0usize;
+                for name in section_data.split(|&b| b == 0) {
+                    let start = pos;
+                    pos += name.len() + 1;
+
+                    let Ok(text) = std::str::from_utf8(name) else {
+                        continue;
+                    };
+                    if text.is_empty() {
+                        continue;
+                    }
+
+                    let rva = section.addr + start as u64;
+                    let tags = if implemented_classes.contains(&rva) {
+                        vec![Tag::ObjC, Tag::Export]
+                    } else {
+                        vec![Tag::ObjC]
+                    };
+                    strings.push(FoundString {
+                        rva: Some(rva),
+                        section: Some(full_name.clone()),
+                        section_type: Some(section_type),
+                        tags,
+                        ..FoundString::new(
+                            text,
+                            Encoding::Utf8,
+                            base_offset + section.offset as u64 + start as u64,
+                            name.len() as u32,
+                            StringSource::SectionData,
+                        )
+                    });
+                }
+            }
+        }
+
+        Ok(strings)
+    }
+
     /// Extracts import information from Mach-O dynamic symbol table.
     ///
     /// Identifies undefined symbols (imports) by checking for symbols with
     /// n_sect == 0 and n_value == 0, which indicates external dependencies.
+    /// The providing dylib comes from the two-level namespace library ordinal
+    /// in the high byte of `n_desc`.
     fn extract_imports(&self, macho: &MachO) -> Vec<ImportInfo> {
         let Some(symbols) = &macho.symbols else {
             return Vec::new();
@@ -88,8 +506,11 @@ fn extract_imports(&self, macho: &MachO) -> Vec<ImportInfo> {
                 if Self::is_undefined_symbol(&nlist) {
                     Some(ImportInfo {
                         name: name.to_string(),
-                        library: None, // Mach-O doesn't directly specify library names in symbols
+                        library: Self::library_for_ordinal(macho, nlist.n_desc),
                         address: Some(nlist.n_value),
+                        demangled: demangle_symbol(name),
+                        delayed: false,
+                        version: None,
                     })
                 } else {
                     None
@@ -98,16 +519,95 @@ fn extract_imports(&self, macho: &MachO) -> Vec<ImportInfo> {
             .collect()
     }
 
+    /// Resolves a symbol's library ordinal (`GET_LIBRARY_ORDINAL(n_desc)`) to
+    /// the install name of the dylib it is bound to.
+    ///
+    /// Ordinals count the dylib load commands from 1. The self (0),
+    /// main-executable (0xfe) and flat-lookup (0xff) ordinals name no library.
+    fn library_for_ordinal(macho: &MachO, n_desc: u16) -> Option<String> {
+        match n_desc >> 8 {
+            0 | 0xfe | 0xff => None,
+            // goblin keeps the image's own name at index 0
+            ordinal => macho.libs.get(ordinal as usize).map(|lib| lib.to_string()),
+        }
+    }
+
     /// Checks if a symbol is undefined (indicating an import).
     fn is_undefined_symbol(nlist: &goblin::mach::symbols::Nlist) -> bool {
         nlist.n_sect == 0 && nlist.n_value == 0
     }
 
-    /// Extracts export information from Mach-O symbol table.
+    /// Extracts export information from the dyld export trie.
     ///
-    /// Identifies defined symbols (exports) and filters out internal symbols
-    /// that are unlikely to be meaningful for string analysis.
+    /// The trie (`LC_DYLD_INFO`/`LC_DYLD_EXPORTS_TRIE`) is what dyld binds
+    /// against, so it lists exactly the public symbols, including re-exports.
+    /// Binaries without a trie (or with a malformed one) fall back to the
+    /// symbol table; see [`MachoParser::extract_symbol_table_exports`].
     fn extract_exports(&self, macho: &MachO) -> Vec<ExportInfo> {
+        if !Self::has_export_trie(macho) {
+            return self.extract_symbol_table_exports(macho);
+        }
+
+        let exports = match macho.exports() {
+            Ok(exports) => exports,
+            Err(err) => {
+                log::warn!(
+                    "Malformed export trie, falling back to symbol table: {}",
+                    err
+                );
+                return self.extract_symbol_table_exports(macho);
+            }
+        };
+
+        // Trie addresses are offsets from the start of the image
+        let image_base = Self::image_base(macho);
+
+        exports
+            .into_iter()
+            .map(|export| {
+                let (address, forwarded_to) = match export.info {
+                    TrieExportInfo::Regular { address, .. } => (image_base + address, None),
+                    TrieExportInfo::Stub { stub_offset, .. } => {
+                        (image_base + u64::from(stub_offset), None)
+                    }
+                    TrieExportInfo::Reexport {
+                        lib,
+                        lib_symbol_name,
+                        ..
+                    } => {
+                        let symbol = lib_symbol_name.unwrap_or(&export.name);
+                        (0, Some(format!("{}:{}", lib, symbol)))
+                    }
+                };
+                ExportInfo {
+                    demangled: demangle_symbol(&export.name),
+                    name: export.name,
+                    address,
+                    ordinal: None, // Mach-O doesn't use ordinals
+                    forwarded_to,
+                    version: None,
+                    version_hidden: false,
+                }
+            })
+            .collect()
+    }
+
+    /// Checks for a non-empty export trie load command.
+    fn has_export_trie(macho: &MachO) -> bool {
+        macho.load_commands.iter().any(|lc| match &lc.command {
+            CommandVariant::DyldInfo(cmd) | CommandVariant::DyldInfoOnly(cmd) => {
+                cmd.export_size != 0
+            }
+            CommandVariant::DyldExportsTrie(cmd) => cmd.datasize != 0,
+            _ => false,
+        })
+    }
+
+    /// Extracts export information from Mach-O symbol table.
+    ///
+    /// Identifies defined external symbols (exports) and filters out internal
+    /// symbols that are unlikely to be meaningful for string analysis.
+    fn extract_symbol_table_exports(&self, macho: &MachO) -> Vec<ExportInfo> {
         let Some(symbols) = &macho.symbols else {
             return Vec::new();
         };
@@ -121,6 +621,10 @@ fn extract_exports(&self, macho: &MachO) -> Vec<ExportInfo> {
                         name: name.to_string(),
                         address: nlist.n_value,
                         ordinal: None, // Mach-O doesn't use ordinals
+                        demangled: demangle_symbol(name),
+                        forwarded_to: None,
+                        version: None,
+                        version_hidden: false,
                     })
                 } else {
                     None
@@ -129,15 +633,28 @@ fn extract_exports(&self, macho: &MachO) -> Vec<ExportInfo> {
             .collect()
     }
 
-    /// Checks if a symbol is defined (indicating an export).
+    /// Checks if a symbol is a defined external symbol (indicating an export).
+    ///
+    /// Debugger (`N_STAB`) entries and non-external symbols are never exported.
     fn is_defined_symbol(nlist: &goblin::mach::symbols::Nlist) -> bool {
-        nlist.n_sect != 0 && nlist.n_value != 0
+        use goblin::mach::symbols::{N_EXT, N_STAB};
+
+        nlist.n_type & N_STAB == 0
+            && nlist.n_type & N_EXT != 0
+            && nlist.n_sect != 0
+            && nlist.n_value != 0
     }
 
     /// Determines if a symbol name is meaningful for analysis.
-    /// Filters out single-character underscore symbols which are typically internal.
+    /// Filters out single-character underscore symbols which are typically
+    /// internal, and assembler-local labels (`l_`, `L_`, `ltmp`).
     fn is_meaningful_symbol(name: &str) -> bool {
-        !name.starts_with('_') || name.len() > 1
+        const LOCAL_LABEL_PREFIXES: &[&str] = &["l_", "L_", "ltmp", "Ltmp"];
+
+        (!name.starts_with('_') || name.len() > 1)
+            && !LOCAL_LABEL_PREFIXES
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
     }
 
     /// Parses a single Mach-O binary and extracts container information.
@@ -151,12 +668,35 @@ fn parse_single_macho(&self, macho: &MachO) -> Result<ContainerInfo> {
 
         Ok(ContainerInfo {
             format: BinaryFormat::MachO,
+            is_64bit: macho.is_64,
+            architecture: Self::architecture_name(
+                macho.header.cputype(),
+                macho.header.cpusubtype(),
+            ),
             sections,
             imports,
             exports,
+            elf: None,
+            rich_header: None,
+            tls_callbacks: Vec::new(),
         })
     }
 
+    /// Returns the conventional name of a CPU type, e.g. `x86_64` or `arm64`.
+    ///
+    /// Unknown subtypes fall back to the name of the generic subtype, and
+    /// unknown CPU types to their hex value.
+    fn architecture_name(cputype: u32, cpusubtype: u32) -> Option<String> {
+        use goblin::mach::constants::cputype::{CPU_SUBTYPE_MASK, get_arch_name_from_types};
+
+        let name = get_arch_name_from_types(cputype, cpusubtype & !CPU_SUBTYPE_MASK)
+            .or_else(|| get_arch_name_from_types(cputype, 0))
+            .or_else(|| get_arch_name_from_types(cputype, 3))
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("cpu_{:#x}", cputype));
+        Some(name)
+    }
+
     /// Extracts section information from all segments in the Mach-O binary.
     fn extract_sections(&self, macho: &MachO) -> Result<Vec<SectionInfo>> {
         let mut sections = Vec::new();
@@ -174,6 +714,18 @@ fn extract_sections(&self, macho: &MachO) -> Result<Vec<SectionInfo>> {
         Ok(sections)
     }
 
+    /// Checks if a section is zero-filled at load time and has no file data
+    /// (`__DATA,__bss`, `__DATA,__common`, thread-local zero-fill).
+    fn is_zerofill_section(section: &goblin::mach::segment::Section) -> bool {
+        use goblin::mach::constants::{
+            S_GB_ZEROFILL, S_THREAD_LOCAL_ZEROFILL, S_ZEROFILL, SECTION_TYPE,
+        };
+        matches!(
+            section.flags & SECTION_TYPE,
+            S_ZEROFILL | S_GB_ZEROFILL | S_THREAD_LOCAL_ZEROFILL
+        )
+    }
+
     /// Processes a single section and returns section info if the section is non-empty.
     fn process_section(
         &self,
@@ -192,14 +744,38 @@ fn process_section(
         Some(SectionInfo {
             name: full_name,
             offset: section.offset as u64,
-            size: section.size,
+            size: if Self::is_zerofill_section(section) {
+                0
+            } else {
+                section.size
+            },
             rva: Some(section.addr), // Mach-O uses virtual addresses
             section_type,
             is_executable: Self::is_executable_section(segment_name, section_name),
             is_writable: Self::is_writable_section(segment_name),
+            entropy: None,
+            truncated: false,
         })
     }
 
+    /// Returns the file offset just past the load commands or the file data
+    /// of the last segment, whichever is later.
+    fn image_end(macho: &MachO) -> u64 {
+        use goblin::mach::header::{SIZEOF_HEADER_32, SIZEOF_HEADER_64};
+
+        let header_size = if macho.is_64 {
+            SIZEOF_HEADER_64
+        } else {
+            SIZEOF_HEADER_32
+        };
+        let commands_end = header_size as u64 + u64::from(macho.header.sizeofcmds);
+        macho
+            .segments
+            .iter()
+            .map(|segment| segment.fileoff.saturating_add(segment.filesize))
+            .fold(commands_end, u64::max)
+    }
+
     /// Formats the full section name as "segment,section".
     fn format_section_name(segment_name: &str, section_name: &str) -> String {
         format!("{},{}", segment_name, section_name)
@@ -228,7 +804,9 @@ fn detect(data: &[u8]) -> bool {
     /// Parses Mach-O binary data and extracts container information.
     ///
     /// Supports both single architecture binaries and universal (fat) binaries.
-    /// For fat binaries, parses the first available architecture.
+    /// For fat binaries, parses the first available architecture; see
+    /// [`MachoParser::parse_all`] for every slice. Data appended to a single
+    /// architecture executable or library is reported as an overlay section.
     ///
     /// # Errors
     ///
@@ -240,10 +818,21 @@ fn parse(&self, data: &[u8]) -> Result<ContainerInfo> {
         let mach = self.parse_mach_object(data)?;
 
         match mach {
-            Mach::Binary(macho) => self.parse_single_macho(&macho),
+            Mach::Binary(macho) => self.parse_binary(&macho, data),
             Mach::Fat(fat) => self.parse_fat_binary(&fat, data),
         }
     }
+
+    /// Returns Objective-C class names and selectors, load command strings
+    /// and code signature strings; see [`MachoParser::extract_objc_strings`],
+    /// [`MachoParser::extract_load_command_strings`] and
+    /// [`MachoParser::extract_code_signature_strings`].
+    fn extract_metadata_strings(&self, data: &[u8]) -> Result<Vec<FoundString>> {
+        let mut strings = self.extract_objc_strings(data)?;
+        strings.extend(self.extract_load_command_strings(data)?);
+        strings.extend(self.extract_code_signature_strings(data)?);
+        Ok(strings)
+    }
 }
 
 impl MachoParser {
@@ -255,10 +844,55 @@ fn parse_mach_object<'a>(&self, data: &'a [u8]) -> Result<Mach<'a>> {
         }
     }
 
+    /// Parses every architecture of a Mach-O binary, one `ContainerInfo` per
+    /// slice in fat-header order.
+    ///
+    /// Thin binaries yield a single entry, identical to [`ContainerParser::parse`].
+    /// Section offsets of fat slices are relative to the start of the file,
+    /// not of the slice.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StringyError::ParseError` if the data is not Mach-O, the fat
+    /// binary has no architectures, or any slice fails to parse.
+    pub fn parse_all(&self, data: &[u8]) -> Result<Vec<ContainerInfo>> {
+        match self.parse_mach_object(data)? {
+            Mach::Binary(macho) => Ok(vec![self.parse_binary(&macho, data)?]),
+            Mach::Fat(fat) => {
+                let slices = fat
+                    .iter_arches()
+                    .map(|arch| self.parse_fat_slice(&arch?, data))
+                    .collect::<Result<Vec<_>>>()?;
+                if slices.is_empty() {
+                    return Err(StringyError::ParseError(
+                        "No architectures found in fat binary".to_string(),
+                    ));
+                }
+                Ok(slices)
+            }
+        }
+    }
+
+    /// Parses a single architecture binary that makes up the whole of `data`,
+    /// including its overlay.
+    fn parse_binary(&self, macho: &MachO, data: &[u8]) -> Result<ContainerInfo> {
+        let mut info = self.parse_single_macho(macho)?;
+        // Object files keep symbols and relocations outside their only
+        // segment, so trailing bytes there are not an overlay
+        if macho.header.filetype != goblin::mach::header::MH_OBJECT {
+            info.sections
+                .extend(overlay_section(Self::image_end(macho), data.len() as u64));
+        }
+        for section in &mut info.sections {
+            section.clamp_to(data.len() as u64);
+            section.measure_entropy(data);
+        }
+        Ok(info)
+    }
+
     /// Parses a fat (universal) binary by extracting the first architecture.
     ///
-    /// TODO: Consider parsing all architectures instead of just the first one
-    /// for more comprehensive analysis in future versions.
+    /// Use [`MachoParser::parse_all`] to get every architecture.
     fn parse_fat_binary(
         &self,
         fat: &goblin::mach::MultiArch,
@@ -268,15 +902,35 @@ fn parse_fat_binary(
             StringyError::ParseError("No architectures found in fat binary".to_string())
         })?;
 
-        let arch = arch?;
-        let arch_data = self.extract_architecture_data(&arch, data)?;
+        self.parse_fat_slice(&arch?, data)
+    }
+
+    /// Parses one architecture of a fat binary, rebasing its section offsets
+    /// onto the whole file.
+    fn parse_fat_slice(
+        &self,
+        arch: &goblin::mach::fat::FatArch,
+        data: &[u8],
+    ) -> Result<ContainerInfo> {
+        let arch_data = self.extract_architecture_data(arch, data)?;
 
-        match Object::parse(arch_data)? {
-            Object::Mach(Mach::Binary(macho)) => self.parse_single_macho(&macho),
-            _ => Err(StringyError::ParseError(
-                "Invalid architecture data in fat binary".to_string(),
-            )),
+        let mut info = match Object::parse(arch_data)? {
+            Object::Mach(Mach::Binary(macho)) => self.parse_single_macho(&macho)?,
+            _ => {
+                return Err(StringyError::ParseError(
+                    "Invalid architecture data in fat binary".to_string(),
+                ));
+            }
+        };
+        for section in &mut info.sections {
+            // A slice's sections cannot reach into the next slice
+            section.clamp_to(arch_data.len() as u64);
+            if section.size > 0 {
+                section.offset += u64::from(arch.offset);
+            }
+            section.measure_entropy(data);
         }
+        Ok(info)
     }
 
     /// Extracts architecture-specific data from a fat binary.
@@ -288,13 +942,12 @@ fn extract_architecture_data<'a>(
         let offset = arch.offset as usize;
         let size = arch.size as usize;
 
-        if offset + size <= data.len() {
-            Ok(&data[offset..offset + size])
-        } else {
-            Err(StringyError::ParseError(
-                "Architecture data extends beyond file bounds".to_string(),
-            ))
-        }
+        offset
+            .checked_add(size)
+            .and_then(|end| data.get(offset..end))
+            .ok_or_else(|| {
+                StringyError::ParseError("Architecture data extends beyond file bounds".to_string())
+            })
     }
 }
 
@@ -302,6 +955,576 @@ fn extract_architecture_data<'a>(
 mod tests {
     use super::*;
 
+    /// Fixed-size Mach-O name field.
+    fn name16(name: &str) -> [u8; 16] {
+        let mut out = [0u8; 16];
+        out[..name.len()].copy_from_slice(name.as_bytes());
+        out
+    }
+
+    /// Builds a minimal 64-bit Mach-O executable with one `LC_SEGMENT_64` per
+    /// `(segment, section, vmaddr, contents)` entry.
+    fn build_macho(sections: &[(&str, &str, u64, &[u8])]) -> Vec<u8> {
+        TestMacho {
+            sections,
+            ..Default::default()
+        }
+        .build()
+    }
+
+    /// A synthetic 64-bit Mach-O executable.
+    #[derive(Default)]
+    struct TestMacho<'a> {
+        /// `(segment, section, vmaddr, contents)`, one `LC_SEGMENT_64` each
+        sections: &'a [(&'a str, &'a str, u64, &'a [u8])],
+        /// Extra position-independent load commands
+        commands: Vec<Vec<u8>>,
+        /// `(name, n_type, n_sect, n_desc, n_value)` entries for an `LC_SYMTAB`
+        symbols: &'a [(&'a str, u8, u8, u16, u64)],
+        /// Export trie, referenced by an `LC_DYLD_EXPORTS_TRIE`
+        export_trie: Option<&'a [u8]>,
+    }
+
+    impl TestMacho<'_> {
+        fn build(&self) -> Vec<u8> {
+            const HEADER_SIZE: usize = 32;
+            const SEGMENT_CMD_SIZE: usize = 72 + 80;
+            const SYMTAB_CMD_SIZE: usize = 24;
+            const EXPORTS_TRIE_CMD_SIZE: usize = 16;
+
+            let symtab_size = if self.symbols.is_empty() {
+                0
+            } else {
+                SYMTAB_CMD_SIZE
+            };
+            let trie_size = if self.export_trie.is_some() {
+                EXPORTS_TRIE_CMD_SIZE
+            } else {
+                0
+            };
+            let ncmds = self.sections.len()
+                + self.commands.len()
+                + usize::from(!self.symbols.is_empty())
+                + usize::from(self.export_trie.is_some());
+            let sizeofcmds = SEGMENT_CMD_SIZE * self.sections.len()
+                + self.commands.iter().map(Vec::len).sum::<usize>()
+                + symtab_size
+                + trie_size;
+
+            let mut data_offset = HEADER_SIZE + sizeofcmds;
+            let mut out = Vec::new();
+            out.extend_from_slice(&0xfeed_facfu32.to_le_bytes()); // MH_MAGIC_64
+            out.extend_from_slice(&0x0100_0007u32.to_le_bytes()); // CPU_TYPE_X86_64
+            out.extend_from_slice(&3u32.to_le_bytes()); // CPU_SUBTYPE_X86_64_ALL
+            out.extend_from_slice(&2u32.to_le_bytes()); // MH_EXECUTE
+            out.extend_from_slice(&(ncmds as u32).to_le_bytes());
+            out.extend_from_slice(&(sizeofcmds as u32).to_le_bytes());
+            out.extend_from_slice(&0u32.to_le_bytes()); // flags
+            out.extend_from_slice(&0u32.to_le_bytes()); // reserved
+
+            let mut payload = Vec::new();
+            for (segment, section, addr, contents) in self.sections {
+                let size = contents.len() as u64;
+                out.extend_from_slice(&0x19u32.to_le_bytes()); // LC_SEGMENT_64
+                out.extend_from_slice(&(SEGMENT_CMD_SIZE as u32).to_le_bytes());
+                out.extend_from_slice(&name16(segment));
+                out.extend_from_slice(&addr.to_le_bytes()); // vmaddr
+                out.extend_from_slice(&size.to_le_bytes()); // vmsize
+                out.extend_from_slice(&(data_offset as u64).to_le_bytes()); // fileoff
+                out.extend_from_slice(&size.to_le_bytes()); // filesize
+                out.extend_from_slice(&5u32.to_le_bytes()); // maxprot r-x
+                out.extend_from_slice(&5u32.to_le_bytes()); // initprot r-x
+                out.extend_from_slice(&1u32.to_le_bytes()); // nsects
+                out.extend_from_slice(&0u32.to_le_bytes()); // flags
+
+                out.extend_from_slice(&name16(section));
+                out.extend_from_slice(&name16(segment));
+                out.extend_from_slice(&addr.to_le_bytes());
+                out.extend_from_slice(&size.to_le_bytes());
+                out.extend_from_slice(&(data_offset as u32).to_le_bytes());
+                out.extend_from_slice(&[0u8; 4 * 7]); // align, reloff, nreloc, flags, reserved1-3
+
+                payload.extend_from_slice(contents);
+                data_offset += contents.len();
+            }
+            for command in &self.commands {
+                out.extend_from_slice(command);
+            }
+
+            if !self.symbols.is_empty() {
+                let mut strtab = vec![0u8];
+                let mut nlists = Vec::new();
+                for (name, n_type, n_sect, n_desc, n_value) in self.symbols {
+                    nlists.extend_from_slice(&(strtab.len() as u32).to_le_bytes()); // n_strx
+                    nlists.push(*n_type);
+                    nlists.push(*n_sect);
+                    nlists.extend_from_slice(&n_desc.to_le_bytes());
+                    nlists.extend_from_slice(&n_value.to_le_bytes());
+                    strtab.extend_from_slice(name.as_bytes());
+                    strtab.push(0);
+                }
+                let symoff = data_offset;
+                let stroff = symoff + nlists.len();
+                out.extend_from_slice(&0x2u32.to_le_bytes()); // LC_SYMTAB
+                out.extend_from_slice(&(SYMTAB_CMD_SIZE as u32).to_le_bytes());
+                out.extend_from_slice(&(symoff as u32).to_le_bytes());
+                out.extend_from_slice(&(self.symbols.len() as u32).to_le_bytes());
+                out.extend_from_slice(&(stroff as u32).to_le_bytes());
+                out.extend_from_slice(&(strtab.len() as u32).to_le_bytes());
+                payload.extend_from_slice(&nlists);
+                payload.extend_from_slice(&strtab);
+                data_offset += nlists.len() + strtab.len();
+            }
+
+            if let Some(trie) = self.export_trie {
+                out.extend_from_slice(&0x8000_0033u32.to_le_bytes()); // LC_DYLD_EXPORTS_TRIE
+                out.extend_from_slice(&(EXPORTS_TRIE_CMD_SIZE as u32).to_le_bytes());
+                out.extend_from_slice(&(data_offset as u32).to_le_bytes());
+                out.extend_from_slice(&(trie.len() as u32).to_le_bytes());
+                payload.extend_from_slice(trie);
+            }
+
+            out.extend_from_slice(&payload);
+            out
+        }
+    }
+
+    /// Builds a load command whose only string (an `lc_str`) starts at
+    /// `string_offset`, after `fields` following `cmd` and `cmdsize`.
+    fn string_command(cmd: u32, fields: &[u32], text: &str) -> Vec<u8> {
+        let string_offset = 8 + 4 * fields.len();
+        let size = (string_offset + text.len() + 1).next_multiple_of(8);
+        let mut out = Vec::new();
+        out.extend_from_slice(&cmd.to_le_bytes());
+        out.extend_from_slice(&(size as u32).to_le_bytes());
+        for field in fields {
+            out.extend_from_slice(&field.to_le_bytes());
+        }
+        out.extend_from_slice(text.as_bytes());
+        out.resize(size, 0);
+        out
+    }
+
+    /// `LC_LOAD_DYLIB`: name offset, timestamp, current and compatibility version
+    fn load_dylib(name: &str) -> Vec<u8> {
+        string_command(0xc, &[24, 2, 0x0001_0000, 0x0001_0000], name)
+    }
+
+    /// Wraps thin Mach-O slices in a fat header, each slice 4 KiB aligned.
+    fn build_fat(slices: &[Vec<u8>]) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&0xcafe_babeu32.to_be_bytes()); // FAT_MAGIC
+        out.extend_from_slice(&(slices.len() as u32).to_be_bytes());
+
+        let mut offset = 0x1000usize;
+        let mut placed = Vec::new();
+        for slice in slices {
+            // fat_arch is big-endian; the CPU fields come from the slice header
+            let cputype = u32::from_le_bytes(slice[4..8].try_into().unwrap());
+            let cpusubtype = u32::from_le_bytes(slice[8..12].try_into().unwrap());
+            out.extend_from_slice(&cputype.to_be_bytes());
+            out.extend_from_slice(&cpusubtype.to_be_bytes());
+            out.extend_from_slice(&(offset as u32).to_be_bytes());
+            out.extend_from_slice(&(slice.len() as u32).to_be_bytes());
+            out.extend_from_slice(&12u32.to_be_bytes()); // align 2^12
+            placed.push(offset);
+            offset = (offset + slice.len()).next_multiple_of(0x1000);
+        }
+        for (slice, start) in slices.iter().zip(placed) {
+            out.resize(start, 0);
+            out.extend_from_slice(slice);
+        }
+        out
+    }
+
+    #[test]
+    fn test_parse_all_fat_slices() {
+        let x86 = build_macho(&[("__TEXT", "__cstring", 0x1000, b"intel slice\0")]);
+        let mut arm = build_macho(&[("__TEXT", "__cstring", 0x1000, b"arm slice\0")]);
+        arm[4..8].copy_from_slice(&0x0100_000cu32.to_le_bytes()); // CPU_TYPE_ARM64
+        arm[8..12].copy_from_slice(&0u32.to_le_bytes()); // CPU_SUBTYPE_ARM64_ALL
+        let data = build_fat(&[x86, arm]);
+        let parser = MachoParser::new();
+
+        let slices = parser.parse_all(&data).unwrap();
+        assert_eq!(slices.len(), 2);
+        assert_eq!(slices[0].architecture.as_deref(), Some("x86_64"));
+        assert_eq!(slices[1].architecture.as_deref(), Some("arm64"));
+
+        // Section offsets point into the fat file, not the slice
+        let section = &slices[1].sections[0];
+        assert_eq!(section.data(&data), Some(&b"arm slice\0"[..]));
+
+        // parse keeps returning the first slice
+        let first = parser.parse(&data).unwrap();
+        assert_eq!(first.architecture.as_deref(), Some("x86_64"));
+        assert_eq!(first.sections[0].data(&data), Some(&b"intel slice\0"[..]));
+    }
+
+    #[test]
+    fn test_parse_all_thin_binary() {
+        let data = build_macho(&[("__TEXT", "__cstring", 0x1000, b"thin\0")]);
+        let slices = MachoParser::new().parse_all(&data).unwrap();
+        assert_eq!(slices.len(), 1);
+        assert_eq!(slices[0].architecture.as_deref(), Some("x86_64"));
+    }
+
+    #[test]
+    fn test_load_command_strings() {
+        let mut build_version = Vec::new();
+        for field in [0x32u32, 24, 1, 0x000a_0f00, 0x000e_0000, 0] {
+            build_version.extend_from_slice(&field.to_le_bytes()); // LC_BUILD_VERSION, macOS 10.15
+        }
+        let data = TestMacho {
+            sections: &[("__TEXT", "__cstring", 0x1000, b"hello\0")],
+            commands: vec![
+                string_command(0xe, &[12], "/usr/lib/dyld"), // LC_LOAD_DYLINKER
+                load_dylib("/usr/lib/libSystem.B.dylib"),
+                load_dylib("@rpath/libcurl.4.dylib"),
+                string_command(0x8000_001c, &[12], "@executable_path/../Frameworks"), // LC_RPATH
+                build_version,
+            ],
+            ..Default::default()
+        }
+        .build();
+        let strings = MachoParser::new()
+            .extract_load_command_strings(&data)
+            .unwrap();
+
+        let texts: Vec<&str> = strings.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "/usr/lib/dyld",
+                "/usr/lib/libSystem.B.dylib",
+                "@rpath/libcurl.4.dylib",
+                "@executable_path/../Frameworks",
+                "macOS 10.15",
+            ]
+        );
+        assert!(
+            strings
+                .iter()
+                .all(|s| s.source == StringSource::LoadCommand)
+        );
+        assert_eq!(strings[1].tags, vec![Tag::FilePath]);
+        assert_eq!(strings[4].tags, vec![Tag::Version]);
+
+        let libsystem = &strings[1];
+        let start = libsystem.offset as usize;
+        assert_eq!(&data[start..start + 26], b"/usr/lib/libSystem.B.dylib");
+    }
+
+    #[test]
+    fn test_code_signature_strings() {
+        use crate::container::codesign::tests::{PLIST, signature};
+
+        const PLACEHOLDER: u32 = 0xdead_beef;
+        let blob = signature("com.example.app", "ABCDE12345", PLIST);
+        let mut command = Vec::new();
+        for field in [0x1du32, 16, PLACEHOLDER, blob.len() as u32] {
+            command.extend_from_slice(&field.to_le_bytes()); // LC_CODE_SIGNATURE
+        }
+        let mut data = TestMacho {
+            sections: &[("__TEXT", "__cstring", 0x1000, b"hello\0")],
+            commands: vec![command],
+            ..Default::default()
+        }
+        .build();
+        let dataoff = data.len() as u32;
+        let field = data
+            .windows(4)
+            .position(|w| w == PLACEHOLDER.to_le_bytes())
+            .unwrap();
+        data[field..field + 4].copy_from_slice(&dataoff.to_le_bytes());
+        data.extend_from_slice(&blob);
+
+        let parser = MachoParser::new();
+        let strings = parser.extract_code_signature_strings(&data).unwrap();
+        assert!(
+            strings
+                .iter()
+                .all(|s| s.source == StringSource::CodeSignature)
+        );
+
+        let identifier = &strings[0];
+        assert_eq!(identifier.text, "com.example.app");
+        assert_eq!(identifier.tags, vec![Tag::Resource]);
+        let start = identifier.offset as usize;
+        assert_eq!(&data[start..start + 15], b"com.example.app");
+        assert_eq!(strings[1].text, "ABCDE12345");
+
+        let plist = strings.iter().find(|s| s.tags == [Tag::Manifest]).unwrap();
+        assert_eq!(plist.text, PLIST);
+        let sandbox = strings
+            .iter()
+            .find(|s| s.text == "com.apple.security.app-sandbox")
+            .unwrap();
+        assert_eq!(sandbox.tags, vec![Tag::Resource]);
+
+        assert!(
+            parser
+                .extract_metadata_strings(&data)
+                .unwrap()
+                .iter()
+                .any(|s| s.text == "ABCDE12345")
+        );
+
+        // The signature outranks ordinary C strings after rescoring
+        let result = crate::analyze(&data, &crate::AnalysisConfig::default()).unwrap();
+        let hello = result.strings.iter().position(|s| s.text == "hello");
+        for text in [
+            "com.example.app",
+            "ABCDE12345",
+            "com.apple.security.app-sandbox",
+            PLIST,
+        ] {
+            let position = result.strings.iter().position(|s| s.text == text);
+            assert!(position < hello, "{text}: {:?}", result.strings);
+        }
+    }
+
+    #[test]
+    fn test_unsigned_binary_has_no_code_signature_strings() {
+        let data = build_macho(&[("__TEXT", "__cstring", 0x1000, b"hello\0")]);
+        assert!(
+            MachoParser::new()
+                .extract_code_signature_strings(&data)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_import_library_ordinals() {
+        let data = TestMacho {
+            sections: &[("__TEXT", "__cstring", 0x1000, b"hello\0")],
+            commands: vec![
+                load_dylib("/usr/lib/libSystem.B.dylib"),
+                load_dylib("/usr/lib/libcurl.4.dylib"),
+            ],
+            symbols: &[
+                ("_printf", 0x01, 0, 1 << 8, 0),
+                ("_curl_easy_perform", 0x01, 0, 2 << 8, 0),
+                ("_flat_lookup", 0x01, 0, 0xff << 8, 0),
+            ],
+            ..Default::default()
+        }
+        .build();
+        let info = MachoParser::new().parse(&data).unwrap();
+
+        let library = |name: &str| {
+            info.imports
+                .iter()
+                .find(|import| import.name == name)
+                .and_then(|import| import.library.clone())
+        };
+        assert_eq!(
+            library("_printf").as_deref(),
+            Some("/usr/lib/libSystem.B.dylib")
+        );
+        assert_eq!(
+            library("_curl_easy_perform").as_deref(),
+            Some("/usr/lib/libcurl.4.dylib")
+        );
+        assert_eq!(library("_flat_lookup"), None);
+    }
+
+    /// Export trie with `_encrypt_file` at image offset 0x1000 and
+    /// `_compress` re-exported from the first dylib.
+    const EXPORT_TRIE: &[u8] = &[
+        0x00, 0x02, // root: not terminal, two children
+        b'_', b'e', b'n', b'c', b'r', b'y', b'p', b't', b'_', b'f', b'i', b'l', b'e', 0, 28, b'_',
+        b'c', b'o', b'm', b'p', b'r', b'e', b's', b's', 0, 33, //
+        0x03, 0x00, 0x80, 0x20, 0x00, // flags 0, address uleb 0x1000, no children
+        0x03, 0x08, 0x01, 0x00, 0x00, // re-export from ordinal 1 under the same name
+    ];
+
+    /// Symbols of a small dylib: two public functions and an internal label
+    const DYLIB_SYMBOLS: &[(&str, u8, u8, u16, u64)] = &[
+        ("_encrypt_file", 0x0f, 1, 0, 0x1000),    // N_SECT | N_EXT
+        ("l_internal_table", 0x0e, 1, 0, 0x1010), // N_SECT, not external
+        ("_helper", 0x0e, 1, 0, 0x1020),
+        ("_compress", 0x0f, 1, 0, 0x1030),
+    ];
+
+    #[test]
+    fn test_exports_from_trie() {
+        let data = TestMacho {
+            sections: &[("__TEXT", "__text", 0, &[0xc3; 16])],
+            commands: vec![load_dylib("/usr/lib/libz.1.dylib")],
+            symbols: DYLIB_SYMBOLS,
+            export_trie: Some(EXPORT_TRIE),
+        }
+        .build();
+        let info = MachoParser::new().parse(&data).unwrap();
+
+        let names: Vec<&str> = info.exports.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["_encrypt_file", "_compress"]);
+        assert_eq!(info.exports[0].address, 0x1000);
+        assert_eq!(info.exports[0].forwarded_to, None);
+        assert_eq!(
+            info.exports[1].forwarded_to.as_deref(),
+            Some("/usr/lib/libz.1.dylib:_compress")
+        );
+    }
+
+    #[test]
+    fn test_exports_from_symbol_table_without_trie() {
+        let data = TestMacho {
+            sections: &[("__TEXT", "__text", 0, &[0xc3; 16])],
+            symbols: DYLIB_SYMBOLS,
+            ..Default::default()
+        }
+        .build();
+        let info = MachoParser::new().parse(&data).unwrap();
+
+        let names: Vec<&str> = info.exports.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["_encrypt_file", "_compress"]);
+    }
+
+    #[test]
+    fn test_objc_name_extraction() {
+        let data = build_macho(&[
+            ("__TEXT", "__cstring", 0x1000, b"not objc\0"),
+            ("__TEXT", "__objc_classname", 0x2000, b"PayloadManager\0"),
+            (
+                "__TEXT",
+                "__objc_methname",
+                0x3000,
+                b"init\0decryptPayload:\0\0sendTo:withKey:\0",
+            ),
+        ]);
+        let parser = MachoParser::new();
+        let strings = parser.extract_metadata_strings(&data).unwrap();
+
+        let texts: Vec<&str> = strings.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "PayloadManager",
+                "init",
+                "decryptPayload:",
+                "sendTo:withKey:"
+            ]
+        );
+        assert!(strings.iter().all(|s| s.tags == vec![Tag::ObjC]));
+
+        let selector = &strings[2];
+        assert_eq!(selector.section.as_deref(), Some("__TEXT,__objc_methname"));
+        assert_eq!(selector.rva, Some(0x3005));
+        assert_eq!(&data[selector.offset as usize..][..15], b"decryptPayload:");
+    }
+
+    #[test]
+    fn test_objc_names_reported_once_by_analyze() {
+        let data = build_macho(&[
+            ("__TEXT", "__cstring", 0x1000, b"ordinary text\0"),
+            ("__TEXT", "__objc_classname", 0x2000, b"PayloadManager\0"),
+            (
+                "__TEXT",
+                "__objc_methname",
+                0x3000,
+                b"decryptPayload:\0sendTo:withKey:\0",
+            ),
+        ]);
+        let result = crate::analyze(&data, &crate::AnalysisConfig::default()).unwrap();
+
+        for name in ["PayloadManager", "decryptPayload:", "sendTo:withKey:"] {
+            let found: Vec<&FoundString> =
+                result.strings.iter().filter(|s| s.text == name).collect();
+            assert_eq!(found.len(), 1, "{name}: {found:?}");
+            assert_eq!(found[0].tags, vec![Tag::ObjC]);
+            assert_eq!(found[0].section_type, Some(SectionType::StringData));
+        }
+
+        // The ObjC bonus survives rescoring: names outrank plain C strings
+        let score = |text: &str| {
+            result
+                .strings
+                .iter()
+                .find(|s| s.text == text)
+                .map(|s| s.score)
+                .unwrap()
+        };
+        assert!(
+            score("decryptPayload:") > score("ordinary text"),
+            "{:?}",
+            result.strings
+        );
+    }
+
+    #[test]
+    fn test_objc_implemented_classes_rank_higher() {
+        fn words(values: &[u64]) -> Vec<u8> {
+            values.iter().flat_map(|v| v.to_le_bytes()).collect()
+        }
+        // "NSObject" is only referenced (as a superclass); "Implant" is
+        // listed in __objc_classlist
+        let classlist = words(&[0x5000]);
+        // class_t: isa, superclass, cache, vtable, data (with a flag bit set)
+        let class = words(&[0, 0, 0, 0, 0x6000 | 1]);
+        // class_ro_t: flags/instanceStart, instanceSize/reserved, ivarLayout, name
+        let class_ro = words(&[0, 0, 0, 0x2009]);
+        let data = build_macho(&[
+            ("__TEXT", "__objc_classname", 0x2000, b"NSObject\0Implant\0"),
+            ("__TEXT", "__objc_methname", 0x3000, b"beacon\0"),
+            ("__DATA_CONST", "__objc_classlist", 0x4000, &classlist),
+            ("__DATA", "__objc_data", 0x5000, &class),
+            ("__DATA_CONST", "__objc_const", 0x6000, &class_ro),
+        ]);
+        let strings = MachoParser::new().extract_objc_strings(&data).unwrap();
+
+        let tags: Vec<(&str, &[Tag])> = strings
+            .iter()
+            .map(|s| (s.text.as_str(), s.tags.as_slice()))
+            .collect();
+        assert_eq!(
+            tags,
+            vec![
+                ("NSObject", &[Tag::ObjC][..]),
+                ("Implant", &[Tag::ObjC, Tag::Export][..]),
+                ("beacon", &[Tag::ObjC][..]),
+            ]
+        );
+
+        let result = crate::analyze(&data, &crate::AnalysisConfig::default()).unwrap();
+        let score = |text: &str| {
+            result
+                .strings
+                .iter()
+                .find(|s| s.text == text)
+                .map(|s| s.score)
+                .unwrap()
+        };
+        assert!(score("Implant") > score("NSObject"), "{:?}", result.strings);
+    }
+
+    #[test]
+    fn test_legacy_objc_sections() {
+        let data = build_macho(&[
+            ("__OBJC", "__class_names", 0x1000, b"LegacyClass\0"),
+            ("__OBJC", "__meth_var_names", 0x2000, b"doWork\0"),
+        ]);
+        let strings = MachoParser::new().extract_objc_strings(&data).unwrap();
+        let texts: Vec<&str> = strings.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["LegacyClass", "doWork"]);
+
+        let info = MachoParser::new().parse(&data).unwrap();
+        assert!(
+            info.sections
+                .iter()
+                .all(|s| s.section_type == SectionType::StringData)
+        );
+    }
+
+    #[test]
+    fn test_no_objc_sections() {
+        let data = build_macho(&[("__TEXT", "__cstring", 0x1000, b"hello\0")]);
+        let strings = MachoParser::new().extract_objc_strings(&data).unwrap();
+        assert!(strings.is_empty());
+    }
+
     #[test]
     fn test_macho_detection() {
         // Invalid data
@@ -312,6 +1535,18 @@ fn test_macho_detection() {
         // which would be better tested with actual binary files
     }
 
+    #[test]
+    fn test_fast_detection_matches_full() {
+        use crate::container::{detect_format, detect_format_fast};
+
+        let thin = build_macho(&[("__TEXT", "__cstring", 0x1000, b"hello\0")]);
+        let fat = build_fat(std::slice::from_ref(&thin));
+        for data in [thin, fat] {
+            assert_eq!(detect_format_fast(&data), BinaryFormat::MachO);
+            assert_eq!(detect_format(&data), BinaryFormat::MachO);
+        }
+    }
+
     #[test]
     fn test_section_classification() {
         // Test string data sections
@@ -394,16 +1629,28 @@ fn test_symbol_classification() {
         assert!(MachoParser::is_undefined_symbol(&undefined_symbol));
         assert!(!MachoParser::is_defined_symbol(&undefined_symbol));
 
-        // Test defined symbol (export)
+        // Test defined external symbol (export)
         let defined_symbol = Nlist {
             n_strx: 0,
-            n_type: 0,
+            n_type: 0x0f, // N_SECT | N_EXT
             n_sect: 1,
             n_desc: 0,
             n_value: 0x1000,
         };
         assert!(!MachoParser::is_undefined_symbol(&defined_symbol));
         assert!(MachoParser::is_defined_symbol(&defined_symbol));
+
+        // Private (non-external) and debugger symbols are not exports
+        let private_symbol = Nlist {
+            n_type: 0x0e, // N_SECT
+            ..defined_symbol
+        };
+        assert!(!MachoParser::is_defined_symbol(&private_symbol));
+        let stab_symbol = Nlist {
+            n_type: 0x24, // N_FUN
+            ..defined_symbol
+        };
+        assert!(!MachoParser::is_defined_symbol(&stab_symbol));
     }
 
     #[test]
@@ -415,6 +1662,8 @@ fn test_meaningful_symbol_detection() {
 
         // Non-meaningful symbols
         assert!(!MachoParser::is_meaningful_symbol("_"));
+        assert!(!MachoParser::is_meaningful_symbol("l_OBJC_PROTOCOL"));
+        assert!(!MachoParser::is_meaningful_symbol("ltmp0"));
     }
 
     #[test]
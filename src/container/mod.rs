@@ -1,11 +1,17 @@
 // Container format detection and parsing
 
-use crate::types::{BinaryFormat, ContainerInfo, Result, StringyError};
+use crate::types::{BinaryFormat, ContainerInfo, FoundString, Result, StringyError};
 use goblin::Object;
 
+pub mod archive;
+pub mod codesign;
+pub mod debug_file;
 pub mod elf;
 pub mod macho;
+pub mod normalize;
+pub mod overlay;
 pub mod pe;
+pub mod raw;
 
 /// Trait for parsing different container formats
 pub trait ContainerParser {
@@ -16,15 +22,173 @@ fn detect(data: &[u8]) -> bool
 
     /// Parse the container and extract metadata
     fn parse(&self, data: &[u8]) -> Result<ContainerInfo>;
+
+    /// Extract strings recovered from format metadata rather than by scanning
+    /// section bytes, such as Objective-C class and selector names.
+    ///
+    /// Parsers without such metadata return no strings.
+    fn extract_metadata_strings(&self, _data: &[u8]) -> Result<Vec<FoundString>> {
+        Ok(Vec::new())
+    }
 }
 
+/// Confidence in a format goblin parsed in full
+const PARSED_CONFIDENCE: f32 = 1.0;
+/// Confidence in a format whose magic bytes and basic header fields are
+/// valid, although goblin could not parse it
+const HEADER_CONFIDENCE: f32 = 0.45;
+/// Confidence in a format whose magic bytes alone match
+const MAGIC_CONFIDENCE: f32 = 0.25;
+
 /// Detect the binary format of the given data
+///
+/// Returns the top candidate of [`detect_format_detailed`]: the format goblin
+/// parses the data as, or `Unknown`.
 pub fn detect_format(data: &[u8]) -> BinaryFormat {
-    match Object::parse(data) {
-        Ok(Object::Elf(_)) => BinaryFormat::Elf,
-        Ok(Object::PE(_)) => BinaryFormat::Pe,
-        Ok(Object::Mach(_)) => BinaryFormat::MachO,
-        _ => BinaryFormat::Unknown,
+    detect_format_detailed(data)
+        .first()
+        .map_or(BinaryFormat::Unknown, |&(format, _)| format)
+}
+
+/// Ranks the formats `data` could be in, most likely first, each with a
+/// confidence between 0 and 1.
+///
+/// A format goblin parses the data as has confidence 1. Formats whose magic
+/// bytes match but that goblin rejects are still listed: with 0.45 if their
+/// basic header fields are valid (an `MZ` stub pointing at a `PE\0\0`
+/// signature, an ELF identification with a known class and byte order), and
+/// 0.25 on the magic alone. Unless a format parsed, `Unknown` is listed too,
+/// with the confidence the others leave over, so it ranks first and the
+/// input is scanned raw; the other candidates show what the input may be a
+/// damaged or truncated instance of.
+///
+/// # Examples
+///
+/// ```rust
+/// use stringy::BinaryFormat;
+/// use stringy::container::detect_format_detailed;
+///
+/// // A DOS stub whose PE header is missing
+/// let mut data = b"MZ".to_vec();
+/// data.resize(0x40, 0);
+/// let candidates = detect_format_detailed(&data);
+/// assert_eq!(candidates[0].0, BinaryFormat::Unknown);
+/// assert_eq!(candidates[1].0, BinaryFormat::Pe);
+/// ```
+pub fn detect_format_detailed(data: &[u8]) -> Vec<(BinaryFormat, f32)> {
+    let parsed = match Object::parse(data) {
+        Ok(Object::Elf(_)) => Some(BinaryFormat::Elf),
+        Ok(Object::PE(_)) => Some(BinaryFormat::Pe),
+        Ok(Object::Mach(_)) => Some(BinaryFormat::MachO),
+        Ok(Object::Archive(_)) => Some(BinaryFormat::Archive),
+        _ => None,
+    };
+
+    let mut candidates: Vec<(BinaryFormat, f32)> = [
+        BinaryFormat::Elf,
+        BinaryFormat::Pe,
+        BinaryFormat::MachO,
+        BinaryFormat::Archive,
+    ]
+    .into_iter()
+    .filter_map(|format| {
+        if parsed == Some(format) {
+            Some((format, PARSED_CONFIDENCE))
+        } else {
+            header_confidence(format, data).map(|confidence| (format, confidence))
+        }
+    })
+    .collect();
+
+    if parsed.is_none() {
+        let best = candidates
+            .iter()
+            .map(|&(_, confidence)| confidence)
+            .fold(0.0, f32::max);
+        candidates.push((BinaryFormat::Unknown, 1.0 - best));
+    }
+    candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+    candidates
+}
+
+/// Guesses the format of `data` from its first bytes, without parsing it.
+///
+/// Checks the magic numbers and the header fields next to them that
+/// [`detect_format_detailed`] also checks: `\x7fELF` with a known class and
+/// byte order, `MZ` with `e_lfanew` pointing at a `PE\0\0` signature, the
+/// thin and fat Mach-O magics, and the `!<arch>` archive magic. Much cheaper
+/// than [`detect_format`] on large inputs, but only a hint: a file with a
+/// valid header can still fail to parse. WebAssembly modules (`\0asm`) and
+/// other formats without a parser are `Unknown`.
+///
+/// # Examples
+///
+/// ```rust
+/// use stringy::BinaryFormat;
+/// use stringy::container::detect_format_fast;
+///
+/// assert_eq!(detect_format_fast(b"!<arch>\n"), BinaryFormat::Archive);
+/// assert_eq!(detect_format_fast(b"#!/bin/sh\n"), BinaryFormat::Unknown);
+/// ```
+pub fn detect_format_fast(data: &[u8]) -> BinaryFormat {
+    [
+        BinaryFormat::Elf,
+        BinaryFormat::Pe,
+        BinaryFormat::MachO,
+        BinaryFormat::Archive,
+    ]
+    .into_iter()
+    .find(|&format| header_confidence(format, data) == Some(HEADER_CONFIDENCE))
+    .unwrap_or(BinaryFormat::Unknown)
+}
+
+/// Confidence that `data` is in `format` judging by its first bytes only, or
+/// `None` if the magic does not match.
+fn header_confidence(format: BinaryFormat, data: &[u8]) -> Option<f32> {
+    use goblin::mach::fat::FAT_MAGIC;
+    use goblin::mach::header::{MH_CIGAM, MH_CIGAM_64, MH_MAGIC, MH_MAGIC_64};
+
+    let checked = |valid: bool| {
+        if valid {
+            HEADER_CONFIDENCE
+        } else {
+            MAGIC_CONFIDENCE
+        }
+    };
+    let word = |at: usize| data.get(at..at + 4).map(|b| [b[0], b[1], b[2], b[3]]);
+    let be_u32 = |at: usize| word(at).map(u32::from_be_bytes);
+
+    match format {
+        BinaryFormat::Elf => {
+            if !data.starts_with(b"\x7fELF") {
+                return None;
+            }
+            // EI_CLASS and EI_DATA: 32/64-bit, little/big endian
+            let known = |at: usize| matches!(data.get(at), Some(1 | 2));
+            Some(checked(known(4) && known(5)))
+        }
+        BinaryFormat::Pe => {
+            if !data.starts_with(b"MZ") {
+                return None;
+            }
+            let pe_header = word(0x3c)
+                .map(u32::from_le_bytes)
+                .and_then(|e_lfanew| data.get(e_lfanew as usize..)?.get(..4));
+            Some(checked(pe_header == Some(b"PE\0\0")))
+        }
+        BinaryFormat::MachO => match be_u32(0)? {
+            MH_MAGIC | MH_CIGAM | MH_MAGIC_64 | MH_CIGAM_64 => {
+                // mach_header: magic, cputype, cpusubtype, filetype, ncmds,
+                // sizeofcmds, flags
+                Some(checked(data.len() >= 28))
+            }
+            // Java class files share the fat magic; where fat headers hold
+            // the architecture count, they hold a class file version of 45+
+            FAT_MAGIC => Some(checked(be_u32(4).is_some_and(|n| n > 0 && n < 45))),
+            _ => None,
+        },
+        BinaryFormat::Archive => data.starts_with(b"!<arch>\n").then_some(HEADER_CONFIDENCE),
+        BinaryFormat::Unknown => None,
     }
 }
 
@@ -34,10 +198,50 @@ pub fn create_parser(format: BinaryFormat) -> Result<Box<dyn ContainerParser>> {
         BinaryFormat::Elf => Ok(Box::new(elf::ElfParser::new())),
         BinaryFormat::Pe => Ok(Box::new(pe::PeParser::new())),
         BinaryFormat::MachO => Ok(Box::new(macho::MachoParser::new())),
+        BinaryFormat::Archive => Ok(Box::new(archive::ArchiveParser::new())),
         BinaryFormat::Unknown => Err(StringyError::UnsupportedFormat),
     }
 }
 
+/// Create the parser for the detected format, falling back to
+/// [`raw::RawParser`] for unknown formats
+///
+/// Firmware dumps, shellcode and memory snapshots have no container
+/// structure; scanning them as one raw section still finds their strings.
+/// The raw parser treats the input as loaded at `base_address`, if given
+/// (see [`raw::RawParser::with_base_address`]); recognized formats report
+/// their own addresses, so it is ignored for them with a warning.
+///
+/// # Errors
+///
+/// Returns `StringyError::ConfigError` if `base_address` is not aligned to
+/// [`raw::BASE_ADDRESS_ALIGNMENT`].
+pub fn create_parser_or_raw(
+    format: BinaryFormat,
+    base_address: Option<u64>,
+) -> Result<Box<dyn ContainerParser>> {
+    let raw = match base_address {
+        Some(base) => raw::RawParser::with_base_address(base)?,
+        None => raw::RawParser::new(),
+    };
+    match create_parser(format) {
+        Ok(parser) => {
+            if let Some(base) = base_address {
+                log::warn!(
+                    "Ignoring base address {:#x}: {} input reports its own addresses",
+                    base,
+                    format
+                );
+            }
+            Ok(parser)
+        }
+        Err(_) => {
+            log::debug!("Unrecognized format, scanning input as raw bytes");
+            Ok(Box::new(raw))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -55,14 +259,86 @@ fn test_format_detection() {
         // which would be better tested in integration tests
     }
 
+    #[test]
+    fn test_ambiguous_formats() {
+        // DOS executable: an MZ header with no PE header behind it
+        let mut dos = b"MZ".to_vec();
+        dos.resize(0x40, 0);
+        let candidates = detect_format_detailed(&dos);
+        assert_eq!(
+            candidates,
+            vec![(BinaryFormat::Unknown, 0.75), (BinaryFormat::Pe, 0.25)]
+        );
+        assert_eq!(detect_format(&dos), BinaryFormat::Unknown);
+
+        // Java class file (version 52) with the fat Mach-O magic
+        let class = b"\xca\xfe\xba\xbe\x00\x00\x00\x34\x00\x1d";
+        assert_eq!(
+            detect_format_detailed(class),
+            vec![(BinaryFormat::Unknown, 0.75), (BinaryFormat::MachO, 0.25)]
+        );
+
+        // Plain data matches nothing
+        assert_eq!(
+            detect_format_detailed(b"UNKNOWN_FORMAT_DATA"),
+            vec![(BinaryFormat::Unknown, 1.0)]
+        );
+    }
+
+    #[test]
+    fn test_fast_detection_of_non_binaries() {
+        for data in [
+            &b""[..],
+            b"UNKNOWN_FORMAT_DATA",
+            b"#!/bin/sh\necho hello\n",
+            b"\0asm\x01\0\0\0",
+            b"\xca\xfe\xba\xbe\x00\x00\x00\x34",
+            b"MZ",
+        ] {
+            assert_eq!(detect_format_fast(data), BinaryFormat::Unknown, "{data:?}");
+            assert_eq!(detect_format_fast(data), detect_format(data), "{data:?}");
+        }
+    }
+
     #[test]
     fn test_parser_creation() {
         // Test successful parser creation
         assert!(create_parser(BinaryFormat::Elf).is_ok());
         assert!(create_parser(BinaryFormat::Pe).is_ok());
         assert!(create_parser(BinaryFormat::MachO).is_ok());
+        assert!(create_parser(BinaryFormat::Archive).is_ok());
 
         // Test error for unknown format
         assert!(create_parser(BinaryFormat::Unknown).is_err());
     }
+
+    #[test]
+    fn test_raw_fallback_for_unknown_format() {
+        let data = b"\x00\x01firmware v1.2\x00";
+        let info = create_parser_or_raw(detect_format(data), None)
+            .unwrap()
+            .parse(data)
+            .unwrap();
+        assert_eq!(info.format, BinaryFormat::Unknown);
+        assert_eq!(info.sections.len(), 1);
+        assert_eq!(info.sections[0].size, data.len() as u64);
+
+        // Known formats still get their own parser
+        let parser = create_parser_or_raw(BinaryFormat::Elf, None).unwrap();
+        assert!(parser.parse(data).is_err());
+    }
+
+    #[test]
+    fn test_raw_fallback_base_address() {
+        let data = b"\x00\x01firmware v1.2\x00";
+        let info = create_parser_or_raw(BinaryFormat::Unknown, Some(0x4000_0000))
+            .unwrap()
+            .parse(data)
+            .unwrap();
+        assert_eq!(info.sections[0].rva, Some(0x4000_0000));
+
+        // Misalignment is rejected even when the format would not use it
+        assert!(create_parser_or_raw(BinaryFormat::Unknown, Some(0x4000_0010)).is_err());
+        assert!(create_parser_or_raw(BinaryFormat::Elf, Some(0x4000_0010)).is_err());
+    }
 }
@@ -0,0 +1,189 @@
+use crate::types::SectionInfo;
+use std::borrow::Borrow;
+use std::fmt;
+
+/// A file range to scan, attributed to one section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScanRange {
+    /// Index of the owning section in the slice given to [`normalize_sections`]
+    pub section: usize,
+    /// File offset where scanning starts
+    pub offset: u64,
+    /// Number of bytes to scan
+    pub size: u64,
+}
+
+/// Two sections whose file ranges overlap.
+///
+/// Legitimate toolchains rarely emit overlapping sections, so this is often a
+/// sign of a packer or deliberate anti-analysis tampering.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionOverlap {
+    /// Name of the section that keeps the shared bytes
+    pub first: String,
+    /// Name of the section that overlaps it
+    pub second: String,
+    /// File offset where the overlap starts
+    pub offset: u64,
+    /// Number of shared bytes
+    pub size: u64,
+}
+
+impl fmt::Display for SectionOverlap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "sections {} and {} overlap at file offset {:#x} ({} bytes)",
+            self.first, self.second, self.offset, self.size
+        )
+    }
+}
+
+/// Result of [`normalize_sections`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SectionLayout {
+    /// Disjoint ranges sorted by file offset
+    pub ranges: Vec<ScanRange>,
+    /// Overlaps found while building the ranges
+    pub overlaps: Vec<SectionOverlap>,
+}
+
+/// Orders sections by file offset and trims overlaps so each byte of the
+/// file is scanned at most once.
+///
+/// Sections are sorted by offset (larger sections first on ties). Bytes
+/// shared by several sections are attributed to the section that starts
+/// first; later sections only keep the part past what is already covered, and
+/// sections fully contained in another produce no range. Every overlap is
+/// recorded in [`SectionLayout::overlaps`] so callers can surface a warning.
+/// Empty sections are skipped.
+pub fn normalize_sections<S: Borrow<SectionInfo>>(sections: &[S]) -> SectionLayout {
+    let sections: Vec<&SectionInfo> = sections.iter().map(Borrow::borrow).collect();
+    let mut order: Vec<usize> = (0..sections.len())
+        .filter(|&i| sections[i].size > 0)
+        .collect();
+    order.sort_by(|&a, &b| {
+        sections[a]
+            .offset
+            .cmp(&sections[b].offset)
+            .then(sections[b].size.cmp(&sections[a].size))
+    });
+
+    let mut layout = SectionLayout::default();
+    // End of the covered region and the section that reaches it
+    let mut covered: Option<(u64, usize)> = None;
+
+    for index in order {
+        let section = &sections[index];
+        let start = section.offset;
+        let end = start.saturating_add(section.size);
+
+        let scan_start = match covered {
+            Some((covered_end, owner)) if start < covered_end => {
+                layout.overlaps.push(SectionOverlap {
+                    first: sections[owner].name.clone(),
+                    second: section.name.clone(),
+                    offset: start,
+                    size: end.min(covered_end) - start,
+                });
+                covered_end
+            }
+            _ => start,
+        };
+
+        if end > scan_start {
+            layout.ranges.push(ScanRange {
+                section: index,
+                offset: scan_start,
+                size: end - scan_start,
+            });
+            covered = Some((end, index));
+        }
+    }
+
+    layout
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SectionType;
+
+    fn section(name: &str, offset: u64, size: u64) -> SectionInfo {
+        SectionInfo {
+            name: name.to_string(),
+            offset,
+            size,
+            rva: None,
+            section_type: SectionType::ReadOnlyData,
+            is_executable: false,
+            is_writable: false,
+            entropy: None,
+            truncated: false,
+        }
+    }
+
+    #[test]
+    fn test_overlapping_sections_scanned_once() {
+        let sections = vec![
+            section(".rdata", 0x100, 0x100),
+            section(".fake", 0x180, 0x100),
+        ];
+        let layout = normalize_sections(&sections);
+
+        assert_eq!(
+            layout.ranges,
+            vec![
+                ScanRange {
+                    section: 0,
+                    offset: 0x100,
+                    size: 0x100
+                },
+                ScanRange {
+                    section: 1,
+                    offset: 0x200,
+                    size: 0x80
+                },
+            ]
+        );
+        assert_eq!(
+            layout.overlaps,
+            vec![SectionOverlap {
+                first: ".rdata".to_string(),
+                second: ".fake".to_string(),
+                offset: 0x180,
+                size: 0x80,
+            }]
+        );
+        assert!(layout.overlaps[0].to_string().contains("0x180"));
+    }
+
+    #[test]
+    fn test_out_of_order_sections_are_sorted() {
+        let sections = vec![
+            section(".data", 0x300, 0x10),
+            section(".bss", 0x0, 0x0),
+            section(".text", 0x100, 0x20),
+        ];
+        let layout = normalize_sections(&sections);
+
+        let order: Vec<usize> = layout.ranges.iter().map(|r| r.section).collect();
+        assert_eq!(order, vec![2, 0]);
+        assert!(layout.overlaps.is_empty());
+    }
+
+    #[test]
+    fn test_contained_section_produces_no_range() {
+        let sections = vec![
+            section("inner", 0x110, 0x10),
+            section("outer", 0x100, 0x100),
+        ];
+        let layout = normalize_sections(&sections);
+
+        assert_eq!(layout.ranges.len(), 1);
+        assert_eq!(layout.ranges[0].section, 1);
+        assert_eq!(layout.overlaps.len(), 1);
+        assert_eq!(layout.overlaps[0].first, "outer");
+        assert_eq!(layout.overlaps[0].size, 0x10);
+    }
+}
@@ -0,0 +1,52 @@
+use crate::types::{SectionInfo, SectionType};
+
+/// Name of the synthetic section covering overlay data.
+pub const OVERLAY_SECTION: &str = "overlay";
+
+/// Returns a synthetic section covering the bytes between `image_end` and
+/// `file_end`, or `None` if there are none.
+///
+/// Installers, self-extracting archives and droppers append their payload
+/// after the last byte the format's headers account for. The loader never
+/// maps this overlay, so no section describes it; exposing it as an
+/// [`OVERLAY_SECTION`] of type [`SectionType::Other`] lets extraction scan it
+/// like any other section. Parsers compute `image_end` from their headers
+/// and pass the input length, or less to leave out trailing data that does
+/// belong to the image, as `file_end`.
+pub fn overlay_section(image_end: u64, file_end: u64) -> Option<SectionInfo> {
+    if file_end <= image_end {
+        return None;
+    }
+    let size = file_end - image_end;
+    log::info!("Overlay at file offset {:#x} ({} bytes)", image_end, size);
+    Some(SectionInfo {
+        name: OVERLAY_SECTION.to_string(),
+        offset: image_end,
+        size,
+        rva: None,
+        section_type: SectionType::Other,
+        is_executable: false,
+        is_writable: false,
+        entropy: None,
+        truncated: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlay_section() {
+        let overlay = overlay_section(0x400, 0x1000).unwrap();
+        assert_eq!(overlay.name, OVERLAY_SECTION);
+        assert_eq!(overlay.offset, 0x400);
+        assert_eq!(overlay.size, 0xc00);
+        assert_eq!(overlay.rva, None);
+        assert_eq!(overlay.section_type, SectionType::Other);
+
+        assert!(overlay_section(0x1000, 0x1000).is_none());
+        // Headers claiming more than the file holds leave no overlay
+        assert!(overlay_section(0x2000, 0x1000).is_none());
+    }
+}
@@ -1,10 +1,45 @@
+use crate::classification::demangle::demangle_symbol;
 use crate::container::ContainerParser;
+use crate::container::overlay::overlay_section;
 use crate::types::{
-    BinaryFormat, ContainerInfo, ExportInfo, ImportInfo, Result, SectionInfo, SectionType,
-    StringyError,
+    BinaryFormat, ContainerInfo, Encoding, ExportInfo, FoundString, ImportInfo, Result, RichEntry,
+    RichHeader, SectionInfo, SectionType, StringSource, Tag,
 };
 use goblin::Object;
+use goblin::pe::export::ExportAddressTableEntry;
+use goblin::pe::options::{ParseMode, ParseOptions};
+use goblin::pe::resource::{RT_MANIFEST, RT_STRING, RT_VERSION};
 use goblin::pe::{PE, section_table::SectionTable};
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// Number of strings in each `RT_STRING` block.
+const STRINGS_PER_BLOCK: u32 = 16;
+
+/// Depth of the resource directory tree: type, name, language.
+const RESOURCE_TREE_DEPTH: usize = 3;
+
+/// A data entry of the resource directory tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ResourceLeaf {
+    /// Numeric resource type, `None` for named types
+    type_id: Option<u32>,
+    /// RVA of the resource data
+    rva: u32,
+    /// Size of the resource data in bytes
+    size: u32,
+}
+
+/// An entry of the export address table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ExportEntry<'a> {
+    /// Export name, `None` for exports by ordinal only
+    name: Option<&'a str>,
+    /// Export ordinal: the directory's ordinal base plus the table index
+    ordinal: u32,
+    /// Function RVA, or forwarder string RVA
+    rva: u32,
+}
 
 /// Parser for PE (Portable Executable) binaries
 pub struct PeParser;
@@ -20,6 +55,129 @@ pub fn new() -> Self {
         Self
     }
 
+    /// Parses the PE headers and tables.
+    ///
+    /// Parsing is permissive, so a debug directory pointing outside the file
+    /// does not reject the whole binary. goblin's own resource parsing is
+    /// skipped: it rejects the file over a single out-of-range resource,
+    /// whereas [`PeParser::extract_resource_strings`] skips just that entry.
+    fn parse_pe(data: &[u8]) -> Result<PE<'_>> {
+        let options = ParseOptions::default()
+            .with_parse_mode(ParseMode::Permissive)
+            .with_parse_resources(false);
+        Ok(PE::parse_with_opts(data, &options)?)
+    }
+
+    /// Returns the RVAs of the callbacks in the TLS directory
+    /// (`IMAGE_DIRECTORY_ENTRY_TLS`), in table order.
+    ///
+    /// The table holds virtual addresses, so the image base is subtracted.
+    /// A malformed TLS directory yields no callbacks.
+    fn tls_callbacks(pe: &PE) -> Vec<u64> {
+        pe.tls_data
+            .as_ref()
+            .map(|tls| {
+                tls.callbacks
+                    .iter()
+                    .map(|&va| va.saturating_sub(pe.image_base))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Reads the preferred load address from the optional header, or `None`
+    /// if `data` has no valid PE header.
+    ///
+    /// Absolute pointers in the image hold `ImageBase + RVA`.
+    pub fn image_base(data: &[u8]) -> Option<u64> {
+        let header = goblin::pe::header::Header::parse(data).ok()?;
+        Some(header.optional_header?.windows_fields.image_base)
+    }
+
+    /// Decodes the Rich header between the DOS stub and the PE header.
+    ///
+    /// The header is a `DanS` marker, three padding words and one
+    /// `(product_id << 16 | build, count)` pair per tool, all XORed with the
+    /// checksum key that follows the plain-text `Rich` marker. Returns `None`
+    /// when either marker is missing.
+    pub fn parse_rich_header(data: &[u8]) -> Option<RichHeader> {
+        const RICH: u32 = u32::from_le_bytes(*b"Rich");
+        const DANS: u32 = u32::from_le_bytes(*b"DanS");
+        // Offset of e_lfanew in the DOS header; the Rich header ends before it
+        const E_LFANEW: usize = 0x3c;
+
+        let word = |at: usize| {
+            data.get(at..at + 4)
+                .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        };
+        let pe_offset = (word(E_LFANEW)? as usize).min(data.len());
+
+        let rich = (E_LFANEW + 4..pe_offset.saturating_sub(7))
+            .step_by(4)
+            .find(|&at| word(at) == Some(RICH))?;
+        let key = word(rich + 4)?;
+        let dans = (0..rich)
+            .step_by(4)
+            .rev()
+            .find(|&at| word(at).map(|value| value ^ key) == Some(DANS))?;
+
+        let entries = (dans + 16..rich)
+            .step_by(8)
+            .filter_map(|at| {
+                let comp_id = word(at)? ^ key;
+                let count = word(at + 4)? ^ key;
+                Some(RichEntry {
+                    product_id: (comp_id >> 16) as u16,
+                    build: comp_id as u16,
+                    count,
+                })
+            })
+            .collect();
+
+        Some(RichHeader {
+            checksum: key,
+            entries,
+        })
+    }
+
+    /// Returns the file range of the overlay: from the end of the headers or
+    /// the raw data of the last section, whichever is later, to the end of
+    /// the file.
+    ///
+    /// An Authenticode signature is stored past the sections too, but it is
+    /// part of the image rather than appended data, so a certificate table at
+    /// the end of the file is left out of the range.
+    fn overlay_bounds(pe: &PE, file_len: u64) -> (u64, u64) {
+        let headers_end = pe
+            .header
+            .optional_header
+            .map_or(0, |oh| u64::from(oh.windows_fields.size_of_headers));
+        let image_end = pe
+            .sections
+            .iter()
+            .filter(|section| section.size_of_raw_data > 0)
+            .map(|section| {
+                u64::from(section.pointer_to_raw_data) + u64::from(section.size_of_raw_data)
+            })
+            .fold(headers_end, u64::max);
+
+        // The certificate table's "address" is a file offset
+        let certificates = pe
+            .header
+            .optional_header
+            .and_then(|oh| oh.data_directories.get_certificate_table().copied());
+        let file_end = match certificates {
+            Some(table)
+                if u64::from(table.virtual_address) >= image_end
+                    && u64::from(table.virtual_address) + u64::from(table.size) >= file_len =>
+            {
+                u64::from(table.virtual_address)
+            }
+            _ => file_len,
+        };
+        (image_end, file_end)
+    }
+
     /// Classify PE section based on its name and characteristics
     fn classify_section(section: &SectionTable) -> SectionType {
         let name_bytes = String::from_utf8_lossy(&section.name);
@@ -68,30 +226,754 @@ fn extract_imports(&self, pe: &PE) -> Vec<ImportInfo> {
                 name: import.name.to_string(),
                 library: Some(import.dll.to_string()),
                 address: Some(import.rva as u64),
+                demangled: demangle_symbol(&import.name),
+                delayed: false,
+                version: None,
             });
         }
 
         imports
     }
 
+    /// Extract imports from the delay-load import directory
+    ///
+    /// Delay-loaded DLLs are only mapped when one of their functions is first
+    /// called, so they are absent from the regular import table. Entries are
+    /// named like goblin's regular imports (`ORDINAL <n>` for imports by
+    /// ordinal) and marked `delayed`. Descriptors or thunks that fall outside
+    /// the file end the walk.
+    fn extract_delay_imports(&self, pe: &PE, data: &[u8]) -> Vec<ImportInfo> {
+        // ImgDelayDescr: grAttrs, rvaDLLName, rvaHmod, rvaIAT, rvaINT,
+        // rvaBoundIAT, rvaUnloadIAT, dwTimeStamp
+        const DESCRIPTOR_SIZE: u32 = 32;
+        // grAttrs bit set when the descriptor holds RVAs rather than VAs
+        const DLATTR_RVA: u32 = 1;
+
+        let Some(table) = pe
+            .header
+            .optional_header
+            .as_ref()
+            .and_then(|header| header.data_directories.get_delay_import_descriptor())
+        else {
+            return Vec::new();
+        };
+
+        let sections = &pe.sections;
+        let thunk_size: u32 = if pe.is_64 { 8 } else { 4 };
+        let ordinal_flag = if pe.is_64 { 1 << 63 } else { 1 << 31 };
+        let mut imports = Vec::new();
+
+        for index in 0.. {
+            let Some(descriptor) = table
+                .virtual_address
+                .checked_add(index * DESCRIPTOR_SIZE)
+                .and_then(|rva| rva_bytes(sections, data, rva, DESCRIPTOR_SIZE))
+            else {
+                break;
+            };
+            let field =
+                |i: usize| u32::from_le_bytes(descriptor[i * 4..i * 4 + 4].try_into().unwrap());
+            let (attributes, name_field, lookup_field) = (field(0), field(1), field(4));
+            if name_field == 0 {
+                break;
+            }
+
+            // Pre-RVA descriptors (Visual C++ 6) store virtual addresses
+            let to_rva = |value: u64| -> Option<u32> {
+                if attributes & DLATTR_RVA != 0 {
+                    u32::try_from(value).ok()
+                } else {
+                    u32::try_from(value.checked_sub(pe.image_base)?).ok()
+                }
+            };
+            let Some(dll) =
+                to_rva(u64::from(name_field)).and_then(|rva| rva_c_string(sections, data, rva))
+            else {
+                break;
+            };
+            let Some(lookup_rva) = to_rva(u64::from(lookup_field)) else {
+                break;
+            };
+
+            for slot in 0.. {
+                let Some(thunk) = lookup_rva
+                    .checked_add(slot * thunk_size)
+                    .and_then(|rva| rva_bytes(sections, data, rva, thunk_size))
+                else {
+                    break;
+                };
+                let thunk = match *thunk {
+                    [a, b, c, d] => u64::from(u32::from_le_bytes([a, b, c, d])),
+                    _ => u64::from_le_bytes(thunk.try_into().unwrap()),
+                };
+                if thunk == 0 {
+                    break;
+                }
+
+                let (name, address) = if thunk & ordinal_flag != 0 {
+                    (format!("ORDINAL {}", thunk & 0xffff), 0)
+                } else {
+                    // IMAGE_IMPORT_BY_NAME: a 2-byte hint, then the name
+                    let Some(hint_rva) = to_rva(thunk) else {
+                        break;
+                    };
+                    let Some(name) = hint_rva
+                        .checked_add(2)
+                        .and_then(|rva| rva_c_string(sections, data, rva))
+                    else {
+                        break;
+                    };
+                    (name.to_string(), u64::from(hint_rva))
+                };
+                imports.push(ImportInfo {
+                    demangled: demangle_symbol(&name),
+                    name,
+                    library: Some(dll.to_string()),
+                    address: Some(address),
+                    delayed: true,
+                    version: None,
+                });
+            }
+        }
+
+        imports
+    }
+
     /// Extract export information from PE export table
-    fn extract_exports(&self, pe: &PE) -> Vec<ExportInfo> {
-        let mut exports = Vec::new();
-
-        // Extract from export table
-        for (i, export) in pe.exports.iter().enumerate() {
-            exports.push(ExportInfo {
-                name: export
-                    .name
-                    .map(|s| s.to_string())
-                    .unwrap_or_else(|| format!("ordinal_{}", i)),
-                address: export.rva as u64,
-                ordinal: Some(i as u16), // Use index as ordinal since goblin doesn't expose it directly
+    ///
+    /// Ordinals are the export directory's `Base` plus the address table
+    /// index. Unnamed (ordinal-only) exports are named `ordinal_<n>`.
+    ///
+    /// Forwarded exports (e.g. kernel32's `HeapAlloc`, implemented by
+    /// `NTDLL.RtlAllocateHeap`) have no address of their own: their RVA points
+    /// at the forwarder string inside the export directory, which is reported
+    /// in `forwarded_to` instead.
+    fn extract_exports(&self, pe: &PE, data: &[u8]) -> Vec<ExportInfo> {
+        Self::export_entries(pe, data)
+            .into_iter()
+            .map(|entry| {
+                let forwarded_to = Self::forwarder(pe, data, entry.rva);
+                ExportInfo {
+                    demangled: entry.name.and_then(demangle_symbol),
+                    name: entry
+                        .name
+                        .map(|s| s.to_string())
+                        .unwrap_or_else(|| format!("ordinal_{}", entry.ordinal)),
+                    address: if forwarded_to.is_some() {
+                        0
+                    } else {
+                        u64::from(entry.rva)
+                    },
+                    ordinal: u16::try_from(entry.ordinal).ok(),
+                    forwarded_to: forwarded_to.map(str::to_string),
+                    version: None,
+                    version_hidden: false,
+                }
+            })
+            .collect()
+    }
+
+    /// Lists the export address table in ordinal order, with the names the
+    /// name pointer and name ordinal tables bind to each entry.
+    ///
+    /// A function with several names appears once per name; empty (zero RVA)
+    /// slots left by ordinal gaps are skipped.
+    fn export_entries<'a>(pe: &PE, data: &'a [u8]) -> Vec<ExportEntry<'a>> {
+        let Some(export_data) = &pe.export_data else {
+            return Vec::new();
+        };
+        let base = export_data.export_directory_table.ordinal_base;
+        let addresses = &export_data.export_address_table;
+
+        let mut names: Vec<Vec<&'a str>> = vec![Vec::new(); addresses.len()];
+        for (&name_rva, &index) in export_data
+            .export_name_pointer_table
+            .iter()
+            .zip(&export_data.export_ordinal_table)
+        {
+            let Some(slot) = names.get_mut(usize::from(index)) else {
+                continue;
+            };
+            if let Some(name) = rva_c_string(&pe.sections, data, name_rva) {
+                slot.push(name);
+            }
+        }
+
+        let mut entries = Vec::new();
+        for (index, (address, names)) in addresses.iter().zip(names).enumerate() {
+            let (ExportAddressTableEntry::ExportRVA(rva)
+            | ExportAddressTableEntry::ForwarderRVA(rva)) = *address;
+            if rva == 0 {
+                continue;
+            }
+            let ordinal = base.saturating_add(index as u32);
+            if names.is_empty() {
+                entries.push(ExportEntry {
+                    name: None,
+                    ordinal,
+                    rva,
+                });
+            }
+            for name in names {
+                entries.push(ExportEntry {
+                    name: Some(name),
+                    ordinal,
+                    rva,
+                });
+            }
+        }
+        entries
+    }
+
+    /// Returns the forwarder string (`<DLL>.<symbol>` or `<DLL>.#<ordinal>`)
+    /// at `rva` if it lies within the export directory, where the loader
+    /// treats export RVAs as forwarders rather than code.
+    fn forwarder<'a>(pe: &PE, data: &'a [u8], rva: u32) -> Option<&'a str> {
+        let table = pe
+            .header
+            .optional_header
+            .as_ref()?
+            .data_directories
+            .get_export_table()?;
+        let in_directory = rva >= table.virtual_address && rva - table.virtual_address < table.size;
+        if !in_directory {
+            return None;
+        }
+        rva_c_string(&pe.sections, data, rva)
+    }
+
+    /// Extracts the forwarder strings of forwarded exports.
+    ///
+    /// Each becomes a `FoundString` tagged `Export` with
+    /// `StringSource::ExportName`, located where it is stored in the export
+    /// directory. See [`ExportInfo::forwarded_to`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `StringyError::ParseError` if the data is not a valid PE.
+    pub fn extract_forwarder_strings(&self, data: &[u8]) -> Result<Vec<FoundString>> {
+        let pe = Self::parse_pe(data)?;
+
+        let mut strings = Vec::new();
+        let mut seen = HashSet::new();
+        for entry in Self::export_entries(&pe, data) {
+            // Aliases share one forwarder string
+            if !seen.insert(entry.rva) {
+                continue;
+            }
+            let Some(text) = Self::forwarder(&pe, data, entry.rva) else {
+                continue;
+            };
+            let Some((offset, section)) =
+                rva_range_to_offset(&pe.sections, data.len(), entry.rva, 1)
+            else {
+                continue;
+            };
+            strings.push(FoundString {
+                rva: Some(u64::from(entry.rva)),
+                section: Some(section_name(section)),
+                tags: vec![Tag::Export],
+                ..FoundString::new(
+                    text,
+                    Encoding::Ascii,
+                    offset as u64,
+                    text.len() as u32,
+                    StringSource::ExportName,
+                )
+            });
+        }
+
+        Ok(strings)
+    }
+
+    /// Extracts strings from the resource section.
+    ///
+    /// The embedded manifest (`RT_MANIFEST`) becomes a single UTF-8 string
+    /// tagged `Manifest`; each non-empty
+    /// `STRINGTABLE` (`RT_STRING`) entry becomes a UTF-16LE string tagged
+    /// `Resource`; each non-empty value in the `StringFileInfo` of a
+    /// `VERSIONINFO` (`RT_VERSION`) becomes a UTF-16LE string tagged
+    /// `Version` for `FileVersion` and `ProductVersion` and `Resource`
+    /// otherwise. All use `StringSource::ResourceString` and
+    /// `SectionType::Resources`; their rank comes from those tags.
+    ///
+    /// Resource data whose RVA range does not lie within a section's raw data
+    /// is skipped, as are malformed directory entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StringyError::ParseError` if the data is not a valid PE.
+    pub fn extract_resource_strings(&self, data: &[u8]) -> Result<Vec<FoundString>> {
+        let pe = Self::parse_pe(data)?;
+
+        let Some(table) = pe
+            .header
+            .optional_header
+            .as_ref()
+            .and_then(|header| header.data_directories.get_resource_table())
+        else {
+            return Ok(Vec::new());
+        };
+        let Some((rsrc_offset, _)) =
+            rva_range_to_offset(&pe.sections, data.len(), table.virtual_address, table.size)
+        else {
+            log::debug!("Resource directory lies outside the file");
+            return Ok(Vec::new());
+        };
+        let rsrc = &data[rsrc_offset..rsrc_offset + table.size as usize];
+
+        let mut leaves = Vec::new();
+        collect_resource_leaves(rsrc, 0, 0, None, &mut HashSet::new(), &mut leaves);
+
+        let mut strings = Vec::new();
+        for leaf in leaves {
+            let Some((offset, section)) =
+                rva_range_to_offset(&pe.sections, data.len(), leaf.rva, leaf.size)
+            else {
+                log::debug!("Resource data at RVA {:#x} lies outside the file", leaf.rva);
+                continue;
+            };
+            let bytes = &data[offset..offset + leaf.size as usize];
+            let section_name = Some(section_name(section));
+
+            match leaf.type_id.and_then(|id| u16::try_from(id).ok()) {
+                Some(RT_MANIFEST) => {
+                    if let Some((start, text)) = manifest_text(bytes) {
+                        strings.push(FoundString {
+                            rva: Some(u64::from(leaf.rva) + start as u64),
+                            section: section_name,
+                            section_type: Some(SectionType::Resources),
+                            tags: vec![Tag::Manifest],
+                            ..FoundString::new(
+                                text,
+                                Encoding::Utf8,
+                                (offset + start) as u64,
+                                text.len() as u32,
+                                StringSource::ResourceString,
+                            )
+                        });
+                    }
+                }
+                Some(RT_STRING) => {
+                    for (start, text) in string_table_entries(bytes) {
+                        let length = (text.encode_utf16().count() * 2) as u32;
+                        strings.push(FoundString {
+                            rva: Some(u64::from(leaf.rva) + start as u64),
+                            section: section_name.clone(),
+                            section_type: Some(SectionType::Resources),
+                            tags: vec![Tag::Resource],
+                            ..FoundString::new(
+                                text,
+                                Encoding::Utf16Le,
+                                (offset + start) as u64,
+                                length,
+                                StringSource::ResourceString,
+                            )
+                        });
+                    }
+                }
+                Some(RT_VERSION) => {
+                    for (start, key, text) in version_info_strings(bytes) {
+                        let length = (text.encode_utf16().count() * 2) as u32;
+                        let tag = match key.as_str() {
+                            "FileVersion" | "ProductVersion" => Tag::Version,
+                            _ => Tag::Resource,
+                        };
+                        strings.push(FoundString {
+                            rva: Some(u64::from(leaf.rva) + start as u64),
+                            section: section_name.clone(),
+                            section_type: Some(SectionType::Resources),
+                            tags: vec![tag],
+                            ..FoundString::new(
+                                text,
+                                Encoding::Utf16Le,
+                                (offset + start) as u64,
+                                length,
+                                StringSource::ResourceString,
+                            )
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(strings)
+    }
+
+    /// Extracts the PDB path and GUID from CodeView (`RSDS`) debug entries.
+    ///
+    /// MSVC records where the PDB was written, which often exposes the build
+    /// machine's user name and project layout. The path is tagged `FilePath`
+    /// and the GUID, formatted `{XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX}`,
+    /// `Guid`; both use `StringSource::DebugInfo`. Binaries without a debug
+    /// directory, or whose debug data lies outside the file, yield nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StringyError::ParseError` if the data is not a valid PE.
+    pub fn extract_debug_strings(&self, data: &[u8]) -> Result<Vec<FoundString>> {
+        // IMAGE_DEBUG_DIRECTORY: characteristics, timestamp, version, type,
+        // size, RVA and file offset of the data
+        const DIRECTORY_SIZE: u32 = 28;
+        const IMAGE_DEBUG_TYPE_CODEVIEW: u32 = 2;
+        // "RSDS" signature, GUID, age, then the NUL-terminated path
+        const GUID_OFFSET: usize = 4;
+        const PATH_OFFSET: usize = 24;
+
+        let pe = Self::parse_pe(data)?;
+        let Some(table) = pe
+            .header
+            .optional_header
+            .as_ref()
+            .and_then(|header| header.data_directories.get_debug_table())
+        else {
+            return Ok(Vec::new());
+        };
+        let Some(directory) = rva_bytes(&pe.sections, data, table.virtual_address, table.size)
+        else {
+            log::debug!("Debug directory lies outside the file");
+            return Ok(Vec::new());
+        };
+
+        let mut strings = Vec::new();
+        for entry in directory.chunks_exact(DIRECTORY_SIZE as usize) {
+            let field = |at: usize| u32::from_le_bytes(entry[at..at + 4].try_into().unwrap());
+            let (data_type, size, rva, offset) = (field(12), field(16), field(20), field(24));
+            if data_type != IMAGE_DEBUG_TYPE_CODEVIEW {
+                continue;
+            }
+
+            let offset = offset as usize;
+            let Some(codeview) = offset
+                .checked_add(size as usize)
+                .and_then(|end| data.get(offset..end))
+            else {
+                log::debug!(
+                    "CodeView data at offset {:#x} lies outside the file",
+                    offset
+                );
+                continue;
+            };
+            if codeview.len() <= PATH_OFFSET || !codeview.starts_with(b"RSDS") {
+                continue;
+            }
+
+            let section = rva_range_to_offset(&pe.sections, data.len(), rva, size)
+                .map(|(_, section)| section_name(section));
+            let rva_at = |at: usize| (rva != 0).then(|| u64::from(rva) + at as u64);
+
+            let guid: &[u8; 16] = codeview[GUID_OFFSET..GUID_OFFSET + 16].try_into().unwrap();
+            strings.push(FoundString {
+                rva: rva_at(GUID_OFFSET),
+                section: section.clone(),
+                tags: vec![Tag::Guid],
+                ..FoundString::new(
+                    format_guid(guid),
+                    Encoding::Ascii,
+                    (offset + GUID_OFFSET) as u64,
+                    16,
+                    StringSource::DebugInfo,
+                )
             });
+
+            let path = &codeview[PATH_OFFSET..];
+            let path = &path[..path.iter().position(|&b| b == 0).unwrap_or(path.len())];
+            if let Ok(text) = std::str::from_utf8(path) {
+                if !text.is_empty() {
+                    strings.push(FoundString {
+                        rva: rva_at(PATH_OFFSET),
+                        section,
+                        tags: vec![Tag::FilePath],
+                        ..FoundString::new(
+                            text,
+                            if text.is_ascii() {
+                                Encoding::Ascii
+                            } else {
+                                Encoding::Utf8
+                            },
+                            (offset + PATH_OFFSET) as u64,
+                            path.len() as u32,
+                            StringSource::DebugInfo,
+                        )
+                    });
+                }
+            }
+        }
+
+        Ok(strings)
+    }
+}
+
+/// Formats a GUID stored in its little-endian in-memory layout the way
+/// Windows displays it: `{XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX}`.
+fn format_guid(bytes: &[u8; 16]) -> String {
+    let data1 = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    let data2 = u16::from_le_bytes([bytes[4], bytes[5]]);
+    let data3 = u16::from_le_bytes([bytes[6], bytes[7]]);
+    let data4: String = bytes[8..].iter().map(|b| format!("{:02X}", b)).collect();
+    format!(
+        "{{{:08X}-{:04X}-{:04X}-{}-{}}}",
+        data1,
+        data2,
+        data3,
+        &data4[..4],
+        &data4[4..]
+    )
+}
+
+/// Returns a section's name without its NUL padding.
+fn section_name(section: &SectionTable) -> String {
+    String::from_utf8_lossy(&section.name)
+        .trim_end_matches('\0')
+        .to_string()
+}
+
+/// Maps `size` bytes at `rva` to a file offset.
+///
+/// The whole range must lie within a single section's raw data, and that
+/// raw data within a file of `file_len` bytes.
+fn rva_range_to_offset(
+    sections: &[SectionTable],
+    file_len: usize,
+    rva: u32,
+    size: u32,
+) -> Option<(usize, &SectionTable)> {
+    let section = sections.iter().find(|section| {
+        rva >= section.virtual_address && rva - section.virtual_address < section.size_of_raw_data
+    })?;
+    let delta = rva - section.virtual_address;
+    if u64::from(delta) + u64::from(size) > u64::from(section.size_of_raw_data) {
+        return None;
+    }
+    let start = section.pointer_to_raw_data.checked_add(delta)? as usize;
+    let end = start.checked_add(size as usize)?;
+    (end <= file_len).then_some((start, section))
+}
+
+/// Returns the `size` bytes at `rva`, if they lie within one section's raw
+/// data; see [`rva_range_to_offset`].
+fn rva_bytes<'a>(
+    sections: &[SectionTable],
+    data: &'a [u8],
+    rva: u32,
+    size: u32,
+) -> Option<&'a [u8]> {
+    let (offset, _) = rva_range_to_offset(sections, data.len(), rva, size)?;
+    Some(&data[offset..offset + size as usize])
+}
+
+/// Reads the NUL-terminated string at `rva`, which must end within the same
+/// section's raw data. Returns `None` for empty or non-UTF-8 strings.
+fn rva_c_string<'a>(sections: &[SectionTable], data: &'a [u8], rva: u32) -> Option<&'a str> {
+    let (offset, section) = rva_range_to_offset(sections, data.len(), rva, 1)?;
+    let section_end =
+        (section.pointer_to_raw_data as usize + section.size_of_raw_data as usize).min(data.len());
+    let bytes = &data[offset..section_end];
+    let len = bytes.iter().position(|&b| b == 0)?;
+    std::str::from_utf8(&bytes[..len])
+        .ok()
+        .filter(|s| !s.is_empty())
+}
+
+/// Walks the resource directory at `offset` within `rsrc`, collecting the
+/// data entries of the type/name/language tree.
+///
+/// `type_id` is the resource type of the enclosing directory below the root.
+/// Directories are visited at most once, so cyclic trees terminate.
+fn collect_resource_leaves(
+    rsrc: &[u8],
+    offset: usize,
+    depth: usize,
+    type_id: Option<u32>,
+    visited: &mut HashSet<usize>,
+    leaves: &mut Vec<ResourceLeaf>,
+) {
+    // IMAGE_RESOURCE_DIRECTORY is 16 bytes; entries follow it
+    const DIRECTORY_SIZE: usize = 16;
+    const ENTRY_SIZE: usize = 8;
+    const HIGH_BIT: u32 = 0x8000_0000;
+
+    if depth >= RESOURCE_TREE_DEPTH || !visited.insert(offset) {
+        return;
+    }
+    let read_u16 = |at: usize| {
+        rsrc.get(at..at + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+    };
+    let read_u32 = |at: usize| {
+        rsrc.get(at..at + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    };
+
+    let (Some(named), Some(numbered)) = (read_u16(offset + 12), read_u16(offset + 14)) else {
+        return;
+    };
+    let count = usize::from(named) + usize::from(numbered);
+
+    for index in 0..count {
+        let entry = offset + DIRECTORY_SIZE + index * ENTRY_SIZE;
+        let (Some(name), Some(target)) = (read_u32(entry), read_u32(entry + 4)) else {
+            return;
+        };
+        let id = (name & HIGH_BIT == 0).then_some(name);
+        let target_offset = (target & !HIGH_BIT) as usize;
+
+        if target & HIGH_BIT != 0 {
+            let type_id = if depth == 0 { id } else { type_id };
+            collect_resource_leaves(rsrc, target_offset, depth + 1, type_id, visited, leaves);
+        } else if let (Some(rva), Some(size)) =
+            (read_u32(target_offset), read_u32(target_offset + 4))
+        {
+            leaves.push(ResourceLeaf { type_id, rva, size });
+        }
+    }
+}
+
+/// Decodes an `RT_MANIFEST` resource, dropping a UTF-8 byte order mark and
+/// trailing padding, and returns it with its offset within `bytes`.
+///
+/// Returns `None` for empty or non-UTF-8 manifests.
+fn manifest_text(bytes: &[u8]) -> Option<(usize, &str)> {
+    let (start, bytes) = match bytes.strip_prefix(b"\xef\xbb\xbf") {
+        Some(rest) => (3, rest),
+        None => (0, bytes),
+    };
+    let text = std::str::from_utf8(bytes).ok()?;
+    let text = text.trim_end_matches(|c: char| c == '\0' || c.is_whitespace());
+    (!text.is_empty()).then_some((start, text))
+}
+
+/// Decodes the strings of an `RT_STRING` block, returning each non-empty
+/// entry with the offset of its characters within the block.
+///
+/// A block holds [`STRINGS_PER_BLOCK`] length-prefixed UTF-16LE strings;
+/// decoding stops at the first truncated entry.
+fn string_table_entries(block: &[u8]) -> Vec<(usize, String)> {
+    let mut entries = Vec::new();
+    let mut pos = 0usize;
+    for _ in 0..STRINGS_PER_BLOCK {
+        let Some(len) = block.get(pos..pos + 2) else {
+            break;
+        };
+        let len = usize::from(u16::from_le_bytes([len[0], len[1]]));
+        let start = pos + 2;
+        let Some(chars) = block.get(start..start + len * 2) else {
+            break;
+        };
+        pos = start + len * 2;
+
+        let units: Vec<u16> = chars
+            .chunks_exact(2)
+            .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+            .collect();
+        if let Ok(text) = String::from_utf16(&units) {
+            if !text.is_empty() {
+                entries.push((start, text));
+            }
+        }
+    }
+    entries
+}
+
+/// A node of a `VERSIONINFO` resource: `VS_VERSIONINFO`, `StringFileInfo`,
+/// a `StringTable` or a `String`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct VersionNode {
+    key: String,
+    /// Byte range of the value, UTF-16LE text for `String` nodes
+    value: Range<usize>,
+    /// Byte range holding the child nodes
+    children: Range<usize>,
+}
+
+/// Reads the version node at `pos`, which must end by `limit`.
+///
+/// Each node is a `wLength`, `wValueLength`, `wType` header, a
+/// NUL-terminated UTF-16LE key, then the value and the children, each
+/// aligned to 4 bytes. `wValueLength` counts characters for text values
+/// (`wType` 1) and bytes otherwise.
+fn version_node(block: &[u8], pos: usize, limit: usize) -> Option<VersionNode> {
+    let word = |at: usize| {
+        block
+            .get(at..at + 2)
+            .map(|b| usize::from(u16::from_le_bytes([b[0], b[1]])))
+    };
+    let end = pos.checked_add(word(pos)?)?.min(limit);
+    let value_len = word(pos + 2)?;
+    let is_text = word(pos + 4)? == 1;
+
+    let mut units = Vec::new();
+    let mut key_end = pos + 6;
+    loop {
+        if key_end + 2 > end {
+            return None;
+        }
+        let unit = word(key_end)? as u16;
+        key_end += 2;
+        if unit == 0 {
+            break;
         }
+        units.push(unit);
+    }
+    let key = String::from_utf16(&units).ok()?;
+
+    let value_start = key_end.next_multiple_of(4).min(end);
+    let value_bytes = if is_text { value_len * 2 } else { value_len };
+    let value = value_start..(value_start + value_bytes).min(end);
+    let children = value.end.next_multiple_of(4).min(end)..end;
+    Some(VersionNode {
+        key,
+        value,
+        children,
+    })
+}
+
+/// Returns the nodes directly within `range`.
+fn version_children(block: &[u8], range: Range<usize>) -> Vec<VersionNode> {
+    let mut nodes = Vec::new();
+    let mut pos = range.start;
+    while pos + 6 <= range.end {
+        let Some(node) = version_node(block, pos, range.end) else {
+            break;
+        };
+        pos = node.children.end.next_multiple_of(4);
+        nodes.push(node);
+    }
+    nodes
+}
 
-        exports
+/// Decodes the `StringFileInfo` strings of an `RT_VERSION` resource,
+/// returning each non-empty value with its key and the offset of its
+/// characters within the resource.
+///
+/// Malformed nodes end decoding of their siblings; strings already decoded
+/// are kept.
+fn version_info_strings(block: &[u8]) -> Vec<(usize, String, String)> {
+    let mut strings = Vec::new();
+    let Some(root) = version_node(block, 0, block.len()) else {
+        return strings;
+    };
+    for info in version_children(block, root.children) {
+        if info.key != "StringFileInfo" {
+            continue;
+        }
+        for table in version_children(block, info.children) {
+            for entry in version_children(block, table.children) {
+                let units: Vec<u16> = block[entry.value.clone()]
+                    .chunks_exact(2)
+                    .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+                    .take_while(|&unit| unit != 0)
+                    .collect();
+                if let Ok(text) = String::from_utf16(&units) {
+                    if !text.is_empty() {
+                        strings.push((entry.value.start, entry.key, text));
+                    }
+                }
+            }
+        }
     }
+    strings
 }
 
 impl ContainerParser for PeParser {
@@ -100,10 +982,7 @@ fn detect(data: &[u8]) -> bool {
     }
 
     fn parse(&self, data: &[u8]) -> Result<ContainerInfo> {
-        let pe = match Object::parse(data)? {
-            Object::PE(pe) => pe,
-            _ => return Err(StringyError::ParseError("Not a PE file".to_string())),
-        };
+        let pe = Self::parse_pe(data)?;
 
         let mut sections = Vec::new();
 
@@ -132,19 +1011,44 @@ fn parse(&self, data: &[u8]) -> Result<ContainerInfo> {
                 is_writable: section.characteristics
                     & goblin::pe::section_table::IMAGE_SCN_MEM_WRITE
                     != 0,
+                entropy: None,
+                truncated: false,
             });
         }
+        let (image_end, file_end) = Self::overlay_bounds(&pe, data.len() as u64);
+        sections.extend(overlay_section(image_end, file_end));
+        for section in &mut sections {
+            section.clamp_to(data.len() as u64);
+            section.measure_entropy(data);
+        }
 
-        let imports = self.extract_imports(&pe);
-        let exports = self.extract_exports(&pe);
+        let mut imports = self.extract_imports(&pe);
+        imports.extend(self.extract_delay_imports(&pe, data));
+        let exports = self.extract_exports(&pe, data);
 
         Ok(ContainerInfo {
             format: BinaryFormat::Pe,
+            is_64bit: pe.is_64,
+            architecture: None,
             sections,
             imports,
             exports,
+            elf: None,
+            rich_header: Self::parse_rich_header(data),
+            tls_callbacks: Self::tls_callbacks(&pe),
         })
     }
+
+    /// Returns manifest and string table resources, the PDB path and GUID,
+    /// and export forwarders; see [`PeParser::extract_resource_strings`],
+    /// [`PeParser::extract_debug_strings`] and
+    /// [`PeParser::extract_forwarder_strings`].
+    fn extract_metadata_strings(&self, data: &[u8]) -> Result<Vec<FoundString>> {
+        let mut strings = self.extract_resource_strings(data)?;
+        strings.extend(self.extract_debug_strings(data)?);
+        strings.extend(self.extract_forwarder_strings(data)?);
+        Ok(strings)
+    }
 }
 
 #[cfg(test)]
@@ -152,6 +1056,776 @@ mod tests {
     use super::*;
     use goblin::pe::section_table::{IMAGE_SCN_CNT_CODE, IMAGE_SCN_MEM_WRITE, SectionTable};
 
+    const FILE_ALIGNMENT: usize = 0x200;
+
+    /// Minimal PE32+ image with one section per entry.
+    struct TestPe<'a> {
+        /// `(name, rva, contents)` of each section
+        sections: &'a [(&'a str, u32, &'a [u8])],
+        /// `(index, rva, size)` of the non-empty data directories
+        directories: &'a [(usize, u32, u32)],
+    }
+
+    impl TestPe<'_> {
+        fn build(&self) -> Vec<u8> {
+            const PE_OFFSET: usize = 0x40;
+            const OPTIONAL_HEADER_SIZE: usize = 112 + 16 * 8;
+
+            let mut out = vec![0u8; PE_OFFSET];
+            out[..2].copy_from_slice(b"MZ");
+            out[0x3c..0x40].copy_from_slice(&(PE_OFFSET as u32).to_le_bytes());
+
+            out.extend_from_slice(b"PE\0\0");
+            out.extend_from_slice(&0x8664u16.to_le_bytes()); // IMAGE_FILE_MACHINE_AMD64
+            out.extend_from_slice(&(self.sections.len() as u16).to_le_bytes());
+            out.extend_from_slice(&[0u8; 12]); // timestamp, symbol table, symbol count
+            out.extend_from_slice(&(OPTIONAL_HEADER_SIZE as u16).to_le_bytes());
+            out.extend_from_slice(&0x22u16.to_le_bytes()); // executable, large address aware
+
+            let headers_size = (out.len() + OPTIONAL_HEADER_SIZE + 40 * self.sections.len())
+                .next_multiple_of(FILE_ALIGNMENT);
+            let image_size = self
+                .sections
+                .iter()
+                .map(|(_, rva, contents)| *rva as usize + contents.len())
+                .max()
+                .unwrap_or(0x1000)
+                .next_multiple_of(0x1000);
+
+            out.extend_from_slice(&0x20bu16.to_le_bytes()); // PE32+
+            out.extend_from_slice(&[14, 0]); // linker version
+            out.extend_from_slice(&[0u8; 12]); // code and data sizes
+            out.extend_from_slice(&0x1000u32.to_le_bytes()); // entry point
+            out.extend_from_slice(&0x1000u32.to_le_bytes()); // base of code
+            out.extend_from_slice(&0x1_4000_0000u64.to_le_bytes()); // image base
+            out.extend_from_slice(&0x1000u32.to_le_bytes()); // section alignment
+            out.extend_from_slice(&(FILE_ALIGNMENT as u32).to_le_bytes());
+            out.extend_from_slice(&[6, 0, 0, 0, 0, 0, 0, 0, 6, 0, 0, 0, 0, 0, 0, 0]); // versions
+            out.extend_from_slice(&(image_size as u32).to_le_bytes());
+            out.extend_from_slice(&(headers_size as u32).to_le_bytes());
+            out.extend_from_slice(&0u32.to_le_bytes()); // checksum
+            out.extend_from_slice(&3u16.to_le_bytes()); // IMAGE_SUBSYSTEM_WINDOWS_CUI
+            out.extend_from_slice(&0x8160u16.to_le_bytes()); // DLL characteristics
+            for size in [0x10_0000u64, 0x1000, 0x10_0000, 0x1000] {
+                out.extend_from_slice(&size.to_le_bytes()); // stack and heap
+            }
+            out.extend_from_slice(&0u32.to_le_bytes()); // loader flags
+            out.extend_from_slice(&16u32.to_le_bytes()); // number of data directories
+            for index in 0..16 {
+                let (rva, size) = self
+                    .directories
+                    .iter()
+                    .find(|(i, ..)| *i == index)
+                    .map_or((0, 0), |(_, rva, size)| (*rva, *size));
+                out.extend_from_slice(&rva.to_le_bytes());
+                out.extend_from_slice(&size.to_le_bytes());
+            }
+
+            let mut raw_offset = headers_size;
+            let mut payload = Vec::new();
+            for (name, rva, contents) in self.sections {
+                let raw_size = contents.len().next_multiple_of(FILE_ALIGNMENT);
+                let mut name_bytes = [0u8; 8];
+                name_bytes[..name.len()].copy_from_slice(name.as_bytes());
+                out.extend_from_slice(&name_bytes);
+                out.extend_from_slice(&(contents.len() as u32).to_le_bytes()); // virtual size
+                out.extend_from_slice(&rva.to_le_bytes());
+                out.extend_from_slice(&(raw_size as u32).to_le_bytes());
+                out.extend_from_slice(&(raw_offset as u32).to_le_bytes());
+                out.extend_from_slice(&[0u8; 12]); // relocations and line numbers
+                out.extend_from_slice(&0x4000_0040u32.to_le_bytes()); // initialized, readable
+
+                payload.extend_from_slice(contents);
+                payload.resize(payload.len() + raw_size - contents.len(), 0);
+                raw_offset += raw_size;
+            }
+
+            out.resize(headers_size, 0);
+            out.extend_from_slice(&payload);
+            out
+        }
+    }
+
+    /// Builds a resource section loaded at `rva` holding one language of each
+    /// `(type, name, data)` resource.
+    fn build_rsrc(rva: u32, resources: &[(u32, u32, &[u8])]) -> Vec<u8> {
+        fn directory(out: &mut Vec<u8>, entries: &[(u32, u32)]) {
+            out.extend_from_slice(&[0u8; 12]); // characteristics, timestamp, version
+            out.extend_from_slice(&0u16.to_le_bytes()); // named entries
+            out.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+            for (id, target) in entries {
+                out.extend_from_slice(&id.to_le_bytes());
+                out.extend_from_slice(&target.to_le_bytes());
+            }
+        }
+        const SUBDIRECTORY: u32 = 0x8000_0000;
+
+        // Root, then per resource: type directory, name directory, data entry
+        let root_size = 16 + 8 * resources.len();
+        let per_resource = 24 + 24 + 16;
+        let data_start = root_size + per_resource * resources.len();
+
+        let mut out = Vec::new();
+        let root: Vec<(u32, u32)> = resources
+            .iter()
+            .enumerate()
+            .map(|(i, (type_id, ..))| {
+                (
+                    *type_id,
+                    SUBDIRECTORY | (root_size + per_resource * i) as u32,
+                )
+            })
+            .collect();
+        directory(&mut out, &root);
+
+        let mut data = Vec::new();
+        for (i, (_, name_id, contents)) in resources.iter().enumerate() {
+            let type_dir = root_size + per_resource * i;
+            directory(
+                &mut out,
+                &[(*name_id, SUBDIRECTORY | (type_dir + 24) as u32)],
+            );
+            directory(&mut out, &[(0x409, (type_dir + 48) as u32)]);
+            let data_rva = rva + (data_start + data.len()) as u32;
+            out.extend_from_slice(&data_rva.to_le_bytes());
+            out.extend_from_slice(&(contents.len() as u32).to_le_bytes());
+            out.extend_from_slice(&[0u8; 8]); // code page, reserved
+            data.extend_from_slice(contents);
+            data.resize(data.len().next_multiple_of(4), 0);
+        }
+        out.extend_from_slice(&data);
+        out
+    }
+
+    /// Encodes a 16-entry `RT_STRING` block.
+    fn string_block(strings: &[&str]) -> Vec<u8> {
+        let mut out = Vec::new();
+        for i in 0..16 {
+            let units: Vec<u16> = strings
+                .get(i)
+                .map_or(Vec::new(), |s| s.encode_utf16().collect());
+            out.extend_from_slice(&(units.len() as u16).to_le_bytes());
+            for unit in units {
+                out.extend_from_slice(&unit.to_le_bytes());
+            }
+        }
+        out
+    }
+
+    /// Encodes a `VERSIONINFO` resource whose `StringFileInfo` holds
+    /// `strings`, followed by a `VarFileInfo`.
+    fn version_info(strings: &[(&str, &str)]) -> Vec<u8> {
+        fn node(key: &str, value: &[u8], text: bool, children: &[Vec<u8>]) -> Vec<u8> {
+            let mut out = vec![0u8; 6];
+            out.extend(key.encode_utf16().chain([0]).flat_map(u16::to_le_bytes));
+            out.resize(out.len().next_multiple_of(4), 0);
+            out.extend_from_slice(value);
+            for child in children {
+                out.resize(out.len().next_multiple_of(4), 0);
+                out.extend_from_slice(child);
+            }
+            let value_len = if text { value.len() / 2 } else { value.len() };
+            let len = out.len() as u16;
+            out[0..2].copy_from_slice(&len.to_le_bytes());
+            out[2..4].copy_from_slice(&(value_len as u16).to_le_bytes());
+            out[4..6].copy_from_slice(&u16::from(text).to_le_bytes());
+            out
+        }
+        let utf16z = |text: &str| -> Vec<u8> {
+            text.encode_utf16()
+                .chain([0])
+                .flat_map(u16::to_le_bytes)
+                .collect()
+        };
+
+        let entries: Vec<Vec<u8>> = strings
+            .iter()
+            .map(|(key, value)| node(key, &utf16z(value), true, &[]))
+            .collect();
+        let table = node("040904b0", &[], true, &entries);
+        let string_info = node("StringFileInfo", &[], true, &[table]);
+        let translation = node("Translation", &[0x09, 0x04, 0xb0, 0x04], false, &[]);
+        let var_info = node("VarFileInfo", &[], true, &[translation]);
+        let mut fixed = vec![0u8; 52];
+        fixed[..4].copy_from_slice(&0xfeef_04bdu32.to_le_bytes());
+        node("VS_VERSION_INFO", &fixed, false, &[string_info, var_info])
+    }
+
+    #[test]
+    fn test_version_info_strings() {
+        let info = version_info(&[
+            ("CompanyName", "Evil Bit Labs"),
+            ("FileVersion", "1.2.3.4"),
+            ("Comments", ""),
+            ("ProductName", "Stringy Test App"),
+        ]);
+        let rsrc = build_rsrc(0x2000, &[(16, 1, &info)]);
+        let data = TestPe {
+            sections: &[(".text", 0x1000, &[0xc3]), (".rsrc", 0x2000, &rsrc)],
+            directories: &[(2, 0x2000, rsrc.len() as u32)],
+        }
+        .build();
+
+        let strings = PeParser::new().extract_resource_strings(&data).unwrap();
+        let found: Vec<(&str, &[Tag])> = strings
+            .iter()
+            .map(|s| (s.text.as_str(), s.tags.as_slice()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Evil Bit Labs", &[Tag::Resource][..]),
+                ("1.2.3.4", &[Tag::Version][..]),
+                ("Stringy Test App", &[Tag::Resource][..]),
+            ]
+        );
+        let product = &strings[2];
+        assert_eq!(product.encoding, Encoding::Utf16Le);
+        assert_eq!(product.length, 32);
+        assert_eq!(&data[product.offset as usize..][..4], b"S\0t\0");
+
+        // The byte scan of .rsrc does not report the decoded values again
+        let result = crate::analyze(&data, &crate::AnalysisConfig::default()).unwrap();
+        for text in ["ProductName", "Stringy Test App", "Evil Bit Labs"] {
+            let count = result.strings.iter().filter(|s| s.text == text).count();
+            assert_eq!(count, 1, "{text}");
+        }
+        let product = result
+            .strings
+            .iter()
+            .find(|s| s.text == "Stringy Test App")
+            .unwrap();
+        assert_eq!(product.source, StringSource::ResourceString);
+    }
+
+    #[test]
+    fn test_truncated_version_info() {
+        let info = version_info(&[("ProductName", "Stringy Test App")]);
+        for len in [0, 4, 40, info.len() - 10] {
+            // Must not panic; a cut-off entry is dropped
+            let strings = version_info_strings(&info[..len]);
+            assert!(strings.iter().all(|(_, key, _)| key == "ProductName"));
+        }
+        assert_eq!(version_info_strings(&info).len(), 1);
+    }
+
+    const MANIFEST: &[u8] = b"\xef\xbb\xbf<assembly><trustInfo><security><requestedPrivileges>\
+        <requestedExecutionLevel level=\"requireAdministrator\"/>\
+        </requestedPrivileges></security></trustInfo></assembly>\r\n";
+
+    #[test]
+    fn test_resource_strings() {
+        let block = string_block(&["", "Connecting to %s", "", "Access denied"]);
+        let rsrc = build_rsrc(0x2000, &[(24, 1, MANIFEST), (6, 1, &block)]);
+        let data = TestPe {
+            sections: &[(".text", 0x1000, &[0xc3]), (".rsrc", 0x2000, &rsrc)],
+            directories: &[(2, 0x2000, rsrc.len() as u32)],
+        }
+        .build();
+        let parser = PeParser::new();
+        let strings = parser.extract_metadata_strings(&data).unwrap();
+        assert_eq!(strings.len(), 3);
+
+        let manifest = &strings[0];
+        assert!(manifest.text.starts_with("<assembly>"));
+        assert!(manifest.text.ends_with("</assembly>"));
+        assert!(manifest.text.contains("requireAdministrator"));
+        assert_eq!(manifest.tags, vec![Tag::Manifest]);
+        assert_eq!(manifest.encoding, Encoding::Utf8);
+        assert_eq!(manifest.source, StringSource::ResourceString);
+        assert_eq!(manifest.section.as_deref(), Some(".rsrc"));
+        assert_eq!(&data[manifest.offset as usize..][..10], b"<assembly>");
+
+        let texts: Vec<&str> = strings[1..].iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["Connecting to %s", "Access denied"]);
+        let denied = &strings[2];
+        assert_eq!(denied.tags, vec![Tag::Resource]);
+        assert_eq!(denied.encoding, Encoding::Utf16Le);
+        assert_eq!(denied.length, 26);
+        assert_eq!(denied.rva, Some(denied.offset - 0x400 + 0x2000));
+        assert_eq!(&data[denied.offset as usize..][..4], b"A\0c\0");
+
+        // The manifest keeps its bonus through rescoring
+        let result = crate::analyze(&data, &crate::AnalysisConfig::default()).unwrap();
+        let manifest = &result.strings[0];
+        assert!(
+            manifest.tags.contains(&Tag::Manifest),
+            "{:?}",
+            result.strings
+        );
+        for text in ["Connecting to %s", "Access denied"] {
+            let string = result.strings.iter().find(|s| s.text == text).unwrap();
+            assert!(string.score < manifest.score, "{string:?}");
+        }
+    }
+
+    #[test]
+    fn test_resource_data_outside_section_is_skipped() {
+        let mut rsrc = build_rsrc(0x2000, &[(24, 1, MANIFEST)]);
+        // Point the manifest's data entry past the end of the section
+        let entry = 16 + 8 + 24 + 24;
+        rsrc[entry..entry + 4].copy_from_slice(&0x2ff0u32.to_le_bytes());
+        let data = TestPe {
+            sections: &[(".rsrc", 0x2000, &rsrc)],
+            directories: &[(2, 0x2000, rsrc.len() as u32)],
+        }
+        .build();
+        let strings = PeParser::new().extract_resource_strings(&data).unwrap();
+        assert!(strings.is_empty());
+    }
+
+    #[test]
+    fn test_no_resources() {
+        let data = TestPe {
+            sections: &[(".text", 0x1000, &[0xc3])],
+            directories: &[],
+        }
+        .build();
+        let strings = PeParser::new().extract_resource_strings(&data).unwrap();
+        assert!(strings.is_empty());
+    }
+
+    #[test]
+    fn test_delay_load_imports() {
+        const IDATA: u32 = 0x3000;
+        let mut didat = vec![0u8; 0x100];
+        let mut put = |at: usize, bytes: &[u8]| didat[at..at + bytes.len()].copy_from_slice(bytes);
+        // Descriptor (RVA-based), followed by an all-zero terminator
+        put(0x00, &1u32.to_le_bytes()); // grAttrs: dlattrRva
+        put(0x04, &(IDATA + 0x60).to_le_bytes()); // DLL name
+        put(0x0c, &(IDATA + 0x80).to_le_bytes()); // IAT
+        put(0x10, &(IDATA + 0x40).to_le_bytes()); // INT
+        // INT: one import by name, one by ordinal
+        put(0x40, &u64::from(IDATA + 0x70).to_le_bytes());
+        put(0x48, &(0x8000_0000_0000_0000u64 | 165).to_le_bytes());
+        put(0x60, b"SHELL32.dll\0");
+        put(0x72, b"ShellExecuteW\0");
+
+        let data = TestPe {
+            sections: &[(".text", 0x1000, &[0xc3]), (".didat", IDATA, &didat)],
+            directories: &[(13, IDATA, 0x40)],
+        }
+        .build();
+        let info = PeParser::new().parse(&data).unwrap();
+
+        let imports: Vec<(&str, Option<&str>, Option<u64>, bool)> = info
+            .imports
+            .iter()
+            .map(|i| (i.name.as_str(), i.library.as_deref(), i.address, i.delayed))
+            .collect();
+        assert_eq!(
+            imports,
+            vec![
+                ("ShellExecuteW", Some("SHELL32.dll"), Some(0x3070), true),
+                ("ORDINAL 165", Some("SHELL32.dll"), Some(0), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_delay_load_descriptor_outside_file() {
+        let data = TestPe {
+            sections: &[(".text", 0x1000, &[0xc3])],
+            directories: &[(13, 0x8000, 0x40)],
+        }
+        .build();
+        let info = PeParser::new().parse(&data).unwrap();
+        assert!(info.imports.is_empty());
+    }
+
+    #[test]
+    fn test_imports_ranked_in_analysis() {
+        const IDATA: u32 = 0x3000;
+        let mut idata = vec![0u8; 0x100];
+        let mut put = |at: usize, bytes: &[u8]| idata[at..at + bytes.len()].copy_from_slice(bytes);
+        // Descriptor, followed by an all-zero terminator
+        put(0x00, &(IDATA + 0x40).to_le_bytes()); // import lookup table
+        put(0x0c, &(IDATA + 0xa0).to_le_bytes()); // DLL name
+        put(0x10, &(IDATA + 0x60).to_le_bytes()); // IAT
+        for (i, hint_name) in [0xb0u32, 0xc4].into_iter().enumerate() {
+            let entry = u64::from(IDATA + hint_name).to_le_bytes();
+            put(0x40 + 8 * i, &entry);
+            put(0x60 + 8 * i, &entry);
+        }
+        put(0xa0, b"KERNEL32.dll\0");
+        put(0xb2, b"GetTickCount\0");
+        put(0xc6, b"VirtualAllocEx\0");
+
+        let data = TestPe {
+            sections: &[(".text", 0x1000, &[0xc3]), (".idata", IDATA, &idata)],
+            directories: &[(1, IDATA, 0x28)],
+        }
+        .build();
+        let result = crate::analyze(&data, &crate::AnalysisConfig::default()).unwrap();
+
+        let position = |name: &str| {
+            result
+                .strings
+                .iter()
+                .position(|s| s.text == name && s.source == StringSource::ImportName)
+                .unwrap()
+        };
+        let alloc = &result.strings[position("VirtualAllocEx")];
+        assert!(alloc.tags.contains(&Tag::Import));
+        assert_eq!(alloc.section.as_deref(), Some("KERNEL32.dll"));
+        assert!(position("VirtualAllocEx") < position("GetTickCount"));
+    }
+
+    #[test]
+    fn test_xrefs_subtract_image_base() {
+        // .data holds ImageBase + RVA of the first .rdata string
+        let pointer = (0x1_4000_0000u64 + 0x2000).to_le_bytes();
+        let data = TestPe {
+            sections: &[
+                (".rdata", 0x2000, b"config.ini\0unused.dat\0"),
+                (".data", 0x3000, &pointer),
+            ],
+            directories: &[],
+        }
+        .build();
+        assert_eq!(PeParser::image_base(&data), Some(0x1_4000_0000));
+
+        let config = crate::AnalysisConfig {
+            count_xrefs: true,
+            ..crate::AnalysisConfig::default()
+        };
+        let result = crate::analyze(&data, &config).unwrap();
+        let count = |text: &str| {
+            result
+                .strings
+                .iter()
+                .find(|s| s.text == text)
+                .and_then(|s| s.xref_count)
+        };
+        assert_eq!(count("config.ini"), Some(1));
+        assert_eq!(count("unused.dat"), Some(0));
+    }
+
+    /// Builds a PE whose `.rdata` holds a debug directory with one CodeView
+    /// entry, stored at `codeview_offset` within the section.
+    fn build_pe_with_pdb(path: &str, codeview_offset: Option<u32>) -> Vec<u8> {
+        const RDATA: u32 = 0x2000;
+        const RDATA_FILE_OFFSET: u32 = 0x400; // after the headers of a 2-section image
+        let mut rdata = vec![0u8; 0x40];
+        let mut codeview = b"RSDS".to_vec();
+        codeview.extend_from_slice(&0x3f25_04e0u32.to_le_bytes());
+        codeview.extend_from_slice(&0x4f89u16.to_le_bytes());
+        codeview.extend_from_slice(&0x11d3u16.to_le_bytes());
+        codeview.extend_from_slice(&[0x9a, 0x0c, 0x03, 0x05, 0xe8, 0x2c, 0x33, 0x01]);
+        codeview.extend_from_slice(&1u32.to_le_bytes()); // age
+        codeview.extend_from_slice(path.as_bytes());
+        codeview.push(0);
+
+        let within = codeview_offset.unwrap_or(0x40);
+        let entry = [
+            0,                          // characteristics
+            0,                          // timestamp
+            0,                          // version
+            2,                          // IMAGE_DEBUG_TYPE_CODEVIEW
+            codeview.len() as u32,      // size of data
+            RDATA + within,             // RVA of data
+            RDATA_FILE_OFFSET + within, // file offset of data
+        ];
+        for (i, value) in entry.iter().enumerate() {
+            rdata[i * 4..i * 4 + 4].copy_from_slice(&value.to_le_bytes());
+        }
+        if codeview_offset.is_some() {
+            rdata.resize(within as usize, 0);
+            rdata.extend_from_slice(&codeview);
+        } else {
+            // Point the entry far past the end of the file
+            rdata[24..28].copy_from_slice(&0x10_0000u32.to_le_bytes());
+        }
+
+        TestPe {
+            sections: &[(".text", 0x1000, &[0xc3]), (".rdata", RDATA, &rdata)],
+            directories: &[(6, RDATA, 28)],
+        }
+        .build()
+    }
+
+    #[test]
+    fn test_pdb_path_and_guid() {
+        let path = r"C:\Users\dev\project\Release\app.pdb";
+        let data = build_pe_with_pdb(path, Some(0x40));
+        let strings = PeParser::new().extract_debug_strings(&data).unwrap();
+        assert_eq!(strings.len(), 2);
+
+        let guid = &strings[0];
+        assert_eq!(guid.text, "{3F2504E0-4F89-11D3-9A0C-0305E82C3301}");
+        assert_eq!(guid.tags, vec![Tag::Guid]);
+        assert_eq!(guid.source, StringSource::DebugInfo);
+        assert_eq!(guid.rva, Some(0x2044));
+
+        let pdb = &strings[1];
+        assert_eq!(pdb.text, path);
+        assert_eq!(pdb.tags, vec![Tag::FilePath]);
+        assert_eq!(pdb.source, StringSource::DebugInfo);
+        assert_eq!(pdb.section.as_deref(), Some(".rdata"));
+        assert_eq!(&data[pdb.offset as usize..][..path.len()], path.as_bytes());
+
+        let metadata = PeParser::new().extract_metadata_strings(&data).unwrap();
+        assert_eq!(metadata.len(), 2);
+    }
+
+    #[test]
+    fn test_pdb_outside_file() {
+        let data = build_pe_with_pdb(r"C:\app.pdb", None);
+        assert!(PeParser::new().parse(&data).is_ok());
+        let strings = PeParser::new().extract_debug_strings(&data).unwrap();
+        assert!(strings.is_empty());
+
+        let data = TestPe {
+            sections: &[(".text", 0x1000, &[0xc3])],
+            directories: &[],
+        }
+        .build();
+        let strings = PeParser::new().extract_debug_strings(&data).unwrap();
+        assert!(strings.is_empty());
+    }
+
+    /// Target of an export address table entry.
+    enum ExportTarget {
+        Code(u32),
+        Forwarder(&'static str),
+    }
+
+    /// Builds an export directory loaded at `rva` for `dll`, whose address
+    /// table holds `functions` (ordinals `base..`) and whose name table maps
+    /// each name to an address table index.
+    fn build_edata(
+        rva: u32,
+        dll: &str,
+        base: u32,
+        functions: &[ExportTarget],
+        names: &[(&str, u16)],
+    ) -> Vec<u8> {
+        const DIRECTORY_SIZE: usize = 40;
+        let functions_at = DIRECTORY_SIZE;
+        let names_at = functions_at + 4 * functions.len();
+        let ordinals_at = names_at + 4 * names.len();
+        let mut strings_at = ordinals_at + 2 * names.len();
+
+        let mut out = vec![0u8; strings_at];
+        let mut strings = Vec::new();
+        let mut add_string = |text: &str| {
+            let at = strings_at;
+            strings.extend_from_slice(text.as_bytes());
+            strings.push(0);
+            strings_at += text.len() + 1;
+            rva + at as u32
+        };
+        let mut put = |at: usize, value: u32| out[at..at + 4].copy_from_slice(&value.to_le_bytes());
+
+        put(12, add_string(dll));
+        put(16, base);
+        put(20, functions.len() as u32);
+        put(24, names.len() as u32);
+        put(28, rva + functions_at as u32);
+        put(32, rva + names_at as u32);
+        put(36, rva + ordinals_at as u32);
+        for (i, function) in functions.iter().enumerate() {
+            let target = match function {
+                ExportTarget::Code(address) => *address,
+                ExportTarget::Forwarder(forwarder) => add_string(forwarder),
+            };
+            put(functions_at + 4 * i, target);
+        }
+        for (i, (name, _)) in names.iter().enumerate() {
+            put(names_at + 4 * i, add_string(name));
+        }
+        for (i, (_, index)) in names.iter().enumerate() {
+            let at = ordinals_at + 2 * i;
+            out[at..at + 2].copy_from_slice(&index.to_le_bytes());
+        }
+        out.extend_from_slice(&strings);
+        out
+    }
+
+    #[test]
+    fn test_forwarded_exports() {
+        const EDATA: u32 = 0x2000;
+        let edata = build_edata(
+            EDATA,
+            "KERNEL32.dll",
+            1,
+            &[
+                ExportTarget::Code(0x1000),
+                ExportTarget::Forwarder("NTDLL.RtlAllocateHeap"),
+                ExportTarget::Forwarder("NTDLL.RtlFreeHeap"),
+            ],
+            &[("CreateFileW", 0), ("HeapAlloc", 1), ("HeapFree", 2)],
+        );
+        let data = TestPe {
+            sections: &[(".text", 0x1000, &[0xc3]), (".edata", EDATA, &edata)],
+            directories: &[(0, EDATA, edata.len() as u32)],
+        }
+        .build();
+        let parser = PeParser::new();
+
+        let info = parser.parse(&data).unwrap();
+        let exports: Vec<(&str, u64, Option<&str>)> = info
+            .exports
+            .iter()
+            .map(|e| (e.name.as_str(), e.address, e.forwarded_to.as_deref()))
+            .collect();
+        assert_eq!(
+            exports,
+            vec![
+                ("CreateFileW", 0x1000, None),
+                ("HeapAlloc", 0, Some("NTDLL.RtlAllocateHeap")),
+                ("HeapFree", 0, Some("NTDLL.RtlFreeHeap")),
+            ]
+        );
+
+        let strings = parser.extract_forwarder_strings(&data).unwrap();
+        let texts: Vec<&str> = strings.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["NTDLL.RtlAllocateHeap", "NTDLL.RtlFreeHeap"]);
+        let first = &strings[0];
+        assert_eq!(first.tags, vec![Tag::Export]);
+        assert_eq!(first.source, StringSource::ExportName);
+        assert_eq!(first.section.as_deref(), Some(".edata"));
+        assert_eq!(
+            &data[first.offset as usize..][..21],
+            b"NTDLL.RtlAllocateHeap"
+        );
+    }
+
+    #[test]
+    fn test_export_ordinals_use_ordinal_base() {
+        const EDATA: u32 = 0x2000;
+        // Ordinals 100..=104: 101 is a gap, 103 is exported by ordinal only,
+        // and names are bound out of address-table order
+        let edata = build_edata(
+            EDATA,
+            "payload.dll",
+            100,
+            &[
+                ExportTarget::Code(0x1000),
+                ExportTarget::Code(0),
+                ExportTarget::Code(0x1010),
+                ExportTarget::Code(0x1020),
+                ExportTarget::Code(0x1030),
+            ],
+            &[("Install", 4), ("ServiceMain", 0), ("Start", 2)],
+        );
+        let data = TestPe {
+            sections: &[(".text", 0x1000, &[0xc3; 0x40]), (".edata", EDATA, &edata)],
+            directories: &[(0, EDATA, edata.len() as u32)],
+        }
+        .build();
+        let info = PeParser::new().parse(&data).unwrap();
+
+        let exports: Vec<(&str, Option<u16>, u64)> = info
+            .exports
+            .iter()
+            .map(|e| (e.name.as_str(), e.ordinal, e.address))
+            .collect();
+        assert_eq!(
+            exports,
+            vec![
+                ("ServiceMain", Some(100), 0x1000),
+                ("Start", Some(102), 0x1010),
+                ("ordinal_103", Some(103), 0x1020),
+                ("Install", Some(104), 0x1030),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rich_header() {
+        const KEY: u32 = 0x8c2b_6f3a;
+        let records: [(u32, u32); 2] = [
+            (0x0104 << 16 | 30_159, 12), // Utc1900_C, VS2019
+            (0x0102 << 16 | 30_159, 1),  // Linker1400
+        ];
+
+        let mut data = vec![0u8; 0x80];
+        data[..2].copy_from_slice(b"MZ");
+        for word in [u32::from_le_bytes(*b"DanS"), 0, 0, 0] {
+            data.extend_from_slice(&(word ^ KEY).to_le_bytes());
+        }
+        for (comp_id, count) in records {
+            data.extend_from_slice(&(comp_id ^ KEY).to_le_bytes());
+            data.extend_from_slice(&(count ^ KEY).to_le_bytes());
+        }
+        data.extend_from_slice(b"Rich");
+        data.extend_from_slice(&KEY.to_le_bytes());
+        data.resize(data.len() + 8, 0);
+        let pe_offset = data.len() as u32;
+        data[0x3c..0x40].copy_from_slice(&pe_offset.to_le_bytes());
+        data.extend_from_slice(b"PE\0\0");
+
+        let rich = PeParser::parse_rich_header(&data).unwrap();
+        assert_eq!(rich.checksum, KEY);
+        assert_eq!(
+            rich.entries,
+            vec![
+                RichEntry {
+                    product_id: 0x0104,
+                    build: 30_159,
+                    count: 12
+                },
+                RichEntry {
+                    product_id: 0x0102,
+                    build: 30_159,
+                    count: 1
+                },
+            ]
+        );
+
+        // Without the DanS marker the header cannot be decoded
+        data[0x80..0x84].copy_from_slice(&0u32.to_le_bytes());
+        assert_eq!(PeParser::parse_rich_header(&data), None);
+    }
+
+    #[test]
+    fn test_no_rich_header() {
+        let data = TestPe {
+            sections: &[(".text", 0x1000, &[0xc3])],
+            directories: &[],
+        }
+        .build();
+        assert_eq!(PeParser::parse_rich_header(&data), None);
+        assert_eq!(PeParser::new().parse(&data).unwrap().rich_header, None);
+        assert_eq!(PeParser::parse_rich_header(b"MZ"), None);
+    }
+
+    #[test]
+    fn test_tls_callbacks() {
+        const IMAGE_BASE: u64 = 0x1_4000_0000;
+        const RDATA: u32 = 0x2000;
+        let mut rdata = vec![0u8; 0x60];
+        // IMAGE_TLS_DIRECTORY64 with only AddressOfCallBacks set, pointing at
+        // a null-terminated table of two callbacks in .text
+        let callbacks = IMAGE_BASE + u64::from(RDATA) + 0x40;
+        rdata[0x18..0x20].copy_from_slice(&callbacks.to_le_bytes());
+        rdata[0x40..0x48].copy_from_slice(&(IMAGE_BASE + 0x1000).to_le_bytes());
+        rdata[0x48..0x50].copy_from_slice(&(IMAGE_BASE + 0x1008).to_le_bytes());
+
+        let text = [0xc3; 0x10];
+        let data = TestPe {
+            sections: &[(".text", 0x1000, &text), (".rdata", RDATA, &rdata)],
+            directories: &[(9, RDATA, 40)],
+        }
+        .build();
+        let info = PeParser::new().parse(&data).unwrap();
+        assert_eq!(info.tls_callbacks, vec![0x1000, 0x1008]);
+
+        let data = TestPe {
+            sections: &[(".text", 0x1000, &text)],
+            directories: &[],
+        }
+        .build();
+        assert!(
+            PeParser::new()
+                .parse(&data)
+                .unwrap()
+                .tls_callbacks
+                .is_empty()
+        );
+    }
+
     #[test]
     fn test_pe_detection() {
         // Invalid data
@@ -162,6 +1836,26 @@ fn test_pe_detection() {
         // which would be better tested with actual binary files
     }
 
+    #[test]
+    fn test_detect_format_detailed() {
+        use crate::container::{detect_format_detailed, detect_format_fast};
+
+        let data = TestPe {
+            sections: &[(".text", 0x1000, &[0xc3])],
+            directories: &[],
+        }
+        .build();
+        assert_eq!(detect_format_detailed(&data), vec![(BinaryFormat::Pe, 1.0)]);
+        assert_eq!(detect_format_fast(&data), BinaryFormat::Pe);
+
+        // A valid DOS stub and PE signature in front of a corrupt COFF header
+        let mut corrupt = data[..0x48].to_vec();
+        corrupt[0x44..0x48].fill(0xff);
+        let candidates = detect_format_detailed(&corrupt);
+        assert_eq!(candidates[0].0, BinaryFormat::Unknown);
+        assert_eq!(candidates[1], (BinaryFormat::Pe, 0.45));
+    }
+
     #[test]
     fn test_section_classification() {
         // Test code section
@@ -0,0 +1,167 @@
+use crate::classification::entropy::byte_entropy;
+use crate::container::ContainerParser;
+use crate::types::{BinaryFormat, ContainerInfo, Result, SectionInfo, SectionType, StringyError};
+
+/// Alignment required for a raw-mode base address (one 4 KiB page).
+pub const BASE_ADDRESS_ALIGNMENT: u64 = 0x1000;
+
+/// Parser for raw, unstructured input such as firmware images, shellcode, and
+/// process memory dumps.
+///
+/// The whole input is exposed as a single synthetic section. When a base
+/// address is configured, the section is treated as if it were loaded at that
+/// virtual address, so reported `rva` values reflect real process addresses
+/// rather than file offsets. The base address only applies in raw mode; the
+/// structured parsers always report the addresses recorded in the binary.
+///
+/// # Examples
+///
+/// ```rust
+/// use stringy::container::{ContainerParser, raw::RawParser};
+///
+/// let parser = RawParser::with_base_address(0x7ff6_0000_0000).unwrap();
+/// let info = parser.parse(b"memory dump contents").unwrap();
+/// assert_eq!(info.sections[0].rva, Some(0x7ff6_0000_0000));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RawParser {
+    base_address: Option<u64>,
+}
+
+impl RawParser {
+    /// Creates a raw parser that reports no virtual addresses.
+    pub fn new() -> Self {
+        Self { base_address: None }
+    }
+
+    /// Creates a raw parser that treats the input as loaded at `base`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StringyError::ConfigError` if `base` is not aligned to
+    /// [`BASE_ADDRESS_ALIGNMENT`].
+    pub fn with_base_address(base: u64) -> Result<Self> {
+        Self::validate_base_address(base)?;
+        Ok(Self {
+            base_address: Some(base),
+        })
+    }
+
+    /// Returns the configured base address, if any.
+    pub fn base_address(&self) -> Option<u64> {
+        self.base_address
+    }
+
+    /// Checks that a base address is page aligned.
+    fn validate_base_address(base: u64) -> Result<()> {
+        if !base.is_multiple_of(BASE_ADDRESS_ALIGNMENT) {
+            return Err(StringyError::ConfigError(format!(
+                "Base address {:#x} is not aligned to {:#x}",
+                base, BASE_ADDRESS_ALIGNMENT
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl ContainerParser for RawParser {
+    /// Raw mode accepts any input.
+    fn detect(_data: &[u8]) -> bool {
+        true
+    }
+
+    /// Exposes the entire input as one synthetic section.
+    ///
+    /// Addresses are reported as 64-bit only when the input, loaded at the
+    /// base address, extends past 4 GiB.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StringyError::ConfigError` if the input would extend past the
+    /// end of the address space when loaded at the configured base address.
+    fn parse(&self, data: &[u8]) -> Result<ContainerInfo> {
+        let size = data.len() as u64;
+
+        if let Some(base) = self.base_address {
+            if base.checked_add(size).is_none() {
+                return Err(StringyError::ConfigError(format!(
+                    "Input of {} bytes does not fit in the address space at base {:#x}",
+                    size, base
+                )));
+            }
+        }
+
+        let mut sections = Vec::new();
+        if size > 0 {
+            sections.push(SectionInfo {
+                name: "raw".to_string(),
+                offset: 0,
+                size,
+                rva: self.base_address,
+                section_type: SectionType::StringData,
+                is_executable: false,
+                is_writable: false,
+                entropy: Some(byte_entropy(data)),
+                truncated: false,
+            });
+        }
+
+        Ok(ContainerInfo {
+            format: BinaryFormat::Unknown,
+            is_64bit: self
+                .base_address
+                .is_some_and(|base| base.saturating_add(size) > u64::from(u32::MAX) + 1),
+            architecture: None,
+            sections,
+            imports: Vec::new(),
+            exports: Vec::new(),
+            elf: None,
+            rich_header: None,
+            tls_callbacks: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_parse_without_base() {
+        let info = RawParser::new().parse(b"some raw bytes").unwrap();
+        assert_eq!(info.format, BinaryFormat::Unknown);
+        assert_eq!(info.sections.len(), 1);
+        assert_eq!(info.sections[0].offset, 0);
+        assert_eq!(info.sections[0].size, 14);
+        assert_eq!(info.sections[0].rva, None);
+    }
+
+    #[test]
+    fn test_raw_parse_with_base() {
+        let parser = RawParser::with_base_address(0x40_0000).unwrap();
+        assert_eq!(parser.base_address(), Some(0x40_0000));
+
+        let info = parser.parse(&[0u8; 64]).unwrap();
+        assert_eq!(info.sections[0].rva, Some(0x40_0000));
+    }
+
+    #[test]
+    fn test_base_address_validation() {
+        assert!(RawParser::with_base_address(0).is_ok());
+        assert!(RawParser::with_base_address(0x1000).is_ok());
+        assert!(matches!(
+            RawParser::with_base_address(0x1234),
+            Err(StringyError::ConfigError(_))
+        ));
+
+        // Aligned, but the input would wrap past the end of the address space
+        let parser = RawParser::with_base_address(u64::MAX - 0xfff).unwrap();
+        assert!(parser.parse(&[0u8; 0x2000]).is_err());
+    }
+
+    #[test]
+    fn test_raw_parse_empty_input() {
+        let info = RawParser::new().parse(&[]).unwrap();
+        assert!(info.sections.is_empty());
+    }
+}
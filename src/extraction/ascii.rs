@@ -0,0 +1,415 @@
+use crate::extraction::{BorrowedString, ExtractionConfig};
+use crate::types::{Encoding, FoundString, Result, StringyError};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// Returns `true` if a byte continues an ASCII string run by default.
+///
+/// Printable ASCII (0x20-0x7E) and horizontal tab are accepted.
+pub fn is_printable_ascii(byte: u8) -> bool {
+    matches!(byte, 0x20..=0x7e | b'\t')
+}
+
+/// Set of bytes that continue an ASCII string run; see
+/// [`ExtractionConfig::ascii_charset`].
+///
+/// The default is [`AsciiCharset::printable`]. Bytes above 0x7F are decoded
+/// as Latin-1, so adding [`AsciiCharset::LATIN1`] turns `caf\xe9` into
+/// `café`.
+///
+/// # Examples
+///
+/// ```rust
+/// use stringy::extraction::ascii::AsciiCharset;
+///
+/// let multiline = AsciiCharset::printable().with(b'\n').with(b'\r');
+/// assert!(multiline.contains(b'\n'));
+/// assert!(!AsciiCharset::default().contains(b'\n'));
+///
+/// let parsed: AsciiCharset = "printable,tab,newline".parse().unwrap();
+/// assert_eq!(parsed, multiline);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AsciiCharset {
+    bits: [u64; 4],
+}
+
+impl AsciiCharset {
+    /// Printable ASCII, 0x20-0x7E
+    pub const PRINTABLE: RangeInclusive<u8> = 0x20..=0x7e;
+    /// Printable Latin-1 supplement, 0xA0-0xFF
+    pub const LATIN1: RangeInclusive<u8> = 0xa0..=0xff;
+
+    /// No bytes at all.
+    pub fn empty() -> Self {
+        Self { bits: [0; 4] }
+    }
+
+    /// Printable ASCII and horizontal tab, as accepted by
+    /// [`is_printable_ascii`].
+    pub fn printable() -> Self {
+        Self::empty().with_range(Self::PRINTABLE).with(b'\t')
+    }
+
+    /// Adds `byte` to the set.
+    pub fn with(mut self, byte: u8) -> Self {
+        self.bits[usize::from(byte >> 6)] |= 1 << (byte & 63);
+        self
+    }
+
+    /// Removes `byte` from the set.
+    pub fn without(mut self, byte: u8) -> Self {
+        self.bits[usize::from(byte >> 6)] &= !(1 << (byte & 63));
+        self
+    }
+
+    /// Adds every byte in `range` to the set.
+    pub fn with_range(self, range: RangeInclusive<u8>) -> Self {
+        range.fold(self, Self::with)
+    }
+
+    /// Removes every byte in `range` from the set.
+    pub fn without_range(self, range: RangeInclusive<u8>) -> Self {
+        range.fold(self, Self::without)
+    }
+
+    /// Returns `true` if `byte` continues a run.
+    #[inline]
+    pub fn contains(&self, byte: u8) -> bool {
+        self.bits[usize::from(byte >> 6)] & (1 << (byte & 63)) != 0
+    }
+}
+
+impl Default for AsciiCharset {
+    fn default() -> Self {
+        Self::printable()
+    }
+}
+
+/// Parses a comma-separated list of byte classes, e.g. `printable,newline`.
+///
+/// The classes are `printable` (0x20-0x7E), `tab`, `newline` (LF and CR)
+/// and `latin1` (0xA0-0xFF). They are applied in order to an empty set; a
+/// class prefixed with `-` is removed instead, so `printable,-tab` is not
+/// the same as `-tab,printable`.
+impl FromStr for AsciiCharset {
+    type Err = StringyError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut charset = Self::empty();
+        for item in s.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            let (remove, class) = match item.strip_prefix('-') {
+                Some(class) => (true, class),
+                None => (false, item),
+            };
+            let ranges: &[RangeInclusive<u8>] = match class.to_ascii_lowercase().as_str() {
+                "printable" => &[Self::PRINTABLE],
+                "tab" => &[b'\t'..=b'\t'],
+                "newline" => &[b'\n'..=b'\n', b'\r'..=b'\r'],
+                "latin1" => &[Self::LATIN1],
+                _ => {
+                    return Err(StringyError::ConfigError(format!(
+                        "Unknown ASCII character class: {}",
+                        class
+                    )));
+                }
+            };
+            for range in ranges {
+                charset = if remove {
+                    charset.without_range(range.clone())
+                } else {
+                    charset.with_range(range.clone())
+                };
+            }
+        }
+        if charset == Self::empty() {
+            return Err(StringyError::ConfigError(format!(
+                "ASCII character set {:?} accepts no bytes",
+                s
+            )));
+        }
+        Ok(charset)
+    }
+}
+
+/// Iterator over runs of printable ASCII borrowed from an input buffer.
+///
+/// Created by [`iter_ascii`]. No allocation happens during iteration; each
+/// item is a slice of the original buffer plus its offset.
+#[derive(Debug, Clone)]
+pub struct AsciiStrings<'a> {
+    data: &'a [u8],
+    pos: usize,
+    min_len: usize,
+    max_len: usize,
+    charset: AsciiCharset,
+}
+
+impl<'a> Iterator for AsciiStrings<'a> {
+    type Item = BorrowedString<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.data.len() {
+            let rest = &self.data[self.pos..];
+            let Some(start) = rest.iter().position(|&b| self.charset.contains(b)) else {
+                self.pos = self.data.len();
+                break;
+            };
+            // Over-long runs are cut at `max_len`; the next call resumes at the
+            // cut because the byte there still belongs to the charset.
+            let run_len = rest[start..]
+                .iter()
+                .position(|&b| !self.charset.contains(b))
+                .unwrap_or(rest.len() - start)
+                .min(self.max_len);
+
+            let begin = self.pos + start;
+            self.pos = begin + run_len;
+
+            if run_len >= self.min_len {
+                return Some(BorrowedString {
+                    bytes: &self.data[begin..begin + run_len],
+                    offset: begin as u64,
+                    encoding: Encoding::Ascii,
+                });
+            }
+        }
+        None
+    }
+}
+
+/// Scans `data` for runs of printable ASCII without copying them.
+///
+/// Runs are made of the bytes in `config.ascii_charset`. Runs shorter than
+/// `config.min_length` are skipped and runs longer than `config.max_length`
+/// are split (see [`ExtractionConfig::max_length`]). A `min_length` of zero
+/// is treated as one.
+///
+/// # Examples
+///
+/// ```rust
+/// use stringy::extraction::ExtractionConfig;
+/// use stringy::extraction::ascii::iter_ascii;
+///
+/// let data = b"\x00\x01hello\x00world\xff";
+/// let config = ExtractionConfig::default();
+/// let found: Vec<&str> = iter_ascii(data, &config)
+///     .filter_map(|s| s.as_str())
+///     .collect();
+/// assert_eq!(found, vec!["hello", "world"]);
+/// ```
+pub fn iter_ascii<'a>(data: &'a [u8], config: &ExtractionConfig) -> AsciiStrings<'a> {
+    AsciiStrings {
+        data,
+        pos: 0,
+        min_len: config.min_length.max(1),
+        max_len: config.max_length.unwrap_or(usize::MAX).max(1),
+        charset: config.ascii_charset,
+    }
+}
+
+/// Extracts runs of printable ASCII at least `config.min_length` bytes long.
+///
+/// A run ends at the first byte outside `config.ascii_charset` (by default
+/// anything but printable ASCII and tab), usually a NUL terminator; a run at the end of the
+/// buffer needs no terminator. Runs longer than `config.max_length` are
+/// split. Use [`iter_ascii`] to scan without allocating.
+///
+/// # Examples
+///
+/// ```rust
+/// use stringy::extraction::ExtractionConfig;
+/// use stringy::extraction::ascii::extract_ascii;
+///
+/// let data = b"\x00GetProcAddress\x00ab\x00";
+/// let found = extract_ascii(data, &ExtractionConfig::default());
+/// assert_eq!(found.len(), 1);
+/// assert_eq!(found[0].text, "GetProcAddress");
+/// assert_eq!(found[0].offset, 1);
+/// ```
+pub fn extract_ascii(data: &[u8], config: &ExtractionConfig) -> Vec<FoundString> {
+    iter_ascii(data, config)
+        .map(|s| s.to_found_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extraction::DEFAULT_MAX_LENGTH;
+
+    fn config(min_length: usize, max_length: Option<usize>) -> ExtractionConfig {
+        ExtractionConfig {
+            min_length,
+            max_length,
+            ..ExtractionConfig::default()
+        }
+    }
+
+    fn min_length(min_length: usize) -> ExtractionConfig {
+        config(min_length, Some(DEFAULT_MAX_LENGTH))
+    }
+
+    #[test]
+    fn test_borrowed_runs() {
+        let data = b"ab\x00hello\x00\x00world!\x01xyz";
+        let found: Vec<BorrowedString> = iter_ascii(data, &config(3, None)).collect();
+
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[0].as_str(), Some("hello"));
+        assert_eq!(found[0].offset, 3);
+        assert_eq!(found[1].as_str(), Some("world!"));
+        assert_eq!(found[1].offset, 10);
+        assert_eq!(found[2].as_str(), Some("xyz"));
+        assert_eq!(found[2].offset, 17);
+    }
+
+    #[test]
+    fn test_slices_point_into_input() {
+        let data = b"\xffborrowed\xff".to_vec();
+        let item = iter_ascii(&data, &config(4, None)).next().unwrap();
+        assert_eq!(item.bytes.as_ptr(), data[1..].as_ptr());
+        assert_eq!(item.length(), 8);
+    }
+
+    #[test]
+    fn test_tab_continues_run() {
+        let data = b"key\tvalue\n";
+        let found: Vec<&str> = iter_ascii(data, &config(4, None))
+            .filter_map(|s| s.as_str())
+            .collect();
+        assert_eq!(found, vec!["key\tvalue"]);
+    }
+
+    #[test]
+    fn test_newline_charset() {
+        let data = b"\0first line\nsecond line\0";
+
+        // By default a newline ends a run
+        let found: Vec<&str> = iter_ascii(data, &config(4, None))
+            .filter_map(|s| s.as_str())
+            .collect();
+        assert_eq!(found, vec!["first line", "second line"]);
+
+        let multiline = ExtractionConfig {
+            ascii_charset: AsciiCharset::printable().with(b'\n'),
+            ..config(4, None)
+        };
+        let found: Vec<&str> = iter_ascii(data, &multiline)
+            .filter_map(|s| s.as_str())
+            .collect();
+        assert_eq!(found, vec!["first line\nsecond line"]);
+    }
+
+    #[test]
+    fn test_latin1_charset() {
+        let data = b"\0caf\xe9 cr\xe8me\0";
+        let latin1 = ExtractionConfig {
+            ascii_charset: AsciiCharset::printable().with_range(AsciiCharset::LATIN1),
+            ..config(4, None)
+        };
+        let found = extract_ascii(data, &latin1);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].text, "café crème");
+        assert_eq!(found[0].length, 10);
+        // Not valid UTF-8, so it cannot be borrowed as text
+        assert_eq!(iter_ascii(data, &latin1).next().unwrap().as_str(), None);
+
+        let texts: Vec<String> = extract_ascii(data, &config(3, None))
+            .into_iter()
+            .map(|s| s.text)
+            .collect();
+        assert_eq!(texts, vec!["caf", " cr"]);
+    }
+
+    #[test]
+    fn test_charset_parsing() {
+        assert_eq!(
+            "printable,tab".parse::<AsciiCharset>().unwrap(),
+            AsciiCharset::default()
+        );
+        let no_tab: AsciiCharset = "printable, -tab, latin1".parse().unwrap();
+        assert!(!no_tab.contains(b'\t'));
+        assert!(no_tab.contains(b'A') && no_tab.contains(0xe9));
+        assert!(!no_tab.contains(0x9f));
+
+        assert!("printable,emoji".parse::<AsciiCharset>().is_err());
+        assert!("tab,-tab".parse::<AsciiCharset>().is_err());
+        assert!("".parse::<AsciiCharset>().is_err());
+
+        let all = AsciiCharset::empty().with_range(0..=255);
+        assert!((0..=255).all(|b| all.contains(b)));
+        assert_eq!(all.without_range(0..=255), AsciiCharset::empty());
+    }
+
+    #[test]
+    fn test_empty_and_unprintable_input() {
+        assert_eq!(iter_ascii(&[], &config(4, None)).count(), 0);
+        assert_eq!(iter_ascii(&[0u8, 1, 2, 0xff], &config(1, None)).count(), 0);
+    }
+
+    #[test]
+    fn test_long_run_is_split() {
+        let mut data = vec![0u8; 2];
+        data.extend(std::iter::repeat_n(b'A', 10));
+        data.push(0);
+
+        let found: Vec<BorrowedString> = iter_ascii(&data, &config(2, Some(4))).collect();
+        let pieces: Vec<(u64, &str)> = found
+            .iter()
+            .map(|s| (s.offset, s.as_str().unwrap()))
+            .collect();
+        assert_eq!(pieces, vec![(2, "AAAA"), (6, "AAAA"), (10, "AA")]);
+
+        // A tail shorter than the minimum length is dropped
+        let found: Vec<BorrowedString> = iter_ascii(&data, &config(3, Some(4))).collect();
+        assert_eq!(found.len(), 2);
+    }
+
+    #[test]
+    fn test_default_cap_on_pathological_run() {
+        let data = vec![b'A'; DEFAULT_MAX_LENGTH * 2 + 10];
+        let found: Vec<BorrowedString> = iter_ascii(&data, &ExtractionConfig::default()).collect();
+
+        assert_eq!(found.len(), 3);
+        assert!(found.iter().all(|s| s.bytes.len() <= DEFAULT_MAX_LENGTH));
+        assert_eq!(found[1].offset, DEFAULT_MAX_LENGTH as u64);
+        assert_eq!(found[2].offset, (DEFAULT_MAX_LENGTH * 2) as u64);
+        assert_eq!(found[2].length(), 10);
+    }
+
+    #[test]
+    fn test_extract_ascii_min_length() {
+        let data = b"abc\x00abcd\x00abcde";
+        let texts: Vec<String> = extract_ascii(data, &min_length(4))
+            .into_iter()
+            .map(|s| s.text)
+            .collect();
+        assert_eq!(texts, vec!["abcd", "abcde"]);
+        assert_eq!(extract_ascii(data, &min_length(3)).len(), 3);
+        assert!(extract_ascii(data, &min_length(6)).is_empty());
+    }
+
+    #[test]
+    fn test_extract_ascii_nul_separated() {
+        let found = extract_ascii(b"first\x00second\x00\x00third\x00", &min_length(4));
+        let located: Vec<(u64, u32, &str)> = found
+            .iter()
+            .map(|s| (s.offset, s.length, s.text.as_str()))
+            .collect();
+        assert_eq!(
+            located,
+            vec![(0, 5, "first"), (6, 6, "second"), (14, 5, "third")]
+        );
+        assert!(found.iter().all(|s| s.encoding == Encoding::Ascii));
+    }
+
+    #[test]
+    fn test_extract_ascii_unterminated_tail() {
+        let found = extract_ascii(b"\xff\xfeno terminator", &min_length(4));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].text, "no terminator");
+        assert_eq!(found[0].offset, 2);
+        assert_eq!(found[0].length, 13);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::types::{Encoding, FoundString};
+use std::collections::HashMap;
+
+/// Collapses strings with the same text and encoding into one entry.
+///
+/// The first occurrence in input order is kept, so it keeps its offset,
+/// section and score. Its `occurrences` becomes the sum over all duplicates
+/// and it gains any tags the duplicates had that it lacked. Entries stay in
+/// the order of their first occurrence.
+///
+/// The same text in different encodings is not a duplicate: an ASCII and a
+/// UTF-16LE `"error"` are reported separately.
+///
+/// # Examples
+///
+/// ```rust
+/// use stringy::extraction::dedup::deduplicate;
+/// use stringy::extraction::{ExtractionConfig, extract_all};
+///
+/// let found = extract_all(b"%s\n\0%s:%d\0%s:%d\0", &ExtractionConfig {
+///     min_length: 2,
+///     ..ExtractionConfig::default()
+/// });
+/// let unique = deduplicate(found);
+/// assert_eq!(unique.len(), 2);
+/// assert_eq!(unique[1].text, "%s:%d");
+/// assert_eq!(unique[1].occurrences, 2);
+/// ```
+pub fn deduplicate(strings: Vec<FoundString>) -> Vec<FoundString> {
+    let mut unique: Vec<FoundString> = Vec::with_capacity(strings.len());
+    let mut index: HashMap<(String, Encoding), usize> = HashMap::new();
+
+    for string in strings {
+        let key = (string.text.clone(), string.encoding);
+        match index.get(&key) {
+            Some(&first) => {
+                let kept = &mut unique[first];
+                kept.occurrences = kept.occurrences.saturating_add(string.occurrences);
+                for tag in string.tags {
+                    if !kept.tags.contains(&tag) {
+                        kept.tags.push(tag);
+                    }
+                }
+            }
+            None => {
+                index.insert(key, unique.len());
+                unique.push(string);
+            }
+        }
+    }
+
+    unique
+}
+
+/// Drops the strings whose bytes lie entirely within one of `covering`.
+///
+/// Format parsers decode some structures, such as PE string tables and
+/// version info, more precisely than a byte scan can; scanning the same
+/// bytes again would report each of those strings twice. Overlapping
+/// strings in `covering` cover the union of their bytes.
+pub fn drop_covered<'a>(
+    strings: Vec<FoundString>,
+    covering: impl IntoIterator<Item = &'a FoundString>,
+) -> Vec<FoundString> {
+    let mut spans: Vec<(u64, u64)> = covering
+        .into_iter()
+        .map(|s| (s.offset, s.offset + u64::from(s.length)))
+        .collect();
+    if spans.is_empty() {
+        return strings;
+    }
+    spans.sort_unstable();
+    let mut ranges: Vec<(u64, u64)> = Vec::with_capacity(spans.len());
+    for (start, end) in spans {
+        match ranges.last_mut() {
+            Some(last) if start < last.1 => last.1 = last.1.max(end),
+            _ => ranges.push((start, end)),
+        }
+    }
+
+    strings
+        .into_iter()
+        .filter(|string| {
+            let end = string.offset + u64::from(string.length);
+            // The last range starting at or before the string is the only
+            // one that can contain it
+            let index = ranges.partition_point(|&(start, _)| start <= string.offset);
+            index
+                .checked_sub(1)
+                .is_none_or(|index| ranges[index].1 < end)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{StringSource, Tag};
+
+    fn found(text: &str, offset: u64, encoding: Encoding, tags: Vec<Tag>) -> FoundString {
+        FoundString::builder()
+            .text(text)
+            .encoding(encoding)
+            .offset(offset)
+            .length(text.len() as u32)
+            .source(StringSource::SectionData)
+            .tags(tags)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_identical_strings_collapse() {
+        let strings = vec![
+            found("Error: %s", 0x10, Encoding::Ascii, vec![Tag::FormatString]),
+            found("other", 0x20, Encoding::Ascii, Vec::new()),
+            found("Error: %s", 0x30, Encoding::Ascii, Vec::new()),
+            found("Error: %s", 0x40, Encoding::Ascii, vec![Tag::Resource]),
+        ];
+
+        let unique = deduplicate(strings);
+        assert_eq!(unique.len(), 2);
+
+        let error = &unique[0];
+        assert_eq!(error.text, "Error: %s");
+        assert_eq!(error.offset, 0x10);
+        assert_eq!(error.occurrences, 3);
+        assert_eq!(error.tags, vec![Tag::FormatString, Tag::Resource]);
+        assert_eq!(unique[1].occurrences, 1);
+    }
+
+    #[test]
+    fn test_drop_covered() {
+        let strings = vec![
+            found("ProductName", 0x10, Encoding::Utf16Le, Vec::new()),
+            found("Stringy", 0x30, Encoding::Utf16Le, Vec::new()),
+            found("ringy", 0x31, Encoding::Ascii, Vec::new()),
+            found("trailing", 0x35, Encoding::Ascii, Vec::new()),
+        ];
+        let covering = vec![found(
+            "Stringy",
+            0x30,
+            Encoding::Utf16Le,
+            vec![Tag::Resource],
+        )];
+
+        let kept = drop_covered(strings, &covering);
+        let texts: Vec<&str> = kept.iter().map(|s| s.text.as_str()).collect();
+        // "trailing" starts inside the covered range but runs past it
+        assert_eq!(texts, vec!["ProductName", "trailing"]);
+    }
+
+    #[test]
+    fn test_drop_covered_by_overlapping_strings() {
+        let strings = vec![
+            found("inner", 0x12, Encoding::Ascii, Vec::new()),
+            found("spanning", 0x14, Encoding::Ascii, Vec::new()),
+        ];
+        // The second covering string lies within the first and must not hide it
+        let covering = vec![
+            found("outer string", 0x10, Encoding::Ascii, Vec::new()),
+            found("r st", 0x14, Encoding::Ascii, Vec::new()),
+        ];
+
+        let kept = drop_covered(strings, &covering);
+        assert!(kept.is_empty());
+    }
+
+    #[test]
+    fn test_encodings_are_kept_apart() {
+        let strings = vec![
+            found("error", 0, Encoding::Ascii, Vec::new()),
+            found("error", 8, Encoding::Utf16Le, Vec::new()),
+        ];
+        let unique = deduplicate(strings);
+        assert_eq!(unique.len(), 2);
+        assert!(unique.iter().all(|s| s.occurrences == 1));
+    }
+}
@@ -1 +1,1088 @@
 // String extraction logic
+
+use crate::classification::entropy::EntropyFilter;
+use crate::classification::ranking::{ScoringConfig, named_section_weight, section_score};
+use crate::container::normalize::{ScanRange, normalize_sections};
+use crate::types::{
+    ContainerInfo, Encoding, FoundString, Result, SectionInfo, SectionType, StringSource,
+    StringyError,
+};
+use ascii::AsciiCharset;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+pub mod ascii;
+pub mod dedup;
+pub mod symbols;
+pub mod top_n;
+pub mod utf16;
+pub mod utf8;
+pub mod xref;
+
+/// Default minimum string length in characters.
+pub const DEFAULT_MIN_LENGTH: usize = 4;
+
+/// Default cap on the length of a single string in characters.
+pub const DEFAULT_MAX_LENGTH: usize = 4096;
+
+/// Every encoding the extractors support, in [`Encoding`] order.
+pub const ALL_ENCODINGS: [Encoding; 4] = [
+    Encoding::Ascii,
+    Encoding::Utf8,
+    Encoding::Utf16Le,
+    Encoding::Utf16Be,
+];
+
+/// Options controlling string extraction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractionConfig {
+    /// Minimum string length in characters
+    pub min_length: usize,
+    /// Maximum string length in characters, or `None` for no limit.
+    ///
+    /// Longer runs are split: the first `max_length` characters are emitted
+    /// and scanning continues right after them, so a pathological run (e.g.
+    /// megabytes of `0x41`) cannot produce one giant string. A trailing piece
+    /// shorter than `min_length` is dropped.
+    pub max_length: Option<usize>,
+    /// Encodings scanned by [`extract_all`] and [`extract_from_container`]
+    pub encodings: Vec<Encoding>,
+    /// Bytes that continue an ASCII run, by default printable ASCII and tab;
+    /// see [`ascii::AsciiCharset`]. UTF-8 and UTF-16 scanning is unaffected
+    pub ascii_charset: AsciiCharset,
+    /// Section types scanned by [`extract_from_container`], or `None` for
+    /// all sections
+    pub include_sections: Option<Vec<SectionType>>,
+    /// Names of the sections scanned by [`extract_from_container`], or
+    /// `None` for all sections; combines with `include_sections`
+    pub section_names: Option<Vec<String>>,
+    /// File offsets scanned by [`extract_from_container`], or `None` for the
+    /// whole file.
+    ///
+    /// Each section is scanned where it overlaps the range, so a string
+    /// crossing either end is cut there.
+    pub byte_range: Option<Range<u64>>,
+    /// Collapse repeated strings in [`extract_from_container`] results into
+    /// one entry with an occurrence count; see [`dedup::deduplicate`]
+    pub deduplicate: bool,
+    /// Drop or down-score high-entropy garbage in [`extract_from_container`]
+    /// results, or `None` to keep every string as scanned
+    pub entropy_filter: Option<EntropyFilter>,
+    /// Keep only this many of the highest-scoring strings from
+    /// [`extract_from_container`], or `None` to keep them all.
+    ///
+    /// Strings are ranked as they are extracted (see [`top_n::TopN`]), so
+    /// memory is bounded by the cap rather than by the number of strings in
+    /// the binary; with `deduplicate` set, every string is held until
+    /// duplicates are merged.
+    pub top_n: Option<usize>,
+    /// Fill in [`FoundString::demangled`] for mangled import and export names
+    /// and for scanned strings that are mangled names (C++, Rust or Swift;
+    /// see [`crate::classification::demangle`])
+    pub demangle: bool,
+}
+
+impl Default for ExtractionConfig {
+    fn default() -> Self {
+        Self {
+            min_length: DEFAULT_MIN_LENGTH,
+            max_length: Some(DEFAULT_MAX_LENGTH),
+            encodings: ALL_ENCODINGS.to_vec(),
+            ascii_charset: AsciiCharset::default(),
+            include_sections: None,
+            section_names: None,
+            byte_range: None,
+            deduplicate: false,
+            entropy_filter: None,
+            top_n: None,
+            demangle: false,
+        }
+    }
+}
+
+impl ExtractionConfig {
+    /// Checks that the length limits are consistent and that at least one
+    /// encoding is selected.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StringyError::ConfigError` if `max_length` is zero or smaller
+    /// than `min_length`, if `encodings` is empty, if `top_n` is zero, or if
+    /// `byte_range` is empty.
+    pub fn validate(&self) -> Result<()> {
+        if let Some(max) = self.max_length {
+            if max == 0 || max < self.min_length {
+                return Err(StringyError::ConfigError(format!(
+                    "max_length ({}) must be at least min_length ({}) and non-zero",
+                    max, self.min_length
+                )));
+            }
+        }
+        if self.encodings.is_empty() {
+            return Err(StringyError::ConfigError(
+                "At least one encoding must be selected".to_string(),
+            ));
+        }
+        if self.top_n == Some(0) {
+            return Err(StringyError::ConfigError(
+                "top_n must be non-zero".to_string(),
+            ));
+        }
+        if let Some(range) = &self.byte_range {
+            if range.is_empty() {
+                return Err(StringyError::ConfigError(format!(
+                    "byte_range start ({:#x}) must be below its end ({:#x})",
+                    range.start, range.end
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if strings in `encoding` should be extracted.
+    pub fn includes_encoding(&self, encoding: Encoding) -> bool {
+        self.encodings.contains(&encoding)
+    }
+
+    /// Returns `true` if sections of `section_type` should be scanned.
+    pub fn includes_section(&self, section_type: SectionType) -> bool {
+        self.include_sections
+            .as_ref()
+            .is_none_or(|types| types.contains(&section_type))
+    }
+
+    /// Returns `true` if a string in section `section` at file offset
+    /// `offset` lies within the regions selected by `section_names` and
+    /// `byte_range`.
+    ///
+    /// This applies the region restrictions to strings that were not found by
+    /// scanning sections, such as those recovered from format metadata.
+    pub fn includes_location(&self, section: Option<&str>, offset: u64) -> bool {
+        let named = self.section_names.as_ref().is_none_or(|names| {
+            section.is_some_and(|section| names.iter().any(|name| name == section))
+        });
+        named
+            && self
+                .byte_range
+                .as_ref()
+                .is_none_or(|range| range.contains(&offset))
+    }
+
+    /// Returns `true` if `section` is selected by `include_sections` and
+    /// `section_names`.
+    fn selects_section(&self, section: &SectionInfo) -> bool {
+        self.includes_section(section.section_type)
+            && self
+                .section_names
+                .as_ref()
+                .is_none_or(|names| names.contains(&section.name))
+    }
+
+    /// Returns the bytes of `range` to scan and their file offset, or `None`
+    /// if the range has no bytes in `byte_range`.
+    fn scan_window<'a>(&self, data: &'a [u8], range: &ScanRange) -> Option<(&'a [u8], u64)> {
+        let end = range.offset.checked_add(range.size)?;
+        let (start, stop) = match &self.byte_range {
+            Some(selected) => (range.offset.max(selected.start), end.min(selected.end)),
+            None => (range.offset, end),
+        };
+        if start >= stop {
+            return None;
+        }
+        let bytes = data.get(usize::try_from(start).ok()?..usize::try_from(stop).ok()?)?;
+        Some((bytes, start))
+    }
+}
+
+/// Progress of [`extract_from_container_with_progress`] through the sections
+/// of a container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtractionProgress<'a> {
+    /// Bytes of the sections scanned so far
+    pub bytes_processed: u64,
+    /// Bytes of all sections that will be scanned
+    pub total_bytes: u64,
+    /// Section about to be scanned, or `None` once all sections are done
+    pub section: Option<&'a str>,
+}
+
+/// A string located in an input buffer, borrowed rather than copied.
+///
+/// This is the zero-copy counterpart of [`FoundString`] for throughput-sensitive
+/// callers: it holds a slice of the scanned buffer plus its location, and only
+/// allocates when converted with [`BorrowedString::to_found_string`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BorrowedString<'a> {
+    /// Raw bytes of the string within the input buffer
+    pub bytes: &'a [u8],
+    /// Offset of the first byte within the scanned buffer
+    pub offset: u64,
+    /// Encoding of `bytes`
+    pub encoding: Encoding,
+}
+
+impl<'a> BorrowedString<'a> {
+    /// Returns the string as `&str` without copying.
+    ///
+    /// Only single-byte-compatible encodings (ASCII and UTF-8) can be borrowed
+    /// as text; UTF-16 strings, and ASCII runs containing bytes above 0x7F,
+    /// return `None` and must be decoded.
+    pub fn as_str(&self) -> Option<&'a str> {
+        match self.encoding {
+            Encoding::Ascii if !self.bytes.is_ascii() => None,
+            Encoding::Ascii | Encoding::Utf8 => std::str::from_utf8(self.bytes).ok(),
+            Encoding::Utf16Le | Encoding::Utf16Be => None,
+        }
+    }
+
+    /// Length of the string in bytes.
+    pub fn length(&self) -> u32 {
+        self.bytes.len() as u32
+    }
+
+    /// Decodes the string into an owned `String`.
+    ///
+    /// ASCII strings are decoded as Latin-1, which only matters when
+    /// [`ExtractionConfig::ascii_charset`] admits bytes above 0x7F. Invalid
+    /// UTF-8 and UTF-16 sequences are replaced with U+FFFD.
+    pub fn decode(&self) -> String {
+        match self.encoding {
+            Encoding::Ascii => self.bytes.iter().map(|&b| char::from(b)).collect(),
+            Encoding::Utf8 => String::from_utf8_lossy(self.bytes).into_owned(),
+            Encoding::Utf16Le => decode_utf16_units(self.bytes, u16::from_le_bytes),
+            Encoding::Utf16Be => decode_utf16_units(self.bytes, u16::from_be_bytes),
+        }
+    }
+
+    /// Allocates an owned [`FoundString`] for this string.
+    ///
+    /// The result has no section, RVA, tags, or score; callers that know the
+    /// surrounding context fill those in.
+    pub fn to_found_string(&self) -> FoundString {
+        FoundString::new(
+            self.decode(),
+            self.encoding,
+            self.offset,
+            self.length(),
+            StringSource::SectionData,
+        )
+    }
+}
+
+/// Extracts strings in every encoding selected by `config`, in offset order.
+///
+/// The same bytes can decode under several encodings: `\0A\0B\0C\0D\0` is
+/// both big-endian `ABCD` at offset 0 and little-endian `ABCD` at offset 1.
+/// Where candidates overlap, only the one with the most characters is kept;
+/// on a tie, the earlier encoding in [`Encoding`] order wins. A UTF-8 run is
+/// therefore only reported when it extends an ASCII run with multibyte
+/// characters.
+///
+/// # Examples
+///
+/// ```rust
+/// use stringy::Encoding;
+/// use stringy::extraction::{ExtractionConfig, extract_all};
+///
+/// let data = b"narrow\0\0w\0i\0d\0e\0\0\0";
+/// let found = extract_all(data, &ExtractionConfig::default());
+/// assert_eq!(found.len(), 2);
+/// assert_eq!((found[0].text.as_str(), found[0].encoding), ("narrow", Encoding::Ascii));
+/// assert_eq!((found[1].text.as_str(), found[1].encoding), ("wide", Encoding::Utf16Le));
+///
+/// let ascii_only = ExtractionConfig {
+///     encodings: vec![Encoding::Ascii],
+///     ..ExtractionConfig::default()
+/// };
+/// assert_eq!(extract_all(data, &ascii_only).len(), 1);
+/// ```
+pub fn extract_all(data: &[u8], config: &ExtractionConfig) -> Vec<FoundString> {
+    let mut candidates: Vec<FoundString> = Vec::new();
+    for &encoding in &ALL_ENCODINGS {
+        if !config.includes_encoding(encoding) {
+            continue;
+        }
+        let found = match encoding {
+            Encoding::Ascii => ascii::extract_ascii(data, config),
+            Encoding::Utf8 => utf8::extract_utf8(data, config),
+            Encoding::Utf16Le => utf16::extract_utf16le(data, config),
+            Encoding::Utf16Be => utf16::extract_utf16be(data, config),
+        };
+        candidates.extend(found);
+    }
+
+    // Longest decode first. Ties go to the more specific encoding (ASCII
+    // before UTF-8, UTF-16LE before UTF-16BE), then to the earlier offset.
+    candidates.sort_by_cached_key(|s| (Reverse(s.text.chars().count()), s.encoding, s.offset));
+
+    // Accepted byte ranges, start -> end. They never overlap, so only the
+    // range starting last before a candidate's end can collide with it.
+    let mut claimed: BTreeMap<u64, u64> = BTreeMap::new();
+    let mut strings = Vec::new();
+    for string in candidates {
+        let end = string.offset + u64::from(string.length);
+        let overlaps = claimed
+            .range(..end)
+            .next_back()
+            .is_some_and(|(_, &claimed_end)| claimed_end > string.offset);
+        if !overlaps {
+            claimed.insert(string.offset, end);
+            strings.push(string);
+        }
+    }
+
+    strings.sort_by_key(|s| (s.offset, s.encoding));
+    strings
+}
+
+/// Lazy iterator over the strings of a parsed container, section by
+/// section; see [`extract_iter`].
+///
+/// Only the strings of the section being read are held in memory: each
+/// section is scanned as by [`extract_all`] when the previous one runs out.
+#[derive(Debug, Clone)]
+pub struct ContainerStrings<'a> {
+    config: &'a ExtractionConfig,
+    scoring: ScoringConfig,
+    /// Sections still to scan, with their selected bytes and file offset
+    sections: std::vec::IntoIter<(&'a SectionInfo, &'a [u8], u64)>,
+    /// Strings of the last section scanned not yet yielded
+    current: std::vec::IntoIter<FoundString>,
+    bytes_processed: u64,
+    total_bytes: u64,
+}
+
+impl<'a> ContainerStrings<'a> {
+    fn new(data: &'a [u8], info: &'a ContainerInfo, config: &'a ExtractionConfig) -> Self {
+        if let Some(names) = &config.section_names {
+            for name in names {
+                if !info.sections.iter().any(|section| &section.name == name) {
+                    log::warn!("No section named {:?}", name);
+                }
+            }
+        }
+        let selected: Vec<&SectionInfo> = info
+            .sections
+            .iter()
+            .filter(|section| config.selects_section(section))
+            .collect();
+        // Overlapping sections would report the shared bytes once per section
+        let layout = normalize_sections(&selected);
+        for overlap in &layout.overlaps {
+            log::warn!("{}", overlap);
+        }
+        let mut sections: Vec<(&SectionInfo, &[u8], u64)> = layout
+            .ranges
+            .iter()
+            .filter_map(|range| {
+                let (bytes, offset) = config.scan_window(data, range)?;
+                Some((selected[range.section], bytes, offset))
+            })
+            .collect();
+        sections.sort_by_key(|(section, _, _)| {
+            Reverse(named_section_weight(
+                Some(&section.name),
+                section.section_type,
+            ))
+        });
+        let total_bytes = sections
+            .iter()
+            .map(|(_, bytes, _)| bytes.len() as u64)
+            .sum();
+
+        Self {
+            config,
+            scoring: ScoringConfig::default(),
+            sections: sections.into_iter(),
+            current: Vec::new().into_iter(),
+            bytes_processed: 0,
+            total_bytes,
+        }
+    }
+
+    /// Scans the next section, reporting it to `progress` first, and returns
+    /// its strings; `None` once every section has been scanned.
+    fn next_section(
+        &mut self,
+        progress: Option<&dyn Fn(ExtractionProgress)>,
+    ) -> Option<Vec<FoundString>> {
+        let (section, bytes, start) = self.sections.next()?;
+        if let Some(progress) = progress {
+            progress(ExtractionProgress {
+                bytes_processed: self.bytes_processed,
+                total_bytes: self.total_bytes,
+                section: Some(&section.name),
+            });
+        }
+        self.bytes_processed += bytes.len() as u64;
+
+        let found: Vec<FoundString> = extract_all(bytes, self.config)
+            .into_iter()
+            .map(|mut string| {
+                string.offset += start;
+                let relative = string.offset - section.offset;
+                string.rva = section.rva.map(|rva| rva + relative);
+                string.section = Some(section.name.clone());
+                string.section_type = Some(section.section_type);
+                string.score = section_score(&string, Some(section.section_type), &self.scoring);
+                string
+            })
+            .collect();
+        Some(match &self.config.entropy_filter {
+            Some(filter) => filter.apply(found),
+            None => found,
+        })
+    }
+}
+
+impl Iterator for ContainerStrings<'_> {
+    type Item = FoundString;
+
+    fn next(&mut self) -> Option<FoundString> {
+        loop {
+            if let Some(string) = self.current.next() {
+                return Some(string);
+            }
+            self.current = self.next_section(None)?.into_iter();
+        }
+    }
+}
+
+/// Lazily extracts strings from each section of a parsed container.
+///
+/// Strings are yielded as [`extract_from_container`] returns them, section
+/// by section, without collecting them all first. Deduplication and
+/// [`ExtractionConfig::top_n`] need every string before they can emit the
+/// first, so the iterator ignores `deduplicate` and `top_n`; with both
+/// unset, collecting it gives exactly the result of
+/// [`extract_from_container`].
+///
+/// # Examples
+///
+/// ```rust
+/// use stringy::container::{ContainerParser, raw::RawParser};
+/// use stringy::extraction::{ExtractionConfig, extract_iter};
+///
+/// let data = b"first string\0\xff\0second string\0";
+/// let info = RawParser::new().parse(data)?;
+/// let config = ExtractionConfig::default();
+///
+/// let mut strings = extract_iter(data, &info, &config);
+/// assert_eq!(strings.next().unwrap().text, "first string");
+/// assert_eq!(strings.next().unwrap().text, "second string");
+/// assert!(strings.next().is_none());
+/// # Ok::<(), stringy::StringyError>(())
+/// ```
+pub fn extract_iter<'a>(
+    data: &'a [u8],
+    info: &'a ContainerInfo,
+    config: &'a ExtractionConfig,
+) -> ContainerStrings<'a> {
+    ContainerStrings::new(data, info, config)
+}
+
+/// Extracts strings from each section of a parsed container.
+///
+/// Every section with file data whose type is selected by
+/// [`ExtractionConfig::include_sections`] and whose name is selected by
+/// [`ExtractionConfig::section_names`] is scanned as by [`extract_all`],
+/// limited to [`ExtractionConfig::byte_range`] if set.
+/// Each string records its section name and type, its file offset, and its
+/// RVA when the section has one. Its initial `score` is the section's
+/// [`section_score`], so a string in `.rodata` starts well ahead of the same
+/// bytes in `.text`; [`crate::classification::score`] computes the final one.
+///
+/// Sections are visited in descending [`named_section_weight`] order (file order
+/// among equal weights), so strings from high-value sections come first.
+/// With [`ExtractionConfig::deduplicate`] set, a string repeated across or
+/// within sections is reported once, at its first occurrence in that order.
+/// With [`ExtractionConfig::top_n`] set, only the highest-scoring strings are
+/// returned, highest first. [`extract_iter`] yields the same strings lazily.
+pub fn extract_from_container(
+    data: &[u8],
+    info: &ContainerInfo,
+    config: &ExtractionConfig,
+) -> Vec<FoundString> {
+    extract_from_container_scored(data, info, config, |_| {})
+}
+
+/// Like [`extract_from_container`], but calls `rescore` on each string once
+/// its section fields and initial score are set.
+///
+/// This lets [`ExtractionConfig::top_n`] rank strings by their final score,
+/// e.g. after classification, while they are extracted.
+pub fn extract_from_container_scored<F>(
+    data: &[u8],
+    info: &ContainerInfo,
+    config: &ExtractionConfig,
+    rescore: F,
+) -> Vec<FoundString>
+where
+    F: FnMut(&mut FoundString),
+{
+    extract_from_container_with_progress(data, info, config, rescore, None)
+}
+
+/// Like [`extract_from_container_scored`], but reports progress to
+/// `progress`, if given.
+///
+/// `progress` is called before each section is scanned, with the bytes of
+/// the sections already scanned and the name of the next one, and once more
+/// when all are done, with `bytes_processed` equal to `total_bytes`.
+/// `bytes_processed` never decreases, so a GUI can drive a progress bar from
+/// it directly.
+pub fn extract_from_container_with_progress<F>(
+    data: &[u8],
+    info: &ContainerInfo,
+    config: &ExtractionConfig,
+    mut rescore: F,
+    progress: Option<&dyn Fn(ExtractionProgress)>,
+) -> Vec<FoundString>
+where
+    F: FnMut(&mut FoundString),
+{
+    let mut sections = ContainerStrings::new(data, info, config);
+
+    // Without deduplication, strings can be ranked section by section
+    let mut top = config
+        .top_n
+        .filter(|_| !config.deduplicate)
+        .map(top_n::TopN::new);
+    let mut strings = Vec::new();
+    while let Some(mut found) = sections.next_section(progress) {
+        found.iter_mut().for_each(&mut rescore);
+        match &mut top {
+            Some(top) => top.extend(found),
+            None => strings.extend(found),
+        }
+    }
+    if let Some(progress) = progress {
+        progress(ExtractionProgress {
+            bytes_processed: sections.bytes_processed,
+            total_bytes: sections.total_bytes,
+            section: None,
+        });
+    }
+
+    if let Some(top) = top {
+        return top.into_sorted_vec();
+    }
+    if config.deduplicate {
+        strings = dedup::deduplicate(strings);
+    }
+    match config.top_n {
+        Some(n) => {
+            let mut top = top_n::TopN::new(n);
+            top.extend(strings);
+            top.into_sorted_vec()
+        }
+        None => strings,
+    }
+}
+
+/// Decodes UTF-16 code units read with `read_unit`.
+fn decode_utf16_units(bytes: &[u8], read_unit: fn([u8; 2]) -> u16) -> String {
+    let units = bytes
+        .chunks_exact(2)
+        .map(|pair| read_unit([pair[0], pair[1]]));
+    char::decode_utf16(units)
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{BinaryFormat, SectionType};
+
+    /// Unmapped section `name` of `size` bytes at file `offset`.
+    fn section(name: &str, offset: u64, size: u64, section_type: SectionType) -> SectionInfo {
+        SectionInfo {
+            name: name.to_string(),
+            offset,
+            size,
+            rva: None,
+            section_type,
+            is_executable: section_type == SectionType::Code,
+            is_writable: section_type == SectionType::WritableData,
+            entropy: None,
+            truncated: false,
+        }
+    }
+
+    /// `section` loaded at `rva`.
+    fn mapped(section: SectionInfo, rva: u64) -> SectionInfo {
+        SectionInfo {
+            rva: Some(rva),
+            ..section
+        }
+    }
+
+    /// A 64-bit container of `format` holding only `sections`.
+    fn container(format: BinaryFormat, sections: Vec<SectionInfo>) -> ContainerInfo {
+        ContainerInfo {
+            format,
+            is_64bit: true,
+            architecture: None,
+            sections,
+            imports: Vec::new(),
+            exports: Vec::new(),
+            elf: None,
+            rich_header: None,
+            tls_callbacks: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(ExtractionConfig::default().validate().is_ok());
+        assert!(
+            ExtractionConfig {
+                min_length: 4,
+                max_length: None,
+                ..ExtractionConfig::default()
+            }
+            .validate()
+            .is_ok()
+        );
+        assert!(
+            ExtractionConfig {
+                min_length: 8,
+                max_length: Some(4),
+                ..ExtractionConfig::default()
+            }
+            .validate()
+            .is_err()
+        );
+        assert!(
+            ExtractionConfig {
+                top_n: Some(0),
+                ..ExtractionConfig::default()
+            }
+            .validate()
+            .is_err()
+        );
+        assert!(
+            ExtractionConfig {
+                min_length: 0,
+                max_length: Some(0),
+                ..ExtractionConfig::default()
+            }
+            .validate()
+            .is_err()
+        );
+        assert!(
+            ExtractionConfig {
+                encodings: Vec::new(),
+                ..ExtractionConfig::default()
+            }
+            .validate()
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_extract_all_prefers_longest_overlap() {
+        // Big-endian "ABCDE" at 0 overlaps little-endian "ABCD" at 1
+        let data = b"\0A\0B\0C\0D\0E";
+        let found = extract_all(data, &ExtractionConfig::default());
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].text, "ABCDE");
+        assert_eq!(found[0].encoding, Encoding::Utf16Be);
+        assert_eq!(found[0].offset, 0);
+    }
+
+    #[test]
+    fn test_extract_all_keeps_disjoint_strings() {
+        let mut data = b"ascii text\0\0".to_vec();
+        data.extend("wide text".encode_utf16().flat_map(u16::to_le_bytes));
+        data.extend(b"\0\0\0tail");
+
+        let found: Vec<(u64, String, Encoding)> = extract_all(&data, &ExtractionConfig::default())
+            .into_iter()
+            .map(|s| (s.offset, s.text, s.encoding))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (0, "ascii text".to_string(), Encoding::Ascii),
+                (12, "wide text".to_string(), Encoding::Utf16Le),
+                (33, "tail".to_string(), Encoding::Ascii),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_all_utf8_supersedes_ascii() {
+        let data = "\0plain\0café menu\0".as_bytes();
+        let found: Vec<(String, Encoding)> = extract_all(data, &ExtractionConfig::default())
+            .into_iter()
+            .map(|s| (s.text, s.encoding))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("plain".to_string(), Encoding::Ascii),
+                ("café menu".to_string(), Encoding::Utf8),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rodata_outranks_text() {
+        let mut data = vec![0u8; 0x40];
+        data[0x08..0x0d].copy_from_slice(b"usage");
+        data[0x28..0x2d].copy_from_slice(b"usage");
+        let info = container(
+            BinaryFormat::Elf,
+            vec![
+                mapped(section(".text", 0, 0x20, SectionType::Code), 0x1000),
+                mapped(
+                    section(".rodata", 0x20, 0x20, SectionType::StringData),
+                    0x1020,
+                ),
+            ],
+        );
+
+        let found = extract_from_container(&data, &info, &ExtractionConfig::default());
+        assert_eq!(found.len(), 2);
+
+        let rodata = &found[0];
+        assert_eq!(rodata.section.as_deref(), Some(".rodata"));
+        assert_eq!(rodata.offset, 0x28);
+        assert_eq!(rodata.rva, Some(0x1028));
+
+        let text = &found[1];
+        assert_eq!(text.section.as_deref(), Some(".text"));
+        assert_eq!(text.offset, 0x08);
+        assert!(rodata.score > text.score);
+
+        let string_data_only = ExtractionConfig {
+            include_sections: Some(vec![SectionType::StringData]),
+            ..ExtractionConfig::default()
+        };
+        let found = extract_from_container(&data, &info, &string_data_only);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].section.as_deref(), Some(".rodata"));
+
+        let deduplicated = ExtractionConfig {
+            deduplicate: true,
+            ..ExtractionConfig::default()
+        };
+        let found = extract_from_container(&data, &info, &deduplicated);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].section.as_deref(), Some(".rodata"));
+        assert_eq!(found[0].occurrences, 2);
+    }
+
+    #[test]
+    fn test_rva_follows_section_address() {
+        let mut data = vec![0u8; 0x480];
+        data[0x410..0x41c].copy_from_slice(b"mapped entry");
+        data[0x450..0x462].copy_from_slice(b"unmapped debug str");
+        let info = container(
+            BinaryFormat::Pe,
+            vec![
+                mapped(
+                    section(".rdata", 0x400, 0x40, SectionType::StringData),
+                    0x1400,
+                ),
+                section(".debug_str", 0x440, 0x40, SectionType::StringData),
+            ],
+        );
+
+        let found = extract_from_container(&data, &info, &ExtractionConfig::default());
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].text, "mapped entry");
+        assert_eq!(found[0].offset, 0x410);
+        assert_eq!(found[0].rva, Some(0x1410));
+        assert_eq!(found[1].text, "unmapped debug str");
+        assert_eq!(found[1].rva, None);
+    }
+
+    #[test]
+    fn test_overlapping_sections_scanned_once() {
+        let mut data = vec![0u8; 0x300];
+        data[0x120..0x12e].copy_from_slice(b"shared string!");
+        data[0x1a0..0x1ae].copy_from_slice(b"trailing bytes");
+        data[0x220..0x22e].copy_from_slice(b"past the end!!");
+        let info = container(
+            BinaryFormat::Pe,
+            vec![
+                section(".rdata", 0x100, 0x100, SectionType::StringData),
+                section(".fake", 0x100, 0x100, SectionType::StringData),
+            ],
+        );
+
+        // The iterator does not deduplicate, so each string is found once
+        // only because the shared bytes are scanned once
+        let found: Vec<FoundString> =
+            extract_iter(&data, &info, &ExtractionConfig::default()).collect();
+        let texts: Vec<&str> = found.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, ["shared string!", "trailing bytes"]);
+        assert!(found.iter().all(|s| s.section.as_deref() == Some(".rdata")));
+
+        // A section only partly covered keeps the bytes past the overlap
+        let info = ContainerInfo {
+            sections: vec![
+                section(".rdata", 0x100, 0x100, SectionType::StringData),
+                section(".fake", 0x180, 0x100, SectionType::StringData),
+            ],
+            ..info
+        };
+        let found: Vec<FoundString> =
+            extract_iter(&data, &info, &ExtractionConfig::default()).collect();
+        let located: Vec<(&str, Option<&str>)> = found
+            .iter()
+            .map(|s| (s.text.as_str(), s.section.as_deref()))
+            .collect();
+        assert_eq!(
+            located,
+            [
+                ("shared string!", Some(".rdata")),
+                ("trailing bytes", Some(".rdata")),
+                ("past the end!!", Some(".fake")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_top_n() {
+        let mut data = Vec::new();
+        for i in 0..10 {
+            data.extend_from_slice(format!("string-{}\0", i).as_bytes());
+        }
+        let info = container(
+            BinaryFormat::Elf,
+            vec![
+                section(".rodata", 0, 45, SectionType::ReadOnlyData),
+                section(".data.rel.ro", 45, 45, SectionType::ReadOnlyData),
+            ],
+        );
+        let config = ExtractionConfig {
+            top_n: Some(5),
+            ..ExtractionConfig::default()
+        };
+
+        // Distinct scores, out of file order
+        let found = extract_from_container_scored(&data, &info, &config, |string| {
+            let digit = string.text.strip_prefix("string-").unwrap();
+            string.score = (digit.parse::<i32>().unwrap() * 7) % 10;
+        });
+        let scores: Vec<i32> = found.iter().map(|s| s.score).collect();
+        assert_eq!(scores, vec![9, 8, 7, 6, 5]);
+
+        let texts: Vec<&str> = found.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec!["string-7", "string-4", "string-1", "string-8", "string-5"]
+        );
+    }
+
+    #[test]
+    fn test_section_names_and_byte_range() {
+        let data = b"in rodata one\0in rodata two\0in data section\0".to_vec();
+        let info = container(
+            BinaryFormat::Elf,
+            vec![
+                mapped(section(".rodata", 0, 28, SectionType::ReadOnlyData), 0x1000),
+                section(".data", 28, 16, SectionType::ReadOnlyData),
+            ],
+        );
+        let texts = |config: &ExtractionConfig| -> Vec<(String, Option<String>, u64)> {
+            let mut found = extract_from_container(&data, &info, config);
+            found.sort_by_key(|s| s.offset);
+            found
+                .into_iter()
+                .map(|s| (s.text, s.section, s.offset))
+                .collect()
+        };
+
+        let data_only = ExtractionConfig {
+            section_names: Some(vec![".data".to_string()]),
+            ..ExtractionConfig::default()
+        };
+        assert_eq!(
+            texts(&data_only),
+            vec![("in data section".to_string(), Some(".data".to_string()), 28)]
+        );
+
+        // The window covers the second .rodata string and the start of .data
+        let window = ExtractionConfig {
+            byte_range: Some(14..35),
+            ..ExtractionConfig::default()
+        };
+        assert_eq!(
+            texts(&window),
+            vec![
+                ("in rodata two".to_string(), Some(".rodata".to_string()), 14),
+                ("in data".to_string(), Some(".data".to_string()), 28),
+            ]
+        );
+        let found = extract_from_container(&data, &info, &window);
+        let two = found.iter().find(|s| s.offset == 14).unwrap();
+        assert_eq!(two.rva, Some(0x100e));
+
+        assert!(window.includes_location(Some(".data"), 20));
+        assert!(!window.includes_location(None, 40));
+        assert!(!data_only.includes_location(Some(".rodata"), 0));
+        assert!(!data_only.includes_location(None, 30));
+
+        let empty = ExtractionConfig {
+            byte_range: Some(10..10),
+            ..ExtractionConfig::default()
+        };
+        assert!(empty.validate().is_err());
+    }
+
+    #[test]
+    fn test_progress_reports() {
+        use std::cell::RefCell;
+
+        let data = b"first string\0second string\0code bytes here\0".to_vec();
+        let info = container(
+            BinaryFormat::Elf,
+            vec![
+                section(".data", 0, 13, SectionType::WritableData),
+                section(".rodata", 13, 14, SectionType::StringData),
+                section(".text", 27, 16, SectionType::Code),
+            ],
+        );
+        let config = ExtractionConfig {
+            include_sections: Some(vec![SectionType::StringData, SectionType::WritableData]),
+            ..ExtractionConfig::default()
+        };
+
+        let reports = RefCell::new(Vec::new());
+        let record = |progress: ExtractionProgress| {
+            reports.borrow_mut().push((
+                progress.bytes_processed,
+                progress.total_bytes,
+                progress.section.map(str::to_string),
+            ));
+        };
+        let found =
+            extract_from_container_with_progress(&data, &info, &config, |_| {}, Some(&record));
+        let texts: Vec<&str> = found.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["second string", "first string"]);
+
+        // Highest-weight section first; excluded sections are not counted
+        let reports = reports.into_inner();
+        assert_eq!(
+            reports,
+            vec![
+                (0, 27, Some(".rodata".to_string())),
+                (14, 27, Some(".data".to_string())),
+                (27, 27, None),
+            ]
+        );
+        assert!(reports.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+    }
+
+    #[test]
+    fn test_iter_matches_vec() {
+        let mut data = b"first string\0second string\0code bytes here\0".to_vec();
+        data.extend("wide text".encode_utf16().flat_map(u16::to_le_bytes));
+        let info = container(
+            BinaryFormat::Elf,
+            vec![
+                mapped(section(".data", 0, 13, SectionType::WritableData), 0x1000),
+                mapped(section(".text", 27, 16, SectionType::Code), 0x101b),
+                mapped(section(".rodata", 13, 14, SectionType::StringData), 0x100d),
+                mapped(section(".rsrc", 43, 18, SectionType::Resources), 0x102b),
+            ],
+        );
+        let config = ExtractionConfig::default();
+
+        let json = |strings: Vec<FoundString>| -> Vec<String> {
+            strings
+                .iter()
+                .map(|s| serde_json::to_string(s).unwrap())
+                .collect()
+        };
+        let lazy = json(extract_iter(&data, &info, &config).collect());
+        assert_eq!(lazy.len(), 4);
+        assert_eq!(lazy, json(extract_from_container(&data, &info, &config)));
+
+        // Strings are produced one section at a time
+        let mut strings = extract_iter(&data, &info, &config);
+        assert_eq!(strings.next().unwrap().section.as_deref(), Some(".rodata"));
+        assert_eq!(strings.sections.len(), 3);
+    }
+
+    #[test]
+    fn test_extract_all_max_length() {
+        let config = ExtractionConfig {
+            max_length: Some(8),
+            ..ExtractionConfig::default()
+        };
+        let texts: Vec<String> = extract_all(b"\0abcdefghijkl\0", &config)
+            .into_iter()
+            .map(|s| s.text)
+            .collect();
+        assert_eq!(texts, vec!["abcdefgh", "ijkl"]);
+    }
+
+    #[test]
+    fn test_extract_all_encoding_selection() {
+        let mut data = b"narrow text\0\0".to_vec();
+        data.extend("wide text".encode_utf16().flat_map(u16::to_le_bytes));
+        data.extend(b"\0\0");
+
+        let encodings = |selected: Vec<Encoding>| -> Vec<Encoding> {
+            let config = ExtractionConfig {
+                encodings: selected,
+                ..ExtractionConfig::default()
+            };
+            extract_all(&data, &config)
+                .into_iter()
+                .map(|s| s.encoding)
+                .collect()
+        };
+        assert_eq!(
+            encodings(ALL_ENCODINGS.to_vec()),
+            vec![Encoding::Ascii, Encoding::Utf16Le]
+        );
+        assert_eq!(encodings(vec![Encoding::Ascii]), vec![Encoding::Ascii]);
+        assert_eq!(encodings(vec![Encoding::Utf16Le]), vec![Encoding::Utf16Le]);
+        // Without ASCII, the narrow string is reported as (ASCII-only) UTF-8
+        assert_eq!(encodings(vec![Encoding::Utf8]), vec![Encoding::Utf8]);
+    }
+
+    #[test]
+    fn test_borrowed_string_conversion() {
+        let data = b"..hello..";
+        let borrowed = BorrowedString {
+            bytes: &data[2..7],
+            offset: 2,
+            encoding: Encoding::Ascii,
+        };
+        assert_eq!(borrowed.as_str(), Some("hello"));
+
+        let owned = borrowed.to_found_string();
+        assert_eq!(owned.text, "hello");
+        assert_eq!(owned.offset, 2);
+        assert_eq!(owned.length, 5);
+        assert_eq!(owned.encoding, Encoding::Ascii);
+    }
+
+    #[test]
+    fn test_borrowed_utf16_decode() {
+        let le = BorrowedString {
+            bytes: b"h\0i\0",
+            offset: 0,
+            encoding: Encoding::Utf16Le,
+        };
+        assert_eq!(le.as_str(), None);
+        assert_eq!(le.decode(), "hi");
+
+        let be = BorrowedString {
+            bytes: b"\0h\0i",
+            offset: 0,
+            encoding: Encoding::Utf16Be,
+        };
+        assert_eq!(be.decode(), "hi");
+        assert_eq!(be.to_found_string().length, 4);
+    }
+}
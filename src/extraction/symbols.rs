@@ -0,0 +1,179 @@
+use crate::classification::demangle::demangle_symbol;
+use crate::types::{ContainerInfo, Encoding, FoundString, StringSource, Tag};
+
+/// Converts the import and export names of `container` into strings.
+///
+/// Each import becomes a `FoundString` tagged `Import` with
+/// `StringSource::ImportName` and its library, if known, as `section`; each
+/// export becomes one tagged `Export` with `StringSource::ExportName`.
+/// Versioned ELF symbols keep their version in the text, as in
+/// `memcpy@GLIBC_2.14`; see [`crate::types::ImportInfo::display_name`]. `rva` is the symbol's address and `offset` the file offset mapping it
+/// (`0` if no section does), not where the name itself is stored. Scores are
+/// left at `0` for [`crate::classification::score`] to fill in, which ranks
+/// sensitive APIs such as `VirtualAllocEx` or `ptrace` by their category.
+/// With `demangle` set, the symbol's demangled name, with its version, goes
+/// into `demangled`.
+pub fn symbol_strings(container: &ContainerInfo, demangle: bool) -> Vec<FoundString> {
+    let imports = container.imports.iter().map(|import| FoundString {
+        demangled: import.demangled.clone().filter(|_| demangle),
+        ..symbol_string(
+            container,
+            &import.display_name(),
+            import.address,
+            import.library.clone(),
+            Tag::Import,
+            StringSource::ImportName,
+        )
+    });
+    let exports = container.exports.iter().map(|export| FoundString {
+        demangled: export.demangled.clone().filter(|_| demangle),
+        ..symbol_string(
+            container,
+            &export.display_name(),
+            Some(export.address).filter(|&address| address != 0),
+            None,
+            Tag::Export,
+            StringSource::ExportName,
+        )
+    });
+
+    imports
+        .chain(exports)
+        .filter(|string| !string.text.is_empty())
+        .collect()
+}
+
+/// Sets `demangled` on each string whose text is a mangled C++, Rust or
+/// Swift name and that has no demangled name yet.
+pub fn demangle_strings(strings: &mut [FoundString]) {
+    for string in strings.iter_mut().filter(|s| s.demangled.is_none()) {
+        string.demangled = demangle_symbol(&string.text);
+    }
+}
+
+fn symbol_string(
+    container: &ContainerInfo,
+    name: &str,
+    address: Option<u64>,
+    library: Option<String>,
+    tag: Tag,
+    source: StringSource,
+) -> FoundString {
+    FoundString {
+        rva: address,
+        section: library,
+        tags: vec![tag],
+        ..FoundString::new(
+            name,
+            Encoding::Ascii,
+            address
+                .and_then(|rva| container.rva_to_offset(rva))
+                .unwrap_or(0),
+            name.len() as u32,
+            source,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{BinaryFormat, ExportInfo, ImportInfo, SectionInfo, SectionType};
+
+    #[test]
+    fn test_symbol_strings() {
+        let container = ContainerInfo {
+            imports: vec![ImportInfo {
+                name: "VirtualAllocEx".to_string(),
+                library: Some("KERNEL32.dll".to_string()),
+                address: Some(0x2010),
+                demangled: None,
+                delayed: false,
+                version: None,
+            }],
+            exports: vec![ExportInfo {
+                name: "ServiceMain".to_string(),
+                address: 0x5000,
+                ordinal: Some(1),
+                demangled: None,
+                forwarded_to: None,
+                version: None,
+                version_hidden: false,
+            }],
+            sections: vec![SectionInfo {
+                name: ".rdata".to_string(),
+                offset: 0x800,
+                size: 0x400,
+                rva: Some(0x2000),
+                section_type: SectionType::ReadOnlyData,
+                is_executable: false,
+                is_writable: false,
+                entropy: None,
+                truncated: false,
+            }],
+            format: BinaryFormat::Pe,
+            is_64bit: true,
+            architecture: None,
+            elf: None,
+            rich_header: None,
+            tls_callbacks: Vec::new(),
+        };
+
+        let strings = symbol_strings(&container, false);
+        assert_eq!(strings.len(), 2);
+
+        let import = &strings[0];
+        assert_eq!(import.text, "VirtualAllocEx");
+        assert_eq!(import.section.as_deref(), Some("KERNEL32.dll"));
+        assert_eq!(import.tags, vec![Tag::Import]);
+        assert_eq!(import.source, StringSource::ImportName);
+        assert_eq!(import.rva, Some(0x2010));
+        assert_eq!(import.offset, 0x810);
+
+        let export = &strings[1];
+        assert_eq!(export.text, "ServiceMain");
+        assert_eq!(export.section, None);
+        assert_eq!(export.tags, vec![Tag::Export]);
+        assert_eq!(export.source, StringSource::ExportName);
+        // Not mapped by any section
+        assert_eq!(export.offset, 0);
+
+        let mut container = container;
+        container.exports[0].name = "_ZN7Service4mainEv".to_string();
+        container.exports[0].demangled = Some("Service::main()".to_string());
+        let export = &symbol_strings(&container, true)[1];
+        assert_eq!(export.text, "_ZN7Service4mainEv");
+        assert_eq!(export.display_text(), "Service::main()");
+        assert_eq!(symbol_strings(&container, false)[1].demangled, None);
+    }
+
+    #[test]
+    fn test_demangle_strings() {
+        let string = |text: &str| {
+            FoundString::builder()
+                .text(text)
+                .encoding(Encoding::Ascii)
+                .offset(0)
+                .length(text.len() as u32)
+                .source(StringSource::SectionData)
+                .build()
+                .unwrap()
+        };
+        let mut strings = vec![
+            string("_ZN7Crypter7decryptEPKhm"),
+            string("hello world"),
+            string("_ZN3fooEv"),
+        ];
+        strings[2].demangled = Some("foo()@@V1".to_string());
+        demangle_strings(&mut strings);
+
+        assert_eq!(
+            strings[0].display_text(),
+            "Crypter::decrypt(unsigned char const*, unsigned long)"
+        );
+        assert_eq!(strings[1].demangled, None);
+        assert_eq!(strings[1].display_text(), "hello world");
+        // Symbol strings keep the name their container demangled
+        assert_eq!(strings[2].display_text(), "foo()@@V1");
+    }
+}
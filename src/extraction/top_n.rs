@@ -0,0 +1,177 @@
+use crate::types::FoundString;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+/// Bounded collection of the highest-scoring strings seen so far.
+///
+/// Strings are pushed one at a time into a min-heap of at most `cap`
+/// entries; once full, a new string only gets in by evicting the current
+/// lowest. Memory stays proportional to `cap` however many strings are
+/// pushed. Among equal scores the string first in canonical order (lower
+/// file offset, then encoding, then source) is kept, so the result does not
+/// depend on push order.
+///
+/// # Examples
+///
+/// ```rust
+/// use stringy::extraction::extract_all;
+/// use stringy::extraction::top_n::TopN;
+///
+/// let mut top = TopN::new(2);
+/// for (score, mut string) in extract_all(b"alpha\0bravo\0charlie\0", &Default::default())
+///     .into_iter()
+///     .enumerate()
+/// {
+///     string.score = score as i32;
+///     top.push(string);
+/// }
+/// let texts: Vec<String> = top.into_sorted_vec().into_iter().map(|s| s.text).collect();
+/// assert_eq!(texts, ["charlie", "bravo"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TopN {
+    cap: usize,
+    heap: BinaryHeap<Reverse<Ranked>>,
+}
+
+impl TopN {
+    /// Creates an empty collection keeping at most `cap` strings.
+    pub fn new(cap: usize) -> Self {
+        Self {
+            cap,
+            heap: BinaryHeap::with_capacity(cap.saturating_add(1).min(4096)),
+        }
+    }
+
+    /// Adds `string`, evicting the lowest-ranked string if that exceeds the
+    /// cap. A string ranked below everything already kept in a full
+    /// collection is dropped.
+    pub fn push(&mut self, string: FoundString) {
+        if self.cap == 0 {
+            return;
+        }
+        let ranked = Ranked(string);
+        if self.heap.len() == self.cap {
+            match self.heap.peek() {
+                Some(Reverse(lowest)) if ranked > *lowest => {
+                    self.heap.pop();
+                }
+                _ => return,
+            }
+        }
+        self.heap.push(Reverse(ranked));
+    }
+
+    /// Number of strings kept.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns `true` if no string has been kept.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns the kept strings, highest score first, ties in canonical
+    /// order.
+    pub fn into_sorted_vec(self) -> Vec<FoundString> {
+        // Ascending order of `Reverse` is descending rank
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(Ranked(string))| string)
+            .collect()
+    }
+}
+
+impl Extend<FoundString> for TopN {
+    fn extend<I: IntoIterator<Item = FoundString>>(&mut self, iter: I) {
+        for string in iter {
+            self.push(string);
+        }
+    }
+}
+
+/// A string ordered by score, then earlier in canonical order.
+#[derive(Debug, Clone)]
+struct Ranked(FoundString);
+
+impl PartialEq for Ranked {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ranked {}
+
+impl PartialOrd for Ranked {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ranked {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .score
+            .cmp(&other.0.score)
+            .then_with(|| other.0.cmp_canonical(&self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Encoding, StringSource};
+
+    fn found(text: &str, offset: u64, score: i32) -> FoundString {
+        FoundString::builder()
+            .text(text)
+            .encoding(Encoding::Ascii)
+            .offset(offset)
+            .length(text.len() as u32)
+            .source(StringSource::SectionData)
+            .score(score)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_ties_keep_lower_offsets() {
+        let mut top = TopN::new(2);
+        top.extend([
+            found("c", 0x30, 5),
+            found("a", 0x10, 5),
+            found("b", 0x20, 5),
+        ]);
+
+        let texts: Vec<String> = top.into_sorted_vec().into_iter().map(|s| s.text).collect();
+        assert_eq!(texts, vec!["a", "b"]);
+    }
+
+    /// Strings at the same offset are ranked by source, not push order.
+    #[test]
+    fn test_ties_at_same_offset() {
+        let mut section = found("name", 0x10, 5);
+        section.source = StringSource::SectionData;
+        let mut export = found("name", 0x10, 5);
+        export.source = StringSource::ExportName;
+
+        for order in [
+            [section.clone(), export.clone()],
+            [export.clone(), section.clone()],
+        ] {
+            let mut top = TopN::new(1);
+            top.extend(order);
+            let kept = top.into_sorted_vec();
+            assert_eq!(kept[0].source, StringSource::SectionData);
+        }
+    }
+
+    #[test]
+    fn test_zero_cap() {
+        let mut top = TopN::new(0);
+        top.push(found("kept?", 0, 100));
+        assert!(top.is_empty());
+    }
+}
@@ -0,0 +1,202 @@
+use crate::extraction::ascii::is_printable_ascii;
+use crate::extraction::{BorrowedString, ExtractionConfig};
+use crate::types::{Encoding, FoundString};
+
+/// Returns `true` if a little-endian code unit continues a UTF-16 run.
+///
+/// Only units in the printable ASCII range are accepted: `char, 0x00`.
+fn is_le_unit(pair: &[u8]) -> bool {
+    is_printable_ascii(pair[0]) && pair[1] == 0
+}
+
+/// Returns `true` if a big-endian code unit continues a UTF-16 run:
+/// `0x00, char`.
+fn is_be_unit(pair: &[u8]) -> bool {
+    pair[0] == 0 && is_printable_ascii(pair[1])
+}
+
+/// Iterator over UTF-16 runs borrowed from an input buffer.
+///
+/// Created by [`iter_utf16le`] and [`iter_utf16be`]. Runs may start at any
+/// byte offset, not only even ones.
+#[derive(Debug, Clone)]
+pub struct Utf16Strings<'a> {
+    data: &'a [u8],
+    pos: usize,
+    min_len: usize,
+    max_len: usize,
+    encoding: Encoding,
+}
+
+impl<'a> Utf16Strings<'a> {
+    fn new(data: &'a [u8], config: &ExtractionConfig, encoding: Encoding) -> Self {
+        Self {
+            data,
+            pos: 0,
+            min_len: config.min_length.max(1),
+            max_len: config.max_length.unwrap_or(usize::MAX).max(1),
+            encoding,
+        }
+    }
+
+    fn is_unit(&self, pos: usize) -> bool {
+        let pair = &self.data[pos..pos + 2];
+        match self.encoding {
+            Encoding::Utf16Be => is_be_unit(pair),
+            _ => is_le_unit(pair),
+        }
+    }
+}
+
+impl<'a> Iterator for Utf16Strings<'a> {
+    type Item = BorrowedString<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos + 1 < self.data.len() {
+            if !self.is_unit(self.pos) {
+                self.pos += 1;
+                continue;
+            }
+
+            // Over-long runs are cut at `max_len` units, as for ASCII
+            let begin = self.pos;
+            let mut units = 0;
+            while self.pos + 1 < self.data.len() && units < self.max_len && self.is_unit(self.pos) {
+                self.pos += 2;
+                units += 1;
+            }
+
+            if units >= self.min_len {
+                return Some(BorrowedString {
+                    bytes: &self.data[begin..self.pos],
+                    offset: begin as u64,
+                    encoding: self.encoding,
+                });
+            }
+        }
+        self.pos = self.data.len();
+        None
+    }
+}
+
+/// Scans `data` for UTF-16LE runs without copying them.
+///
+/// Lengths in `config` count code units, so a string of `min_length` units
+/// spans twice as many bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// use stringy::extraction::ExtractionConfig;
+/// use stringy::extraction::utf16::iter_utf16le;
+///
+/// let data = b"\xffW\0i\0d\0e\0\0\0";
+/// let found: Vec<String> = iter_utf16le(data, &ExtractionConfig::default())
+///     .map(|s| s.decode())
+///     .collect();
+/// assert_eq!(found, vec!["Wide"]);
+/// ```
+pub fn iter_utf16le<'a>(data: &'a [u8], config: &ExtractionConfig) -> Utf16Strings<'a> {
+    Utf16Strings::new(data, config, Encoding::Utf16Le)
+}
+
+/// Scans `data` for UTF-16BE runs without copying them.
+///
+/// See [`iter_utf16le`].
+pub fn iter_utf16be<'a>(data: &'a [u8], config: &ExtractionConfig) -> Utf16Strings<'a> {
+    Utf16Strings::new(data, config, Encoding::Utf16Be)
+}
+
+/// Extracts UTF-16LE strings at least `config.min_length` code units long.
+///
+/// Offsets and `length` are in bytes, so `length` is twice the number of
+/// code units.
+pub fn extract_utf16le(data: &[u8], config: &ExtractionConfig) -> Vec<FoundString> {
+    iter_utf16le(data, config)
+        .map(|s| s.to_found_string())
+        .collect()
+}
+
+/// Extracts UTF-16BE strings at least `config.min_length` code units long.
+///
+/// See [`extract_utf16le`].
+pub fn extract_utf16be(data: &[u8], config: &ExtractionConfig) -> Vec<FoundString> {
+    iter_utf16be(data, config)
+        .map(|s| s.to_found_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn min_length(min_length: usize) -> ExtractionConfig {
+        ExtractionConfig {
+            min_length,
+            ..ExtractionConfig::default()
+        }
+    }
+
+    /// Encodes `text` as UTF-16LE.
+    fn wide(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    #[test]
+    fn test_utf16le_offsets_and_lengths() {
+        let mut data = vec![0xff, 0xfe, 0x01];
+        data.extend(wide("Kernel32"));
+        data.extend([0, 0]);
+        data.extend(wide("abc"));
+
+        let found = extract_utf16le(&data, &min_length(4));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].text, "Kernel32");
+        assert_eq!(found[0].offset, 3);
+        assert_eq!(found[0].length, 16);
+        assert_eq!(found[0].encoding, Encoding::Utf16Le);
+
+        assert_eq!(extract_utf16le(&data, &min_length(3)).len(), 2);
+    }
+
+    #[test]
+    fn test_utf16be() {
+        let mut data = vec![0x01];
+        data.extend("Title".encode_utf16().flat_map(u16::to_be_bytes));
+        data.push(0xff);
+
+        let found = extract_utf16be(&data, &min_length(4));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].text, "Title");
+        assert_eq!(found[0].offset, 1);
+        assert_eq!(found[0].length, 10);
+        assert_eq!(found[0].encoding, Encoding::Utf16Be);
+    }
+
+    #[test]
+    fn test_plain_ascii_is_not_utf16() {
+        assert!(extract_utf16le(b"plain ascii text", &min_length(2)).is_empty());
+        assert!(extract_utf16be(b"plain ascii text", &min_length(2)).is_empty());
+    }
+
+    #[test]
+    fn test_utf16_long_run_is_split() {
+        let data = wide("AAAAAAAAAA");
+        let config = ExtractionConfig {
+            min_length: 2,
+            max_length: Some(4),
+            ..ExtractionConfig::default()
+        };
+        let pieces: Vec<(u64, String)> = iter_utf16le(&data, &config)
+            .map(|s| (s.offset, s.decode()))
+            .collect();
+        assert_eq!(
+            pieces,
+            vec![
+                (0, "AAAA".to_string()),
+                (8, "AAAA".to_string()),
+                (16, "AA".to_string())
+            ]
+        );
+    }
+}
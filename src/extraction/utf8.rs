@@ -0,0 +1,166 @@
+use crate::extraction::ascii::is_printable_ascii;
+use crate::extraction::{BorrowedString, ExtractionConfig};
+use crate::types::{Encoding, FoundString};
+
+/// Returns the byte length of the printable character starting `bytes`, or
+/// `None` if `bytes` does not start with one.
+///
+/// ASCII is accepted as by [`is_printable_ascii`]. Multibyte sequences must
+/// be valid UTF-8: overlong encodings, surrogates, code points past U+10FFFF,
+/// truncated sequences and lone continuation bytes are rejected, as are
+/// non-ASCII control characters.
+fn printable_char_len(bytes: &[u8]) -> Option<usize> {
+    let lead = *bytes.first()?;
+    let len = match lead {
+        _ if is_printable_ascii(lead) => return Some(1),
+        0xc2..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf4 => 4,
+        _ => return None,
+    };
+    let c = std::str::from_utf8(bytes.get(..len)?)
+        .ok()?
+        .chars()
+        .next()?;
+    (!c.is_control()).then_some(len)
+}
+
+/// Iterator over runs of printable UTF-8 borrowed from an input buffer.
+///
+/// Created by [`iter_utf8`].
+#[derive(Debug, Clone)]
+pub struct Utf8Strings<'a> {
+    data: &'a [u8],
+    pos: usize,
+    min_len: usize,
+    max_len: usize,
+}
+
+impl<'a> Iterator for Utf8Strings<'a> {
+    type Item = BorrowedString<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.data.len() {
+            let begin = self.pos;
+            let mut chars = 0;
+            // Over-long runs are cut at `max_len` characters, as for ASCII
+            while chars < self.max_len {
+                let Some(len) = printable_char_len(&self.data[self.pos..]) else {
+                    break;
+                };
+                self.pos += len;
+                chars += 1;
+            }
+
+            if chars == 0 {
+                self.pos += 1;
+            } else if chars >= self.min_len {
+                return Some(BorrowedString {
+                    bytes: &self.data[begin..self.pos],
+                    offset: begin as u64,
+                    encoding: Encoding::Utf8,
+                });
+            }
+        }
+        None
+    }
+}
+
+/// Scans `data` for runs of printable UTF-8 without copying them.
+///
+/// Lengths in `config` count characters, not bytes. Runs may mix ASCII and
+/// multibyte characters; an invalid sequence ends the run.
+///
+/// # Examples
+///
+/// ```rust
+/// use stringy::extraction::ExtractionConfig;
+/// use stringy::extraction::utf8::iter_utf8;
+///
+/// let data = "\u{1}ファイル\u{0}".as_bytes();
+/// let found: Vec<&str> = iter_utf8(data, &ExtractionConfig::default())
+///     .filter_map(|s| s.as_str())
+///     .collect();
+/// assert_eq!(found, vec!["ファイル"]);
+/// ```
+pub fn iter_utf8<'a>(data: &'a [u8], config: &ExtractionConfig) -> Utf8Strings<'a> {
+    Utf8Strings {
+        data,
+        pos: 0,
+        min_len: config.min_length.max(1),
+        max_len: config.max_length.unwrap_or(usize::MAX).max(1),
+    }
+}
+
+/// Extracts UTF-8 strings at least `config.min_length` characters long.
+///
+/// `length` is the byte length, which exceeds the character count when the
+/// string contains multibyte characters.
+pub fn extract_utf8(data: &[u8], config: &ExtractionConfig) -> Vec<FoundString> {
+    iter_utf8(data, config)
+        .map(|s| s.to_found_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn min_length(min_length: usize) -> ExtractionConfig {
+        ExtractionConfig {
+            min_length,
+            ..ExtractionConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_mixed_ascii_and_cjk() {
+        let mut data = vec![0x00, 0xff];
+        data.extend("config: 设置文件".as_bytes());
+        data.push(0x00);
+
+        let found = extract_utf8(&data, &min_length(4));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].text, "config: 设置文件");
+        assert_eq!(found[0].offset, 2);
+        assert_eq!(found[0].length, 8 + 4 * 3);
+        assert_eq!(found[0].encoding, Encoding::Utf8);
+    }
+
+    #[test]
+    fn test_min_length_counts_characters() {
+        // Three characters in nine bytes
+        let data = "日本語".as_bytes();
+        assert!(extract_utf8(data, &min_length(4)).is_empty());
+        assert_eq!(extract_utf8(data, &min_length(3)).len(), 1);
+
+        // Emoji are four bytes each
+        let found = extract_utf8("ok 👍👍".as_bytes(), &min_length(5));
+        assert_eq!(found[0].length, 11);
+    }
+
+    #[test]
+    fn test_invalid_sequences_break_the_run() {
+        // Lone continuation byte
+        let data = b"abcd\x80efgh";
+        let texts: Vec<String> = extract_utf8(data, &min_length(4))
+            .into_iter()
+            .map(|s| s.text)
+            .collect();
+        assert_eq!(texts, vec!["abcd", "efgh"]);
+
+        // Overlong encoding of '/' (0xC0 0xAF) and of U+0000 in three bytes
+        let texts: Vec<String> = extract_utf8(b"abcd\xc0\xafefgh\xe0\x80\x80ijkl", &min_length(4))
+            .into_iter()
+            .map(|s| s.text)
+            .collect();
+        assert_eq!(texts, vec!["abcd", "efgh", "ijkl"]);
+
+        // Truncated sequence at the end of the input and a UTF-16 surrogate
+        let texts: Vec<String> = extract_utf8(b"abcd\xed\xa0\x80efgh\xe6\x97", &min_length(4))
+            .into_iter()
+            .map(|s| s.text)
+            .collect();
+        assert_eq!(texts, vec!["abcd", "efgh"]);
+    }
+}
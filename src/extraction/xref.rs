@@ -0,0 +1,258 @@
+//! Best-effort cross-reference counting for extracted strings.
+//!
+//! A string referenced from many places is usually a core message, while a
+//! string nobody points at may be dead data. Counting real references needs
+//! relocation processing and disassembly; this module instead scans the file
+//! for pointer-sized values equal to a string's address.
+//!
+//! # Limitations
+//!
+//! - Only absolute pointers are found. Position-independent code (x86-64
+//!   RIP-relative addressing, ARM64 `adrp`/`add` pairs) references strings
+//!   through relative displacements, which this scan cannot see.
+//! - Pointers are matched against the start of a string only; references into
+//!   the middle of a string (e.g. after suffix merging by the linker) are not
+//!   counted.
+//! - Pointers that the loader fills in via relocations (e.g. ELF `R_*_RELATIVE`
+//!   entries in PIE binaries) are zero in the file and are missed.
+//! - Any pointer-sized value that happens to equal a string address is
+//!   counted, so small binaries with low addresses can over-count.
+
+use crate::container::pe::PeParser;
+use crate::types::{BinaryFormat, ContainerInfo, FoundString, SectionInfo, SectionType};
+use std::collections::HashMap;
+
+/// Size of a pointer in the scanned binary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PointerWidth {
+    /// 32-bit pointers
+    Four,
+    /// 64-bit pointers
+    #[default]
+    Eight,
+}
+
+impl PointerWidth {
+    /// Returns the pointer width of a parsed container.
+    pub fn for_container(info: &ContainerInfo) -> Self {
+        if info.is_64bit {
+            Self::Eight
+        } else {
+            Self::Four
+        }
+    }
+
+    /// Returns the pointer size in bytes.
+    pub fn bytes(self) -> usize {
+        match self {
+            Self::Four => 4,
+            Self::Eight => 8,
+        }
+    }
+}
+
+/// Options for [`count_xrefs`].
+#[derive(Debug, Clone, Default)]
+pub struct XrefConfig {
+    /// Pointer width of the target architecture
+    pub pointer_width: PointerWidth,
+    /// Value subtracted from pointers before matching against string RVAs.
+    ///
+    /// PE pointers hold `ImageBase + RVA` while `SectionInfo::rva` stores the
+    /// RVA alone, so the image base goes here for PE. ELF and Mach-O record
+    /// full virtual addresses in their sections and use `0`.
+    pub image_base: u64,
+    /// Also scan executable sections at every byte offset.
+    ///
+    /// This catches absolute immediates in 32-bit x86 code (`push offset str`)
+    /// at the cost of more false positives.
+    pub scan_code: bool,
+}
+
+impl XrefConfig {
+    /// Returns the options for a parsed container: its pointer width and,
+    /// for PE input, the image base read from `data`.
+    pub fn for_container(info: &ContainerInfo, data: &[u8]) -> Self {
+        let image_base = match info.format {
+            BinaryFormat::Pe => PeParser::image_base(data).unwrap_or(0),
+            _ => 0,
+        };
+        Self {
+            pointer_width: PointerWidth::for_container(info),
+            image_base,
+            scan_code: false,
+        }
+    }
+}
+
+/// Counts pointer-sized references to each string and stores the result in
+/// [`FoundString::xref_count`].
+///
+/// Data sections are scanned at pointer-aligned offsets. Executable sections
+/// are only scanned when `config.scan_code` is set, and debug sections are
+/// never scanned, nor are sections extending past the end of `data`, which
+/// parsers prevent with [`SectionInfo::clamp_to`]. Strings without an RVA
+/// are left with `xref_count: None`.
+/// See the module documentation for the limitations of this heuristic.
+pub fn count_xrefs(
+    data: &[u8],
+    sections: &[SectionInfo],
+    strings: &mut [FoundString],
+    config: &XrefConfig,
+) {
+    let mut wanted: HashMap<u64, u32> = strings
+        .iter()
+        .filter_map(|s| s.rva)
+        .map(|rva| (rva, 0))
+        .collect();
+
+    if !wanted.is_empty() {
+        let width = config.pointer_width.bytes();
+        for section in sections {
+            let step = match section.section_type {
+                SectionType::Debug => continue,
+                SectionType::Code if !config.scan_code => continue,
+                SectionType::Code => 1,
+                _ => width,
+            };
+            let Some(bytes) = section.data(data) else {
+                continue;
+            };
+
+            let mut pos = 0;
+            while pos + width <= bytes.len() {
+                let value = read_pointer(&bytes[pos..pos + width], config.pointer_width);
+                if let Some(target) = value.checked_sub(config.image_base) {
+                    if let Some(count) = wanted.get_mut(&target) {
+                        *count += 1;
+                    }
+                }
+                pos += step;
+            }
+        }
+    }
+
+    for string in strings.iter_mut() {
+        string.xref_count = string.rva.and_then(|rva| wanted.get(&rva).copied());
+    }
+}
+
+/// Reads a little-endian pointer.
+fn read_pointer(bytes: &[u8], width: PointerWidth) -> u64 {
+    match width {
+        PointerWidth::Four => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as u64,
+        PointerWidth::Eight => u64::from_le_bytes([
+            bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
+        ]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Encoding, StringSource};
+
+    fn section(name: &str, offset: u64, size: u64, rva: u64, ty: SectionType) -> SectionInfo {
+        SectionInfo {
+            name: name.to_string(),
+            offset,
+            size,
+            rva: Some(rva),
+            section_type: ty,
+            is_executable: ty == SectionType::Code,
+            is_writable: false,
+            entropy: None,
+            truncated: false,
+        }
+    }
+
+    fn found(text: &str, rva: Option<u64>) -> FoundString {
+        FoundString {
+            rva,
+            ..FoundString::builder()
+                .text(text)
+                .encoding(Encoding::Ascii)
+                .offset(0)
+                .length(text.len() as u32)
+                .source(StringSource::SectionData)
+                .build()
+                .unwrap()
+        }
+    }
+
+    #[test]
+    fn test_counts_aligned_pointers_in_data() {
+        let mut data = vec![0u8; 0x40];
+        // Pointer table at file offset 0x20 (RVA 0x2000) referencing 0x1000 twice
+        data[0x20..0x28].copy_from_slice(&0x1000u64.to_le_bytes());
+        data[0x28..0x30].copy_from_slice(&0x1000u64.to_le_bytes());
+        data[0x30..0x38].copy_from_slice(&0x1010u64.to_le_bytes());
+
+        let sections = vec![section(
+            ".data.rel.ro",
+            0x20,
+            0x20,
+            0x2000,
+            SectionType::ReadOnlyData,
+        )];
+        let mut strings = vec![
+            found("referenced twice", Some(0x1000)),
+            found("referenced once", Some(0x1010)),
+            found("dead string", Some(0x1020)),
+            found("no address", None),
+        ];
+
+        count_xrefs(&data, &sections, &mut strings, &XrefConfig::default());
+
+        assert_eq!(strings[0].xref_count, Some(2));
+        assert_eq!(strings[1].xref_count, Some(1));
+        assert_eq!(strings[2].xref_count, Some(0));
+        assert_eq!(strings[3].xref_count, None);
+    }
+
+    #[test]
+    fn test_pe_image_base_and_code_scan() {
+        let mut data = vec![0x90u8; 0x20];
+        // `push 0x00402000` in 32-bit code at an unaligned offset
+        data[0x05] = 0x68;
+        data[0x06..0x0a].copy_from_slice(&0x0040_2000u32.to_le_bytes());
+
+        let sections = vec![section(".text", 0, 0x20, 0x1000, SectionType::Code)];
+        let mut strings = vec![found("Usage: %s", Some(0x2000))];
+
+        let mut config = XrefConfig {
+            pointer_width: PointerWidth::Four,
+            image_base: 0x0040_0000,
+            scan_code: false,
+        };
+        count_xrefs(&data, &sections, &mut strings, &config);
+        assert_eq!(strings[0].xref_count, Some(0));
+
+        config.scan_code = true;
+        count_xrefs(&data, &sections, &mut strings, &config);
+        assert_eq!(strings[0].xref_count, Some(1));
+    }
+
+    #[test]
+    fn test_out_of_bounds_section_needs_clamping() {
+        let mut data = vec![0u8; 0x10];
+        data[0x08..0x10].copy_from_slice(&0x1000u64.to_le_bytes());
+
+        let mut sections = vec![section(
+            ".data",
+            0x08,
+            0x1000,
+            0x3000,
+            SectionType::WritableData,
+        )];
+        let mut strings = vec![found("hello", Some(0x1000))];
+        // Never read past the input
+        count_xrefs(&data, &sections, &mut strings, &XrefConfig::default());
+        assert_eq!(strings[0].xref_count, Some(0));
+
+        // Parsers clamp sections to the input, which keeps them scanned
+        sections[0].clamp_to(data.len() as u64);
+        count_xrefs(&data, &sections, &mut strings, &XrefConfig::default());
+        assert_eq!(strings[0].xref_count, Some(1));
+    }
+}
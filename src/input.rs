@@ -0,0 +1,277 @@
+// Input file access
+
+use crate::types::{Result, StringyError};
+use memmap2::Mmap;
+use std::fs::File;
+use std::io::{self, Read};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// Bytes of an input file, either memory-mapped or read into memory.
+///
+/// Dereferences to `&[u8]`, so it can be passed directly to
+/// [`detect_format`](crate::container::detect_format), the container parsers
+/// and the extractors. Mapping lets multi-gigabyte inputs be analyzed without
+/// reading them into memory up front; the OS pages in only what is touched.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use stringy::Input;
+/// use stringy::container::detect_format;
+///
+/// let input = Input::open("/bin/ls")?;
+/// let format = detect_format(&input);
+/// # Ok::<(), stringy::StringyError>(())
+/// ```
+#[derive(Debug)]
+pub enum Input {
+    /// File contents copied into memory
+    Owned(Vec<u8>),
+    /// Read-only memory map of the file
+    Mapped(Mmap),
+}
+
+impl Input {
+    /// Opens `path`, memory-mapping it when possible and reading it into
+    /// memory otherwise.
+    ///
+    /// Empty files, and files the OS refuses to map (pipes, some special
+    /// files), are read.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StringyError::IoError` if the file cannot be opened or read.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        if file.metadata()?.len() > 0 {
+            match Self::map_file(&file) {
+                Ok(input) => return Ok(input),
+                Err(err) => log::debug!("Reading {} instead: {}", path.display(), err),
+            }
+        }
+        Ok(Self::Owned(std::fs::read(path)?))
+    }
+
+    /// Reads all of `reader` into memory.
+    ///
+    /// For streams that cannot be mapped, such as stdin or a pipe.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StringyError::IoError` if reading fails, or with
+    /// `io::ErrorKind::UnexpectedEof` if the stream is empty.
+    pub fn read_from(mut reader: impl Read) -> Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        if data.is_empty() {
+            return Err(
+                io::Error::new(io::ErrorKind::UnexpectedEof, "input stream is empty").into(),
+            );
+        }
+        Ok(Self::Owned(data))
+    }
+
+    /// Reads all of standard input into memory; see [`Input::read_from`].
+    pub fn stdin() -> Result<Self> {
+        Self::read_from(io::stdin().lock())
+    }
+
+    /// Memory-maps `path` without falling back to reading it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StringyError::IoError` if the file cannot be opened and
+    /// `StringyError::MemoryMapError` if it cannot be mapped.
+    pub fn map(path: impl AsRef<Path>) -> Result<Self> {
+        Self::map_file(&File::open(path)?)
+    }
+
+    fn map_file(file: &File) -> Result<Self> {
+        // SAFETY: the map is read-only. If another process truncates or
+        // rewrites the file while it is mapped, reads may fault or observe
+        // the new contents; that is the accepted cost of mapping inputs.
+        let map = unsafe { Mmap::map(file) }
+            .map_err(|err| StringyError::MemoryMapError(err.to_string()))?;
+        Ok(Self::Mapped(map))
+    }
+
+    /// Returns `true` if the input is memory-mapped rather than copied.
+    pub fn is_mapped(&self) -> bool {
+        matches!(self, Self::Mapped(_))
+    }
+}
+
+impl From<Vec<u8>> for Input {
+    fn from(data: Vec<u8>) -> Self {
+        Self::Owned(data)
+    }
+}
+
+impl Deref for Input {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Self::Owned(data) => data,
+            Self::Mapped(map) => map,
+        }
+    }
+}
+
+impl AsRef<[u8]> for Input {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+/// Lists the regular files in directory `root`, descending into
+/// subdirectories if `recursive` is set.
+///
+/// Paths are returned sorted, so runs over the same tree visit files in the
+/// same order. Symbolic links, devices, FIFOs and sockets are skipped.
+/// Subdirectories that cannot be read are logged and skipped, so one
+/// permission-denied folder does not end a bulk scan.
+///
+/// # Errors
+///
+/// Returns `StringyError::IoError` if `root` itself cannot be read.
+pub fn collect_files(root: impl AsRef<Path>, recursive: bool) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.as_ref().to_path_buf()];
+    let mut is_root = true;
+    while let Some(dir) = pending.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(err) if !is_root => {
+                log::warn!("Skipping {}: {}", dir.display(), err);
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        };
+        is_root = false;
+
+        for entry in entries {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) => {
+                    log::warn!("Skipping entry in {}: {}", dir.display(), err);
+                    continue;
+                }
+            };
+            let path = entry.path();
+            // DirEntry::file_type does not follow symbolic links
+            match entry.file_type() {
+                Ok(kind) if kind.is_file() => files.push(path),
+                Ok(kind) if kind.is_dir() && recursive => pending.push(path),
+                Ok(_) => log::debug!("Skipping {}: not a regular file", path.display()),
+                Err(err) => log::warn!("Skipping {}: {}", path.display(), err),
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extraction::ExtractionConfig;
+    use crate::extraction::ascii::iter_ascii;
+    use std::io::{Seek, SeekFrom, Write};
+
+    #[test]
+    fn test_open_maps_file() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"\x00mapped string\x00").unwrap();
+
+        let input = Input::open(file.path()).unwrap();
+        assert!(input.is_mapped());
+        assert_eq!(&input[1..14], b"mapped string");
+    }
+
+    #[test]
+    fn test_empty_file_is_read() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let input = Input::open(file.path()).unwrap();
+        assert!(!input.is_mapped());
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn test_read_from_stream() {
+        let input = Input::read_from(&b"\x7fELF streamed"[..]).unwrap();
+        assert!(!input.is_mapped());
+        assert_eq!(&input[..], b"\x7fELF streamed");
+
+        match Input::read_from(io::empty()) {
+            Err(StringyError::IoError(err)) => {
+                assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof)
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(matches!(
+            Input::open(dir.path().join("missing")),
+            Err(StringyError::IoError(_))
+        ));
+    }
+
+    #[test]
+    fn test_collect_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("sub/deeper")).unwrap();
+        for name in ["b.bin", "a.bin", "sub/c.bin", "sub/deeper/d.bin"] {
+            std::fs::write(root.join(name), b"data").unwrap();
+        }
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(root.join("a.bin"), root.join("link.bin")).unwrap();
+
+        let relative = |files: Vec<PathBuf>| -> Vec<String> {
+            files
+                .iter()
+                .map(|f| {
+                    f.strip_prefix(root)
+                        .unwrap()
+                        .to_string_lossy()
+                        .replace('\\', "/")
+                })
+                .collect()
+        };
+        assert_eq!(
+            relative(collect_files(root, false).unwrap()),
+            vec!["a.bin", "b.bin"]
+        );
+        assert_eq!(
+            relative(collect_files(root, true).unwrap()),
+            vec!["a.bin", "b.bin", "sub/c.bin", "sub/deeper/d.bin"]
+        );
+        assert!(collect_files(root.join("missing"), true).is_err());
+    }
+
+    /// A sparse 256 MiB file is scanned through the map; only the pages the
+    /// scanner touches are ever resident, and nothing is copied.
+    #[test]
+    fn test_large_file_is_extracted_through_map() {
+        const SIZE: u64 = 256 << 20;
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.as_file().set_len(SIZE).unwrap();
+        file.seek(SeekFrom::Start(SIZE - 64)).unwrap();
+        file.write_all(b"needle at the end").unwrap();
+
+        let input = Input::open(file.path()).unwrap();
+        assert!(input.is_mapped());
+        assert_eq!(input.len() as u64, SIZE);
+
+        let found: Vec<_> = iter_ascii(&input, &ExtractionConfig::default()).collect();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].offset, SIZE - 64);
+        assert_eq!(found[0].as_str(), Some("needle at the end"));
+    }
+}
@@ -3,14 +3,19 @@
 //! Stringy leverages format-specific knowledge to distinguish meaningful strings
 //! from random garbage data in binary files.
 
+pub mod analysis;
 pub mod classification;
 pub mod container;
 pub mod extraction;
+pub mod input;
 pub mod output;
 pub mod types;
 
 // Re-export commonly used types
+pub use analysis::{AnalysisConfig, AnalysisResult, analyze, analyze_with_progress};
+pub use input::Input;
 pub use types::{
-    BinaryFormat, ContainerInfo, Encoding, ExportInfo, FoundString, ImportInfo, Result,
-    SectionInfo, SectionType, StringSource, StringyError, Tag,
+    BinaryFormat, ContainerInfo, ElfMetadata, Encoding, ExportInfo, FoundString,
+    FoundStringBuilder, HashAlgorithm, ImportInfo, Result, RichEntry, RichHeader, SectionInfo,
+    SectionType, StringSource, StringyError, Tag,
 };
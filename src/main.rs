@@ -1,5 +1,26 @@
-use clap::Parser;
-use std::path::PathBuf;
+use clap::builder::{ArgPredicate, PossibleValuesParser, TypedValueParser};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+use log::LevelFilter;
+use std::io::Write;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use stringy::container::raw::BASE_ADDRESS_ALIGNMENT;
+use stringy::container::{create_parser_or_raw, detect_format, detect_format_fast};
+use stringy::extraction::ascii::AsciiCharset;
+use stringy::extraction::{
+    ALL_ENCODINGS, DEFAULT_MAX_LENGTH, DEFAULT_MIN_LENGTH, ExtractionConfig,
+};
+use stringy::input::collect_files;
+use stringy::output::imports::symbol_tables_json;
+use stringy::output::{
+    ColorChoice, Genericity, OutputConfig, OutputFilter, OutputFormat, OutputTarget, SortKey,
+    sort_strings,
+};
+use stringy::{
+    AnalysisConfig, BinaryFormat, Encoding, FoundString, Input, StringyError, Tag, analyze,
+};
 
 /// A smarter alternative to the strings command that leverages format-specific knowledge
 #[derive(Parser)]
@@ -7,17 +28,815 @@
 #[command(about = "Extract meaningful strings from binary files")]
 #[command(version)]
 struct Cli {
-    /// Input binary file to analyze
+    /// Input binary file, or directory of files, to analyze; `-` reads stdin
     #[arg(value_name = "FILE")]
     input: PathBuf,
-}
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let _cli = Cli::parse();
+    /// Also scan files in subdirectories when FILE is a directory
+    #[arg(short, long)]
+    recursive: bool,
+
+    /// Increase diagnostic output on stderr (-v info, -vv debug, -vvv trace)
+    #[arg(short, long, action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
+
+    /// Only report errors on stderr
+    #[arg(short, long)]
+    quiet: bool,
+
+    /// Print the import table as JSON (grouped by library) instead of strings
+    #[arg(long)]
+    imports_json: bool,
+
+    /// Print the export table as JSON instead of strings
+    #[arg(long)]
+    exports_json: bool,
+
+    /// Minimum string length in characters
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = DEFAULT_MIN_LENGTH,
+        value_parser = parse_len,
+    )]
+    min_len: usize,
+
+    /// Maximum string length in characters; longer runs are split
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = DEFAULT_MAX_LENGTH,
+        value_parser = parse_len,
+    )]
+    max_len: usize,
+
+    /// Encoding to extract; repeat or separate with commas for several
+    /// [default: all]
+    #[arg(
+        long = "encoding",
+        value_name = "ENCODING",
+        value_delimiter = ',',
+        value_parser = PossibleValuesParser::new(["ascii", "utf8", "utf16le", "utf16be"])
+            .map(|s| s.parse::<Encoding>().expect("listed encodings parse")),
+    )]
+    encodings: Vec<Encoding>,
+
+    /// Bytes that continue an ASCII string, as comma-separated classes:
+    /// printable, tab, newline, latin1; prefix a class with `-` to remove it
+    /// [default: printable,tab]
+    #[arg(long, value_name = "CLASSES", value_parser = parse_ascii_charset)]
+    ascii_charset: Option<AsciiCharset>,
+
+    /// Show mangled C++, Rust and Swift symbol names demangled; JSON output
+    /// keeps the raw name in `text` and adds a `demangled` field
+    #[arg(long)]
+    demangle: bool,
+
+    /// Add the symbol names and DWARF strings of this separate debug file
+    /// (GNU `.debug`, `.dwo`, or the binary inside a `.dSYM` bundle)
+    #[arg(long, value_name = "PATH")]
+    debug_file: Option<PathBuf>,
+
+    /// Count pointer-sized references to each string (best effort) and
+    /// report them as `xref_count`
+    #[arg(long)]
+    xrefs: bool,
+
+    /// Write output to this file instead of stdout
+    #[arg(short, long, value_name = "PATH")]
+    output: Option<PathBuf>,
+
+    /// Output format [default: from the `--output` extension, else table]
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        default_value_if("stats", ArgPredicate::IsPresent, "summary"),
+        value_parser = PossibleValuesParser::new([
+            "table", "jsonl", "json", "csv", "yara", "markdown", "sarif", "summary", "signature",
+        ])
+        .map(|s| s.parse::<OutputFormat>().expect("listed formats parse")),
+    )]
+    format: Option<OutputFormat>,
 
-    // TODO: Implement main extraction pipeline
-    println!("Stringy - Binary string extraction tool");
-    println!("Implementation coming soon...");
+    /// Print counts by encoding, tag and section and the top strings instead
+    /// of every string; same as `--format summary`
+    #[arg(long, conflicts_with = "format")]
+    stats: bool,
 
+    /// How aggressively `--format signature` drops strings that are common
+    /// to unrelated binaries
+    #[arg(
+        long,
+        value_name = "LEVEL",
+        default_value = "balanced",
+        value_parser = PossibleValuesParser::new(["lenient", "balanced", "strict"])
+            .map(|s| s.parse::<Genericity>().expect("listed genericity levels parse")),
+    )]
+    genericity: Genericity,
+
+    /// When to color table output
+    #[arg(
+        long,
+        value_name = "WHEN",
+        default_value = "auto",
+        value_parser = PossibleValuesParser::new(["auto", "always", "never"])
+            .map(|s| s.parse::<ColorChoice>().expect("listed color choices parse")),
+    )]
+    color: ColorChoice,
+
+    /// Write `--format json` output on one line instead of indented
+    #[arg(long)]
+    compact: bool,
+
+    /// Wrap `--format json` output in an object with file, format and
+    /// summary metadata
+    #[arg(long)]
+    envelope: bool,
+
+    /// Order results by this key; score and length sort highest first
+    #[arg(
+        long,
+        value_name = "KEY",
+        default_value = "score",
+        value_parser = PossibleValuesParser::new(["score", "offset", "length", "text", "section"])
+            .map(|s| s.parse::<SortKey>().expect("listed sort keys parse")),
+    )]
+    sort: SortKey,
+
+    /// Show at most N strings, after sorting and filtering
+    #[arg(long, value_name = "N")]
+    limit: Option<usize>,
+
+    /// Only show strings scoring at least N
+    #[arg(long, value_name = "N", allow_negative_numbers = true)]
+    min_score: Option<i32>,
+
+    /// Only show strings with this tag; repeat to accept any of several
+    #[arg(
+        long = "tag",
+        value_name = "TAG",
+        value_parser = PossibleValuesParser::new([
+            "url", "domain", "ipv4", "ipv6", "filepath", "regpath", "guid", "email", "b64",
+            "fmt", "user-agent-ish", "import", "export", "version", "manifest", "resource",
+            "objc", "jwt", "api-key", "cmd", "sql", "md5", "sha1", "sha256",
+        ])
+        .map(|s| s.parse::<Tag>().expect("listed tags parse")),
+    )]
+    tags: Vec<Tag>,
+
+    /// Only scan the section with this name; repeat for several
+    #[arg(long = "section", value_name = "NAME")]
+    sections: Vec<String>,
+
+    /// Only scan file offsets START (inclusive) to END (exclusive), decimal
+    /// or 0x-prefixed hex; either end may be left out
+    #[arg(long, value_name = "START:END", value_parser = parse_offset_range)]
+    offset: Option<Range<u64>>,
+
+    /// Treat unrecognized (raw) input as loaded at this hex virtual address,
+    /// so strings report process addresses; must be 4 KiB aligned
+    #[arg(long, value_name = "HEX", value_parser = parse_base_address)]
+    base_address: Option<u64>,
+}
+
+fn parse_len(s: &str) -> Result<usize, String> {
+    match s.parse::<usize>() {
+        Ok(0) => Err("must be at least 1".to_string()),
+        Ok(n) => Ok(n),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// Rejects a `--max-len` below `--min-len`, which no string could satisfy.
+fn check_lengths(cli: &Cli) -> Result<(), clap::Error> {
+    if cli.max_len < cli.min_len {
+        return Err(Cli::command().error(
+            ErrorKind::ArgumentConflict,
+            format!(
+                "--max-len ({}) must be at least --min-len ({})",
+                cli.max_len, cli.min_len
+            ),
+        ));
+    }
     Ok(())
 }
+
+fn parse_ascii_charset(s: &str) -> Result<AsciiCharset, String> {
+    s.parse::<AsciiCharset>().map_err(|err| err.to_string())
+}
+
+/// Parses a decimal or `0x`-prefixed hexadecimal offset.
+fn parse_offset(s: &str) -> Result<u64, String> {
+    let parsed = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => s.parse::<u64>(),
+    };
+    parsed.map_err(|err| format!("invalid offset {:?}: {}", s, err))
+}
+
+/// Parses a hexadecimal base address, with or without a `0x` prefix, and
+/// checks that it is page aligned.
+fn parse_base_address(s: &str) -> Result<u64, String> {
+    let hex = s
+        .strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s);
+    let base =
+        u64::from_str_radix(hex, 16).map_err(|err| format!("invalid address {:?}: {}", s, err))?;
+    if !base.is_multiple_of(BASE_ADDRESS_ALIGNMENT) {
+        return Err(format!(
+            "{:#x} is not aligned to {:#x}",
+            base, BASE_ADDRESS_ALIGNMENT
+        ));
+    }
+    Ok(base)
+}
+
+/// Parses `START:END` into a file offset range; a missing start means the
+/// beginning of the file, a missing end its end.
+fn parse_offset_range(s: &str) -> Result<Range<u64>, String> {
+    let (start, end) = s
+        .split_once(':')
+        .ok_or_else(|| "expected START:END".to_string())?;
+    let start = if start.is_empty() {
+        0
+    } else {
+        parse_offset(start)?
+    };
+    let end = if end.is_empty() {
+        u64::MAX
+    } else {
+        parse_offset(end)?
+    };
+    if start >= end {
+        return Err(format!(
+            "start ({:#x}) must be below end ({:#x})",
+            start, end
+        ));
+    }
+    Ok(start..end)
+}
+
+/// Score and length read best highest first; the other keys follow file
+/// or alphabetical order.
+fn sorts_descending(key: SortKey) -> bool {
+    matches!(key, SortKey::Score | SortKey::Length)
+}
+
+/// Maps the verbosity flags to a log level. Warnings and errors are shown by
+/// default.
+fn log_level(verbose: u8, quiet: bool) -> LevelFilter {
+    if quiet {
+        return LevelFilter::Error;
+    }
+    match verbose {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Info,
+        2 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
+
+/// Sends diagnostics to stderr so stdout only carries results.
+///
+/// `RUST_LOG` is honored for per-module filters; `-v`/`-q` override its
+/// global level.
+fn init_logging(cli: &Cli) {
+    let mut builder = env_logger::Builder::new();
+    builder
+        .target(env_logger::Target::Stderr)
+        .filter_level(LevelFilter::Warn)
+        .parse_default_env();
+    if cli.quiet || cli.verbose > 0 {
+        builder.filter_level(log_level(cli.verbose, cli.quiet));
+    }
+    builder.init();
+}
+
+/// Path naming standard input.
+const STDIN: &str = "-";
+
+/// Opens `path`, or reads standard input if it is `-`.
+fn open_input(path: &Path) -> stringy::Result<Input> {
+    if path.as_os_str() == STDIN {
+        Input::stdin()
+    } else {
+        Input::open(path)
+    }
+}
+
+/// Reads the `--debug-file` file.
+fn read_debug_file(path: &Path) -> stringy::Result<Vec<u8>> {
+    std::fs::read(path).map_err(|err| {
+        StringyError::ConfigError(format!(
+            "Cannot read debug file {}: {}",
+            path.display(),
+            err
+        ))
+    })
+}
+
+/// The `--format` choice, or the format named by the `--output` extension.
+fn output_format(cli: &Cli) -> OutputFormat {
+    OutputFormat::resolve(cli.format, cli.output.as_deref())
+}
+
+/// Where to write results: the `--output` file, or stdout.
+fn output_target(cli: &Cli) -> OutputTarget {
+    cli.output
+        .clone()
+        .map_or(OutputTarget::Stdout, OutputTarget::File)
+}
+
+/// Dumps the import and/or export tables of the input as JSON.
+fn dump_symbol_tables(cli: &Cli) -> stringy::Result<()> {
+    let data = open_input(&cli.input)?;
+    let format = detect_format(&data);
+    log::debug!("Detected format: {}", format);
+
+    let info = create_parser_or_raw(format, cli.base_address)?.parse(&data)?;
+    let json = symbol_tables_json(&info, cli.imports_json, cli.exports_json)?;
+
+    let mut out = output_target(cli).open()?;
+    writeln!(out, "{}", json)?;
+    out.finish()
+}
+
+/// Number of leading bytes [`is_text`] inspects.
+const TEXT_PROBE_LEN: usize = 8192;
+
+/// Returns `true` for input that is obviously not a binary: no known magic
+/// number, and text rather than binary data in its first bytes.
+fn is_text(data: &[u8]) -> bool {
+    if detect_format_fast(data) != BinaryFormat::Unknown {
+        return false;
+    }
+    let head = &data[..data.len().min(TEXT_PROBE_LEN)];
+    if head.contains(&0) {
+        return false;
+    }
+    // The probe may cut a multibyte character short
+    match std::str::from_utf8(head) {
+        Ok(_) => true,
+        Err(err) => err.error_len().is_none(),
+    }
+}
+
+/// Warns about PE TLS callbacks: code the loader runs before the entry
+/// point is a common anti-debugging and early-execution trick.
+fn report_tls_callbacks(callbacks: &[u64]) {
+    if callbacks.is_empty() {
+        return;
+    }
+    let addresses: Vec<String> = callbacks.iter().map(|rva| format!("{:#x}", rva)).collect();
+    log::warn!(
+        "{} TLS callback(s) run before the entry point, at RVA {}",
+        callbacks.len(),
+        addresses.join(", ")
+    );
+}
+
+/// The `--min-score` and `--tag` criteria.
+fn output_filter(cli: &Cli) -> OutputFilter {
+    OutputFilter {
+        min_score: cli.min_score,
+        tags_any: cli.tags.clone(),
+        ..OutputFilter::default()
+    }
+}
+
+/// Analyzes the contents of one file and orders and filters its strings for
+/// output. Also returns the file's container format.
+fn analyze_input(
+    data: &[u8],
+    cli: &Cli,
+    config: &AnalysisConfig,
+) -> stringy::Result<(BinaryFormat, Vec<FoundString>)> {
+    let result = analyze(data, config)?;
+    report_tls_callbacks(&result.container.tls_callbacks);
+    let mut strings = result.strings;
+    let filter = output_filter(cli);
+    strings.retain(|string| filter.matches(string));
+    sort_strings(&mut strings, cli.sort, sorts_descending(cli.sort));
+    if let Some(limit) = cli.limit {
+        strings.truncate(limit);
+    }
+    Ok((result.container.format, strings))
+}
+
+/// Output settings for the strings of `path`, a file of `format`; `label`
+/// adds the path to the output when several files are scanned.
+fn output_config(
+    cli: &Cli,
+    path: &Path,
+    format: BinaryFormat,
+    label: bool,
+    color: ColorChoice,
+) -> OutputConfig {
+    let rule_name = path
+        .file_name()
+        .filter(|name| *name != STDIN)
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| OutputConfig::default().rule_name);
+    OutputConfig {
+        color,
+        rule_name,
+        file: label.then(|| path.display().to_string()),
+        binary_format: Some(format),
+        pretty: !cli.compact,
+        envelope: cli.envelope,
+        genericity: cli.genericity,
+        ..OutputConfig::default()
+    }
+}
+
+/// Prints the strings of the input file, or of every file in the input
+/// directory.
+///
+/// In directory mode, text files are passed over, and a file that cannot be
+/// read or parsed is logged and skipped. Returns the number of files skipped
+/// that way.
+fn extract_strings(cli: &Cli) -> stringy::Result<usize> {
+    if cli.debug_file.is_some() && cli.input.is_dir() {
+        return Err(StringyError::ConfigError(
+            "--debug-file needs a single input file, not a directory".to_string(),
+        ));
+    }
+    let config = AnalysisConfig {
+        extraction: ExtractionConfig {
+            min_length: cli.min_len,
+            max_length: Some(cli.max_len),
+            encodings: if cli.encodings.is_empty() {
+                ALL_ENCODINGS.to_vec()
+            } else {
+                cli.encodings.clone()
+            },
+            ascii_charset: cli.ascii_charset.unwrap_or_default(),
+            section_names: (!cli.sections.is_empty()).then(|| cli.sections.clone()),
+            byte_range: cli.offset.clone(),
+            // Sorted by score and unfiltered, the limit can be applied while
+            // extracting
+            top_n: cli.limit.filter(|&limit| {
+                limit > 0
+                    && cli.sort == SortKey::Score
+                    && output_filter(cli) == OutputFilter::default()
+            }),
+            demangle: cli.demangle,
+            ..ExtractionConfig::default()
+        },
+        debug_file: cli.debug_file.as_deref().map(read_debug_file).transpose()?,
+        base_address: cli.base_address,
+        count_xrefs: cli.xrefs,
+        ..AnalysisConfig::default()
+    };
+    config.extraction.validate()?;
+
+    let target = output_target(cli);
+    let format = output_format(cli);
+    let color = cli.color.resolve(target.is_terminal());
+
+    if !cli.input.is_dir() {
+        // SARIF locations point into the scanned file by path, and a JSON
+        // envelope names it
+        let label =
+            (format == OutputFormat::Sarif || cli.envelope) && cli.input.as_os_str() != STDIN;
+        let data = open_input(&cli.input)?;
+        let (binary_format, strings) = analyze_input(&data, cli, &config)?;
+        let formatter =
+            format.formatter(output_config(cli, &cli.input, binary_format, label, color))?;
+        let mut out = target.open()?;
+        formatter.format(&strings, &mut out)?;
+        out.finish()?;
+        return Ok(0);
+    }
+
+    if matches!(
+        format,
+        OutputFormat::Json | OutputFormat::Csv | OutputFormat::Sarif
+    ) {
+        return Err(StringyError::ConfigError(
+            "Directory input needs a format that can label files: table, jsonl, markdown or yara"
+                .to_string(),
+        ));
+    }
+
+    let files = collect_files(&cli.input, cli.recursive)?;
+    let mut out = target.open()?;
+    let mut skipped = 0;
+    let mut first = true;
+    for path in &files {
+        let strings = match open_input(path) {
+            Ok(data) if is_text(&data) => {
+                log::info!("Skipping {}: not a binary file", path.display());
+                continue;
+            }
+            Ok(data) => analyze_input(&data, cli, &config),
+            Err(err) => Err(err),
+        };
+        let (binary_format, strings) = match strings {
+            Ok(analyzed) => analyzed,
+            Err(err) => {
+                log::warn!("Skipping {}: {}", path.display(), err);
+                skipped += 1;
+                continue;
+            }
+        };
+        if !first && format != OutputFormat::Jsonl {
+            writeln!(out)?;
+        }
+        first = false;
+        let formatter = format.formatter(output_config(cli, path, binary_format, true, color))?;
+        formatter.format(&strings, &mut out)?;
+    }
+    out.finish()?;
+    Ok(skipped)
+}
+
+fn run(cli: &Cli) -> stringy::Result<usize> {
+    if cli.imports_json || cli.exports_json {
+        dump_symbol_tables(cli)?;
+        return Ok(0);
+    }
+
+    log::debug!("Input: {}", cli.input.display());
+    extract_strings(cli)
+}
+
+/// Exits with status 1, after reporting the error on stderr, if the input
+/// cannot be read or parsed, or if any file of an input directory had to be
+/// skipped.
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if let Err(err) = check_lengths(&cli) {
+        err.exit();
+    }
+    init_logging(&cli);
+    let name = if cli.input.as_os_str() == STDIN {
+        "stdin".to_string()
+    } else {
+        cli.input.display().to_string()
+    };
+
+    match run(&cli) {
+        Ok(0) => ExitCode::SUCCESS,
+        Ok(skipped) => {
+            eprintln!(
+                "stringy: {}: {} file(s) could not be analyzed",
+                name, skipped
+            );
+            ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("stringy: {}: {}", name, err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_level() {
+        assert_eq!(log_level(0, false), LevelFilter::Warn);
+        assert_eq!(log_level(1, false), LevelFilter::Info);
+        assert_eq!(log_level(2, false), LevelFilter::Debug);
+        assert_eq!(log_level(5, false), LevelFilter::Trace);
+        assert_eq!(log_level(0, true), LevelFilter::Error);
+    }
+
+    #[test]
+    fn test_quiet_conflicts_with_verbose() {
+        assert!(Cli::try_parse_from(["stringy", "-q", "-v", "a.bin"]).is_err());
+        let cli = Cli::try_parse_from(["stringy", "-vv", "a.bin"]).unwrap();
+        assert_eq!(cli.verbose, 2);
+    }
+
+    #[test]
+    fn test_extraction_and_output_flags() {
+        let cli = Cli::try_parse_from([
+            "stringy",
+            "--min-len",
+            "6",
+            "--encoding",
+            "ascii,utf16le",
+            "--encoding",
+            "utf8",
+            "--ascii-charset",
+            "printable,newline",
+            "--format",
+            "csv",
+            "--sort",
+            "offset",
+            "--limit",
+            "10",
+            "a.bin",
+        ])
+        .unwrap();
+        assert_eq!(cli.min_len, 6);
+        assert_eq!(
+            cli.encodings,
+            vec![Encoding::Ascii, Encoding::Utf16Le, Encoding::Utf8]
+        );
+        assert_eq!(
+            cli.ascii_charset,
+            Some(
+                AsciiCharset::printable()
+                    .without(b'\t')
+                    .with(b'\n')
+                    .with(b'\r')
+            )
+        );
+        assert_eq!(cli.format, Some(OutputFormat::Csv));
+        assert_eq!(cli.sort, SortKey::Offset);
+        assert_eq!(cli.limit, Some(10));
+
+        let defaults = Cli::try_parse_from(["stringy", "a.bin"]).unwrap();
+        assert_eq!(defaults.min_len, DEFAULT_MIN_LENGTH);
+        assert_eq!(defaults.max_len, DEFAULT_MAX_LENGTH);
+        assert!(!defaults.xrefs);
+        assert!(defaults.encodings.is_empty());
+        assert_eq!(defaults.ascii_charset, None);
+        assert!(Cli::try_parse_from(["stringy", "--ascii-charset", "emoji", "a.bin"]).is_err());
+        assert_eq!(defaults.format, None);
+        assert_eq!(output_format(&defaults), OutputFormat::Table);
+        assert_eq!(output_target(&defaults), OutputTarget::Stdout);
+        assert_eq!(defaults.sort, SortKey::Score);
+        assert_eq!(defaults.limit, None);
+    }
+
+    #[test]
+    fn test_max_len_flag() {
+        let cli = Cli::try_parse_from(["stringy", "--max-len", "64", "a.bin"]).unwrap();
+        assert_eq!(cli.max_len, 64);
+        assert!(check_lengths(&cli).is_ok());
+
+        // Equal bounds are allowed; a maximum below the minimum is not
+        let cli =
+            Cli::try_parse_from(["stringy", "--min-len", "8", "--max-len", "8", "a.bin"]).unwrap();
+        assert!(check_lengths(&cli).is_ok());
+        let cli =
+            Cli::try_parse_from(["stringy", "--min-len", "8", "--max-len", "4", "a.bin"]).unwrap();
+        let err = check_lengths(&cli).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+        assert!(err.to_string().contains("--max-len (4)"), "{err}");
+
+        assert!(Cli::try_parse_from(["stringy", "--max-len", "0", "a.bin"]).is_err());
+    }
+
+    #[test]
+    fn test_region_flags() {
+        let cli = Cli::try_parse_from([
+            "stringy",
+            "--section",
+            ".rdata",
+            "--section",
+            ".data",
+            "--offset",
+            "0x400:4096",
+            "a.bin",
+        ])
+        .unwrap();
+        assert_eq!(cli.sections, vec![".rdata", ".data"]);
+        assert_eq!(cli.offset, Some(0x400..4096));
+
+        assert_eq!(parse_offset_range(":0x10"), Ok(0..0x10));
+        assert_eq!(parse_offset_range("16:"), Ok(16..u64::MAX));
+        for invalid in ["0x10", "0x20:0x10", "5:5", "x:10", "0x:"] {
+            assert!(parse_offset_range(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_base_address_flag() {
+        let cli =
+            Cli::try_parse_from(["stringy", "--base-address", "0x7ff600000000", "a.bin"]).unwrap();
+        assert_eq!(cli.base_address, Some(0x7ff6_0000_0000));
+
+        assert_eq!(parse_base_address("400000"), Ok(0x40_0000));
+        let err = Cli::try_parse_from(["stringy", "--base-address", "0x1234", "a.bin"])
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("not aligned to 0x1000"), "{err}");
+        assert!(parse_base_address("0xzz").is_err());
+    }
+
+    #[test]
+    fn test_stats_flag() {
+        let cli = Cli::try_parse_from(["stringy", "--stats", "a.bin"]).unwrap();
+        assert_eq!(cli.format, Some(OutputFormat::Summary));
+
+        let cli = Cli::try_parse_from(["stringy", "--format", "summary", "a.bin"]).unwrap();
+        assert_eq!(cli.format, Some(OutputFormat::Summary));
+
+        assert!(Cli::try_parse_from(["stringy", "--stats", "--format", "csv", "a.bin"]).is_err());
+    }
+
+    #[test]
+    fn test_signature_format() {
+        let cli = Cli::try_parse_from(["stringy", "a.bin"]).unwrap();
+        assert_eq!(cli.genericity, Genericity::Balanced);
+
+        let cli = Cli::try_parse_from([
+            "stringy",
+            "--format",
+            "signature",
+            "--genericity",
+            "strict",
+            "a.bin",
+        ])
+        .unwrap();
+        assert_eq!(cli.format, Some(OutputFormat::Signature));
+        assert_eq!(cli.genericity, Genericity::Strict);
+
+        assert!(Cli::try_parse_from(["stringy", "--genericity", "loose", "a.bin"]).is_err());
+    }
+
+    #[test]
+    fn test_output_flag() {
+        let cli = Cli::try_parse_from(["stringy", "-o", "out.csv", "a.bin"]).unwrap();
+        assert_eq!(output_format(&cli), OutputFormat::Csv);
+        assert_eq!(
+            output_target(&cli),
+            OutputTarget::File(PathBuf::from("out.csv"))
+        );
+
+        // An explicit format wins over the extension
+        let cli = Cli::try_parse_from([
+            "stringy", "--output", "out.csv", "--format", "jsonl", "a.bin",
+        ])
+        .unwrap();
+        assert_eq!(output_format(&cli), OutputFormat::Jsonl);
+
+        let cli = Cli::try_parse_from(["stringy", "-o", "strings.out", "a.bin"]).unwrap();
+        assert_eq!(output_format(&cli), OutputFormat::Table);
+    }
+
+    #[test]
+    fn test_filter_flags() {
+        let cli = Cli::try_parse_from([
+            "stringy",
+            "--tag",
+            "url",
+            "--tag",
+            "ipv4",
+            "--min-score",
+            "-5",
+            "a.bin",
+        ])
+        .unwrap();
+        assert_eq!(
+            output_filter(&cli),
+            OutputFilter {
+                min_score: Some(-5),
+                tags_any: vec![Tag::Url, Tag::IPv4],
+                ..OutputFilter::default()
+            }
+        );
+
+        let defaults = Cli::try_parse_from(["stringy", "a.bin"]).unwrap();
+        assert_eq!(output_filter(&defaults), OutputFilter::default());
+    }
+
+    #[test]
+    fn test_is_text() {
+        assert!(is_text(b"#!/bin/sh\necho hello\n"));
+        assert!(is_text("na\u{ef}ve caf\u{e9}".as_bytes()));
+        assert!(is_text(b""));
+
+        // Binary data, or text behind a known magic number
+        assert!(!is_text(b"\0firmware v1.2\0"));
+        assert!(!is_text(b"\xff\xfe\x12\x80 data"));
+        assert!(!is_text(b"!<arch>\nREADME/         0"));
+
+        // A multibyte character cut off by the probe is still text
+        let mut long = "a".repeat(TEXT_PROBE_LEN - 1).into_bytes();
+        long.extend_from_slice("\u{e9}".as_bytes());
+        assert!(is_text(&long));
+    }
+
+    #[test]
+    fn test_invalid_flag_values() {
+        for args in [
+            ["--format", "xml"],
+            ["--encoding", "utf32"],
+            ["--sort", "entropy"],
+            ["--min-len", "0"],
+            ["--offset", "10:2"],
+            ["--tag", "banana"],
+            ["--min-score", "high"],
+        ] {
+            let Err(err) = Cli::try_parse_from(["stringy", args[0], args[1], "a.bin"]) else {
+                panic!("{args:?} accepted");
+            };
+            assert!(
+                matches!(
+                    err.kind(),
+                    ErrorKind::InvalidValue | ErrorKind::ValueValidation
+                ),
+                "{args:?}: {err}"
+            );
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use super::numbers::format_offset;
+use super::{Field, Formatter, OutputConfig};
+use crate::types::{FoundString, Result};
+use std::borrow::Cow;
+use std::io::Write;
+
+/// Comma-separated values per RFC 4180.
+///
+/// Writes a header row followed by one record per string, with the columns
+/// taken from `OutputConfig::fields`. Records end in CRLF, missing values are
+/// empty cells, and tags are joined with `;` into a single cell. Text is
+/// written in full, demangled if the string has a demangled name;
+/// `OutputConfig::truncate` only applies to the table view.
+#[derive(Debug, Clone, Default)]
+pub struct CsvFormatter {
+    config: OutputConfig,
+}
+
+impl CsvFormatter {
+    pub fn new(config: OutputConfig) -> Self {
+        Self { config }
+    }
+
+    fn cell(&self, string: &FoundString, field: Field) -> String {
+        match field {
+            Field::Offset => format_offset(string.offset, self.config.offset_style),
+            Field::Rva => string
+                .rva
+                .map(|rva| format_offset(rva, self.config.offset_style))
+                .unwrap_or_default(),
+            Field::Section => string.section.clone().unwrap_or_default(),
+            Field::Encoding => string.encoding.to_string(),
+            Field::Score => string.score.to_string(),
+            Field::Tags => string
+                .tags
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(";"),
+            Field::Text => string.display_text().to_string(),
+        }
+    }
+
+    fn write_record(writer: &mut dyn Write, cells: &[String]) -> Result<()> {
+        let record: Vec<Cow<'_, str>> = cells.iter().map(|cell| quote(cell)).collect();
+        write!(writer, "{}\r\n", record.join(","))?;
+        Ok(())
+    }
+}
+
+/// Quotes a field if it contains a comma, double quote or line break,
+/// doubling any embedded quotes.
+fn quote(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\r', '\n']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+impl Formatter for CsvFormatter {
+    fn format(&self, strings: &[FoundString], writer: &mut dyn Write) -> Result<()> {
+        let fields = &self.config.fields;
+        let header: Vec<String> = fields.iter().map(|f| f.name().to_string()).collect();
+        Self::write_record(writer, &header)?;
+        for string in strings {
+            let row: Vec<String> = fields.iter().map(|&f| self.cell(string, f)).collect();
+            Self::write_record(writer, &row)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Encoding, StringSource, Tag};
+
+    fn found(text: &str) -> FoundString {
+        FoundString::builder()
+            .text(text)
+            .encoding(Encoding::Utf8)
+            .offset(0x1000)
+            .length(text.len() as u32)
+            .source(StringSource::SectionData)
+            .section(".rdata")
+            .score(25)
+            .build()
+            .unwrap()
+    }
+
+    fn render(formatter: &CsvFormatter, strings: &[FoundString]) -> String {
+        let mut out = Vec::new();
+        formatter.format(strings, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_all_columns() {
+        let mut string = found("https://example.com");
+        string.tags = vec![Tag::Url, Tag::Domain];
+        let output = render(&CsvFormatter::default(), &[string]);
+        assert_eq!(
+            output,
+            "offset,rva,section,encoding,score,tags,text\r\n\
+             0x0000_1000,,.rdata,utf-8,25,url;domain,https://example.com\r\n"
+        );
+    }
+
+    #[test]
+    fn test_embedded_comma() {
+        let formatter = CsvFormatter::new(OutputConfig {
+            fields: vec![Field::Offset, Field::Text, Field::Score],
+            ..OutputConfig::default()
+        });
+        let output = render(&formatter, &[found("Hello, world")]);
+        assert_eq!(
+            output,
+            "offset,text,score\r\n0x0000_1000,\"Hello, world\",25\r\n"
+        );
+    }
+
+    #[test]
+    fn test_embedded_quote_and_newline() {
+        let formatter = CsvFormatter::new(OutputConfig {
+            fields: vec![Field::Text],
+            ..OutputConfig::default()
+        });
+        let output = render(
+            &formatter,
+            &[found("say \"hi\""), found("two\nlines"), found("plain")],
+        );
+        assert_eq!(
+            output,
+            "text\r\n\"say \"\"hi\"\"\"\r\n\"two\nlines\"\r\nplain\r\n"
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use crate::types::{FoundString, Tag};
+
+/// Criteria for narrowing results before they are formatted.
+///
+/// Every criterion that is set must hold for a string to be kept; the
+/// default filter keeps everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutputFilter {
+    /// Minimum score, inclusive
+    pub min_score: Option<i32>,
+    /// Keep strings with at least one of these tags; ignored when empty
+    pub tags_any: Vec<Tag>,
+    /// Keep strings with all of these tags
+    pub tags_all: Vec<Tag>,
+    /// Keep strings found in one of these sections, by exact name. Strings
+    /// without a section are dropped when this is set.
+    pub sections: Option<Vec<String>>,
+}
+
+impl OutputFilter {
+    /// Returns `true` if `string` meets every criterion.
+    pub fn matches(&self, string: &FoundString) -> bool {
+        if self.min_score.is_some_and(|min| string.score < min) {
+            return false;
+        }
+        if !self.tags_any.is_empty() && !self.tags_any.iter().any(|t| string.tags.contains(t)) {
+            return false;
+        }
+        if !self.tags_all.iter().all(|t| string.tags.contains(t)) {
+            return false;
+        }
+        match (&self.sections, &string.section) {
+            (None, _) => true,
+            (Some(sections), Some(section)) => sections.contains(section),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// Returns the strings that pass `filter`, in their original order.
+pub fn filter<'a>(strings: &'a [FoundString], filter: &OutputFilter) -> Vec<&'a FoundString> {
+    strings.iter().filter(|s| filter.matches(s)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Encoding, StringSource};
+
+    fn found(text: &str, score: i32, section: Option<&str>, tags: Vec<Tag>) -> FoundString {
+        FoundString {
+            section: section.map(str::to_string),
+            ..FoundString::builder()
+                .text(text)
+                .encoding(Encoding::Ascii)
+                .offset(0)
+                .length(text.len() as u32)
+                .source(StringSource::SectionData)
+                .tags(tags)
+                .score(score)
+                .build()
+                .unwrap()
+        }
+    }
+
+    fn texts<'a>(strings: &[&'a FoundString]) -> Vec<&'a str> {
+        strings.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    fn sample() -> Vec<FoundString> {
+        vec![
+            found(
+                "https://a.example",
+                40,
+                Some(".rdata"),
+                vec![Tag::Url, Tag::Domain],
+            ),
+            found("a.example", 15, Some(".rdata"), vec![Tag::Domain]),
+            found("http://b.example/x", 35, Some(".data"), vec![Tag::Url]),
+            found("hello world", 5, None, vec![]),
+            found("Error: %s", 30, Some(".rdata"), vec![Tag::FormatString]),
+        ]
+    }
+
+    #[test]
+    fn test_default_keeps_everything() {
+        let strings = sample();
+        assert_eq!(filter(&strings, &OutputFilter::default()).len(), 5);
+    }
+
+    #[test]
+    fn test_url_tagged_only() {
+        let strings = sample();
+        let only_urls = OutputFilter {
+            tags_any: vec![Tag::Url],
+            ..OutputFilter::default()
+        };
+        assert_eq!(
+            texts(&filter(&strings, &only_urls)),
+            vec!["https://a.example", "http://b.example/x"]
+        );
+
+        let both = OutputFilter {
+            tags_all: vec![Tag::Url, Tag::Domain],
+            ..OutputFilter::default()
+        };
+        assert_eq!(texts(&filter(&strings, &both)), vec!["https://a.example"]);
+    }
+
+    #[test]
+    fn test_score_floor_and_section() {
+        let strings = sample();
+        let criteria = OutputFilter {
+            min_score: Some(30),
+            sections: Some(vec![".rdata".to_string()]),
+            ..OutputFilter::default()
+        };
+        assert_eq!(
+            texts(&filter(&strings, &criteria)),
+            vec!["https://a.example", "Error: %s"]
+        );
+    }
+}
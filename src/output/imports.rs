@@ -0,0 +1,188 @@
+use crate::classification::symbols::{RiskLevel, import_risk};
+use crate::types::{ContainerInfo, ExportInfo, ImportInfo, Result};
+use serde::Serialize;
+
+/// Imports resolved from one library.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImportGroup<'a> {
+    /// Library the symbols come from, if the format records it
+    pub library: Option<&'a str>,
+    /// Imported symbols in table order
+    pub symbols: Vec<ImportedSymbol<'a>>,
+}
+
+/// An imported symbol within an [`ImportGroup`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImportedSymbol<'a> {
+    /// Symbol name
+    pub name: &'a str,
+    /// Import address or ordinal, if known
+    pub address: Option<u64>,
+    /// Demangled display name, if the symbol is mangled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub demangled: Option<&'a str>,
+    /// Whether the import goes through the PE delay-load import table
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub delayed: bool,
+    /// GNU symbol version the import requires, if versioned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<&'a str>,
+    /// Triage rating of the API; see [`import_risk`]
+    #[serde(skip_serializing_if = "RiskLevel::is_low")]
+    pub risk: RiskLevel,
+}
+
+/// Groups imports by library, keeping libraries in first-seen order.
+///
+/// PE import tables list symbols per DLL, so grouping mirrors
+/// `dumpbin /imports`. Formats that don't record a library per import
+/// produce a single group with `library: None`.
+pub fn group_imports(imports: &[ImportInfo]) -> Vec<ImportGroup<'_>> {
+    let mut groups: Vec<ImportGroup<'_>> = Vec::new();
+
+    for import in imports {
+        let library = import.library.as_deref();
+        let symbol = ImportedSymbol {
+            name: &import.name,
+            address: import.address,
+            demangled: import.demangled.as_deref(),
+            delayed: import.delayed,
+            version: import.version.as_deref(),
+            risk: import_risk(&import.name, library),
+        };
+        match groups.iter_mut().find(|g| g.library == library) {
+            Some(group) => group.symbols.push(symbol),
+            None => groups.push(ImportGroup {
+                library,
+                symbols: vec![symbol],
+            }),
+        }
+    }
+
+    groups
+}
+
+/// Import and export tables selected for JSON output.
+#[derive(Debug, Serialize)]
+struct SymbolTables<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    imports: Option<Vec<ImportGroup<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    high_risk_imports: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exports: Option<&'a [ExportInfo]>,
+}
+
+/// Serializes the import and/or export tables of a container as pretty JSON.
+///
+/// The result is an object with an `imports` key (grouped by library, see
+/// [`group_imports`]) and/or an `exports` key, depending on the flags. With
+/// imports, `high_risk_imports` counts those rated [`RiskLevel::High`], and
+/// each symbol rated above low carries its `risk`.
+pub fn symbol_tables_json(
+    info: &ContainerInfo,
+    include_imports: bool,
+    include_exports: bool,
+) -> Result<String> {
+    let imports = include_imports.then(|| group_imports(&info.imports));
+    let high_risk_imports = imports.as_ref().map(|groups| {
+        groups
+            .iter()
+            .flat_map(|group| &group.symbols)
+            .filter(|symbol| symbol.risk == RiskLevel::High)
+            .count()
+    });
+    let tables = SymbolTables {
+        imports,
+        high_risk_imports,
+        exports: include_exports.then_some(info.exports.as_slice()),
+    };
+    Ok(serde_json::to_string_pretty(&tables)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::BinaryFormat;
+    use serde_json::{Value, json};
+
+    fn import(name: &str, library: Option<&str>, address: u64) -> ImportInfo {
+        ImportInfo {
+            name: name.to_string(),
+            library: library.map(str::to_string),
+            address: Some(address),
+            demangled: None,
+            delayed: false,
+            version: None,
+        }
+    }
+
+    fn container() -> ContainerInfo {
+        ContainerInfo {
+            format: BinaryFormat::Pe,
+            is_64bit: true,
+            architecture: None,
+            sections: Vec::new(),
+            imports: vec![
+                import("CreateFileW", Some("KERNEL32.dll"), 0x10),
+                import("MessageBoxW", Some("USER32.dll"), 0x20),
+                import("ReadFile", Some("KERNEL32.dll"), 0x18),
+                import("WriteProcessMemory", Some("KERNEL32.dll"), 0x28),
+            ],
+            exports: vec![ExportInfo {
+                name: "DllMain".to_string(),
+                address: 0x1000,
+                ordinal: Some(1),
+                demangled: None,
+                forwarded_to: None,
+                version: None,
+                version_hidden: false,
+            }],
+            elf: None,
+            rich_header: None,
+            tls_callbacks: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_group_imports_by_library() {
+        let info = container();
+        let groups = group_imports(&info.imports);
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].library, Some("KERNEL32.dll"));
+        let names: Vec<&str> = groups[0].symbols.iter().map(|s| s.name).collect();
+        assert_eq!(names, vec!["CreateFileW", "ReadFile", "WriteProcessMemory"]);
+        assert_eq!(groups[1].library, Some("USER32.dll"));
+    }
+
+    #[test]
+    fn test_symbol_tables_json() {
+        let info = container();
+
+        let both: Value =
+            serde_json::from_str(&symbol_tables_json(&info, true, true).unwrap()).unwrap();
+        assert_eq!(
+            both["imports"][0],
+            json!({
+                "library": "KERNEL32.dll",
+                "symbols": [
+                    {"name": "CreateFileW", "address": 16},
+                    {"name": "ReadFile", "address": 24},
+                    {"name": "WriteProcessMemory", "address": 40, "risk": "high"}
+                ]
+            })
+        );
+        assert_eq!(both["high_risk_imports"], 1);
+        assert_eq!(
+            both["exports"],
+            json!([{"name": "DllMain", "address": 4096, "ordinal": 1}])
+        );
+
+        let exports_only: Value =
+            serde_json::from_str(&symbol_tables_json(&info, false, true).unwrap()).unwrap();
+        assert!(exports_only.get("imports").is_none());
+        assert!(exports_only.get("high_risk_imports").is_none());
+        assert!(exports_only.get("exports").is_some());
+    }
+}
@@ -0,0 +1,235 @@
+use super::{Formatter, Summary};
+use crate::types::{BinaryFormat, FoundString, Result};
+use serde::Serialize;
+use std::io::Write;
+
+/// JSON Lines: one JSON object per string, one string per line.
+///
+/// Each object is the serde serialization of [`FoundString`], so text is
+/// never truncated and every field is present. With a file set, each object
+/// also carries a leading `file` field naming the input it came from.
+#[derive(Debug, Clone, Default)]
+pub struct JsonlFormatter {
+    file: Option<String>,
+}
+
+/// A string labelled with the file it was found in.
+#[derive(Serialize)]
+struct FileRecord<'a> {
+    file: &'a str,
+    #[serde(flatten)]
+    string: &'a FoundString,
+}
+
+impl JsonlFormatter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Labels every record with `file`, or with nothing if `None`.
+    pub fn with_file(mut self, file: Option<String>) -> Self {
+        self.file = file;
+        self
+    }
+}
+
+impl Formatter for JsonlFormatter {
+    fn format(&self, strings: &[FoundString], writer: &mut dyn Write) -> Result<()> {
+        for string in strings {
+            match &self.file {
+                Some(file) => serde_json::to_writer(&mut *writer, &FileRecord { file, string })?,
+                None => serde_json::to_writer(&mut *writer, string)?,
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+}
+
+/// A single JSON document: an array of strings, pretty-printed by default.
+///
+/// With an [`Envelope`], the array becomes the `strings` field of an object
+/// that also names the input file and format and carries a [`Summary`].
+#[derive(Debug, Clone)]
+pub struct JsonFormatter {
+    pretty: bool,
+    envelope: Option<Envelope>,
+}
+
+/// Input metadata written around the strings by [`JsonFormatter`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Envelope {
+    /// Input file the strings came from
+    pub file: Option<String>,
+    /// Container format of the input
+    pub format: Option<BinaryFormat>,
+}
+
+/// The enveloped document; unknown metadata is written as `null`.
+#[derive(Serialize)]
+struct Document<'a> {
+    file: Option<&'a str>,
+    format: Option<BinaryFormat>,
+    summary: Summary,
+    strings: &'a [FoundString],
+}
+
+impl Default for JsonFormatter {
+    fn default() -> Self {
+        Self {
+            pretty: true,
+            envelope: None,
+        }
+    }
+}
+
+impl JsonFormatter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Indents the document if `pretty`, or writes it on one line.
+    pub fn with_pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Wraps the strings in `envelope`, or writes a bare array if `None`.
+    pub fn with_envelope(mut self, envelope: Option<Envelope>) -> Self {
+        self.envelope = envelope;
+        self
+    }
+
+    fn write<T: Serialize>(&self, value: &T, writer: &mut dyn Write) -> Result<()> {
+        if self.pretty {
+            serde_json::to_writer_pretty(&mut *writer, value)?;
+        } else {
+            serde_json::to_writer(&mut *writer, value)?;
+        }
+        writeln!(writer)?;
+        Ok(())
+    }
+}
+
+impl Formatter for JsonFormatter {
+    fn format(&self, strings: &[FoundString], writer: &mut dyn Write) -> Result<()> {
+        match &self.envelope {
+            Some(envelope) => {
+                let document = Document {
+                    file: envelope.file.as_deref(),
+                    format: envelope.format,
+                    summary: Summary::new(strings),
+                    strings,
+                };
+                self.write(&document, writer)
+            }
+            None => self.write(&strings, writer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Encoding, StringSource, Tag};
+
+    fn found(text: &str, offset: u64) -> FoundString {
+        FoundString::builder()
+            .text(text)
+            .encoding(Encoding::Ascii)
+            .offset(offset)
+            .length(text.len() as u32)
+            .source(StringSource::SectionData)
+            .section(".rodata")
+            .tags(vec![Tag::FormatString])
+            .score(12)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_jsonl_one_object_per_line() {
+        let strings = vec![found("Error: %s\n", 0x10), found("line %d", 0x20)];
+        let mut out = Vec::new();
+        JsonlFormatter::new().format(&strings, &mut out).unwrap();
+        let output = String::from_utf8(out).unwrap();
+
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let parsed: Vec<FoundString> = lines
+            .iter()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(parsed[0].text, "Error: %s\n");
+        assert_eq!(parsed[1].offset, 0x20);
+    }
+
+    #[test]
+    fn test_jsonl_file_field() {
+        let mut out = Vec::new();
+        JsonlFormatter::new()
+            .with_file(Some("bin/app".to_string()))
+            .format(&[found("hello", 4)], &mut out)
+            .unwrap();
+        let output = String::from_utf8(out).unwrap();
+
+        assert!(output.starts_with("{\"file\":\"bin/app\",\"text\":\"hello\""));
+        let parsed: FoundString = serde_json::from_str(output.trim_end()).unwrap();
+        assert_eq!(parsed.offset, 4);
+    }
+
+    #[test]
+    fn test_json_array() {
+        let strings = vec![found("first", 0), found("second", 8)];
+        let mut out = Vec::new();
+        JsonFormatter::new().format(&strings, &mut out).unwrap();
+
+        let parsed: Vec<FoundString> = serde_json::from_slice(&out).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[1].text, "second");
+
+        let mut empty = Vec::new();
+        JsonFormatter::new().format(&[], &mut empty).unwrap();
+        assert_eq!(empty, b"[]\n");
+
+        let mut compact = Vec::new();
+        JsonFormatter::new()
+            .with_pretty(false)
+            .format(&strings, &mut compact)
+            .unwrap();
+        assert_eq!(compact.iter().filter(|&&byte| byte == b'\n').count(), 1);
+        let parsed: Vec<FoundString> = serde_json::from_slice(&compact).unwrap();
+        assert_eq!(parsed.len(), strings.len());
+    }
+
+    #[test]
+    fn test_json_envelope() {
+        let strings = vec![found("first", 0), found("second", 8), found("third", 16)];
+        let mut out = Vec::new();
+        JsonFormatter::new()
+            .with_envelope(Some(Envelope {
+                file: Some("bin/app".to_string()),
+                format: Some(BinaryFormat::Elf),
+            }))
+            .format(&strings, &mut out)
+            .unwrap();
+
+        let document: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(document["file"], "bin/app");
+        assert_eq!(document["format"], "elf");
+        assert_eq!(document["summary"]["strings"], 3);
+        assert_eq!(document["summary"]["tags"]["fmt"], 3);
+        let parsed: Vec<FoundString> = serde_json::from_value(document["strings"].clone()).unwrap();
+        assert_eq!(parsed.len(), strings.len());
+        assert_eq!(parsed[2].text, "third");
+
+        let mut unknown = Vec::new();
+        JsonFormatter::new()
+            .with_envelope(Some(Envelope::default()))
+            .format(&[], &mut unknown)
+            .unwrap();
+        let document: serde_json::Value = serde_json::from_slice(&unknown).unwrap();
+        assert!(document["file"].is_null());
+        assert_eq!(document["strings"], serde_json::json!([]));
+    }
+}
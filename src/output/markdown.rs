@@ -0,0 +1,358 @@
+use super::numbers::format_offset;
+use super::table::display_text;
+use super::{Field, Formatter, OutputConfig, Summary};
+use crate::classification::symbols::{RiskLevel, import_risk};
+use crate::types::{FoundString, Result, Tag};
+use std::cmp::Reverse;
+use std::io::Write;
+
+/// Maximum number of rows in each table of a Markdown report.
+pub const REPORT_ROWS: usize = 50;
+
+/// Placeholder for a missing value, such as the RVA of an overlay string.
+const EMPTY: &str = "-";
+
+/// Markdown report for pasting into issues and wikis.
+///
+/// The report opens with the input file, its format and a [`Summary`], then
+/// holds three GitHub-flavored tables:
+///
+/// - **Top strings**: the first [`REPORT_ROWS`] strings in the order given,
+///   with the columns of `OutputConfig::fields`
+/// - **Notable imports**: imports of sensitive APIs, highest
+///   [`import_risk`] first
+/// - **Indicators**: strings tagged as network, file system, registry or
+///   command-line indicators
+///
+/// String text, demangled for symbols that have a demangled name, is
+/// truncated like the table view and written as a code span, so Markdown in
+/// it is shown literally; pipes are escaped so they cannot end a cell.
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownFormatter {
+    config: OutputConfig,
+}
+
+impl MarkdownFormatter {
+    pub fn new(config: OutputConfig) -> Self {
+        Self { config }
+    }
+
+    fn text(&self, text: &str) -> String {
+        match display_text(text, self.config.truncate) {
+            (shown, Some(chars)) => format!("{} ({} chars)", code_span(&shown), chars),
+            (shown, None) => code_span(&shown),
+        }
+    }
+
+    fn cell(&self, string: &FoundString, field: Field) -> String {
+        match field {
+            Field::Offset => format_offset(string.offset, self.config.offset_style),
+            Field::Rva => string.rva.map_or_else(
+                || EMPTY.to_string(),
+                |rva| format_offset(rva, self.config.offset_style),
+            ),
+            Field::Section => escape_pipes(string.section.as_deref().unwrap_or(EMPTY)),
+            Field::Encoding => string.encoding.to_string(),
+            Field::Score => string.score.to_string(),
+            Field::Tags => tag_list(&string.tags),
+            Field::Text => self.text(string.display_text()),
+        }
+    }
+
+    fn write_header(&self, strings: &[FoundString], writer: &mut dyn Write) -> Result<()> {
+        let summary = Summary::new(strings);
+        match &self.config.file {
+            Some(file) => writeln!(writer, "# Strings in {}", code_span(file))?,
+            None => writeln!(writer, "# Strings")?,
+        }
+        writeln!(writer)?;
+        if let Some(format) = self.config.binary_format {
+            writeln!(writer, "- **Format:** {}", format)?;
+        }
+        match summary.max_score {
+            Some(max_score) => writeln!(
+                writer,
+                "- **Strings:** {} (highest score {})",
+                summary.strings, max_score
+            )?,
+            None => writeln!(writer, "- **Strings:** 0")?,
+        }
+        if !summary.tags.is_empty() {
+            let tags: Vec<String> = summary
+                .tags
+                .iter()
+                .map(|(tag, count)| format!("{} ({})", escape_pipes(tag), count))
+                .collect();
+            writeln!(writer, "- **Tags:** {}", tags.join(", "))?;
+        }
+        Ok(())
+    }
+
+    fn write_top_strings(&self, strings: &[FoundString], writer: &mut dyn Write) -> Result<()> {
+        let fields = &self.config.fields;
+        writeln!(writer, "## Top strings")?;
+        writeln!(writer)?;
+        if strings.is_empty() {
+            writeln!(writer, "None.")?;
+            return Ok(());
+        }
+        let header: Vec<&str> = fields.iter().map(|field| field.name()).collect();
+        let align: Vec<&str> = fields
+            .iter()
+            .map(|&field| if field == Field::Score { "--:" } else { "---" })
+            .collect();
+        write_row(writer, &header)?;
+        write_row(writer, &align)?;
+        for string in strings.iter().take(REPORT_ROWS) {
+            let row: Vec<String> = fields.iter().map(|&f| self.cell(string, f)).collect();
+            write_row(writer, &row)?;
+        }
+        write_hidden(writer, strings.len())
+    }
+
+    fn write_imports(&self, strings: &[FoundString], writer: &mut dyn Write) -> Result<()> {
+        let mut imports: Vec<(RiskLevel, &FoundString)> = strings
+            .iter()
+            .filter(|string| string.tags.contains(&Tag::Import))
+            .map(|string| (import_risk(&string.text, string.section.as_deref()), string))
+            .filter(|(risk, _)| !risk.is_low())
+            .collect();
+        imports.sort_by_key(|&(risk, _)| Reverse(risk));
+
+        writeln!(writer, "## Notable imports")?;
+        writeln!(writer)?;
+        if imports.is_empty() {
+            writeln!(writer, "None.")?;
+            return Ok(());
+        }
+        write_row(writer, &["risk", "import", "library"])?;
+        write_row(writer, &["---", "---", "---"])?;
+        for (risk, string) in imports.iter().take(REPORT_ROWS) {
+            let risk = match risk {
+                RiskLevel::High => "high",
+                RiskLevel::Medium => "medium",
+                RiskLevel::Low => "low",
+            };
+            let library = escape_pipes(string.section.as_deref().unwrap_or(EMPTY));
+            write_row(writer, &[risk, &self.text(string.display_text()), &library])?;
+        }
+        write_hidden(writer, imports.len())
+    }
+
+    fn write_indicators(&self, strings: &[FoundString], writer: &mut dyn Write) -> Result<()> {
+        let indicators: Vec<&FoundString> = strings
+            .iter()
+            .filter(|string| string.tags.iter().any(is_indicator))
+            .collect();
+
+        writeln!(writer, "## Indicators")?;
+        writeln!(writer)?;
+        if indicators.is_empty() {
+            writeln!(writer, "None.")?;
+            return Ok(());
+        }
+        write_row(writer, &["tags", "score", "offset", "text"])?;
+        write_row(writer, &["---", "--:", "---", "---"])?;
+        for string in indicators.iter().take(REPORT_ROWS) {
+            write_row(
+                writer,
+                &[
+                    tag_list(&string.tags),
+                    string.score.to_string(),
+                    format_offset(string.offset, self.config.offset_style),
+                    self.text(string.display_text()),
+                ],
+            )?;
+        }
+        write_hidden(writer, indicators.len())
+    }
+}
+
+/// Tags worth listing as indicators: where a binary connects to, what it
+/// touches on the host, and what it runs.
+fn is_indicator(tag: &Tag) -> bool {
+    matches!(
+        tag,
+        Tag::Url
+            | Tag::Domain
+            | Tag::IPv4
+            | Tag::IPv6
+            | Tag::Email
+            | Tag::FilePath
+            | Tag::RegistryPath
+            | Tag::UserAgent
+            | Tag::Command
+            | Tag::ApiKey
+            | Tag::Jwt
+    )
+}
+
+/// Comma-separated tag names, or `-` if there are none.
+fn tag_list(tags: &[Tag]) -> String {
+    if tags.is_empty() {
+        return EMPTY.to_string();
+    }
+    let names: Vec<String> = tags.iter().map(ToString::to_string).collect();
+    escape_pipes(&names.join(","))
+}
+
+/// Escapes `|` so it does not end a table cell.
+fn escape_pipes(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+/// Wraps `text` in a code span that is safe inside a table cell.
+///
+/// The fence is one backtick longer than the longest backtick run in the
+/// text, and padded with spaces where CommonMark would otherwise eat or
+/// merge the text's own leading and trailing characters. Pipes are escaped;
+/// GitHub unescapes them inside code spans in tables.
+fn code_span(text: &str) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+    let longest_run = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run + 1);
+    let pad = text.starts_with('`')
+        || text.ends_with('`')
+        || (text.starts_with(' ') && text.ends_with(' ') && !text.trim().is_empty());
+    let space = if pad { " " } else { "" };
+    format!("{}{}{}{}{}", fence, space, escape_pipes(text), space, fence)
+}
+
+fn write_row<S: AsRef<str>>(writer: &mut dyn Write, cells: &[S]) -> Result<()> {
+    let cells: Vec<&str> = cells.iter().map(AsRef::as_ref).collect();
+    writeln!(writer, "| {} |", cells.join(" | "))?;
+    Ok(())
+}
+
+/// Notes how many of `total` rows a table left out.
+fn write_hidden(writer: &mut dyn Write, total: usize) -> Result<()> {
+    if total > REPORT_ROWS {
+        writeln!(writer)?;
+        writeln!(writer, "_{} more not shown._", total - REPORT_ROWS)?;
+    }
+    Ok(())
+}
+
+impl Formatter for MarkdownFormatter {
+    fn format(&self, strings: &[FoundString], writer: &mut dyn Write) -> Result<()> {
+        self.write_header(strings, writer)?;
+        writeln!(writer)?;
+        self.write_top_strings(strings, writer)?;
+        writeln!(writer)?;
+        self.write_imports(strings, writer)?;
+        writeln!(writer)?;
+        self.write_indicators(strings, writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{BinaryFormat, Encoding, StringSource};
+
+    fn found(text: &str, offset: u64, tags: Vec<Tag>, score: i32) -> FoundString {
+        FoundString::builder()
+            .text(text)
+            .encoding(Encoding::Ascii)
+            .offset(offset)
+            .length(text.len() as u32)
+            .source(StringSource::SectionData)
+            .section(".rdata")
+            .tags(tags)
+            .score(score)
+            .build()
+            .unwrap()
+    }
+
+    fn render(config: OutputConfig, strings: &[FoundString]) -> String {
+        let mut out = Vec::new();
+        MarkdownFormatter::new(config)
+            .format(strings, &mut out)
+            .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_markdown_report() {
+        let mut import = found("WriteProcessMemory", 0, vec![Tag::Import], 40);
+        import.section = Some("KERNEL32.dll".to_string());
+        import.source = StringSource::ImportName;
+        let strings = vec![
+            found("https://c2.example.net/a|b", 0x40, vec![Tag::Url], 60),
+            import,
+            found("GetTickCount", 0, vec![Tag::Import], 0),
+            found("cmd | grep", 0x80, Vec::new(), 5),
+        ];
+        let config = OutputConfig {
+            file: Some("app.exe".to_string()),
+            binary_format: Some(BinaryFormat::Pe),
+            fields: vec![Field::Offset, Field::Score, Field::Tags, Field::Text],
+            ..OutputConfig::default()
+        };
+        let output = render(config, &strings);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines[0], "# Strings in `app.exe`");
+        assert!(lines.contains(&"- **Format:** PE"), "{output}");
+        assert!(lines.contains(&"- **Strings:** 4 (highest score 60)"));
+        assert!(lines.contains(&"- **Tags:** import (2), url (1)"));
+
+        // Header and delimiter rows of the top strings table
+        let header = lines
+            .iter()
+            .position(|line| *line == "| offset | score | tags | text |")
+            .expect("table header row");
+        assert_eq!(lines[header + 1], "| --- | --: | --- | --- |");
+        assert_eq!(
+            lines[header + 2],
+            "| 0x0000_0040 | 60 | url | `https://c2.example.net/a\\|b` |"
+        );
+        assert!(lines.contains(&"| 0x0000_0080 | 5 | - | `cmd \\| grep` |"));
+
+        // Only the sensitive import is notable
+        assert!(lines.contains(&"| high | `WriteProcessMemory` | KERNEL32.dll |"));
+        assert!(!output.contains("`GetTickCount` | .rdata |"));
+        assert!(lines.contains(&"| url | 60 | 0x0000_0040 | `https://c2.example.net/a\\|b` |"));
+    }
+
+    #[test]
+    fn test_markdown_empty() {
+        let output = render(OutputConfig::default(), &[]);
+        assert!(output.starts_with("# Strings\n\n- **Strings:** 0\n"));
+        assert_eq!(output.matches("None.").count(), 3);
+    }
+
+    #[test]
+    fn test_markdown_rows_capped() {
+        let strings: Vec<FoundString> = (0..REPORT_ROWS as u64 + 3)
+            .map(|i| found("plain", i, Vec::new(), 1))
+            .collect();
+        let output = render(OutputConfig::default(), &strings);
+        assert_eq!(output.matches("| `plain` |").count(), REPORT_ROWS);
+        assert!(output.contains("_3 more not shown._"));
+    }
+
+    #[test]
+    fn test_markdown_truncation() {
+        let config = OutputConfig {
+            truncate: Some(6),
+            fields: vec![Field::Text],
+            ..OutputConfig::default()
+        };
+        let output = render(config, &[found("ü|ber|lange", 0, Vec::new(), 1)]);
+        assert!(output.contains("| `ü\\|ber…` (11 chars) |"), "{output}");
+    }
+
+    #[test]
+    fn test_code_span() {
+        assert_eq!(code_span("plain"), "`plain`");
+        assert_eq!(code_span("a|b"), "`a\\|b`");
+        assert_eq!(code_span("use `x` here"), "``use `x` here``");
+        assert_eq!(code_span("`quoted`"), "`` `quoted` ``");
+        assert_eq!(code_span(" padded "), "`  padded  `");
+        assert_eq!(code_span("  "), "`  `");
+        assert_eq!(code_span(""), "");
+    }
+}
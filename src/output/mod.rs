@@ -1 +1,682 @@
 // Output formatting
+
+use crate::types::{BinaryFormat, FoundString, Result, StringyError};
+use std::fs::File;
+use std::io::{self, BufWriter, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+pub mod csv;
+pub mod filter;
+pub mod imports;
+pub mod json;
+pub mod markdown;
+pub mod numbers;
+pub mod sarif;
+pub mod signature;
+pub mod summary;
+pub mod table;
+pub mod yara;
+
+pub use csv::CsvFormatter;
+pub use filter::{OutputFilter, filter};
+pub use json::{Envelope, JsonFormatter, JsonlFormatter};
+pub use markdown::MarkdownFormatter;
+pub use numbers::OffsetStyle;
+pub use sarif::SarifFormatter;
+pub use signature::{Genericity, SignatureFormatter};
+pub use summary::{Summary, SummaryFormatter};
+pub use table::TableFormatter;
+pub use yara::YaraFormatter;
+
+/// Renders extracted strings in one output format.
+pub trait Formatter {
+    /// Writes `strings`, in the order given, to `writer`.
+    fn format(&self, strings: &[FoundString], writer: &mut dyn Write) -> Result<()>;
+}
+
+/// A column of per-string output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Offset,
+    Rva,
+    Section,
+    Encoding,
+    Score,
+    Tags,
+    Text,
+}
+
+impl Field {
+    /// All fields, in default column order.
+    pub const ALL: [Field; 7] = [
+        Field::Offset,
+        Field::Rva,
+        Field::Section,
+        Field::Encoding,
+        Field::Score,
+        Field::Tags,
+        Field::Text,
+    ];
+
+    /// Column header name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Offset => "offset",
+            Self::Rva => "rva",
+            Self::Section => "section",
+            Self::Encoding => "encoding",
+            Self::Score => "score",
+            Self::Tags => "tags",
+            Self::Text => "text",
+        }
+    }
+}
+
+impl FromStr for Field {
+    type Err = StringyError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let lower = s.to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|field| field.name() == lower)
+            .ok_or_else(|| StringyError::ConfigError(format!("Unknown output field: {}", s)))
+    }
+}
+
+/// When to emit ANSI colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorChoice {
+    /// Color output to a terminal, unless `NO_COLOR` is set
+    #[default]
+    Auto,
+    /// Always color, even when piped
+    Always,
+    /// Never color
+    Never,
+}
+
+impl ColorChoice {
+    /// Settles `Auto` for output that is, or is not, a terminal; `Always`
+    /// and `Never` are returned unchanged.
+    ///
+    /// `Auto` becomes `Never` when the `NO_COLOR` environment variable is set
+    /// to a non-empty value.
+    pub fn resolve(self, is_terminal: bool) -> Self {
+        match self {
+            Self::Auto => {
+                let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+                if is_terminal && !no_color {
+                    Self::Always
+                } else {
+                    Self::Never
+                }
+            }
+            other => other,
+        }
+    }
+
+    /// Returns `true` if colors should be emitted; `Auto` checks whether
+    /// stdout is a terminal.
+    pub fn enabled(self) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => self.resolve(io::stdout().is_terminal()).enabled(),
+        }
+    }
+}
+
+impl FromStr for ColorChoice {
+    type Err = StringyError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            other => Err(StringyError::ConfigError(format!(
+                "Unknown color choice: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Presentation settings shared by the formatters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputConfig {
+    /// When to emit ANSI colors, where the format supports them
+    pub color: ColorChoice,
+    /// Maximum number of characters of string text the table and Markdown
+    /// views show, or `None` to never truncate; JSON, JSON Lines and CSV
+    /// always write the full text
+    pub truncate: Option<usize>,
+    /// Columns to emit, in order
+    pub fields: Vec<Field>,
+    /// How offsets and RVAs are rendered
+    pub offset_style: OffsetStyle,
+    /// Name of the generated YARA rule; sanitized into a valid identifier
+    pub rule_name: String,
+    /// Maximum number of strings in a generated YARA rule
+    pub rule_strings: usize,
+    /// Number of rule strings that must match (`N of them`); `None` requires
+    /// any one of them
+    pub rule_threshold: Option<usize>,
+    /// How aggressively the suggested signature drops strings common to
+    /// unrelated binaries
+    pub genericity: Genericity,
+    /// Input file the strings came from, labelled in output when several
+    /// files are scanned
+    pub file: Option<String>,
+    /// Container format of the input, when known
+    pub binary_format: Option<BinaryFormat>,
+    /// Indent JSON output; otherwise the document is written on one line
+    pub pretty: bool,
+    /// Wrap JSON output in an object with `file`, `format` and `summary`
+    /// metadata
+    pub envelope: bool,
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            color: ColorChoice::Never,
+            truncate: Some(80),
+            fields: Field::ALL.to_vec(),
+            offset_style: OffsetStyle::default(),
+            rule_name: "stringy_strings".to_string(),
+            rule_strings: 20,
+            rule_threshold: None,
+            genericity: Genericity::default(),
+            file: None,
+            binary_format: None,
+            pretty: true,
+            envelope: false,
+        }
+    }
+}
+
+/// Sorts strings into the canonical output order.
+///
+/// Output must be byte-identical across runs for the same input and
+/// configuration, whatever order parallel extraction or hash-based
+/// deduplication produced the strings in. This orders by file offset, then
+/// encoding, then source, with the text and section as final tie-breakers,
+/// giving a total order; see [`FoundString::cmp_canonical`]. Call it as the
+/// last step before formatting.
+pub fn sort_canonical(strings: &mut [FoundString]) {
+    strings.sort_by(FoundString::cmp_canonical);
+}
+
+/// Field to order output by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortKey {
+    /// File offset
+    #[default]
+    Offset,
+    /// Relevance score
+    Score,
+    /// Length in bytes
+    Length,
+    /// String text, compared bytewise
+    Text,
+    /// Section name; strings without a section sort first
+    Section,
+}
+
+impl FromStr for SortKey {
+    type Err = StringyError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "offset" => Ok(Self::Offset),
+            "score" => Ok(Self::Score),
+            "length" | "len" => Ok(Self::Length),
+            "text" => Ok(Self::Text),
+            "section" => Ok(Self::Section),
+            other => Err(StringyError::ConfigError(format!(
+                "Unknown sort key: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Sorts strings by `key`, in descending order if `descending` is set.
+///
+/// Strings that compare equal on `key` are left in canonical order (file
+/// offset, then encoding, then source; see [`sort_canonical`]) whatever the
+/// direction, so the result does not depend on the input order. Sorting by
+/// score descending gives the "most interesting first" view.
+pub fn sort_strings(strings: &mut [FoundString], key: SortKey, descending: bool) {
+    strings.sort_by(|a, b| {
+        let ordering = match key {
+            SortKey::Offset => a.offset.cmp(&b.offset),
+            SortKey::Score => a.score.cmp(&b.score),
+            SortKey::Length => a.length.cmp(&b.length),
+            SortKey::Text => a.text.cmp(&b.text),
+            SortKey::Section => a.section.cmp(&b.section),
+        };
+        let ordering = if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        };
+        ordering.then_with(|| a.cmp_canonical(b))
+    });
+}
+
+/// Output formats supported by the CLI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Human-readable aligned table
+    #[default]
+    Table,
+    /// One JSON object per line
+    Jsonl,
+    /// A single JSON document
+    Json,
+    /// Comma-separated values
+    Csv,
+    /// YARA rule
+    Yara,
+    /// Markdown report
+    Markdown,
+    /// SARIF 2.1.0 log for code scanning
+    Sarif,
+    /// Aggregate counts and the top strings
+    Summary,
+    /// Candidate YARA signature of the most discriminating strings
+    Signature,
+}
+
+impl OutputFormat {
+    /// Infers the output format from a file extension.
+    ///
+    /// Returns `None` when the extension is missing or not recognized.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "json" => Some(Self::Json),
+            "jsonl" | "ndjson" => Some(Self::Jsonl),
+            "csv" => Some(Self::Csv),
+            "yar" | "yara" => Some(Self::Yara),
+            "md" | "markdown" => Some(Self::Markdown),
+            "sarif" => Some(Self::Sarif),
+            "txt" => Some(Self::Table),
+            _ => None,
+        }
+    }
+
+    /// Picks the format to use: an explicit choice always wins, otherwise the
+    /// output path's extension is used, falling back to the table view.
+    pub fn resolve(explicit: Option<Self>, output: Option<&Path>) -> Self {
+        explicit
+            .or_else(|| output.and_then(Self::from_path))
+            .unwrap_or_default()
+    }
+
+    /// Creates the formatter for this format.
+    pub fn formatter(self, config: OutputConfig) -> Result<Box<dyn Formatter>> {
+        match self {
+            Self::Table => Ok(Box::new(TableFormatter::new(config))),
+            Self::Jsonl => Ok(Box::new(JsonlFormatter::new().with_file(config.file))),
+            Self::Json => {
+                let envelope = config.envelope.then_some(Envelope {
+                    file: config.file,
+                    format: config.binary_format,
+                });
+                Ok(Box::new(
+                    JsonFormatter::new()
+                        .with_pretty(config.pretty)
+                        .with_envelope(envelope),
+                ))
+            }
+            Self::Csv => Ok(Box::new(CsvFormatter::new(config))),
+            Self::Yara => Ok(Box::new(YaraFormatter::new(config))),
+            Self::Sarif => Ok(Box::new(SarifFormatter::new(config))),
+            Self::Markdown => Ok(Box::new(MarkdownFormatter::new(config))),
+            Self::Summary => Ok(Box::new(SummaryFormatter::new(config))),
+            Self::Signature => Ok(Box::new(SignatureFormatter::new(config))),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = StringyError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "table" | "human" => Ok(Self::Table),
+            "jsonl" => Ok(Self::Jsonl),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            "yara" | "yar" => Ok(Self::Yara),
+            "markdown" | "md" => Ok(Self::Markdown),
+            "sarif" => Ok(Self::Sarif),
+            "summary" | "stats" => Ok(Self::Summary),
+            "signature" | "sig" => Ok(Self::Signature),
+            other => Err(StringyError::ConfigError(format!(
+                "Unknown output format: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Where formatted output is written.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum OutputTarget {
+    /// Standard output
+    #[default]
+    Stdout,
+    /// A file, created or truncated on open
+    File(PathBuf),
+}
+
+impl OutputTarget {
+    /// Returns `true` if output goes to an interactive terminal.
+    ///
+    /// Files are never terminals, so [`ColorChoice::resolve`] turns color off
+    /// when writing to a file.
+    pub fn is_terminal(&self) -> bool {
+        match self {
+            Self::Stdout => io::stdout().is_terminal(),
+            Self::File(_) => false,
+        }
+    }
+
+    /// Opens the target for writing.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StringyError::ConfigError` if the parent directory of a file
+    /// target does not exist, and `StringyError::IoError` if the file cannot
+    /// be created.
+    pub fn open(&self) -> Result<OutputWriter> {
+        match self {
+            Self::Stdout => Ok(OutputWriter {
+                inner: Box::new(BufWriter::new(io::stdout())),
+            }),
+            Self::File(path) => {
+                if let Some(parent) = path.parent() {
+                    if !parent.as_os_str().is_empty() && !parent.is_dir() {
+                        return Err(StringyError::ConfigError(format!(
+                            "Output directory does not exist: {}",
+                            parent.display()
+                        )));
+                    }
+                }
+                let file = File::create(path)?;
+                Ok(OutputWriter {
+                    inner: Box::new(BufWriter::new(file)),
+                })
+            }
+        }
+    }
+}
+
+/// Buffered writer for an [`OutputTarget`].
+///
+/// Call [`OutputWriter::finish`] once all output is written so flush errors
+/// are reported instead of silently truncating the output.
+pub struct OutputWriter {
+    inner: Box<dyn Write>,
+}
+
+impl OutputWriter {
+    /// Flushes buffered output and closes the target.
+    pub fn finish(mut self) -> Result<()> {
+        self.inner.flush()?;
+        Ok(())
+    }
+}
+
+impl Write for OutputWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Encoding, StringSource};
+
+    fn found(text: &str, offset: u64, encoding: Encoding) -> FoundString {
+        FoundString::builder()
+            .text(text)
+            .encoding(encoding)
+            .offset(offset)
+            .length(text.len() as u32)
+            .source(StringSource::SectionData)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_sort_canonical() {
+        let mut strings = vec![
+            found("later", 0x40, Encoding::Ascii),
+            found("wide", 0x10, Encoding::Utf16Le),
+            found("narrow", 0x10, Encoding::Ascii),
+            found("first", 0x00, Encoding::Utf8),
+        ];
+        sort_canonical(&mut strings);
+
+        let texts: Vec<&str> = strings.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["first", "narrow", "wide", "later"]);
+    }
+
+    fn scored(text: &str, offset: u64, score: i32) -> FoundString {
+        let mut string = found(text, offset, Encoding::Ascii);
+        string.score = score;
+        string
+    }
+
+    fn texts(strings: &[FoundString]) -> Vec<&str> {
+        strings.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sort_by_score_descending() {
+        let mut strings = vec![
+            scored("low", 0x10, 5),
+            scored("tie later", 0x40, 20),
+            scored("high", 0x30, 50),
+            scored("tie earlier", 0x20, 20),
+        ];
+        sort_strings(&mut strings, SortKey::Score, true);
+        assert_eq!(
+            texts(&strings),
+            vec!["high", "tie earlier", "tie later", "low"]
+        );
+
+        sort_strings(&mut strings, SortKey::Score, false);
+        assert_eq!(
+            texts(&strings),
+            vec!["low", "tie earlier", "tie later", "high"]
+        );
+    }
+
+    #[test]
+    fn test_sort_ties_are_deterministic() {
+        let expected = vec!["c", "bb", "a", "dd"];
+        let mut forward = vec![
+            scored("c", 0x00, 1),
+            scored("bb", 0x08, 1),
+            scored("a", 0x10, 1),
+            scored("dd", 0x18, 1),
+        ];
+        let mut backward: Vec<FoundString> = forward.iter().rev().cloned().collect();
+
+        sort_strings(&mut forward, SortKey::Score, true);
+        sort_strings(&mut backward, SortKey::Score, true);
+        assert_eq!(texts(&forward), expected);
+        assert_eq!(texts(&backward), expected);
+
+        sort_strings(&mut forward, SortKey::Length, true);
+        assert_eq!(texts(&forward), vec!["bb", "dd", "c", "a"]);
+        sort_strings(&mut forward, SortKey::Text, false);
+        assert_eq!(texts(&forward), vec!["a", "bb", "c", "dd"]);
+    }
+
+    #[test]
+    fn test_sort_key_from_str() {
+        assert_eq!("Score".parse::<SortKey>().unwrap(), SortKey::Score);
+        assert_eq!("len".parse::<SortKey>().unwrap(), SortKey::Length);
+        assert!("entropy".parse::<SortKey>().is_err());
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            OutputFormat::from_path(Path::new("out.json")),
+            Some(OutputFormat::Json)
+        );
+        assert_eq!(
+            OutputFormat::from_path(Path::new("out.JSONL")),
+            Some(OutputFormat::Jsonl)
+        );
+        assert_eq!(
+            OutputFormat::from_path(Path::new("dir/out.csv")),
+            Some(OutputFormat::Csv)
+        );
+        assert_eq!(
+            OutputFormat::from_path(Path::new("rules.yar")),
+            Some(OutputFormat::Yara)
+        );
+        assert_eq!(
+            OutputFormat::from_path(Path::new("rules.yara")),
+            Some(OutputFormat::Yara)
+        );
+        assert_eq!(
+            OutputFormat::from_path(Path::new("report.md")),
+            Some(OutputFormat::Markdown)
+        );
+        assert_eq!(OutputFormat::from_path(Path::new("noext")), None);
+        assert_eq!(OutputFormat::from_path(Path::new("out.bin")), None);
+    }
+
+    #[test]
+    fn test_format_resolution() {
+        let path = Path::new("out.csv");
+        assert_eq!(OutputFormat::resolve(None, Some(path)), OutputFormat::Csv);
+        assert_eq!(
+            OutputFormat::resolve(Some(OutputFormat::Yara), Some(path)),
+            OutputFormat::Yara
+        );
+        assert_eq!(OutputFormat::resolve(None, None), OutputFormat::Table);
+        assert_eq!(
+            OutputFormat::resolve(None, Some(Path::new("out.bin"))),
+            OutputFormat::Table
+        );
+    }
+
+    /// Truncation is for people; machine-readable formats keep the full text.
+    #[test]
+    fn test_truncate_only_in_views() {
+        let text = "über-long string ".repeat(10);
+        let strings = vec![found(&text, 0, Encoding::Utf8)];
+        let config = OutputConfig {
+            truncate: Some(8),
+            ..OutputConfig::default()
+        };
+        let render = |format: OutputFormat| {
+            let mut out = Vec::new();
+            format
+                .formatter(config.clone())
+                .unwrap()
+                .format(&strings, &mut out)
+                .unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        let json: Vec<FoundString> = serde_json::from_str(&render(OutputFormat::Json)).unwrap();
+        assert_eq!(json[0].text, text);
+        assert_eq!(json[0].text.chars().count(), 170);
+        let jsonl: FoundString =
+            serde_json::from_str(render(OutputFormat::Jsonl).trim_end()).unwrap();
+        assert_eq!(jsonl.text, text);
+        assert!(render(OutputFormat::Csv).contains(&text));
+
+        let table = render(OutputFormat::Table);
+        assert!(table.contains("über-lo… (170 chars)"), "{table}");
+        assert!(!table.contains(&text));
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!(
+            "JSONL".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Jsonl
+        );
+        assert_eq!(
+            "md".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Markdown
+        );
+        assert_eq!(
+            "sarif".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Sarif
+        );
+        assert_eq!(
+            "stats".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Summary
+        );
+        assert_eq!(
+            "signature".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Signature
+        );
+        assert!("xml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn test_color_choice() {
+        assert_eq!("Never".parse::<ColorChoice>().unwrap(), ColorChoice::Never);
+        assert!("sometimes".parse::<ColorChoice>().is_err());
+
+        assert_eq!(ColorChoice::Auto.resolve(false), ColorChoice::Never);
+        assert_eq!(ColorChoice::Always.resolve(false), ColorChoice::Always);
+        assert_eq!(ColorChoice::Never.resolve(true), ColorChoice::Never);
+        assert!(ColorChoice::Always.enabled());
+        assert!(!ColorChoice::Never.enabled());
+        assert_eq!(OutputConfig::default().color, ColorChoice::Never);
+    }
+
+    #[test]
+    fn test_field_from_str() {
+        assert_eq!("RVA".parse::<Field>().unwrap(), Field::Rva);
+        assert_eq!("tags".parse::<Field>().unwrap(), Field::Tags);
+        assert!("name".parse::<Field>().is_err());
+    }
+
+    #[test]
+    fn test_file_target_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.jsonl");
+
+        let target = OutputTarget::File(path.clone());
+        assert!(!target.is_terminal());
+
+        let mut writer = target.open().unwrap();
+        writer.write_all(b"{\"text\":\"hello\"}\n").unwrap();
+        writer.finish().unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{\"text\":\"hello\"}\n"
+        );
+    }
+
+    #[test]
+    fn test_missing_output_directory() {
+        let target = OutputTarget::File(PathBuf::from("/nonexistent-stringy-dir/out.json"));
+        assert!(matches!(target.open(), Err(StringyError::ConfigError(_))));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::types::{Result, StringyError};
+use std::str::FromStr;
+
+/// How offsets and addresses are rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OffsetStyle {
+    /// Zero-padded hex grouped in 4-digit blocks, e.g. `0x0000_1a2c`
+    #[default]
+    Hex,
+    /// Plain decimal, e.g. `6700`
+    Decimal,
+}
+
+impl FromStr for OffsetStyle {
+    type Err = StringyError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "hex" | "x" => Ok(Self::Hex),
+            "decimal" | "dec" | "d" => Ok(Self::Decimal),
+            other => Err(StringyError::ConfigError(format!(
+                "Unknown offset style: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Formats an offset or address.
+///
+/// Hex output is padded to 8 digits, or 16 once the value no longer fits in
+/// 32 bits, so columns stay aligned within a binary.
+pub fn format_offset(value: u64, style: OffsetStyle) -> String {
+    match style {
+        OffsetStyle::Decimal => value.to_string(),
+        OffsetStyle::Hex => {
+            let digits = if value > u64::from(u32::MAX) {
+                format!("{:016x}", value)
+            } else {
+                format!("{:08x}", value)
+            };
+            let groups: Vec<&str> = digits
+                .as_bytes()
+                .chunks(4)
+                .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+                .collect();
+            format!("0x{}", groups.join("_"))
+        }
+    }
+}
+
+const SIZE_UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+/// Formats a byte count with binary units, e.g. `512 B` or `4.2 KiB`.
+///
+/// Values below 1 KiB are exact; larger values are shown with one decimal.
+pub fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < SIZE_UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    // Avoid "1024.0 KiB" when rounding reaches the next unit
+    if value >= 1023.95 && unit < SIZE_UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{:.1} {}", value, SIZE_UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_offset_hex() {
+        assert_eq!(format_offset(0, OffsetStyle::Hex), "0x0000_0000");
+        assert_eq!(format_offset(0x1a2c, OffsetStyle::Hex), "0x0000_1a2c");
+        assert_eq!(
+            format_offset(u64::from(u32::MAX), OffsetStyle::Hex),
+            "0xffff_ffff"
+        );
+        assert_eq!(
+            format_offset(u64::from(u32::MAX) + 1, OffsetStyle::Hex),
+            "0x0000_0001_0000_0000"
+        );
+        assert_eq!(
+            format_offset(u64::MAX, OffsetStyle::Hex),
+            "0xffff_ffff_ffff_ffff"
+        );
+    }
+
+    #[test]
+    fn test_format_offset_decimal() {
+        assert_eq!(format_offset(0, OffsetStyle::Decimal), "0");
+        assert_eq!(format_offset(0x1a2c, OffsetStyle::Decimal), "6700");
+        assert_eq!(
+            format_offset(u64::MAX, OffsetStyle::Decimal),
+            "18446744073709551615"
+        );
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1024), "1.0 KiB");
+        assert_eq!(format_size(4300), "4.2 KiB");
+        assert_eq!(format_size(1024 * 1024 - 1), "1.0 MiB");
+        assert_eq!(format_size(5 * 1024 * 1024 * 1024), "5.0 GiB");
+        assert_eq!(format_size(u64::MAX - 1), "16.0 EiB");
+        assert_eq!(format_size(u64::MAX), "16.0 EiB");
+    }
+
+    #[test]
+    fn test_offset_style_from_str() {
+        assert_eq!("HEX".parse::<OffsetStyle>().unwrap(), OffsetStyle::Hex);
+        assert_eq!("dec".parse::<OffsetStyle>().unwrap(), OffsetStyle::Decimal);
+        assert!("octal".parse::<OffsetStyle>().is_err());
+    }
+}
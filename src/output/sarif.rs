@@ -0,0 +1,312 @@
+use super::{Formatter, OutputConfig};
+use crate::types::{FoundString, Result, Tag};
+use serde::Serialize;
+use std::io::Write;
+
+/// SARIF schema the output conforms to.
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// SARIF version the output conforms to.
+pub const SARIF_VERSION: &str = "2.1.0";
+
+/// Minimum score reported at `error` level.
+pub const ERROR_SCORE: i32 = 70;
+
+/// Minimum score reported at `warning` level; lower scores are `note`s.
+pub const WARNING_SCORE: i32 = 40;
+
+/// SARIF 2.1.0 log for code scanning in CI.
+///
+/// Every tagged string becomes one `result`, untagged strings are left out.
+/// The result's `ruleId` is its first tag (`url`, `ipv4`, `import`, ...;
+/// custom tags become `custom/<name>`), its `level` follows the score (see
+/// [`ERROR_SCORE`] and [`WARNING_SCORE`]), and its logical location names the
+/// section and the string's RVA, or its file offset if it has none. With
+/// `OutputConfig::file` set, a physical location gives the byte range in
+/// that file as well.
+#[derive(Debug, Clone, Default)]
+pub struct SarifFormatter {
+    config: OutputConfig,
+}
+
+impl SarifFormatter {
+    pub fn new(config: OutputConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[derive(Serialize)]
+struct Log<'a> {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run<'a>>,
+}
+
+#[derive(Serialize)]
+struct Run<'a> {
+    tool: Tool,
+    results: Vec<SarifResult<'a>>,
+}
+
+#[derive(Serialize)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<Rule>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    id: String,
+    short_description: Message,
+}
+
+#[derive(Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult<'a> {
+    rule_id: String,
+    level: &'static str,
+    message: Message,
+    locations: Vec<Location<'a>>,
+    properties: Properties<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    physical_location: Option<PhysicalLocation<'a>>,
+    logical_locations: Vec<LogicalLocation>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation<'a> {
+    artifact_location: ArtifactLocation<'a>,
+    region: Region,
+}
+
+#[derive(Serialize)]
+struct ArtifactLocation<'a> {
+    uri: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    byte_offset: u64,
+    byte_length: u32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogicalLocation {
+    name: String,
+    fully_qualified_name: String,
+    kind: &'static str,
+}
+
+#[derive(Serialize)]
+struct Properties<'a> {
+    score: i32,
+    tags: Vec<String>,
+    text: &'a str,
+}
+
+/// Rule identifier for strings whose first tag is `tag`.
+fn rule_id(tag: &Tag) -> String {
+    match tag {
+        Tag::Custom(name) => format!("custom/{}", name),
+        other => other.to_string(),
+    }
+}
+
+/// SARIF level for a string scored `score`.
+fn level(score: i32) -> &'static str {
+    if score >= ERROR_SCORE {
+        "error"
+    } else if score >= WARNING_SCORE {
+        "warning"
+    } else {
+        "note"
+    }
+}
+
+impl SarifFormatter {
+    fn result<'a>(&'a self, string: &'a FoundString, tag: &Tag) -> SarifResult<'a> {
+        let address = match string.rva {
+            Some(rva) => format!("{:#x}", rva),
+            None => format!("file+{:#x}", string.offset),
+        };
+        let fully_qualified_name = match &string.section {
+            Some(section) => format!("{}!{}", section, address),
+            None => address.clone(),
+        };
+        let physical_location = self.config.file.as_deref().map(|uri| PhysicalLocation {
+            artifact_location: ArtifactLocation { uri },
+            region: Region {
+                byte_offset: string.offset,
+                byte_length: string.length,
+            },
+        });
+
+        SarifResult {
+            rule_id: rule_id(tag),
+            level: level(string.score),
+            message: Message {
+                text: format!("{} string: {}", tag, string.text),
+            },
+            locations: vec![Location {
+                physical_location,
+                logical_locations: vec![LogicalLocation {
+                    name: address,
+                    fully_qualified_name,
+                    kind: "data",
+                }],
+            }],
+            properties: Properties {
+                score: string.score,
+                tags: string.tags.iter().map(ToString::to_string).collect(),
+                text: &string.text,
+            },
+        }
+    }
+}
+
+impl Formatter for SarifFormatter {
+    fn format(&self, strings: &[FoundString], writer: &mut dyn Write) -> Result<()> {
+        let mut rules: Vec<Rule> = Vec::new();
+        let mut results = Vec::new();
+        for string in strings {
+            let Some(tag) = string.tags.first() else {
+                continue;
+            };
+            let id = rule_id(tag);
+            if !rules.iter().any(|rule| rule.id == id) {
+                rules.push(Rule {
+                    id,
+                    short_description: Message {
+                        text: format!("String tagged {}", tag),
+                    },
+                });
+            }
+            results.push(self.result(string, tag));
+        }
+
+        let log = Log {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![Run {
+                tool: Tool {
+                    driver: Driver {
+                        name: "stringy",
+                        version: env!("CARGO_PKG_VERSION"),
+                        information_uri: env!("CARGO_PKG_REPOSITORY"),
+                        rules,
+                    },
+                },
+                results,
+            }],
+        };
+        serde_json::to_writer_pretty(&mut *writer, &log)?;
+        writeln!(writer)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Encoding, StringSource};
+
+    fn found(text: &str, tags: Vec<Tag>, score: i32, rva: Option<u64>) -> FoundString {
+        FoundString {
+            rva,
+            ..FoundString::builder()
+                .text(text)
+                .encoding(Encoding::Ascii)
+                .offset(0x1200)
+                .length(text.len() as u32)
+                .source(StringSource::SectionData)
+                .section(".rdata")
+                .tags(tags)
+                .score(score)
+                .build()
+                .unwrap()
+        }
+    }
+
+    #[test]
+    fn test_sarif_log() {
+        let strings = vec![
+            found(
+                "http://c2.example.com/gate",
+                vec![Tag::Url, Tag::Domain],
+                85,
+                Some(0x3200),
+            ),
+            found("plain text", Vec::new(), 30, None),
+            found("VirtualAllocEx", vec![Tag::Import], 45, None),
+            found("http://example.org/", vec![Tag::Url], 20, None),
+        ];
+        let config = OutputConfig {
+            file: Some("bin/dropper.exe".to_string()),
+            ..OutputConfig::default()
+        };
+        let mut out = Vec::new();
+        SarifFormatter::new(config)
+            .format(&strings, &mut out)
+            .unwrap();
+
+        let log: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(log["$schema"], SARIF_SCHEMA);
+        let run = &log["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "stringy");
+
+        let rules: Vec<&str> = run["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|rule| rule["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(rules, vec!["url", "import"]);
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        let url = &results[0];
+        assert_eq!(url["ruleId"], "url");
+        assert_eq!(url["level"], "error");
+        let location = &url["locations"][0];
+        assert_eq!(
+            location["logicalLocations"][0]["fullyQualifiedName"],
+            ".rdata!0x3200"
+        );
+        assert_eq!(
+            location["physicalLocation"]["artifactLocation"]["uri"],
+            "bin/dropper.exe"
+        );
+        assert_eq!(location["physicalLocation"]["region"]["byteOffset"], 0x1200);
+
+        assert_eq!(results[1]["level"], "warning");
+        assert_eq!(
+            results[1]["locations"][0]["logicalLocations"][0]["name"],
+            "file+0x1200"
+        );
+        assert_eq!(results[2]["level"], "note");
+    }
+}
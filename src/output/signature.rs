@@ -0,0 +1,513 @@
+use super::{Formatter, OutputConfig};
+use crate::types::{Encoding, FoundString, Result, StringyError, Tag};
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::io;
+use std::str::FromStr;
+
+/// Substrings that show up in a large share of unrelated binaries.
+///
+/// These come from compiler runtimes, loaders, and boilerplate resources, so a
+/// signature built on them would match far more than the sample at hand.
+const GENERIC_MARKERS: &[&str] = &[
+    "!This program cannot be run in DOS mode",
+    "This program cannot be run in DOS mode",
+    "GCC: (",
+    "clang version",
+    "GLIBC_",
+    "GLIBCXX_",
+    "CXXABI_",
+    "libc.so",
+    "libstdc++.so",
+    "libgcc_s.so",
+    "ld-linux",
+    "__libc_start_main",
+    "__gmon_start__",
+    "__cxa_finalize",
+    "_ITM_registerTMCloneTable",
+    "_ITM_deregisterTMCloneTable",
+    "crtstuff.c",
+    "Microsoft Visual C++ Runtime Library",
+    "Runtime Error!",
+    "bad allocation",
+    "bad array new length",
+    "Unknown exception",
+    "invalid string position",
+    "string too long",
+    "vector too long",
+    "KERNEL32.dll",
+    "USER32.dll",
+    "ADVAPI32.dll",
+    "msvcrt.dll",
+    "mscoree.dll",
+    "CorExitProcess",
+    "api-ms-win-",
+    "<assembly xmlns=",
+    "/usr/lib/dyld",
+    "/usr/lib/libSystem.B.dylib",
+    "/System/Library/Frameworks/",
+];
+
+/// How aggressively [`suggest_signature`] discards strings that are likely to
+/// appear in many unrelated binaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Genericity {
+    /// Only drop strings containing well-known runtime and loader boilerplate.
+    Lenient,
+    /// Additionally drop import/export names and low-diversity filler runs.
+    #[default]
+    Balanced,
+    /// Additionally require short strings to carry a semantic tag and drop
+    /// strings whose only tag is a format string.
+    Strict,
+}
+
+impl FromStr for Genericity {
+    type Err = StringyError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "lenient" => Ok(Self::Lenient),
+            "balanced" => Ok(Self::Balanced),
+            "strict" => Ok(Self::Strict),
+            other => Err(StringyError::ConfigError(format!(
+                "Unknown genericity: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Configuration for [`suggest_signature`].
+#[derive(Debug, Clone)]
+pub struct SignatureConfig {
+    /// Name of the emitted rule; sanitized into a valid YARA identifier
+    pub rule_name: String,
+    /// Maximum number of strings to include in the signature
+    pub max_strings: usize,
+    /// Minimum length in characters for a string to be considered
+    pub min_length: usize,
+    /// Minimum score for a string to be considered
+    pub min_score: i32,
+    /// How aggressively generic strings are filtered out
+    pub genericity: Genericity,
+}
+
+impl Default for SignatureConfig {
+    fn default() -> Self {
+        Self {
+            rule_name: "stringy_candidate".to_string(),
+            max_strings: 8,
+            min_length: 8,
+            min_score: 0,
+            genericity: Genericity::default(),
+        }
+    }
+}
+
+/// A string selected for a candidate signature, with the reason it was picked.
+#[derive(Debug, Clone)]
+pub struct SignatureCandidate {
+    /// The selected string
+    pub string: FoundString,
+    /// Short human-readable justification for the selection
+    pub justification: String,
+}
+
+/// A suggested detection signature built from the most discriminating strings.
+#[derive(Debug, Clone)]
+pub struct SignatureHint {
+    /// Sanitized rule identifier
+    pub rule_name: String,
+    /// Selected strings, most discriminating first
+    pub candidates: Vec<SignatureCandidate>,
+    /// Suggested number of strings that must match (`N of them`)
+    pub threshold: usize,
+}
+
+impl SignatureHint {
+    /// Returns the suggested YARA condition, e.g. `3 of them`.
+    pub fn condition(&self) -> String {
+        if self.candidates.is_empty() {
+            "false".to_string()
+        } else if self.threshold >= self.candidates.len() {
+            "all of them".to_string()
+        } else {
+            format!("{} of them", self.threshold)
+        }
+    }
+
+    /// Renders the hint as a YARA rule with one justification comment per string.
+    pub fn to_yara(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "rule {}", self.rule_name);
+        out.push_str("{\n");
+        out.push_str("    meta:\n");
+        out.push_str("        description = \"Candidate signature suggested by stringy\"\n");
+        out.push_str("        generated_by = \"stringy\"\n");
+
+        if !self.candidates.is_empty() {
+            out.push_str("\n    strings:\n");
+            for (i, candidate) in self.candidates.iter().enumerate() {
+                let _ = writeln!(
+                    out,
+                    "        $s{} = {} // {}",
+                    i,
+                    yara_string_literal(&candidate.string),
+                    candidate.justification
+                );
+            }
+        }
+
+        let _ = write!(out, "\n    condition:\n        {}\n", self.condition());
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Candidate signature suggested by [`suggest_signature`], rendered as a YARA
+/// rule.
+///
+/// The rule is named after `OutputConfig::rule_name` and filtered with
+/// `OutputConfig::genericity`; the other [`SignatureConfig`] settings keep
+/// their defaults. Unlike [`YaraFormatter`](super::YaraFormatter), strings
+/// common to unrelated binaries are left out and each carries a
+/// justification.
+#[derive(Debug, Clone, Default)]
+pub struct SignatureFormatter {
+    config: OutputConfig,
+}
+
+impl SignatureFormatter {
+    pub fn new(config: OutputConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl Formatter for SignatureFormatter {
+    fn format(&self, strings: &[FoundString], writer: &mut dyn io::Write) -> Result<()> {
+        let config = SignatureConfig {
+            rule_name: self.config.rule_name.clone(),
+            genericity: self.config.genericity,
+            ..SignatureConfig::default()
+        };
+        writer.write_all(suggest_signature(strings, &config).to_yara().as_bytes())?;
+        Ok(())
+    }
+}
+
+/// Selects a small, discriminating subset of `strings` and suggests a
+/// signature condition for them.
+///
+/// Strings are kept when they are long enough, score at least
+/// `config.min_score`, and pass the genericity filter. Survivors are ranked by
+/// score, then length, then offset, and the top `config.max_strings` are kept.
+/// The suggested threshold requires roughly half of the selected strings to
+/// match so the rule tolerates minor variants of the sample.
+pub fn suggest_signature(strings: &[FoundString], config: &SignatureConfig) -> SignatureHint {
+    let mut seen = HashSet::new();
+    let mut selected: Vec<&FoundString> = strings
+        .iter()
+        .filter(|s| s.text.chars().count() >= config.min_length)
+        .filter(|s| s.score >= config.min_score)
+        .filter(|s| !is_generic(s, config.genericity))
+        .filter(|s| seen.insert(s.text.as_str()))
+        .collect();
+
+    selected.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| b.text.len().cmp(&a.text.len()))
+            .then_with(|| a.offset.cmp(&b.offset))
+    });
+    selected.truncate(config.max_strings);
+
+    let candidates: Vec<SignatureCandidate> = selected
+        .into_iter()
+        .map(|s| SignatureCandidate {
+            string: s.clone(),
+            justification: justify(s),
+        })
+        .collect();
+
+    let threshold = match candidates.len() {
+        0..=2 => candidates.len(),
+        n => n.div_ceil(2),
+    };
+
+    SignatureHint {
+        rule_name: sanitize_rule_name(&config.rule_name),
+        candidates,
+        threshold,
+    }
+}
+
+/// Determines whether a string is too common to be useful in a signature.
+fn is_generic(string: &FoundString, genericity: Genericity) -> bool {
+    let text = string.text.as_str();
+    if GENERIC_MARKERS.iter().any(|marker| text.contains(marker)) {
+        return true;
+    }
+    if genericity == Genericity::Lenient {
+        return false;
+    }
+
+    if string
+        .tags
+        .iter()
+        .any(|tag| matches!(tag, Tag::Import | Tag::Export))
+    {
+        return true;
+    }
+    if is_low_diversity(text) {
+        return true;
+    }
+    if genericity == Genericity::Balanced {
+        return false;
+    }
+
+    let only_format =
+        !string.tags.is_empty() && string.tags.iter().all(|t| *t == Tag::FormatString);
+    only_format || (string.tags.is_empty() && text.chars().count() < 16)
+}
+
+/// Detects filler such as `AAAAAAAA` or `--------` that carries no identity.
+fn is_low_diversity(text: &str) -> bool {
+    let distinct: HashSet<char> = text.chars().collect();
+    distinct.len() <= 2 || distinct.len() * 4 < text.chars().count().min(32)
+}
+
+/// Builds the per-string justification comment.
+fn justify(string: &FoundString) -> String {
+    let mut reason = format!("score {}, {} bytes", string.score, string.length);
+    if !string.tags.is_empty() {
+        let tags: Vec<String> = string.tags.iter().map(|t| format!("{:?}", t)).collect();
+        let _ = write!(reason, ", tags: {}", tags.join(", "));
+    }
+    if let Some(section) = &string.section {
+        let _ = write!(reason, ", in {}", section);
+    }
+    reason
+}
+
+/// Renders a string as a YARA string literal with the matching modifiers.
+///
+/// ASCII strings were decoded as Latin-1, so each character is one byte of
+/// the file. The `wide` modifier only interleaves zero bytes, so it matches
+/// UTF-16LE text of ASCII characters alone; other UTF-16 text becomes a hex
+/// string of its code units.
+pub(crate) fn yara_string_literal(string: &FoundString) -> String {
+    let text = &string.text;
+    match string.encoding {
+        Encoding::Ascii => {
+            let latin1: Option<Vec<u8>> = text.chars().map(|c| u8::try_from(c).ok()).collect();
+            match latin1 {
+                Some(bytes) => format!("\"{}\"", escape_yara_bytes(bytes)),
+                None => format!("\"{}\"", escape_yara(text)),
+            }
+        }
+        Encoding::Utf8 => format!("\"{}\"", escape_yara(text)),
+        Encoding::Utf16Le if text.is_ascii() => format!("\"{}\" wide", escape_yara(text)),
+        Encoding::Utf16Le => yara_hex_string(text.encode_utf16().flat_map(u16::to_le_bytes)),
+        Encoding::Utf16Be => yara_hex_string(text.encode_utf16().flat_map(u16::to_be_bytes)),
+    }
+}
+
+/// Renders bytes as a YARA hex string, e.g. `{ 00 7B }`.
+fn yara_hex_string(bytes: impl IntoIterator<Item = u8>) -> String {
+    let bytes: Vec<String> = bytes.into_iter().map(|b| format!("{:02X}", b)).collect();
+    format!("{{ {} }}", bytes.join(" "))
+}
+
+/// Escapes text for use inside a double-quoted YARA string.
+///
+/// Quotes and backslashes are backslash-escaped, common control characters use
+/// their short escapes, and every other non-printable or non-ASCII byte is
+/// emitted as `\xNN`.
+pub fn escape_yara(text: &str) -> String {
+    escape_yara_bytes(text.bytes())
+}
+
+/// Escapes raw bytes like [`escape_yara`].
+fn escape_yara_bytes(bytes: impl IntoIterator<Item = u8>) -> String {
+    let mut out = String::new();
+    for byte in bytes {
+        match byte {
+            b'"' => out.push_str("\\\""),
+            b'\\' => out.push_str("\\\\"),
+            b'\n' => out.push_str("\\n"),
+            b'\r' => out.push_str("\\r"),
+            b'\t' => out.push_str("\\t"),
+            0x20..=0x7e => out.push(byte as char),
+            _ => {
+                let _ = write!(out, "\\x{:02X}", byte);
+            }
+        }
+    }
+    out
+}
+
+/// Turns an arbitrary name into a valid YARA rule identifier.
+pub(crate) fn sanitize_rule_name(name: &str) -> String {
+    let mut ident: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .take(128)
+        .collect();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    ident
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::StringSource;
+
+    fn found(text: &str, score: i32, tags: Vec<Tag>) -> FoundString {
+        FoundString::builder()
+            .text(text)
+            .encoding(Encoding::Ascii)
+            .offset(0)
+            .length(text.len() as u32)
+            .source(StringSource::SectionData)
+            .section(".rodata")
+            .tags(tags)
+            .score(score)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_selects_highest_scoring_strings() {
+        let strings = vec![
+            found("http://c2.example.net/gate.php", 90, vec![Tag::Url]),
+            found("unique mutex name 7f3a", 40, vec![]),
+            found("short", 99, vec![]),
+            found("Global\\MyEvilMutex", 60, vec![]),
+        ];
+        let config = SignatureConfig {
+            max_strings: 2,
+            ..Default::default()
+        };
+
+        let hint = suggest_signature(&strings, &config);
+        let texts: Vec<&str> = hint
+            .candidates
+            .iter()
+            .map(|c| c.string.text.as_str())
+            .collect();
+        assert_eq!(
+            texts,
+            vec!["http://c2.example.net/gate.php", "Global\\MyEvilMutex"]
+        );
+        assert_eq!(hint.condition(), "all of them");
+    }
+
+    #[test]
+    fn test_generic_strings_are_filtered() {
+        let strings = vec![
+            found("GCC: (Debian 12.2.0-14) 12.2.0", 80, vec![]),
+            found("VirtualAllocEx", 70, vec![Tag::Import]),
+            found("AAAAAAAAAAAAAAAA", 70, vec![]),
+            found("decrypting payload stage two", 50, vec![]),
+        ];
+
+        let lenient = suggest_signature(
+            &strings,
+            &SignatureConfig {
+                genericity: Genericity::Lenient,
+                ..Default::default()
+            },
+        );
+        assert_eq!(lenient.candidates.len(), 3);
+
+        let balanced = suggest_signature(&strings, &SignatureConfig::default());
+        assert_eq!(balanced.candidates.len(), 1);
+        assert_eq!(
+            balanced.candidates[0].string.text,
+            "decrypting payload stage two"
+        );
+    }
+
+    #[test]
+    fn test_strict_requires_semantic_signal_for_short_strings() {
+        let strings = vec![
+            found("plainword12", 50, vec![]),
+            found("Error: %s at line %d", 50, vec![Tag::FormatString]),
+            found("https://example.org/x", 50, vec![Tag::Url]),
+        ];
+        let hint = suggest_signature(
+            &strings,
+            &SignatureConfig {
+                genericity: Genericity::Strict,
+                ..Default::default()
+            },
+        );
+        assert_eq!(hint.candidates.len(), 1);
+        assert_eq!(hint.candidates[0].string.text, "https://example.org/x");
+    }
+
+    #[test]
+    fn test_threshold_and_rendering() {
+        let strings: Vec<FoundString> = (0..5)
+            .map(|i| found(&format!("distinct string number {}", i), 50 + i, vec![]))
+            .collect();
+        let config = SignatureConfig {
+            rule_name: "1 bad-rule".to_string(),
+            ..Default::default()
+        };
+        let hint = suggest_signature(&strings, &config);
+        assert_eq!(hint.threshold, 3);
+        assert_eq!(hint.condition(), "3 of them");
+
+        let rule = hint.to_yara();
+        assert!(rule.starts_with("rule _1_bad_rule\n{"));
+        assert!(rule.contains("$s0 = \"distinct string number 4\" // score 54"));
+        assert!(rule.contains("3 of them"));
+        assert_eq!(rule.matches('{').count(), rule.matches('}').count());
+    }
+
+    #[test]
+    fn test_escape_yara() {
+        assert_eq!(escape_yara("a\"b\\c"), "a\\\"b\\\\c");
+        assert_eq!(escape_yara("line\n\t"), "line\\n\\t");
+        assert_eq!(escape_yara("\u{1}é"), "\\x01\\xC3\\xA9");
+    }
+
+    #[test]
+    fn test_signature_formatter() {
+        let strings = vec![
+            found("VirtualAllocEx", 70, vec![Tag::Import]),
+            found("decrypting payload stage two", 50, vec![]),
+        ];
+        let render = |genericity| {
+            let formatter = SignatureFormatter::new(OutputConfig {
+                rule_name: "sample.exe".to_string(),
+                genericity,
+                ..OutputConfig::default()
+            });
+            let mut out = Vec::new();
+            formatter.format(&strings, &mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        let balanced = render(Genericity::Balanced);
+        assert!(balanced.starts_with("rule sample_exe\n"), "{balanced}");
+        assert!(!balanced.contains("VirtualAllocEx"));
+        assert!(balanced.contains("$s0 = \"decrypting payload stage two\""));
+        assert!(render(Genericity::Lenient).contains("VirtualAllocEx"));
+
+        assert_eq!("STRICT".parse::<Genericity>().unwrap(), Genericity::Strict);
+        assert!("loose".parse::<Genericity>().is_err());
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let hint = suggest_signature(&[], &SignatureConfig::default());
+        assert!(hint.candidates.is_empty());
+        assert_eq!(hint.condition(), "false");
+        assert!(!hint.to_yara().contains("strings:"));
+    }
+}
@@ -0,0 +1,272 @@
+use super::table::display_text;
+use super::{Formatter, OutputConfig};
+use crate::types::{Encoding, FoundString, Result, StringSource};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Write;
+
+/// Number of strings listed by [`SummaryFormatter`].
+pub const TOP_STRINGS: usize = 10;
+
+/// Counts describing a set of extracted strings, for report headers.
+///
+/// Maps are ordered by key so the serialized summary is deterministic.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Summary {
+    /// Number of strings
+    pub strings: usize,
+    /// Highest score, or `None` if there are no strings
+    pub max_score: Option<i32>,
+    /// Number of strings per encoding
+    pub encodings: BTreeMap<Encoding, usize>,
+    /// Number of strings carrying each tag, keyed by the tag's display name
+    /// (`url`, `ipv4`, ...)
+    pub tags: BTreeMap<String, usize>,
+    /// Number of strings per section; strings outside any section are not
+    /// counted
+    pub sections: BTreeMap<String, usize>,
+    /// Number of import names
+    pub imports: usize,
+    /// Number of export names
+    pub exports: usize,
+}
+
+impl Summary {
+    /// Summarizes `strings`.
+    pub fn new(strings: &[FoundString]) -> Self {
+        let mut summary = Self {
+            strings: strings.len(),
+            max_score: strings.iter().map(|string| string.score).max(),
+            ..Self::default()
+        };
+        for string in strings {
+            *summary.encodings.entry(string.encoding).or_default() += 1;
+            let mut names: Vec<String> = string.tags.iter().map(ToString::to_string).collect();
+            names.sort_unstable();
+            names.dedup();
+            for name in names {
+                *summary.tags.entry(name).or_default() += 1;
+            }
+            if let Some(section) = &string.section {
+                *summary.sections.entry(section.clone()).or_default() += 1;
+            }
+            match string.source {
+                StringSource::ImportName => summary.imports += 1,
+                StringSource::ExportName => summary.exports += 1,
+                _ => {}
+            }
+        }
+        summary
+    }
+}
+
+/// Aggregate statistics instead of the strings themselves.
+///
+/// Prints the [`Summary`] counts as aligned lists, then the
+/// [`TOP_STRINGS`] highest-scoring strings, truncated like the table view.
+/// With a file set, the summary is preceded by a `==> file <==` heading.
+#[derive(Debug, Clone, Default)]
+pub struct SummaryFormatter {
+    config: OutputConfig,
+}
+
+impl SummaryFormatter {
+    pub fn new(config: OutputConfig) -> Self {
+        Self { config }
+    }
+}
+
+/// Writes `title` and one indented, aligned `name  count` line per entry.
+fn write_counts<K: ToString>(
+    writer: &mut dyn Write,
+    title: &str,
+    counts: impl IntoIterator<Item = (K, usize)>,
+) -> Result<()> {
+    let counts: Vec<(String, usize)> = counts
+        .into_iter()
+        .map(|(key, count)| (key.to_string(), count))
+        .collect();
+    writeln!(writer)?;
+    writeln!(writer, "{}:", title)?;
+    if counts.is_empty() {
+        writeln!(writer, "  none")?;
+        return Ok(());
+    }
+    let name_width = counts.iter().map(|(name, _)| name.chars().count()).max();
+    let count_width = counts
+        .iter()
+        .map(|(_, count)| count.to_string().len())
+        .max();
+    for (name, count) in &counts {
+        writeln!(
+            writer,
+            "  {:<name_width$}  {:>count_width$}",
+            name,
+            count,
+            name_width = name_width.unwrap_or(0),
+            count_width = count_width.unwrap_or(0),
+        )?;
+    }
+    Ok(())
+}
+
+impl Formatter for SummaryFormatter {
+    fn format(&self, strings: &[FoundString], writer: &mut dyn Write) -> Result<()> {
+        let summary = Summary::new(strings);
+
+        if let Some(file) = &self.config.file {
+            writeln!(writer, "==> {} <==", file)?;
+        }
+        match summary.max_score {
+            Some(max_score) => writeln!(
+                writer,
+                "Strings: {} (highest score {})",
+                summary.strings, max_score
+            )?,
+            None => writeln!(writer, "Strings: 0")?,
+        }
+        writeln!(
+            writer,
+            "Symbols: {} imports, {} exports",
+            summary.imports, summary.exports
+        )?;
+        write_counts(writer, "Encodings", summary.encodings)?;
+        write_counts(writer, "Tags", summary.tags)?;
+        write_counts(writer, "Sections", summary.sections)?;
+
+        let mut top: Vec<&FoundString> = strings.iter().collect();
+        top.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.cmp_canonical(b)));
+        top.truncate(TOP_STRINGS);
+        writeln!(writer)?;
+        writeln!(writer, "Top {} by score:", top.len())?;
+        let score_width = top.iter().map(|s| s.score.to_string().len()).max();
+        for string in top {
+            let text = match display_text(string.display_text(), self.config.truncate) {
+                (shown, Some(chars)) => format!("{} ({} chars)", shown, chars),
+                (shown, None) => shown,
+            };
+            writeln!(
+                writer,
+                "  {:>width$}  {}",
+                string.score,
+                text,
+                width = score_width.unwrap_or(0),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Tag;
+
+    fn found(text: &str, encoding: Encoding, tags: Vec<Tag>, score: i32) -> FoundString {
+        FoundString::builder()
+            .text(text)
+            .encoding(encoding)
+            .offset(0)
+            .length(text.len() as u32)
+            .source(StringSource::SectionData)
+            .tags(tags)
+            .score(score)
+            .build()
+            .unwrap()
+    }
+
+    fn sample() -> Vec<FoundString> {
+        let mut import = found("CreateFileW", Encoding::Ascii, vec![Tag::Import], 12);
+        import.source = StringSource::ImportName;
+        import.section = Some("KERNEL32.dll".to_string());
+        let mut url = found("https://example.com", Encoding::Ascii, vec![Tag::Url], 40);
+        url.section = Some(".rdata".to_string());
+        let mut domain = found("example.com", Encoding::Utf16Le, vec![Tag::Domain], 25);
+        domain.section = Some(".rdata".to_string());
+        vec![
+            url,
+            domain,
+            found("hello", Encoding::Ascii, Vec::new(), -3),
+            import,
+        ]
+    }
+
+    #[test]
+    fn test_summary() {
+        let summary = Summary::new(&sample());
+        assert_eq!(summary.strings, 4);
+        assert_eq!(summary.max_score, Some(40));
+        assert_eq!(summary.encodings[&Encoding::Ascii], 3);
+        assert_eq!(summary.encodings[&Encoding::Utf16Le], 1);
+        assert_eq!(
+            summary.tags,
+            BTreeMap::from([
+                ("domain".to_string(), 1),
+                ("import".to_string(), 1),
+                ("url".to_string(), 1)
+            ])
+        );
+        assert_eq!(
+            summary.sections,
+            BTreeMap::from([(".rdata".to_string(), 2), ("KERNEL32.dll".to_string(), 1)])
+        );
+        assert_eq!((summary.imports, summary.exports), (1, 0));
+
+        assert_eq!(Summary::new(&[]), Summary::default());
+    }
+
+    #[test]
+    fn test_summary_formatter() {
+        let mut out = Vec::new();
+        SummaryFormatter::new(OutputConfig::default())
+            .format(&sample(), &mut out)
+            .unwrap();
+        let output = String::from_utf8(out).unwrap();
+        assert_eq!(
+            output,
+            "\
+Strings: 4 (highest score 40)
+Symbols: 1 imports, 0 exports
+
+Encodings:
+  ascii     3
+  utf-16le  1
+
+Tags:
+  domain  1
+  import  1
+  url     1
+
+Sections:
+  .rdata        2
+  KERNEL32.dll  1
+
+Top 4 by score:
+  40  https://example.com
+  25  example.com
+  12  CreateFileW
+  -3  hello
+"
+        );
+    }
+
+    #[test]
+    fn test_summary_formatter_top_strings() {
+        let strings: Vec<FoundString> = (0..TOP_STRINGS as i32 + 5)
+            .map(|score| found("plain", Encoding::Ascii, Vec::new(), score))
+            .collect();
+        let mut out = Vec::new();
+        SummaryFormatter::new(OutputConfig {
+            file: Some("a.bin".to_string()),
+            ..OutputConfig::default()
+        })
+        .format(&strings, &mut out)
+        .unwrap();
+        let output = String::from_utf8(out).unwrap();
+
+        assert!(output.starts_with("==> a.bin <==\n"));
+        assert!(output.contains("Top 10 by score:\n  14  plain\n"));
+        assert_eq!(output.matches("  plain").count(), TOP_STRINGS);
+        assert!(output.contains("Tags:\n  none\n"));
+    }
+}
@@ -0,0 +1,369 @@
+use super::numbers::format_offset;
+use super::{Field, Formatter, OutputConfig};
+use crate::classification::symbols::{RiskLevel, import_risk};
+use crate::types::{FoundString, Result, Tag};
+use std::io::Write;
+
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+/// Ends bold or dim text without resetting colors
+const NORMAL: &str = "\x1b[22m";
+const RED: &str = "\x1b[31m";
+const YELLOW: &str = "\x1b[33m";
+const CYAN: &str = "\x1b[36m";
+/// Restores the default foreground color
+const DEFAULT_FG: &str = "\x1b[39m";
+const RESET: &str = "\x1b[0m";
+
+/// Minimum score whose text is shown in bold when coloring.
+pub const HIGHLIGHT_SCORE: i32 = 70;
+
+/// Rows scoring below this are dimmed when coloring.
+pub const DIM_SCORE: i32 = 15;
+
+/// Placeholder for a missing value, such as the RVA of an overlay string.
+const EMPTY: &str = "-";
+
+/// Human-readable table with aligned columns.
+///
+/// Column widths are sized to their content. The text column, which shows
+/// a symbol's demangled name when it has one, is never padded, so put it
+/// last to keep rows from carrying trailing whitespace.
+/// With a file set, the table is preceded by a `==> file <==` heading.
+///
+/// When `OutputConfig::color` enables it, the header is bold, text scoring
+/// at least [`HIGHLIGHT_SCORE`] is bold, rows scoring below [`DIM_SCORE`]
+/// are dimmed, and tags are colored by kind: URLs, domains and emails cyan,
+/// IP addresses yellow, and commands, credentials and high-risk imports red.
+#[derive(Debug, Clone, Default)]
+pub struct TableFormatter {
+    config: OutputConfig,
+}
+
+impl TableFormatter {
+    pub fn new(config: OutputConfig) -> Self {
+        Self { config }
+    }
+
+    fn cell(&self, string: &FoundString, field: Field) -> String {
+        match field {
+            Field::Offset => format_offset(string.offset, self.config.offset_style),
+            Field::Rva => string.rva.map_or_else(
+                || EMPTY.to_string(),
+                |rva| format_offset(rva, self.config.offset_style),
+            ),
+            Field::Section => string.section.as_deref().unwrap_or(EMPTY).to_string(),
+            Field::Encoding => string.encoding.to_string(),
+            Field::Score => string.score.to_string(),
+            Field::Tags if string.tags.is_empty() => EMPTY.to_string(),
+            Field::Tags => string
+                .tags
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(","),
+            Field::Text => match display_text(string.display_text(), self.config.truncate) {
+                (shown, Some(chars)) => format!("{} ({} chars)", shown, chars),
+                (shown, None) => shown,
+            },
+        }
+    }
+
+    /// Adds color to the plain `cell` of `field` for `string`.
+    fn paint(&self, string: &FoundString, field: Field, cell: &str) -> String {
+        match field {
+            Field::Tags if !string.tags.is_empty() => string
+                .tags
+                .iter()
+                .map(|tag| match tag_color(tag, string) {
+                    Some(color) => format!("{}{}{}", color, tag, DEFAULT_FG),
+                    None => tag.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(","),
+            Field::Text if string.score >= HIGHLIGHT_SCORE => format!("{}{}{}", BOLD, cell, NORMAL),
+            _ => cell.to_string(),
+        }
+    }
+}
+
+/// Color for `tag` on `string`, if its kind is highlighted.
+fn tag_color(tag: &Tag, string: &FoundString) -> Option<&'static str> {
+    match tag {
+        Tag::Url | Tag::Domain | Tag::Email => Some(CYAN),
+        Tag::IPv4 | Tag::IPv6 => Some(YELLOW),
+        Tag::Command | Tag::ApiKey | Tag::Jwt => Some(RED),
+        Tag::Import if import_risk(&string.text, string.section.as_deref()) == RiskLevel::High => {
+            Some(RED)
+        }
+        _ => None,
+    }
+}
+
+/// Escapes control characters so a string always renders on one line.
+fn escape_control(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_control() {
+                c.escape_default().to_string()
+            } else {
+                c.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Escapes and shortens `text` for display, to at most `max` characters.
+///
+/// Returns the text to show and, if it was cut, the original length in
+/// characters so the output can say how much was left out.
+pub(super) fn display_text(text: &str, max: Option<usize>) -> (String, Option<usize>) {
+    let escaped = escape_control(text);
+    let cut = max.is_some_and(|max| escaped.chars().count() > max);
+    let shown = truncate(&escaped, max);
+    (shown, cut.then(|| text.chars().count()))
+}
+
+/// Shortens `text` to at most `max` characters, ending in `…` when cut.
+///
+/// Cuts fall between characters, never inside a multibyte UTF-8 sequence.
+fn truncate(text: &str, max: Option<usize>) -> String {
+    match max {
+        Some(max) if text.chars().count() > max => {
+            let mut short: String = text.chars().take(max.saturating_sub(1)).collect();
+            short.push('…');
+            short
+        }
+        _ => text.to_string(),
+    }
+}
+
+impl Formatter for TableFormatter {
+    fn format(&self, strings: &[FoundString], writer: &mut dyn Write) -> Result<()> {
+        let fields = &self.config.fields;
+        let header: Vec<String> = fields
+            .iter()
+            .map(|field| field.name().to_ascii_uppercase())
+            .collect();
+        let rows: Vec<Vec<String>> = strings
+            .iter()
+            .map(|string| fields.iter().map(|&f| self.cell(string, f)).collect())
+            .collect();
+
+        let mut widths: Vec<usize> = header.iter().map(|h| h.chars().count()).collect();
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let color = self.config.color.enabled();
+        // Pads each cell to its column width; `string` gives data cells color
+        let render = |cells: &[String], string: Option<&FoundString>| -> String {
+            let last = cells.len().saturating_sub(1);
+            cells
+                .iter()
+                .enumerate()
+                .map(|(i, cell)| {
+                    let pad = widths[i] - cell.chars().count();
+                    let shown = match string {
+                        Some(string) if color => self.paint(string, fields[i], cell),
+                        _ => cell.clone(),
+                    };
+                    if i == last {
+                        shown
+                    } else if fields[i] == Field::Score {
+                        format!("{}{}", " ".repeat(pad), shown)
+                    } else {
+                        format!("{}{}", shown, " ".repeat(pad))
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("  ")
+        };
+
+        if let Some(file) = &self.config.file {
+            writeln!(writer, "==> {} <==", file)?;
+        }
+        let header_line = render(&header, None);
+        if color {
+            writeln!(writer, "{}{}{}", BOLD, header_line, RESET)?;
+        } else {
+            writeln!(writer, "{}", header_line)?;
+        }
+        let rule: Vec<String> = widths.iter().map(|&w| "-".repeat(w)).collect();
+        writeln!(writer, "{}", render(&rule, None))?;
+        for (row, string) in rows.iter().zip(strings) {
+            let line = render(row, Some(string));
+            if color && string.score < DIM_SCORE {
+                writeln!(writer, "{}{}{}", DIM, line, RESET)?;
+            } else {
+                writeln!(writer, "{}", line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::ColorChoice;
+    use crate::types::{Encoding, StringSource};
+
+    fn found(text: &str, offset: u64) -> FoundString {
+        FoundString::builder()
+            .text(text)
+            .encoding(Encoding::Ascii)
+            .offset(offset)
+            .length(text.len() as u32)
+            .source(StringSource::SectionData)
+            .rva(offset + 0x1000)
+            .section(".rdata")
+            .score(42)
+            .build()
+            .unwrap()
+    }
+
+    fn render(formatter: &TableFormatter, strings: &[FoundString]) -> String {
+        let mut out = Vec::new();
+        formatter.format(strings, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_table_headers_and_truncation() {
+        let mut url = found("https://example.com/a/very/long/path/to/a/resource", 0x400);
+        url.tags = vec![Tag::Url, Tag::Domain];
+        url.score = 130;
+        let short = found("hello", 0x10);
+
+        let formatter = TableFormatter::new(OutputConfig {
+            truncate: Some(20),
+            ..OutputConfig::default()
+        });
+        let output = render(&formatter, &[url, short]);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[0],
+            "OFFSET       RVA          SECTION  ENCODING  SCORE  TAGS        TEXT"
+        );
+        assert_eq!(
+            lines[2],
+            "0x0000_0400  0x0000_1400  .rdata   ascii       130  url,domain  https://example.com… (50 chars)"
+        );
+        assert_eq!(
+            lines[3],
+            "0x0000_0010  0x0000_1010  .rdata   ascii        42  -           hello"
+        );
+    }
+
+    #[test]
+    fn test_table_field_selection() {
+        let mut string = found("line one\nline two", 0);
+        string.rva = None;
+        let formatter = TableFormatter::new(OutputConfig {
+            fields: vec![Field::Rva, Field::Text],
+            truncate: None,
+            ..OutputConfig::default()
+        });
+        let output = render(&formatter, &[string]);
+        assert_eq!(
+            output,
+            "RVA  TEXT\n---  ------------------\n-    line one\\nline two\n"
+        );
+    }
+
+    #[test]
+    fn test_table_file_heading() {
+        let formatter = TableFormatter::new(OutputConfig {
+            fields: vec![Field::Text],
+            file: Some("samples/a.exe".to_string()),
+            ..OutputConfig::default()
+        });
+        let output = render(&formatter, &[found("hello", 0)]);
+        assert_eq!(output, "==> samples/a.exe <==\nTEXT\n-----\nhello\n");
+    }
+
+    #[test]
+    fn test_table_colors() {
+        let mut url = found("https://example.com/", 0x10);
+        url.tags = vec![Tag::Url, Tag::IPv4];
+        url.score = HIGHLIGHT_SCORE;
+        let mut import = found("CreateRemoteThread", 0);
+        import.tags = vec![Tag::Import];
+        import.section = Some("KERNEL32.dll".to_string());
+        let mut low = found("xq", 0x20);
+        low.score = DIM_SCORE - 1;
+        let strings = [url, import, low];
+
+        let plain = render(
+            &TableFormatter::new(OutputConfig {
+                color: ColorChoice::Never,
+                ..OutputConfig::default()
+            }),
+            &strings,
+        );
+        assert!(!plain.contains('\x1b'), "{plain:?}");
+
+        let colored = render(
+            &TableFormatter::new(OutputConfig {
+                color: ColorChoice::Always,
+                ..OutputConfig::default()
+            }),
+            &strings,
+        );
+        let lines: Vec<&str> = colored.lines().collect();
+        assert!(lines[0].starts_with(BOLD));
+        assert!(lines[2].contains("\x1b[36murl\x1b[39m,\x1b[33mipv4\x1b[39m"));
+        assert!(lines[2].ends_with("\x1b[1mhttps://example.com/\x1b[22m"));
+        assert!(lines[3].contains("\x1b[31mimport\x1b[39m"));
+        assert!(lines[4].starts_with(DIM) && lines[4].ends_with(RESET));
+
+        // Colors do not change the layout
+        let strip = |line: &str| {
+            let mut out = String::new();
+            let mut chars = line.chars();
+            while let Some(c) = chars.next() {
+                if c == '\x1b' {
+                    chars.by_ref().find(|&c| c == 'm');
+                } else {
+                    out.push(c);
+                }
+            }
+            out
+        };
+        assert_eq!(
+            colored.lines().map(strip).collect::<Vec<_>>(),
+            plain.lines().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_truncate_on_char_boundary() {
+        assert_eq!(truncate("héllo wörld", Some(5)), "héll…");
+        assert_eq!(truncate("short", Some(5)), "short");
+        assert_eq!(truncate("short", None), "short");
+        // Cut right after a multibyte character; each is 3 or 4 bytes
+        assert_eq!(truncate("设置文件路径", Some(3)), "设置…");
+        assert_eq!(truncate("🦀🦀🦀", Some(2)), "🦀…");
+        assert_eq!(truncate("🦀🦀", Some(2)), "🦀🦀");
+    }
+
+    #[test]
+    fn test_display_text() {
+        assert_eq!(
+            display_text("设置文件路径", Some(4)),
+            ("设置文…".to_string(), Some(6))
+        );
+        assert_eq!(display_text("设置", Some(4)), ("设置".to_string(), None));
+        // Escaping counts against the limit; the original length does not
+        assert_eq!(
+            display_text("a\nb\nc", Some(4)),
+            ("a\\n…".to_string(), Some(5))
+        );
+        assert_eq!(display_text("a\nb", None), ("a\\nb".to_string(), None));
+    }
+}
@@ -0,0 +1,226 @@
+use super::signature::{sanitize_rule_name, yara_string_literal};
+use super::{Formatter, OutputConfig};
+use crate::types::{FoundString, Result};
+use std::collections::HashSet;
+use std::io::Write;
+
+/// YARA rule built from the highest-scoring strings.
+///
+/// Emits a single rule named after `OutputConfig::rule_name` holding the top
+/// `OutputConfig::rule_strings` strings by score, as `$s0`, `$s1`, ... in
+/// descending score order. Unlike
+/// [`suggest_signature`](super::signature::suggest_signature), no strings are
+/// filtered out as generic; filter the input first if needed.
+#[derive(Debug, Clone, Default)]
+pub struct YaraFormatter {
+    config: OutputConfig,
+}
+
+impl YaraFormatter {
+    pub fn new(config: OutputConfig) -> Self {
+        Self { config }
+    }
+
+    /// Picks the strings for the rule: highest score first, ties broken by
+    /// file offset, skipping repeats of the same text and encoding.
+    fn select<'a>(&self, strings: &'a [FoundString]) -> Vec<&'a FoundString> {
+        let mut seen = HashSet::new();
+        let mut selected: Vec<&FoundString> = strings
+            .iter()
+            .filter(|s| !s.text.is_empty())
+            .filter(|s| seen.insert((s.text.as_str(), s.encoding)))
+            .collect();
+        selected.sort_by(|a, b| b.score.cmp(&a.score).then(a.offset.cmp(&b.offset)));
+        selected.truncate(self.config.rule_strings);
+        selected
+    }
+
+    fn condition(&self, count: usize) -> String {
+        match self.config.rule_threshold {
+            _ if count == 0 => "false".to_string(),
+            None | Some(0) | Some(1) => "any of them".to_string(),
+            Some(n) if n >= count => "all of them".to_string(),
+            Some(n) => format!("{} of them", n),
+        }
+    }
+}
+
+impl Formatter for YaraFormatter {
+    fn format(&self, strings: &[FoundString], writer: &mut dyn Write) -> Result<()> {
+        let selected = self.select(strings);
+
+        writeln!(
+            writer,
+            "rule {}",
+            sanitize_rule_name(&self.config.rule_name)
+        )?;
+        writeln!(writer, "{{")?;
+        writeln!(writer, "    meta:")?;
+        writeln!(
+            writer,
+            "        description = \"Strings extracted by stringy\""
+        )?;
+        writeln!(writer, "        generated_by = \"stringy\"")?;
+
+        if !selected.is_empty() {
+            writeln!(writer)?;
+            writeln!(writer, "    strings:")?;
+            for (i, string) in selected.iter().enumerate() {
+                write!(
+                    writer,
+                    "        $s{} = {} // score {}",
+                    i,
+                    yara_string_literal(string),
+                    string.score
+                )?;
+                if !string.tags.is_empty() {
+                    let tags: Vec<String> = string.tags.iter().map(ToString::to_string).collect();
+                    write!(writer, ", {}", tags.join(", "))?;
+                }
+                writeln!(writer)?;
+            }
+        }
+
+        writeln!(writer)?;
+        writeln!(writer, "    condition:")?;
+        writeln!(writer, "        {}", self.condition(selected.len()))?;
+        writeln!(writer, "}}")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Encoding, StringSource, Tag};
+    use regex::Regex;
+
+    fn found(text: &str, score: i32, offset: u64) -> FoundString {
+        FoundString::builder()
+            .text(text)
+            .encoding(Encoding::Ascii)
+            .offset(offset)
+            .length(text.len() as u32)
+            .source(StringSource::SectionData)
+            .section(".rdata")
+            .score(score)
+            .build()
+            .unwrap()
+    }
+
+    fn render(formatter: &YaraFormatter, strings: &[FoundString]) -> String {
+        let mut out = Vec::new();
+        formatter.format(strings, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    /// Checks the parts of YARA syntax the formatter is responsible for:
+    /// braces balance outside string literals, and every string definition
+    /// is a well-formed, fully escaped literal.
+    fn assert_valid_yara(rule: &str) {
+        let definition = Regex::new(
+            r#"^\s+\$s[0-9]+ = (?:"(?:[\x20-\x21\x23-\x5b\x5d-\x7e]|\\["\\nrt]|\\x[0-9A-F]{2})*"(?: wide)?|\{(?: [0-9A-F]{2})+ \})(?: //[^\n]*)?$"#,
+        )
+        .unwrap();
+
+        let mut depth = 0i32;
+        let mut in_strings = false;
+        for line in rule.lines() {
+            let trimmed = line.trim();
+            if trimmed == "strings:" {
+                in_strings = true;
+                continue;
+            }
+            if trimmed == "condition:" {
+                in_strings = false;
+            }
+            if in_strings && !trimmed.is_empty() {
+                assert!(definition.is_match(line), "malformed string: {line}");
+                continue;
+            }
+            depth += line.matches('{').count() as i32;
+            depth -= line.matches('}').count() as i32;
+            assert!(depth >= 0, "unbalanced braces at: {line}");
+        }
+        assert_eq!(depth, 0, "unbalanced braces");
+    }
+
+    #[test]
+    fn test_rule_is_valid_yara() {
+        let mut url = found("http://c2.example.net/gate.php?id=\"x\"", 90, 0x20);
+        url.tags = vec![Tag::Url, Tag::Domain];
+        let mut wide = found("C:\\Users\\Public\\run.bat", 70, 0x40);
+        wide.encoding = Encoding::Utf16Le;
+        let mut be = found("{config}", 60, 0x60);
+        be.encoding = Encoding::Utf16Be;
+        let mut utf8 = found("café menu", 50, 0x80);
+        utf8.encoding = Encoding::Utf8;
+        // Scanned with the Latin-1 charset, "é" is the single byte 0xE9
+        let latin1 = found("line\none\ttab\u{1}é", 80, 0x10);
+        let strings = vec![latin1, url, wide, be, utf8];
+
+        let rule = render(&YaraFormatter::default(), &strings);
+        assert_valid_yara(&rule);
+        assert!(rule.starts_with("rule stringy_strings\n{\n"));
+        assert!(rule.contains(
+            "$s0 = \"http://c2.example.net/gate.php?id=\\\"x\\\"\" // score 90, url, domain\n"
+        ));
+        assert!(rule.contains("$s1 = \"line\\none\\ttab\\x01\\xE9\" // score 80\n"));
+        assert!(rule.contains("$s2 = \"C:\\\\Users\\\\Public\\\\run.bat\" wide"));
+        assert!(rule.contains("$s3 = { 00 7B 00 63"));
+        assert!(rule.contains("$s4 = \"caf\\xC3\\xA9 menu\""));
+        assert!(rule.ends_with("    condition:\n        any of them\n}\n"));
+    }
+
+    #[test]
+    fn test_non_ascii_wide_string_is_hex() {
+        let mut wide = found("Пароль: %s", 70, 0x40);
+        wide.encoding = Encoding::Utf16Le;
+
+        let rule = render(&YaraFormatter::default(), &[wide]);
+        assert_valid_yara(&rule);
+        // "П" is U+041F, "а" U+0430, ":" and " " stay one byte plus a zero
+        assert!(
+            rule.contains("$s0 = { 1F 04 30 04 40 04 3E 04 3B 04 4C 04 3A 00 20 00 25 00 73 00 }"),
+            "{rule}"
+        );
+        assert!(!rule.contains("wide"), "{rule}");
+    }
+
+    #[test]
+    fn test_top_n_and_threshold() {
+        let strings: Vec<FoundString> = (0..6)
+            .map(|i| found(&format!("string number {}", i), 10 * i, i as u64))
+            .chain([found("string number 5", 50, 0x100)])
+            .collect();
+        let formatter = YaraFormatter::new(OutputConfig {
+            rule_name: "9 sample.exe".to_string(),
+            rule_strings: 3,
+            rule_threshold: Some(2),
+            ..OutputConfig::default()
+        });
+
+        let rule = render(&formatter, &strings);
+        assert_valid_yara(&rule);
+        assert!(rule.starts_with("rule _9_sample_exe\n"));
+        assert_eq!(rule.matches("$s").count(), 3);
+        assert!(rule.contains("$s0 = \"string number 5\""));
+        assert!(rule.contains("$s1 = \"string number 4\""));
+        assert!(rule.contains("$s2 = \"string number 3\""));
+        assert!(rule.contains("        2 of them\n"));
+
+        let all = YaraFormatter::new(OutputConfig {
+            rule_threshold: Some(10),
+            ..OutputConfig::default()
+        });
+        assert!(render(&all, &strings).contains("        all of them\n"));
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let rule = render(&YaraFormatter::default(), &[]);
+        assert_valid_yara(&rule);
+        assert!(!rule.contains("strings:"));
+        assert!(rule.contains("        false\n"));
+    }
+}
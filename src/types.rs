@@ -1,7 +1,14 @@
+use crate::classification::entropy::byte_entropy;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
 
 /// Represents the encoding of an extracted string
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// The derived ordering (declaration order) is part of the canonical output
+/// order; see [`crate::output::sort_canonical`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Encoding {
     Ascii,
     Utf8,
@@ -36,6 +43,156 @@ pub enum Tag {
     Version,
     Manifest,
     Resource,
+    /// Objective-C class name or method selector
+    #[serde(rename = "objc")]
+    ObjC,
+    /// JSON Web Token
+    Jwt,
+    /// API key or access token in a known format
+    #[serde(rename = "api-key")]
+    ApiKey,
+    /// Shell command line
+    #[serde(rename = "cmd")]
+    Command,
+    /// SQL statement
+    Sql,
+    /// Hex digest whose length matches a common hash algorithm
+    #[serde(rename = "hash")]
+    Hash(HashAlgorithm),
+    /// Match of a user-supplied rule, named after the rule; see
+    /// [`TagRule`](crate::classification::custom::TagRule)
+    #[serde(rename = "custom")]
+    Custom(String),
+}
+
+/// Hash algorithm a hex digest's length points to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    /// 32 hex digits
+    Md5,
+    /// 40 hex digits
+    Sha1,
+    /// 64 hex digits
+    Sha256,
+}
+
+impl HashAlgorithm {
+    /// Algorithm whose hex digest is `len` characters long.
+    pub fn from_hex_len(len: usize) -> Option<Self> {
+        match len {
+            32 => Some(Self::Md5),
+            40 => Some(Self::Sha1),
+            64 => Some(Self::Sha256),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Md5 => "md5",
+            Self::Sha1 => "sha1",
+            Self::Sha256 => "sha256",
+        })
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Ascii => "ascii",
+            Self::Utf8 => "utf-8",
+            Self::Utf16Le => "utf-16le",
+            Self::Utf16Be => "utf-16be",
+        })
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = StringyError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "ascii" => Ok(Self::Ascii),
+            "utf8" | "utf-8" => Ok(Self::Utf8),
+            "utf16le" | "utf-16le" => Ok(Self::Utf16Le),
+            "utf16be" | "utf-16be" => Ok(Self::Utf16Be),
+            other => Err(StringyError::ConfigError(format!(
+                "Unknown encoding: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Short tag names for human-readable output; custom tags show their rule
+/// name and hashes their algorithm.
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Url => "url",
+            Self::Domain => "domain",
+            Self::IPv4 => "ipv4",
+            Self::IPv6 => "ipv6",
+            Self::FilePath => "filepath",
+            Self::RegistryPath => "regpath",
+            Self::Guid => "guid",
+            Self::Email => "email",
+            Self::Base64 => "b64",
+            Self::FormatString => "fmt",
+            Self::UserAgent => "user-agent-ish",
+            Self::Import => "import",
+            Self::Export => "export",
+            Self::Version => "version",
+            Self::Manifest => "manifest",
+            Self::Resource => "resource",
+            Self::ObjC => "objc",
+            Self::Jwt => "jwt",
+            Self::ApiKey => "api-key",
+            Self::Command => "cmd",
+            Self::Sql => "sql",
+            Self::Hash(algorithm) => return algorithm.fmt(f),
+            Self::Custom(name) => name,
+        })
+    }
+}
+
+/// Parses the short names [`Tag`]'s `Display` produces. Custom tags are named
+/// after user rules, so they cannot be parsed.
+impl FromStr for Tag {
+    type Err = StringyError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "url" => Ok(Self::Url),
+            "domain" => Ok(Self::Domain),
+            "ipv4" => Ok(Self::IPv4),
+            "ipv6" => Ok(Self::IPv6),
+            "filepath" => Ok(Self::FilePath),
+            "regpath" => Ok(Self::RegistryPath),
+            "guid" => Ok(Self::Guid),
+            "email" => Ok(Self::Email),
+            "b64" => Ok(Self::Base64),
+            "fmt" => Ok(Self::FormatString),
+            "user-agent-ish" => Ok(Self::UserAgent),
+            "import" => Ok(Self::Import),
+            "export" => Ok(Self::Export),
+            "version" => Ok(Self::Version),
+            "manifest" => Ok(Self::Manifest),
+            "resource" => Ok(Self::Resource),
+            "objc" => Ok(Self::ObjC),
+            "jwt" => Ok(Self::Jwt),
+            "api-key" => Ok(Self::ApiKey),
+            "cmd" => Ok(Self::Command),
+            "sql" => Ok(Self::Sql),
+            "md5" => Ok(Self::Hash(HashAlgorithm::Md5)),
+            "sha1" => Ok(Self::Hash(HashAlgorithm::Sha1)),
+            "sha256" => Ok(Self::Hash(HashAlgorithm::Sha256)),
+            other => Err(StringyError::ConfigError(format!("Unknown tag: {}", other))),
+        }
+    }
 }
 
 /// Type of section based on its purpose and likelihood of containing strings
@@ -58,7 +215,7 @@ pub enum SectionType {
 }
 
 /// Source of a string within the binary
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum StringSource {
     /// String found in section data
     SectionData,
@@ -70,40 +227,165 @@ pub enum StringSource {
     ResourceString,
     /// String from Mach-O load command
     LoadCommand,
+    /// String from a Mach-O code signature
+    CodeSignature,
     /// String from debug information
     DebugInfo,
 }
 
 /// Information about a container (binary file)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContainerInfo {
     /// The binary format detected
     pub format: BinaryFormat,
+    /// Whether addresses are 64 bits wide (ELFCLASS64, PE32+, 64-bit Mach-O)
+    ///
+    /// Section RVAs are always stored as `u64`; this tells consumers which
+    /// width to format them with and which pointer size to scan for.
+    pub is_64bit: bool,
+    /// CPU architecture name (e.g. `x86_64`, `arm64`), reported for Mach-O
+    /// binaries and for each slice of a fat binary
+    pub architecture: Option<String>,
     /// List of sections in the binary
     pub sections: Vec<SectionInfo>,
     /// Import information
     pub imports: Vec<ImportInfo>,
     /// Export information
     pub exports: Vec<ExportInfo>,
+    /// Loader metadata for ELF binaries
+    #[serde(default)]
+    pub elf: Option<ElfMetadata>,
+    /// Rich header of PE binaries linked by Microsoft tools
+    #[serde(default)]
+    pub rich_header: Option<RichHeader>,
+    /// RVAs of the TLS callbacks of a PE binary, in table order
+    ///
+    /// The loader runs these before the entry point, so malware uses them to
+    /// execute code ahead of `main` and of debugger breakpoints set there.
+    #[serde(default)]
+    pub tls_callbacks: Vec<u64>,
+}
+
+impl ContainerInfo {
+    /// Returns the synthetic section covering data appended past the end of
+    /// the image, if the parser found any; its `offset` and `size` locate the
+    /// overlay. See [`crate::container::overlay`].
+    pub fn overlay(&self) -> Option<&SectionInfo> {
+        self.sections
+            .iter()
+            .find(|section| section.name == crate::container::overlay::OVERLAY_SECTION)
+    }
+
+    /// Translates a virtual address to a file offset through the section
+    /// that maps it, or `None` if no section with file data covers `rva`.
+    pub fn rva_to_offset(&self, rva: u64) -> Option<u64> {
+        self.sections.iter().find_map(|section| {
+            let start = section.rva?;
+            let delta = rva.checked_sub(start)?;
+            (delta < section.size).then_some(section.offset + delta)
+        })
+    }
+}
+
+/// Decoded Rich header of a PE binary
+///
+/// The undocumented header between the DOS stub and the PE header records
+/// which compilers and linkers produced the object files, which fingerprints
+/// the build toolchain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RichHeader {
+    /// XOR key the header is encoded with, a checksum of the DOS header and
+    /// the entries
+    pub checksum: u32,
+    /// Tool records in header order
+    pub entries: Vec<RichEntry>,
+}
+
+/// One tool record of a [`RichHeader`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RichEntry {
+    /// Product (tool and object type) identifier
+    pub product_id: u16,
+    /// Build number of the tool
+    pub build: u16,
+    /// Number of objects the tool produced
+    pub count: u32,
+}
+
+/// Dynamic loader metadata of an ELF binary
+///
+/// Statically linked binaries have no interpreter and no search paths.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElfMetadata {
+    /// Program interpreter (dynamic loader) path from `PT_INTERP`
+    pub interpreter: Option<String>,
+    /// Library search paths from `DT_RPATH`
+    pub rpaths: Vec<String>,
+    /// Library search paths from `DT_RUNPATH`
+    pub runpaths: Vec<String>,
+    /// GNU build ID (`NT_GNU_BUILD_ID` note) as lowercase hex
+    pub build_id: Option<String>,
 }
 
 /// Binary format types
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Serialized in lowercase: `"elf"`, `"pe"`, `"macho"`, `"archive"`,
+/// `"unknown"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum BinaryFormat {
     Elf,
     Pe,
     MachO,
+    /// `ar` archive (static library) of objects in the other formats
+    Archive,
     Unknown,
 }
 
+/// Conventional format names: `ELF`, `PE`, `Mach-O`, `Archive`, `Unknown`.
+impl fmt::Display for BinaryFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Elf => "ELF",
+            Self::Pe => "PE",
+            Self::MachO => "Mach-O",
+            Self::Archive => "Archive",
+            Self::Unknown => "Unknown",
+        })
+    }
+}
+
+/// Parses a format name case-insensitively: `elf`, `pe`, `macho` or
+/// `mach-o`, `archive` or `ar`. `Unknown` is not a format one can ask for,
+/// so it does not parse.
+impl FromStr for BinaryFormat {
+    type Err = StringyError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "elf" => Ok(Self::Elf),
+            "pe" => Ok(Self::Pe),
+            "macho" | "mach-o" => Ok(Self::MachO),
+            "archive" | "ar" => Ok(Self::Archive),
+            other => Err(StringyError::ConfigError(format!(
+                "Unknown binary format: {}",
+                other
+            ))),
+        }
+    }
+}
+
 /// Information about a section within the binary
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SectionInfo {
     /// Section name
     pub name: String,
     /// File offset of the section
     pub offset: u64,
-    /// Size of the section in bytes
+    /// Size of the section's data in the file, in bytes
+    ///
+    /// Sections with no file backing (`.bss` and other zero-fill sections)
+    /// report 0.
     pub size: u64,
     /// Relative Virtual Address (if available)
     pub rva: Option<u64>,
@@ -113,10 +395,66 @@ pub struct SectionInfo {
     pub is_executable: bool,
     /// Whether the section is writable
     pub is_writable: bool,
+    /// Shannon entropy of the section's file bytes, in bits per byte
+    ///
+    /// Ranges from 0 (one repeated byte) to 8 (uniformly random bytes);
+    /// packed, compressed and encrypted sections sit close to 8. `None` for
+    /// sections with no file data. Set by [`SectionInfo::measure_entropy`].
+    #[serde(default)]
+    pub entropy: Option<f32>,
+    /// Whether the headers declare more bytes than the file holds, in which
+    /// case `size` covers only the bytes that are present
+    ///
+    /// Common in truncated downloads and in packed or deliberately malformed
+    /// binaries. Set by [`SectionInfo::clamp_to`].
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+impl SectionInfo {
+    /// Returns the section's bytes within `file`.
+    ///
+    /// Returns `None` for sections with no file data and for ranges that do
+    /// not fit inside `file`.
+    pub fn data<'a>(&self, file: &'a [u8]) -> Option<&'a [u8]> {
+        if self.size == 0 {
+            return None;
+        }
+        let start = usize::try_from(self.offset).ok()?;
+        let len = usize::try_from(self.size).ok()?;
+        file.get(start..start.checked_add(len)?)
+    }
+
+    /// Shrinks the section to end at file offset `end` if it extends past
+    /// it, marking it [`SectionInfo::truncated`].
+    ///
+    /// A section starting at or past `end` is left with no file data.
+    /// Parsers call this with the input length so that `size` never claims
+    /// bytes that are not there.
+    pub fn clamp_to(&mut self, end: u64) {
+        if self.size == 0 || self.offset.saturating_add(self.size) <= end {
+            return;
+        }
+        let available = end.saturating_sub(self.offset);
+        log::warn!(
+            "Section {} at file offset {:#x} declares {} bytes, but only {} are present",
+            self.name,
+            self.offset,
+            self.size,
+            available
+        );
+        self.size = available;
+        self.truncated = true;
+    }
+
+    /// Sets [`SectionInfo::entropy`] from the section's bytes within `file`.
+    pub fn measure_entropy(&mut self, file: &[u8]) {
+        self.entropy = self.data(file).map(byte_entropy);
+    }
 }
 
 /// Information about an import
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImportInfo {
     /// Name of the imported symbol
     pub name: String,
@@ -124,10 +462,32 @@ pub struct ImportInfo {
     pub library: Option<String>,
     /// Address or ordinal
     pub address: Option<u64>,
+    /// Demangled display name, if `name` is a mangled C++ or Rust symbol
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub demangled: Option<String>,
+    /// Whether the library is loaded on first use through the PE delay-load
+    /// import table rather than at startup
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub delayed: bool,
+    /// GNU symbol version the import requires (e.g. `GLIBC_2.14`), for
+    /// versioned ELF dynamic symbols
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+impl ImportInfo {
+    /// Name as `nm` and `readelf` show it: `name@VERSION` for versioned
+    /// symbols, the bare name otherwise.
+    pub fn display_name(&self) -> String {
+        match &self.version {
+            Some(version) => format!("{}@{}", self.name, version),
+            None => self.name.clone(),
+        }
+    }
 }
 
 /// Information about an export
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExportInfo {
     /// Name of the exported symbol
     pub name: String,
@@ -135,6 +495,36 @@ pub struct ExportInfo {
     pub address: u64,
     /// Export ordinal (if available)
     pub ordinal: Option<u16>,
+    /// Demangled display name, if `name` is a mangled C++ or Rust symbol
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub demangled: Option<String>,
+    /// Target of a re-exported symbol, which has no address of its own
+    ///
+    /// Mach-O re-exports are written `<dylib install name>:<symbol>`; PE
+    /// forwarders keep their own `<DLL>.<symbol>` (or `<DLL>.#<ordinal>`) form.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forwarded_to: Option<String>,
+    /// GNU symbol version the export is defined in (e.g. `GLIBC_2.14`), for
+    /// versioned ELF dynamic symbols
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Whether `version` is a hidden, non-default version kept only for
+    /// binaries linked against it
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub version_hidden: bool,
+}
+
+impl ExportInfo {
+    /// Name as `nm` and `readelf` show it: `name@@VERSION` for the default
+    /// version of a symbol, `name@VERSION` for a hidden one, the bare name
+    /// for unversioned symbols.
+    pub fn display_name(&self) -> String {
+        match &self.version {
+            Some(version) if self.version_hidden => format!("{}@{}", self.name, version),
+            Some(version) => format!("{}@@{}", self.name, version),
+            None => self.name.clone(),
+        }
+    }
 }
 
 /// A string found in the binary with metadata
@@ -150,6 +540,9 @@ pub struct FoundString {
     pub rva: Option<u64>,
     /// Section name where the string was found
     pub section: Option<String>,
+    /// Type of the section where the string was found, if known
+    #[serde(default)]
+    pub section_type: Option<SectionType>,
     /// Length of the string in bytes
     pub length: u32,
     /// Semantic tags applied to this string
@@ -158,6 +551,254 @@ pub struct FoundString {
     pub score: i32,
     /// Source of the string (section data, import, etc.)
     pub source: StringSource,
+    /// Number of pointer-sized references to this string's address, if computed
+    #[serde(default)]
+    pub xref_count: Option<u32>,
+    /// Number of times the string occurs in the input
+    ///
+    /// Always 1 unless duplicates were collapsed with
+    /// [`deduplicate`](crate::extraction::dedup::deduplicate).
+    #[serde(default = "default_occurrences")]
+    pub occurrences: u32,
+    /// Printable preview of the decoded content, for encoded strings whose
+    /// content is worth showing (e.g. base64 of text or of an executable)
+    #[serde(default)]
+    pub decoded: Option<String>,
+    /// Demangled form of a mangled symbol name in `text`, filled in when
+    /// [`ExtractionConfig::demangle`](crate::extraction::ExtractionConfig::demangle)
+    /// is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub demangled: Option<String>,
+}
+
+fn default_occurrences() -> u32 {
+    1
+}
+
+impl FoundString {
+    /// Creates a string with the required fields set and the rest at their
+    /// defaults: no RVA, section or tags, a score of 0, no cross-reference
+    /// count, one occurrence and no decoded preview or demangled name.
+    ///
+    /// Set the other fields with struct update syntax, so adding a field to
+    /// `FoundString` only touches this constructor:
+    ///
+    /// ```rust
+    /// use stringy::{Encoding, FoundString, StringSource, Tag};
+    ///
+    /// let string = FoundString {
+    ///     tags: vec![Tag::Url],
+    ///     ..FoundString::new("https://example.com", Encoding::Ascii, 0x400, 19, StringSource::SectionData)
+    /// };
+    /// assert_eq!(string.occurrences, 1);
+    /// ```
+    pub fn new(
+        text: impl Into<String>,
+        encoding: Encoding,
+        offset: u64,
+        length: u32,
+        source: StringSource,
+    ) -> Self {
+        Self {
+            text: text.into(),
+            encoding,
+            offset,
+            rva: None,
+            section: None,
+            section_type: None,
+            length,
+            tags: Vec::new(),
+            score: 0,
+            source,
+            xref_count: None,
+            occurrences: default_occurrences(),
+            decoded: None,
+            demangled: None,
+        }
+    }
+
+    /// Starts building a string; see [`FoundStringBuilder`].
+    pub fn builder() -> FoundStringBuilder {
+        FoundStringBuilder::default()
+    }
+
+    /// Compares two strings in canonical output order: file offset, then
+    /// encoding, then source, with the text and section as final
+    /// tie-breakers.
+    ///
+    /// Only identical strings compare equal, so sorting by this order does
+    /// not depend on the order the strings arrived in; see
+    /// [`crate::output::sort_canonical`].
+    pub fn cmp_canonical(&self, other: &Self) -> Ordering {
+        self.offset
+            .cmp(&other.offset)
+            .then(self.encoding.cmp(&other.encoding))
+            .then(self.source.cmp(&other.source))
+            .then_with(|| self.text.cmp(&other.text))
+            .then_with(|| self.section.cmp(&other.section))
+    }
+
+    /// Text to show for the string: the demangled name if there is one,
+    /// otherwise the text as found.
+    pub fn display_text(&self) -> &str {
+        self.demangled.as_deref().unwrap_or(&self.text)
+    }
+}
+
+/// Builder for [`FoundString`].
+///
+/// `text`, `offset`, `encoding`, `length` and `source` must be set. The
+/// rest default to no RVA, section or tags, a score of 0, no cross-reference
+/// count, one occurrence and no decoded preview or demangled name.
+///
+/// # Examples
+///
+/// ```rust
+/// use stringy::{Encoding, FoundString, StringSource, Tag};
+///
+/// let string = FoundString::builder()
+///     .text("https://example.com")
+///     .offset(0x400)
+///     .encoding(Encoding::Ascii)
+///     .length(19)
+///     .source(StringSource::SectionData)
+///     .section(".rodata")
+///     .tag(Tag::Url)
+///     .build()?;
+/// assert_eq!(string.rva, None);
+/// assert_eq!(string.tags, vec![Tag::Url]);
+/// # Ok::<(), stringy::StringyError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FoundStringBuilder {
+    text: Option<String>,
+    encoding: Option<Encoding>,
+    offset: Option<u64>,
+    rva: Option<u64>,
+    section: Option<String>,
+    section_type: Option<SectionType>,
+    length: Option<u32>,
+    tags: Vec<Tag>,
+    score: i32,
+    source: Option<StringSource>,
+    xref_count: Option<u32>,
+    occurrences: Option<u32>,
+    decoded: Option<String>,
+    demangled: Option<String>,
+}
+
+impl FoundStringBuilder {
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    pub fn encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = Some(encoding);
+        self
+    }
+
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    pub fn rva(mut self, rva: u64) -> Self {
+        self.rva = Some(rva);
+        self
+    }
+
+    pub fn section(mut self, section: impl Into<String>) -> Self {
+        self.section = Some(section.into());
+        self
+    }
+
+    pub fn section_type(mut self, section_type: SectionType) -> Self {
+        self.section_type = Some(section_type);
+        self
+    }
+
+    /// Length of the string in bytes, as found in the input.
+    pub fn length(mut self, length: u32) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    /// Adds one tag.
+    pub fn tag(mut self, tag: Tag) -> Self {
+        self.tags.push(tag);
+        self
+    }
+
+    /// Replaces all tags.
+    pub fn tags(mut self, tags: Vec<Tag>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    pub fn score(mut self, score: i32) -> Self {
+        self.score = score;
+        self
+    }
+
+    pub fn source(mut self, source: StringSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    pub fn xref_count(mut self, xref_count: u32) -> Self {
+        self.xref_count = Some(xref_count);
+        self
+    }
+
+    pub fn occurrences(mut self, occurrences: u32) -> Self {
+        self.occurrences = Some(occurrences);
+        self
+    }
+
+    pub fn decoded(mut self, decoded: impl Into<String>) -> Self {
+        self.decoded = Some(decoded.into());
+        self
+    }
+
+    pub fn demangled(mut self, demangled: impl Into<String>) -> Self {
+        self.demangled = Some(demangled.into());
+        self
+    }
+
+    /// Finishes the string.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StringyError::ConfigError` naming the first required field
+    /// that was not set.
+    pub fn build(self) -> Result<FoundString> {
+        fn required<T>(value: Option<T>, field: &str) -> Result<T> {
+            value.ok_or_else(|| {
+                StringyError::ConfigError(format!("FoundString is missing `{}`", field))
+            })
+        }
+
+        let string = FoundString::new(
+            required(self.text, "text")?,
+            required(self.encoding, "encoding")?,
+            required(self.offset, "offset")?,
+            required(self.length, "length")?,
+            required(self.source, "source")?,
+        );
+        Ok(FoundString {
+            rva: self.rva,
+            section: self.section,
+            section_type: self.section_type,
+            tags: self.tags,
+            score: self.score,
+            xref_count: self.xref_count,
+            occurrences: self.occurrences.unwrap_or(string.occurrences),
+            decoded: self.decoded,
+            demangled: self.demangled,
+            ..string
+        })
+    }
 }
 
 /// Error types for the stringy library
@@ -180,6 +821,9 @@ pub enum StringyError {
 
     #[error("Memory mapping error: {0}")]
     MemoryMapError(String),
+
+    #[error("Serialization error: {0}")]
+    SerializationError(String),
 }
 
 /// Result type alias for the stringy library
@@ -190,3 +834,320 @@ fn from(err: goblin::error::Error) -> Self {
         StringyError::ParseError(err.to_string())
     }
 }
+
+impl From<serde_json::Error> for StringyError {
+    fn from(err: serde_json::Error) -> Self {
+        StringyError::SerializationError(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(offset: u64, size: u64) -> SectionInfo {
+        SectionInfo {
+            name: ".rodata".to_string(),
+            offset,
+            size,
+            rva: None,
+            section_type: SectionType::StringData,
+            is_executable: false,
+            is_writable: false,
+            entropy: None,
+            truncated: false,
+        }
+    }
+
+    #[test]
+    fn test_section_data() {
+        let file = b"0123456789";
+        assert_eq!(section(2, 3).data(file), Some(&b"234"[..]));
+        assert_eq!(section(0, 10).data(file), Some(&file[..]));
+    }
+
+    #[test]
+    fn test_section_data_out_of_range() {
+        let file = b"0123456789";
+        assert_eq!(section(8, 3).data(file), None);
+        assert_eq!(section(11, 1).data(file), None);
+        assert_eq!(section(u64::MAX, 2).data(file), None);
+    }
+
+    #[test]
+    fn test_clamp_oversized_section() {
+        let file = b"0123456789";
+        let mut oversized = section(6, 100);
+        oversized.clamp_to(file.len() as u64);
+        assert!(oversized.truncated);
+        assert_eq!(oversized.size, 4);
+        assert_eq!(oversized.data(file), Some(&b"6789"[..]));
+
+        let mut past_end = section(20, 4);
+        past_end.clamp_to(file.len() as u64);
+        assert!(past_end.truncated);
+        assert_eq!(past_end.data(file), None);
+
+        let mut fits = section(2, 8);
+        fits.clamp_to(file.len() as u64);
+        assert!(!fits.truncated);
+        assert_eq!(fits.size, 8);
+    }
+
+    #[test]
+    fn test_section_data_without_file_backing() {
+        // .bss-style sections have no bytes in the file
+        assert_eq!(section(4, 0).data(b"0123456789"), None);
+    }
+
+    #[test]
+    fn test_section_entropy() {
+        // xorshift32 output: close enough to uniform for 64 KiB
+        let mut state = 0x1234_5678u32;
+        let mut file: Vec<u8> = (0..0x10000)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect();
+        file.extend_from_slice(&[0; 0x1000]);
+
+        let mut random = section(0, 0x10000);
+        random.measure_entropy(&file);
+        assert!(random.entropy.unwrap() > 7.99, "{:?}", random.entropy);
+
+        let mut zeros = section(0x10000, 0x1000);
+        zeros.measure_entropy(&file);
+        assert_eq!(zeros.entropy, Some(0.0));
+
+        let mut bss = section(0x10000, 0);
+        bss.measure_entropy(&file);
+        assert_eq!(bss.entropy, None);
+    }
+
+    #[test]
+    fn test_encoding_from_str_roundtrip() {
+        for encoding in [
+            Encoding::Ascii,
+            Encoding::Utf8,
+            Encoding::Utf16Le,
+            Encoding::Utf16Be,
+        ] {
+            assert_eq!(encoding.to_string().parse::<Encoding>().unwrap(), encoding);
+        }
+        assert_eq!("UTF16LE".parse::<Encoding>().unwrap(), Encoding::Utf16Le);
+        assert!("utf32".parse::<Encoding>().is_err());
+    }
+
+    #[test]
+    fn test_tag_from_str_roundtrip() {
+        for tag in [
+            Tag::Url,
+            Tag::IPv4,
+            Tag::RegistryPath,
+            Tag::FormatString,
+            Tag::UserAgent,
+            Tag::ApiKey,
+            Tag::Command,
+            Tag::Hash(HashAlgorithm::Sha256),
+        ] {
+            assert_eq!(tag.to_string().parse::<Tag>().unwrap(), tag);
+        }
+        assert_eq!("URL".parse::<Tag>().unwrap(), Tag::Url);
+        assert!("custom".parse::<Tag>().is_err());
+    }
+
+    #[test]
+    fn test_binary_format_from_str_roundtrip() {
+        for format in [
+            BinaryFormat::Elf,
+            BinaryFormat::Pe,
+            BinaryFormat::MachO,
+            BinaryFormat::Archive,
+        ] {
+            assert_eq!(format.to_string().parse::<BinaryFormat>().unwrap(), format);
+        }
+        assert_eq!(BinaryFormat::MachO.to_string(), "Mach-O");
+        assert_eq!(
+            "MACHO".parse::<BinaryFormat>().unwrap(),
+            BinaryFormat::MachO
+        );
+        assert_eq!("Elf".parse::<BinaryFormat>().unwrap(), BinaryFormat::Elf);
+        assert!("wasm".parse::<BinaryFormat>().is_err());
+        assert!("".parse::<BinaryFormat>().is_err());
+    }
+
+    #[test]
+    fn test_container_info_roundtrip() {
+        let info = ContainerInfo {
+            format: BinaryFormat::MachO,
+            is_64bit: true,
+            architecture: Some("arm64".to_string()),
+            sections: vec![section(0x1000, 0x200)],
+            imports: vec![ImportInfo {
+                name: "_open".to_string(),
+                library: Some("/usr/lib/libSystem.B.dylib".to_string()),
+                address: None,
+                demangled: None,
+                delayed: false,
+                version: None,
+            }],
+            exports: vec![ExportInfo {
+                name: "_main".to_string(),
+                address: 0x1f00,
+                ordinal: None,
+                demangled: None,
+                forwarded_to: None,
+                version: None,
+                version_hidden: false,
+            }],
+            elf: Some(ElfMetadata {
+                interpreter: Some("/lib64/ld-linux-x86-64.so.2".to_string()),
+                build_id: Some("0a1b2c".to_string()),
+                ..ElfMetadata::default()
+            }),
+            rich_header: Some(RichHeader {
+                checksum: 0xdead_beef,
+                entries: vec![RichEntry {
+                    product_id: 0x0105,
+                    build: 30_795,
+                    count: 12,
+                }],
+            }),
+            tls_callbacks: vec![0x1010],
+        };
+
+        let json = serde_json::to_string(&info).unwrap();
+        assert!(json.contains("\"format\":\"macho\""));
+        assert!(json.contains("\"section_type\":\"StringData\""));
+
+        let parsed: ContainerInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+        assert_eq!(parsed.format, BinaryFormat::MachO);
+        assert_eq!(parsed.sections[0].offset, 0x1000);
+        assert_eq!(parsed.rich_header, info.rich_header);
+        assert_eq!(parsed.elf, info.elf);
+    }
+
+    #[test]
+    fn test_symbol_display_names() {
+        let mut import = ImportInfo {
+            name: "memcpy".to_string(),
+            library: Some("libc.so.6".to_string()),
+            address: None,
+            demangled: None,
+            delayed: false,
+            version: Some("GLIBC_2.14".to_string()),
+        };
+        assert_eq!(import.display_name(), "memcpy@GLIBC_2.14");
+        import.version = None;
+        assert_eq!(import.display_name(), "memcpy");
+
+        let mut export = ExportInfo {
+            name: "memcpy".to_string(),
+            address: 0x1000,
+            ordinal: None,
+            demangled: None,
+            forwarded_to: None,
+            version: Some("GLIBC_2.14".to_string()),
+            version_hidden: false,
+        };
+        assert_eq!(export.display_name(), "memcpy@@GLIBC_2.14");
+        export.version = Some("GLIBC_2.2.5".to_string());
+        export.version_hidden = true;
+        assert_eq!(export.display_name(), "memcpy@GLIBC_2.2.5");
+
+        let json = serde_json::to_value(&export).unwrap();
+        assert_eq!(json["version"], "GLIBC_2.2.5");
+        assert_eq!(json["version_hidden"], true);
+    }
+
+    #[test]
+    fn test_binary_format_names() {
+        for (format, name) in [
+            (BinaryFormat::Elf, "\"elf\""),
+            (BinaryFormat::Pe, "\"pe\""),
+            (BinaryFormat::MachO, "\"macho\""),
+            (BinaryFormat::Archive, "\"archive\""),
+            (BinaryFormat::Unknown, "\"unknown\""),
+        ] {
+            assert_eq!(serde_json::to_string(&format).unwrap(), name);
+            assert_eq!(serde_json::from_str::<BinaryFormat>(name).unwrap(), format);
+        }
+    }
+
+    fn complete() -> FoundStringBuilder {
+        FoundString::builder()
+            .text("hello")
+            .offset(0x10)
+            .encoding(Encoding::Utf16Le)
+            .length(10)
+            .source(StringSource::SectionData)
+    }
+
+    #[test]
+    fn test_builder_defaults() {
+        let string = complete().build().unwrap();
+        assert_eq!(string.text, "hello");
+        assert_eq!(string.offset, 0x10);
+        assert_eq!(string.encoding, Encoding::Utf16Le);
+        assert_eq!(string.length, 10);
+        assert_eq!(string.source, StringSource::SectionData);
+        assert_eq!(string.rva, None);
+        assert_eq!(string.section, None);
+        assert_eq!(string.section_type, None);
+        assert!(string.tags.is_empty());
+        assert_eq!(string.score, 0);
+        assert_eq!(string.xref_count, None);
+        assert_eq!(string.occurrences, 1);
+        assert_eq!(string.decoded, None);
+    }
+
+    #[test]
+    fn test_builder_setters() {
+        let string = complete()
+            .rva(0x1010)
+            .section(".rdata")
+            .section_type(SectionType::StringData)
+            .tags(vec![Tag::Domain])
+            .tag(Tag::Url)
+            .score(42)
+            .xref_count(3)
+            .occurrences(2)
+            .decoded("decoded")
+            .build()
+            .unwrap();
+        assert_eq!(string.rva, Some(0x1010));
+        assert_eq!(string.section.as_deref(), Some(".rdata"));
+        assert_eq!(string.section_type, Some(SectionType::StringData));
+        assert_eq!(string.tags, vec![Tag::Domain, Tag::Url]);
+        assert_eq!(string.score, 42);
+        assert_eq!(string.xref_count, Some(3));
+        assert_eq!(string.occurrences, 2);
+        assert_eq!(string.decoded.as_deref(), Some("decoded"));
+    }
+
+    #[test]
+    fn test_builder_requires_fields() {
+        let missing = |builder: FoundStringBuilder| match builder.build() {
+            Err(StringyError::ConfigError(message)) => message,
+            other => panic!("unexpected result: {:?}", other),
+        };
+
+        assert!(missing(FoundString::builder()).contains("`text`"));
+        let mut builder = complete();
+        builder.offset = None;
+        assert!(missing(builder).contains("`offset`"));
+        let mut builder = complete();
+        builder.encoding = None;
+        assert!(missing(builder).contains("`encoding`"));
+        let mut builder = complete();
+        builder.length = None;
+        assert!(missing(builder).contains("`length`"));
+        let mut builder = complete();
+        builder.source = None;
+        assert!(missing(builder).contains("`source`"));
+    }
+}
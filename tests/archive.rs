@@ -0,0 +1,90 @@
+//! Archive parsing checks against a static library built with `cc` and `ar`.
+
+#![cfg(all(target_os = "linux", target_env = "gnu"))]
+
+use std::path::Path;
+use std::process::Command;
+
+use stringy::BinaryFormat;
+use stringy::container::{ContainerParser, archive::ArchiveParser, detect_format};
+
+fn compile(dir: &Path, name: &str, source: &str) {
+    let source_path = dir.join(format!("{name}.c"));
+    std::fs::write(&source_path, source).expect("write C source");
+    let status = Command::new("cc")
+        .arg("-c")
+        .arg(&source_path)
+        .arg("-o")
+        .arg(dir.join(format!("{name}.o")))
+        .status()
+        .expect("run cc");
+    assert!(status.success(), "cc failed for {name}.c");
+}
+
+/// Builds `libpair.a` from two objects that each export a function.
+fn build_library(dir: &Path) -> Vec<u8> {
+    compile(
+        dir,
+        "alpha",
+        "const char *alpha_banner(void) { return \"alpha says hello\"; }\n",
+    );
+    compile(
+        dir,
+        "beta",
+        "extern int puts(const char *);\nint beta_greet(void) { return puts(\"beta says hi\"); }\n",
+    );
+    let library = dir.join("libpair.a");
+    let status = Command::new("ar")
+        .arg("rcs")
+        .arg(&library)
+        .arg(dir.join("alpha.o"))
+        .arg(dir.join("beta.o"))
+        .status()
+        .expect("run ar");
+    assert!(status.success(), "ar failed");
+    std::fs::read(library).expect("read archive")
+}
+
+#[test]
+fn test_static_library_members() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let data = build_library(dir.path());
+
+    assert_eq!(detect_format(&data), BinaryFormat::Archive);
+    let info = ArchiveParser::new().parse(&data).expect("parse archive");
+    assert_eq!(info.format, BinaryFormat::Archive);
+
+    // Sections of both members are kept apart by the member prefix
+    for member in ["alpha.o", "beta.o"] {
+        let prefix = format!("{member}:");
+        assert!(
+            info.sections
+                .iter()
+                .any(|s| s.name == format!("{prefix}.text")),
+            "no .text section for {member}: {:?}",
+            info.sections.iter().map(|s| &s.name).collect::<Vec<_>>()
+        );
+    }
+
+    // Section offsets point into the archive itself
+    let rodata = info
+        .sections
+        .iter()
+        .find(|s| s.name.starts_with("alpha.o:.rodata"))
+        .expect("alpha.o rodata section");
+    let bytes = rodata.data(&data).expect("rodata bytes");
+    assert!(
+        bytes
+            .windows(b"alpha says hello".len())
+            .any(|w| w == b"alpha says hello")
+    );
+
+    let exports: Vec<&str> = info.exports.iter().map(|e| e.name.as_str()).collect();
+    assert!(exports.contains(&"alpha_banner"), "exports: {exports:?}");
+    assert!(exports.contains(&"beta_greet"), "exports: {exports:?}");
+    assert!(
+        info.imports.iter().any(|i| i.name == "puts"),
+        "imports: {:?}",
+        info.imports
+    );
+}
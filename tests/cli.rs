@@ -0,0 +1,689 @@
+//! End-to-end checks running the `stringy` binary.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn stringy() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_stringy"))
+}
+
+/// Unrecognized input is scanned as raw bytes.
+#[test]
+fn test_prints_strings_from_raw_input() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let path = dir.path().join("blob.bin");
+    let mut data = vec![0u8; 64];
+    data.extend_from_slice(b"https://c2.example.net/gate.php\0");
+    data.extend_from_slice(&[0xff; 32]);
+    data.extend_from_slice(b"plain marker string\0");
+    std::fs::write(&path, &data).expect("write fixture");
+
+    let output = stringy().arg(&path).output().expect("run stringy");
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).expect("UTF-8 output");
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(lines[0].starts_with("OFFSET"), "{stdout}");
+    // Highest score first: the URL outranks the plain string
+    assert!(
+        lines[2].ends_with("https://c2.example.net/gate.php"),
+        "{stdout}"
+    );
+    assert!(lines[2].contains("url"), "{stdout}");
+    assert!(stdout.contains("plain marker string"), "{stdout}");
+}
+
+#[test]
+fn test_jsonl_output_with_min_len() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let path = dir.path().join("blob.bin");
+    std::fs::write(&path, b"\0tiny\0sixsix\0a longer string\0").expect("write fixture");
+
+    let output = stringy()
+        .args(["--format", "jsonl", "--min-len", "6", "--sort", "offset"])
+        .arg(&path)
+        .output()
+        .expect("run stringy");
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).expect("UTF-8 output");
+    let records: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("one JSON object per line"))
+        .collect();
+    let texts: Vec<&str> = records
+        .iter()
+        .map(|record| record["text"].as_str().expect("text field"))
+        .collect();
+    assert_eq!(texts, vec!["sixsix", "a longer string"]);
+    for record in &records {
+        assert!(record["offset"].is_u64(), "{record}");
+        assert!(record["tags"].is_array(), "{record}");
+        assert!(record["score"].is_i64(), "{record}");
+    }
+}
+
+#[test]
+fn test_unknown_format_is_usage_error() {
+    let output = stringy()
+        .args(["--format", "xml", "input.bin"])
+        .output()
+        .expect("run stringy");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid value 'xml'"), "{stderr}");
+}
+
+/// Two raw "binaries", one in a subdirectory, plus entries that are skipped.
+fn sample_tree() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let root = dir.path();
+    std::fs::create_dir(root.join("nested")).expect("create subdirectory");
+    std::fs::write(root.join("first.bin"), b"\0first binary marker\0").expect("write fixture");
+    std::fs::write(root.join("nested/second.bin"), b"\0second binary marker\0")
+        .expect("write fixture");
+    // Text files are not binaries and are passed over
+    std::fs::write(root.join("notes.txt"), "readme text marker\n").expect("write fixture");
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(root.join("missing"), root.join("dangling.bin"))
+        .expect("create symlink");
+    dir
+}
+
+#[test]
+fn test_directory_jsonl_has_file_field() {
+    let dir = sample_tree();
+    let output = stringy()
+        .args(["--recursive", "--format", "jsonl"])
+        .arg(dir.path())
+        .output()
+        .expect("run stringy");
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).expect("UTF-8 output");
+    let records: Vec<(String, String)> = stdout
+        .lines()
+        .map(|line| {
+            let record: serde_json::Value = serde_json::from_str(line).expect("JSON line");
+            let file = record["file"].as_str().expect("file field").to_string();
+            let text = record["text"].as_str().expect("text field").to_string();
+            (file, text)
+        })
+        .collect();
+
+    let first = dir.path().join("first.bin").display().to_string();
+    let second = dir.path().join("nested/second.bin").display().to_string();
+    assert_eq!(
+        records,
+        vec![
+            (first, "first binary marker".to_string()),
+            (second, "second binary marker".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_directory_table_without_recursion() {
+    let dir = sample_tree();
+    let output = stringy().arg(dir.path()).output().expect("run stringy");
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).expect("UTF-8 output");
+    let heading = format!("==> {} <==", dir.path().join("first.bin").display());
+    assert!(stdout.starts_with(&heading), "{stdout}");
+    assert!(stdout.contains("first binary marker"), "{stdout}");
+    assert!(!stdout.contains("second binary marker"), "{stdout}");
+}
+
+#[test]
+fn test_directory_rejects_unlabelled_format() {
+    let dir = sample_tree();
+    let output = stringy()
+        .args(["--format", "csv"])
+        .arg(dir.path())
+        .output()
+        .expect("run stringy");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+}
+
+/// Runs stringy on `-`, feeding `input` through stdin.
+fn run_with_stdin(args: &[&str], input: &[u8]) -> std::process::Output {
+    let mut child = stringy()
+        .args(args)
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("spawn stringy");
+    child
+        .stdin
+        .take()
+        .expect("stdin pipe")
+        .write_all(input)
+        .expect("write stdin");
+    child.wait_with_output().expect("wait for stringy")
+}
+
+#[test]
+fn test_reads_stdin() {
+    let mut data = vec![0u8; 16];
+    data.extend_from_slice(b"piped marker string\0http://piped.example.org/\0");
+    let output = run_with_stdin(&["--format", "jsonl"], &data);
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).expect("UTF-8 output");
+    assert!(
+        stdout.contains("\"text\":\"piped marker string\""),
+        "{stdout}"
+    );
+    assert!(stdout.contains("\"offset\":16"), "{stdout}");
+}
+
+/// Format detection runs on the in-memory buffer just as on a mapped file.
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn test_reads_elf_from_stdin() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let binary = std::fs::read(compile_fixture(dir.path())).expect("read fixture");
+
+    let output = run_with_stdin(&["--format", "jsonl"], &binary);
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).expect("UTF-8 output");
+    let line = stdout
+        .lines()
+        .find(|line| line.contains("\"text\":\"stringy fixture says hello\""))
+        .unwrap_or_else(|| panic!("fixture string missing:\n{stdout}"));
+    assert!(line.contains("\"section\":\".rodata\""), "{line}");
+}
+
+#[test]
+fn test_empty_stdin_fails() {
+    let output = run_with_stdin(&[], b"");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("stringy: stdin:"), "{stderr}");
+    assert!(stderr.contains("empty"), "{stderr}");
+}
+
+#[test]
+fn test_missing_input_fails() {
+    let output = stringy()
+        .arg("/nonexistent-stringy-dir/input.bin")
+        .output()
+        .expect("run stringy");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.starts_with("stringy: "), "{stderr}");
+}
+
+#[test]
+fn test_offset_window() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let path = dir.path().join("blob.bin");
+    std::fs::write(&path, b"first marker\0second marker\0third marker\0").expect("write fixture");
+
+    let output = stringy()
+        .args(["--format", "jsonl", "--offset", "0xd:0x1b"])
+        .arg(&path)
+        .output()
+        .expect("run stringy");
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).expect("UTF-8 output");
+    let records: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("one JSON object per line"))
+        .collect();
+    assert_eq!(records.len(), 1, "{stdout}");
+    assert_eq!(records[0]["text"], "second marker");
+    assert_eq!(records[0]["offset"], 13);
+}
+
+/// `--format signature` suggests a YARA rule, leaving out filler unless
+/// `--genericity lenient` is given.
+#[test]
+fn test_signature_format() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let path = dir.path().join("implant.bin");
+    std::fs::write(
+        &path,
+        b"\0https://c2.example.net/gate.php\0AAAAAAAAAAAAAAAA\0",
+    )
+    .expect("write fixture");
+
+    let run = |genericity: &str| {
+        let output = stringy()
+            .args(["--format", "signature", "--genericity", genericity])
+            .arg(&path)
+            .output()
+            .expect("run stringy");
+        assert!(output.status.success(), "{:?}", output);
+        String::from_utf8(output.stdout).expect("UTF-8 output")
+    };
+
+    let balanced = run("balanced");
+    assert!(balanced.starts_with("rule implant_bin\n"), "{balanced}");
+    assert!(
+        balanced.contains("$s0 = \"https://c2.example.net/gate.php\""),
+        "{balanced}"
+    );
+    assert!(!balanced.contains("AAAAAAAA"), "{balanced}");
+    assert!(run("lenient").contains("AAAAAAAA"));
+}
+
+/// `--base-address` maps raw input to virtual addresses; a misaligned
+/// address is a usage error.
+#[test]
+fn test_base_address() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let path = dir.path().join("dump.bin");
+    std::fs::write(&path, b"\0\0\0\0heap marker string\0").expect("write fixture");
+
+    let output = stringy()
+        .args(["--format", "jsonl", "--base-address", "0x7ff600000000"])
+        .arg(&path)
+        .output()
+        .expect("run stringy");
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).expect("UTF-8 output");
+    let record: serde_json::Value =
+        serde_json::from_str(stdout.lines().next().expect("one record")).expect("valid JSON");
+    assert_eq!(record["offset"], 4);
+    assert_eq!(record["rva"], 0x7ff6_0000_0004u64);
+
+    let output = stringy()
+        .args(["--base-address", "0x7ff600000010"])
+        .arg(&path)
+        .output()
+        .expect("run stringy");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).expect("UTF-8 stderr");
+    assert!(stderr.contains("not aligned to 0x1000"), "{stderr}");
+}
+
+/// Compiles a native executable with a known string literal into `dir`.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn compile_fixture(dir: &std::path::Path) -> std::path::PathBuf {
+    let source = dir.join("hello.c");
+    let binary = dir.join("hello");
+    std::fs::write(
+        &source,
+        "#include <stdio.h>\nint main(void) { puts(\"stringy fixture says hello\"); return 0; }\n",
+    )
+    .expect("write C source");
+    let status = Command::new("cc")
+        .arg(&source)
+        .arg("-o")
+        .arg(&binary)
+        .status()
+        .expect("run cc");
+    assert!(status.success(), "cc failed");
+    binary
+}
+
+/// Compiles a C++ executable that imports mangled libstdc++ symbols into
+/// `dir`.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn compile_cpp_fixture(dir: &std::path::Path) -> std::path::PathBuf {
+    let source = dir.join("hello.cpp");
+    let binary = dir.join("hello");
+    std::fs::write(
+        &source,
+        "#include <iostream>\nint main() { std::cout << 42 << std::endl; return 0; }\n",
+    )
+    .expect("write C++ source");
+    let status = Command::new("c++")
+        .arg(&source)
+        .arg("-o")
+        .arg(&binary)
+        .status()
+        .expect("run c++");
+    assert!(status.success(), "c++ failed");
+    binary
+}
+
+/// A native executable with a string literal that must survive the pipeline.
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn test_prints_strings_from_elf() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let binary = compile_fixture(dir.path());
+
+    let output = stringy().arg(&binary).output().expect("run stringy");
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).expect("UTF-8 output");
+    let line = stdout
+        .lines()
+        .find(|line| line.ends_with("stringy fixture says hello"))
+        .unwrap_or_else(|| panic!("fixture string missing:\n{stdout}"));
+    assert!(line.contains(".rodata"), "{line}");
+}
+
+/// `--section` keeps strings from the named section only.
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn test_single_section() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let binary = compile_fixture(dir.path());
+
+    let output = stringy()
+        .args(["--format", "jsonl", "--section", ".rodata"])
+        .arg(&binary)
+        .output()
+        .expect("run stringy");
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).expect("UTF-8 output");
+    let records: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("one JSON object per line"))
+        .collect();
+    assert!(
+        records
+            .iter()
+            .any(|record| record["text"] == "stringy fixture says hello"),
+        "{stdout}"
+    );
+    assert!(
+        records.iter().all(|record| record["section"] == ".rodata"),
+        "{stdout}"
+    );
+}
+
+/// `--tag` and `--min-score` narrow the results of any output format.
+#[test]
+fn test_tag_and_score_filters() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let path = dir.path().join("blob.bin");
+    std::fs::write(
+        &path,
+        b"\0https://c2.example.net/gate.php\0\xff10.20.30.40:4444\0\xff\
+          plain marker string\0\xff192.168.1.1\0\xffError: %s\0",
+    )
+    .expect("write fixture");
+
+    let records = |args: &[&str]| -> Vec<serde_json::Value> {
+        let output = stringy()
+            .args(["--format", "jsonl"])
+            .args(args)
+            .arg(&path)
+            .output()
+            .expect("run stringy");
+        assert!(output.status.success(), "{:?}", output);
+        String::from_utf8(output.stdout)
+            .expect("UTF-8 output")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("one JSON object per line"))
+            .collect()
+    };
+    let score = |record: &serde_json::Value| record["score"].as_i64().expect("score field");
+    let has_tag = |record: &serde_json::Value, tag: &str| {
+        record["tags"]
+            .as_array()
+            .expect("tags field")
+            .iter()
+            .any(|t| t.as_str().is_some_and(|t| t.eq_ignore_ascii_case(tag)))
+    };
+
+    let all = records(&[]);
+    let threshold = all
+        .iter()
+        .find(|record| record["text"] == "10.20.30.40:4444")
+        .map(score)
+        .expect("address extracted");
+    let expected: Vec<&serde_json::Value> = all
+        .iter()
+        .filter(|record| has_tag(record, "url") || has_tag(record, "ipv4"))
+        .filter(|record| score(record) >= threshold)
+        .collect();
+    assert!(expected.len() >= 2 && expected.len() < all.len());
+
+    let filtered = records(&[
+        "--tag",
+        "url",
+        "--tag",
+        "ipv4",
+        "--min-score",
+        &threshold.to_string(),
+    ]);
+    assert_eq!(filtered.iter().collect::<Vec<_>>(), expected);
+    assert!(!filtered.iter().any(|record| has_tag(record, "fmt")));
+
+    let output = stringy()
+        .args(["--tag", "bogus"])
+        .arg(&path)
+        .output()
+        .expect("run stringy");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("possible values") && stderr.contains("ipv4"),
+        "{stderr}"
+    );
+}
+
+/// `--output` writes to a file, in the format its extension names.
+#[test]
+fn test_output_file() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let path = dir.path().join("blob.bin");
+    std::fs::write(&path, b"\0first string\0second string\0").expect("write fixture");
+    let out = dir.path().join("out.json");
+
+    let output = stringy()
+        .arg("-o")
+        .arg(&out)
+        .arg(&path)
+        .output()
+        .expect("run stringy");
+    assert!(output.status.success(), "{:?}", output);
+    assert!(output.stdout.is_empty(), "{:?}", output);
+
+    let json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&out).expect("read output"))
+            .expect("valid JSON");
+    let texts: Vec<&str> = json
+        .as_array()
+        .expect("array of strings")
+        .iter()
+        .filter_map(|record| record["text"].as_str())
+        .collect();
+    assert!(texts.contains(&"first string"), "{json}");
+    assert!(texts.contains(&"second string"), "{json}");
+
+    let missing = dir.path().join("missing").join("out.json");
+    let output = stringy()
+        .arg("--output")
+        .arg(&missing)
+        .arg(&path)
+        .output()
+        .expect("run stringy");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Output directory does not exist"));
+}
+
+#[test]
+fn test_json_envelope() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let path = dir.path().join("blob.bin");
+    std::fs::write(&path, b"\0first string\0second string\0").expect("write fixture");
+
+    let output = stringy()
+        .args(["--format", "json", "--envelope", "--compact"])
+        .arg(&path)
+        .output()
+        .expect("run stringy");
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).expect("UTF-8 output");
+    assert_eq!(stdout.lines().count(), 1, "{stdout}");
+    let document: serde_json::Value = serde_json::from_str(&stdout).expect("one JSON document");
+    assert_eq!(document["file"], path.display().to_string());
+    assert_eq!(document["format"], "unknown");
+    assert_eq!(document["summary"]["strings"], 2);
+    assert_eq!(document["strings"].as_array().map(Vec::len), Some(2));
+}
+
+/// Piped output is plain unless color is forced.
+#[test]
+fn test_color_choice() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let path = dir.path().join("blob.bin");
+    std::fs::write(&path, b"\0https://c2.example.net/gate.php\0").expect("write fixture");
+
+    for (args, colored) in [
+        (&[][..], false),
+        (&["--color", "never"][..], false),
+        (&["--color", "always"][..], true),
+    ] {
+        let output = stringy()
+            .args(args)
+            .arg(&path)
+            .output()
+            .expect("run stringy");
+        assert!(output.status.success(), "{:?}", output);
+        assert_eq!(output.stdout.contains(&0x1b), colored, "{args:?}");
+    }
+}
+
+#[test]
+fn test_stats_summary() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let path = dir.path().join("blob.bin");
+    let mut data = b"\0https://c2.example.net/gate.php\0plain marker string\0\0".to_vec();
+    for unit in "wide marker text".encode_utf16() {
+        data.extend_from_slice(&unit.to_le_bytes());
+    }
+    data.extend_from_slice(&[0, 0]);
+    std::fs::write(&path, &data).expect("write fixture");
+
+    let output = stringy()
+        .args(["--stats", "--encoding", "ascii,utf16le"])
+        .arg(&path)
+        .output()
+        .expect("run stringy");
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).expect("UTF-8 output");
+    assert!(stdout.starts_with("Strings: 3 "), "{stdout}");
+    assert!(
+        stdout.contains("Encodings:\n  ascii     2\n  utf-16le  1\n"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("Tags:\n  domain  1\n  url     1\n"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("Top 3 by score:\n"), "{stdout}");
+}
+
+/// `--demangle` shows mangled imports by their signature and adds the
+/// demangled name to JSON next to the raw one.
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn test_demangle_cpp_imports() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let binary = compile_cpp_fixture(dir.path());
+    let run = |args: &[&str]| {
+        let output = stringy()
+            .args(args)
+            .args(["--tag", "import", "--color", "never"])
+            .arg(&binary)
+            .output()
+            .expect("run stringy");
+        assert!(output.status.success(), "{:?}", output);
+        String::from_utf8(output.stdout).expect("UTF-8 output")
+    };
+
+    // std::ostream::operator<<(int), imported from libstdc++
+    let raw = run(&[]);
+    assert!(raw.contains("_ZNSolsEi@GLIBCXX_"), "{raw}");
+    assert!(!raw.contains("std::ostream::operator<<(int)"), "{raw}");
+
+    let table = run(&["--demangle"]);
+    let line = table
+        .lines()
+        .find(|line| line.contains("std::ostream::operator<<(int)@GLIBCXX_"))
+        .unwrap_or_else(|| panic!("demangled import missing:\n{table}"));
+    assert!(line.contains("libstdc++"), "{line}");
+    assert!(!table.contains("_ZNSolsEi"), "{table}");
+
+    let jsonl = run(&["--demangle", "--format", "jsonl"]);
+    let record: serde_json::Value = jsonl
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("valid JSON"))
+        .find(|record| {
+            record["text"]
+                .as_str()
+                .is_some_and(|text| text.starts_with("_ZNSolsEi@"))
+        })
+        .unwrap_or_else(|| panic!("import record missing:\n{jsonl}"));
+    assert!(
+        record["demangled"]
+            .as_str()
+            .is_some_and(|name| name.starts_with("std::ostream::operator<<(int)@GLIBCXX_")),
+        "{record}"
+    );
+    // Strings that are not mangled names get no `demangled` field
+    assert!(
+        jsonl
+            .lines()
+            .any(|line| line.contains("__libc_start_main") && !line.contains("demangled")),
+        "{jsonl}"
+    );
+}
+
+/// `--debug-file` adds the debug file's symbol names, marked as coming from
+/// it, and warns when its build-id belongs to another binary.
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn test_debug_file() {
+    let c_dir = tempfile::tempdir().expect("create temp dir");
+    let binary = compile_fixture(c_dir.path());
+    let cpp_dir = tempfile::tempdir().expect("create temp dir");
+    let other = compile_cpp_fixture(cpp_dir.path());
+    let run = |debug_file: &std::path::Path| {
+        let output = stringy()
+            .args(["--format", "jsonl", "--debug-file"])
+            .arg(debug_file)
+            .arg(&binary)
+            .output()
+            .expect("run stringy");
+        assert!(output.status.success(), "{:?}", output);
+        output
+    };
+
+    // An unstripped binary is its own debug file
+    let output = run(&binary);
+    let stdout = String::from_utf8(output.stdout).expect("UTF-8 output");
+    assert!(
+        stdout
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("valid JSON"))
+            .any(|record| record["text"] == "main" && record["section"] == "<debug-file>"),
+        "{stdout}"
+    );
+    let stderr = String::from_utf8(output.stderr).expect("UTF-8 stderr");
+    assert!(!stderr.contains("does not match"), "{stderr}");
+
+    let output = run(&other);
+    let stderr = String::from_utf8(output.stderr).expect("UTF-8 stderr");
+    assert!(
+        stderr.contains("does not match binary build-id"),
+        "{stderr}"
+    );
+
+    let output = stringy()
+        .arg("--debug-file")
+        .arg(c_dir.path().join("missing.debug"))
+        .arg(&binary)
+        .output()
+        .expect("run stringy");
+    assert_eq!(output.status.code(), Some(1));
+}
@@ -0,0 +1,173 @@
+//! Output must be byte-identical for the same input and configuration,
+//! independent of the order in which strings were produced.
+
+use std::thread;
+use stringy::extraction::ExtractionConfig;
+use stringy::extraction::ascii::iter_ascii;
+use stringy::output::{
+    Formatter, JsonlFormatter, OutputConfig, OutputFormat, SortKey, sort_canonical, sort_strings,
+};
+use stringy::{AnalysisConfig, FoundString, analyze};
+
+/// Builds a buffer of NUL-separated strings, including duplicates.
+fn sample_input() -> Vec<u8> {
+    let mut data = Vec::new();
+    for i in 0..200 {
+        data.extend_from_slice(format!("string number {}", i % 50).as_bytes());
+        data.extend_from_slice(&[0, 0xff, 0]);
+    }
+    data
+}
+
+/// Extracts strings from `data[start..end]`, reporting whole-file offsets.
+fn extract_chunk(data: &[u8], start: usize, end: usize) -> Vec<FoundString> {
+    let config = ExtractionConfig::default();
+    iter_ascii(&data[start..end], &config)
+        .map(|s| {
+            let mut found = s.to_found_string();
+            found.offset += start as u64;
+            found
+        })
+        .collect()
+}
+
+fn render(mut strings: Vec<FoundString>) -> String {
+    sort_canonical(&mut strings);
+    strings
+        .iter()
+        .map(|s| serde_json::to_string(s).unwrap())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Every output format, each with the CLI's default settings.
+const FORMATS: &[OutputFormat] = &[
+    OutputFormat::Table,
+    OutputFormat::Jsonl,
+    OutputFormat::Json,
+    OutputFormat::Csv,
+    OutputFormat::Yara,
+    OutputFormat::Markdown,
+    OutputFormat::Sarif,
+    OutputFormat::Summary,
+    OutputFormat::Signature,
+];
+
+/// Sorts `strings` by score, as the CLI does, and renders them in `format`.
+fn format_bytes(mut strings: Vec<FoundString>, format: OutputFormat) -> Vec<u8> {
+    sort_strings(&mut strings, SortKey::Score, true);
+    let mut out = Vec::new();
+    format
+        .formatter(OutputConfig::default())
+        .unwrap()
+        .format(&strings, &mut out)
+        .unwrap();
+    out
+}
+
+#[test]
+fn test_formatted_output_identical_across_runs_and_input_orders() {
+    let data = sample_input();
+    let config = AnalysisConfig::default();
+    let strings = analyze(&data, &config).unwrap().strings;
+    assert!(!strings.is_empty());
+
+    // Repeated runs, some of them concurrent
+    let runs: Vec<Vec<FoundString>> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..4)
+            .map(|_| scope.spawn(|| analyze(&data, &config).unwrap().strings))
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    // The same strings handed to the formatters in other orders
+    let mut reversed = strings.clone();
+    reversed.reverse();
+    let mut rotated = strings.clone();
+    rotated.rotate_left(strings.len() / 3);
+    let mut by_text = strings.clone();
+    sort_strings(&mut by_text, SortKey::Text, false);
+
+    for &format in FORMATS {
+        let expected = format_bytes(strings.clone(), format);
+        assert!(!expected.is_empty(), "{format:?}");
+        for other in runs.iter().chain([&reversed, &rotated, &by_text]) {
+            assert_eq!(expected, format_bytes(other.clone(), format), "{format:?}");
+        }
+    }
+}
+
+#[test]
+fn test_output_independent_of_input_order() {
+    let data = sample_input();
+    let strings = extract_chunk(&data, 0, data.len());
+
+    let mut reversed = strings.clone();
+    reversed.reverse();
+
+    assert!(!strings.is_empty());
+    assert_eq!(render(strings.clone()), render(reversed));
+    assert_eq!(render(strings.clone()), render(strings));
+}
+
+/// Runs the whole pipeline over `data` and serializes the result.
+fn analyze_to_jsonl(data: &[u8], config: &AnalysisConfig) -> Vec<u8> {
+    let strings = analyze(data, config).unwrap().strings;
+    let mut out = Vec::new();
+    JsonlFormatter::new().format(&strings, &mut out).unwrap();
+    out
+}
+
+#[test]
+fn test_full_pipeline_output_identical() {
+    // Classifiable strings, each in ASCII and UTF-16LE, several times over
+    let mut data = sample_input();
+    for _ in 0..3 {
+        for text in [
+            "https://update.example.com/check",
+            "C:\\Windows\\evil.dll",
+            "v4.0.1-rc2",
+        ] {
+            data.extend_from_slice(text.as_bytes());
+            data.extend_from_slice(&[0, 0xff]);
+            data.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+            data.extend_from_slice(&[0, 0, 0xff]);
+        }
+    }
+
+    for config in [
+        AnalysisConfig::default(),
+        AnalysisConfig {
+            extraction: ExtractionConfig {
+                deduplicate: true,
+                top_n: Some(200),
+                ..ExtractionConfig::default()
+            },
+            ..AnalysisConfig::default()
+        },
+    ] {
+        let first = analyze_to_jsonl(&data, &config);
+        assert!(!first.is_empty());
+        assert_eq!(first, analyze_to_jsonl(&data, &config));
+    }
+}
+
+/// Equal sort keys, here the same score, are ordered canonically rather
+/// than left in input order.
+#[test]
+fn test_sort_ties_independent_of_input_order() {
+    let data = sample_input();
+    let strings = extract_chunk(&data, 0, data.len());
+    let mut reversed = strings.clone();
+    reversed.reverse();
+
+    for key in [SortKey::Score, SortKey::Length, SortKey::Section] {
+        let mut forward = strings.clone();
+        let mut backward = reversed.clone();
+        sort_strings(&mut forward, key, true);
+        sort_strings(&mut backward, key, true);
+        let offsets =
+            |strings: &[FoundString]| strings.iter().map(|s| s.offset).collect::<Vec<_>>();
+        assert_eq!(offsets(&forward), offsets(&backward), "{key:?}");
+    }
+}
@@ -0,0 +1,267 @@
+//! ELF parsing checks against a real binary: the test harness itself.
+
+#![cfg(all(target_os = "linux", target_env = "gnu"))]
+
+use stringy::container::overlay::OVERLAY_SECTION;
+use stringy::container::{ContainerParser, elf::ElfParser};
+use stringy::extraction::symbols::symbol_strings;
+use stringy::extraction::{ExtractionConfig, extract_from_container};
+use stringy::{AnalysisConfig, SectionType, StringSource, analyze};
+
+fn test_executable() -> Vec<u8> {
+    let exe = std::env::current_exe().expect("test executable path");
+    std::fs::read(exe).expect("read test executable")
+}
+
+/// The test harness links against glibc, whose symbols are versioned.
+#[test]
+fn test_imports_attributed_to_needed_library() {
+    let data = test_executable();
+
+    let info = ElfParser::new()
+        .parse(&data)
+        .expect("parse test executable");
+    assert!(!info.imports.is_empty());
+
+    let libc_import = info
+        .imports
+        .iter()
+        .find(|import| import.library.as_deref() == Some("libc.so.6"));
+    assert!(
+        libc_import.is_some(),
+        "no import attributed to libc.so.6: {:?}",
+        info.imports
+    );
+}
+
+/// glibc imports name the symbol version they were linked against.
+#[test]
+fn test_import_symbol_versions() {
+    let data = test_executable();
+    let info = ElfParser::new().parse(&data).unwrap();
+
+    let versioned: Vec<_> = info
+        .imports
+        .iter()
+        .filter(|import| import.library.as_deref() == Some("libc.so.6"))
+        .filter(|import| import.version.is_some())
+        .collect();
+    assert!(
+        !versioned.is_empty(),
+        "no versioned libc import: {:?}",
+        info.imports
+    );
+    for import in versioned {
+        let version = import.version.as_deref().unwrap();
+        assert!(version.starts_with("GLIBC_"), "{import:?}");
+        assert_eq!(
+            import.display_name(),
+            format!("{}@{}", import.name, version)
+        );
+    }
+
+    let strings = symbol_strings(&info, false);
+    assert!(
+        strings
+            .iter()
+            .any(|s| s.source == StringSource::ImportName && s.text.contains("@GLIBC_"))
+    );
+}
+
+/// Test builds carry DWARF, whose compile unit names start with this file.
+#[test]
+#[cfg(debug_assertions)]
+fn test_debug_strings() {
+    let data = test_executable();
+    let parser = ElfParser::new();
+
+    let strings = parser.extract_debug_strings(&data, None).unwrap();
+    assert!(strings.iter().all(|s| s.source == StringSource::DebugInfo));
+    assert!(
+        strings.iter().any(|s| s.text.starts_with("tests/elf.rs")),
+        "no debug string names tests/elf.rs"
+    );
+
+    let limited = parser.extract_debug_strings(&data, Some(0)).unwrap();
+    assert!(limited.is_empty());
+}
+
+/// A stored (uncompressed) ZIP holding one file, as self-extracting
+/// installers append to their stub.
+fn zip_archive(name: &str, contents: &[u8]) -> Vec<u8> {
+    fn crc32(bytes: &[u8]) -> u32 {
+        let mut crc = !0u32;
+        for &byte in bytes {
+            crc ^= u32::from(byte);
+            for _ in 0..8 {
+                crc = if crc & 1 != 0 {
+                    (crc >> 1) ^ 0xedb8_8320
+                } else {
+                    crc >> 1
+                };
+            }
+        }
+        !crc
+    }
+
+    let crc = crc32(contents).to_le_bytes();
+    let size = (contents.len() as u32).to_le_bytes();
+    let name_len = (name.len() as u16).to_le_bytes();
+
+    let mut zip = Vec::new();
+    zip.extend_from_slice(b"PK\x03\x04\x14\x00\x00\x00\x00\x00\x00\x00\x00\x00");
+    zip.extend_from_slice(&crc);
+    zip.extend_from_slice(&size);
+    zip.extend_from_slice(&size);
+    zip.extend_from_slice(&name_len);
+    zip.extend_from_slice(&[0, 0]);
+    zip.extend_from_slice(name.as_bytes());
+    zip.extend_from_slice(contents);
+
+    let central = zip.len() as u32;
+    zip.extend_from_slice(b"PK\x01\x02\x14\x00\x14\x00\x00\x00\x00\x00\x00\x00\x00\x00");
+    zip.extend_from_slice(&crc);
+    zip.extend_from_slice(&size);
+    zip.extend_from_slice(&size);
+    zip.extend_from_slice(&name_len);
+    zip.extend_from_slice(&[0; 12]);
+    zip.extend_from_slice(&0u32.to_le_bytes());
+    zip.extend_from_slice(name.as_bytes());
+    let central_size = zip.len() as u32 - central;
+
+    zip.extend_from_slice(b"PK\x05\x06\x00\x00\x00\x00\x01\x00\x01\x00");
+    zip.extend_from_slice(&central_size.to_le_bytes());
+    zip.extend_from_slice(&central.to_le_bytes());
+    zip.extend_from_slice(&[0, 0]);
+    zip
+}
+
+#[test]
+fn test_appended_zip_is_overlay() {
+    let mut data = test_executable();
+    let parser = ElfParser::new();
+    assert!(parser.parse(&data).unwrap().overlay().is_none());
+
+    let image_len = data.len() as u64;
+    let zip = zip_archive(
+        "payload/config.ini",
+        b"[update]\nserver=https://updates.example.net/channel\n",
+    );
+    data.extend_from_slice(&zip);
+
+    let info = parser.parse(&data).expect("parse executable with overlay");
+    let overlay = info.overlay().expect("overlay section");
+    assert_eq!(overlay.offset, image_len);
+    assert_eq!(overlay.size, zip.len() as u64);
+    assert_eq!(overlay.section_type, SectionType::Other);
+
+    let config = ExtractionConfig {
+        include_sections: Some(vec![SectionType::Other]),
+        ..ExtractionConfig::default()
+    };
+    let strings = extract_from_container(&data, &info, &config);
+    for text in [
+        "payload/config.ini",
+        "server=https://updates.example.net/channel",
+    ] {
+        let string = strings
+            .iter()
+            .find(|s| s.text.contains(text))
+            .unwrap_or_else(|| panic!("{text:?} not extracted"));
+        assert_eq!(string.section.as_deref(), Some(OVERLAY_SECTION));
+        assert!(string.offset >= image_len);
+    }
+}
+
+#[test]
+fn test_section_entropy() {
+    let data = test_executable();
+    let info = ElfParser::new().parse(&data).unwrap();
+
+    for section in &info.sections {
+        match section.entropy {
+            Some(entropy) => assert!((0.0..=8.0).contains(&entropy), "{section:?}"),
+            None => assert_eq!(section.size, 0, "{section:?}"),
+        }
+    }
+    let bss = info.sections.iter().find(|s| s.name == ".bss").unwrap();
+    assert_eq!(bss.entropy, None);
+}
+
+/// Only sections the loader maps have an address.
+#[test]
+fn test_unmapped_sections_have_no_rva() {
+    let data = test_executable();
+    let info = ElfParser::new().parse(&data).unwrap();
+    let section = |name: &str| info.sections.iter().find(|s| s.name == name).unwrap();
+
+    assert!(section(".rodata").rva.is_some_and(|rva| rva > 0));
+    assert_eq!(section(".comment").rva, None);
+}
+
+/// A section header claiming more bytes than the file holds is clamped to
+/// the end of the file rather than dropped or read past it.
+#[test]
+fn test_oversized_section_is_truncated() {
+    let mut data = test_executable();
+    let elf = goblin::elf::Elf::parse(&data).unwrap();
+    let index = elf
+        .section_headers
+        .iter()
+        .position(|sh| elf.shdr_strtab.get_at(sh.sh_name) == Some(".comment"))
+        .unwrap();
+    let comment_offset = elf.section_headers[index].sh_offset;
+    // sh_size of an Elf64_Shdr
+    let field = elf.header.e_shoff as usize + index * 64 + 0x20;
+    drop(elf);
+    let claimed = data.len() as u64 * 4;
+    data[field..field + 8].copy_from_slice(&claimed.to_le_bytes());
+
+    let info = ElfParser::new().parse(&data).unwrap();
+    let comment = info.sections.iter().find(|s| s.name == ".comment").unwrap();
+    assert!(comment.truncated);
+    assert_eq!(comment.offset + comment.size, data.len() as u64);
+    assert_eq!(comment.offset, comment_offset);
+    assert!(
+        !info
+            .sections
+            .iter()
+            .any(|s| s.truncated && s.name != ".comment")
+    );
+
+    // The clamped section is scanned without reading past the file
+    let strings = extract_from_container(&data, &info, &ExtractionConfig::default());
+    assert!(
+        strings
+            .iter()
+            .any(|s| s.section.as_deref() == Some(".comment"))
+    );
+}
+
+/// The interpreter path is reported once, from `PT_INTERP`, not again by
+/// the scan of `.interp` holding the same bytes.
+#[test]
+fn test_interpreter_reported_once() {
+    let data = test_executable();
+    let interpreter = goblin::elf::Elf::parse(&data)
+        .unwrap()
+        .interpreter
+        .expect("dynamically linked test executable")
+        .to_string();
+
+    let config = AnalysisConfig {
+        extraction: ExtractionConfig {
+            section_names: Some(vec![".interp".to_string()]),
+            ..ExtractionConfig::default()
+        },
+        ..AnalysisConfig::default()
+    };
+    let result = analyze(&data, &config).unwrap();
+    let found: Vec<_> = result
+        .strings
+        .iter()
+        .filter(|s| s.text == interpreter)
+        .collect();
+    assert_eq!(found.len(), 1, "{found:?}");
+    assert_eq!(found[0].source, StringSource::LoadCommand);
+}